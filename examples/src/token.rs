@@ -58,7 +58,7 @@ pub async fn token_info() {
     ];
 
     let info = client
-        .shield(&token_mints)
+        .get_shield(&token_mints)
         .await
         .expect("failed the token info");
 
@@ -53,6 +53,7 @@ pub async fn trigger() {
     let execute_response = client
         .execute_trigger_order(&execute_request)
         .await
+        .and_then(|response| response.into_result())
         .expect("Failed to execute trigger order");
 
     println!(
@@ -0,0 +1,70 @@
+//! Throughput of the transaction and instruction decode paths.
+//!
+//! Run with `cargo bench -p jup-ag-sdk --bench decode`. Plain `std` timing keeps the crate free
+//! of benchmark dependencies; numbers are per call, averaged over many iterations.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use jup_ag_sdk::{
+    jito::jito_tip_account,
+    submission::message_hash,
+    types::{Instruction, RouteInstruction},
+};
+
+const ITERATIONS: u32 = 100_000;
+
+/// A signed v0 transaction near the packet size limit, like a multi-hop swap.
+fn swap_transaction() -> String {
+    let mut tx = vec![1u8];
+    tx.extend([7u8; 64]);
+    tx.extend([0x80, 1, 0, 9]);
+    tx.push(24);
+    for key in 0..24u8 {
+        tx.extend([key; 32]);
+    }
+    tx.resize(1200, 3);
+    STANDARD.encode(tx)
+}
+
+/// A route instruction with a four-hop route plan.
+fn route_instruction() -> Instruction {
+    let mut data = vec![229, 23, 203, 151, 122, 227, 173, 42];
+    data.extend([5u8; 4 * 40]);
+    data.extend_from_slice(&1_000_000u64.to_le_bytes());
+    data.extend_from_slice(&50u16.to_le_bytes());
+    data.push(0);
+    Instruction {
+        program_id: "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4".to_string(),
+        accounts: vec![],
+        data: STANDARD.encode(data),
+    }
+}
+
+fn bench<T>(name: &str, mut f: impl FnMut() -> T) {
+    for _ in 0..ITERATIONS / 10 {
+        black_box(f());
+    }
+    let started = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    let per_call: Duration = started.elapsed() / ITERATIONS;
+    println!("{name:<28} {:>8} ns/call", per_call.as_nanos());
+}
+
+fn main() {
+    let transaction = swap_transaction();
+    let instruction = route_instruction();
+
+    bench("message_hash", || message_hash(black_box(&transaction)));
+    bench("jito_tip_account", || {
+        jito_tip_account(black_box(&transaction))
+    });
+    bench("RouteInstruction::decode", || {
+        RouteInstruction::decode(black_box(&instruction))
+    });
+}
@@ -0,0 +1,56 @@
+//! Cost of building quote queries in a tight quoting loop, from a fresh request and from a
+//! [`QuoteTemplate`](jup_ag_sdk::types::QuoteTemplate).
+//!
+//! Run with `cargo bench -p jup-ag-sdk --bench quote_request`. Numbers are per call, averaged
+//! over many iterations.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use jup_ag_sdk::{JupiterClient, types::QuoteRequest};
+
+const ITERATIONS: u32 = 1_000_000;
+
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+fn bench<T>(name: &str, mut f: impl FnMut(u64) -> T) {
+    for amount in 0..u64::from(ITERATIONS / 10) {
+        black_box(f(amount));
+    }
+    let started = Instant::now();
+    for amount in 0..u64::from(ITERATIONS) {
+        black_box(f(amount));
+    }
+    let per_call: Duration = started.elapsed() / ITERATIONS;
+    println!("{name:<36} {:>8} ns/call", per_call.as_nanos());
+}
+
+fn main() {
+    // what a call does before the request is sent: build it, then clone it to apply defaults
+    bench("QuoteRequest::new + clone", |amount| {
+        let request = QuoteRequest::new(black_box(SOL_MINT), black_box(USDC_MINT), amount);
+        request.clone().slippage_bps(50)
+    });
+
+    let client = reqwest::Client::new();
+    bench("QuoteRequest::new + query", |amount| {
+        let request = QuoteRequest::new(SOL_MINT, USDC_MINT, amount);
+        client
+            .get("https://lite-api.jup.ag/swap/v1/quote")
+            .query(&request.clone().slippage_bps(50).query())
+            .build()
+    });
+
+    let template = JupiterClient::new("https://lite-api.jup.ag")
+        .quote_template(QuoteRequest::new(SOL_MINT, USDC_MINT, 0).slippage_bps(50))
+        .unwrap();
+    bench("QuoteTemplate::query", |amount| {
+        client
+            .get("https://lite-api.jup.ag/swap/v1/quote")
+            .query(&template.query(amount))
+            .build()
+    });
+}
@@ -0,0 +1,484 @@
+//! Creates a wallet's associated token accounts (ATAs) up front.
+//!
+//! Swaps built with [`skip_user_account_rpc_calls(true)`](crate::types::SwapRequest::skip_user_account_rpc_calls)
+//! skip Jupiter's account lookups and therefore fail if an ATA they touch is missing. Pre-warming
+//! every mint a bot will trade once, with [`plan_ata_prewarm`], makes that flag safe to use.
+//!
+//! [`plan_account_closure`] does the reverse, closing emptied ATAs to reclaim their rent.
+
+use std::collections::HashMap;
+
+use base64::Engine;
+use reqwest::Client;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    JupiterClientError,
+    error::handle_response,
+    types::{AccountMeta, Instruction, TOKEN_ACCOUNT_SIZE},
+};
+
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+
+/// How many ATAs are created per transaction, leaving room for compute budget instructions
+/// within the 1232-byte transaction limit.
+pub const MAX_ATAS_PER_TRANSACTION: usize = 8;
+
+/// How many token accounts are closed per transaction. A close only adds the account key and a
+/// one-byte instruction, as the wallet and token program are shared.
+pub const MAX_CLOSES_PER_TRANSACTION: usize = 20;
+
+/// `getMultipleAccounts` accepts at most this many keys per call.
+const MAX_ACCOUNTS_PER_RPC_CALL: usize = 100;
+
+/// An associated token account of a wallet for one mint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssociatedTokenAccount {
+    pub mint: String,
+    pub address: String,
+
+    /// The token program owning the mint, Token or Token-2022.
+    pub token_program: String,
+}
+
+/// The ATAs to create for a wallet, batched into transactions.
+#[derive(Debug)]
+pub struct AtaPrewarmPlan {
+    /// ATAs that already exist.
+    pub existing: Vec<AssociatedTokenAccount>,
+
+    /// ATAs that will be created by `batches`.
+    pub missing: Vec<AssociatedTokenAccount>,
+
+    /// Mints that don't exist on the cluster or aren't owned by a token program.
+    pub unknown_mints: Vec<String>,
+
+    /// One instruction set per transaction, each creating up to [`MAX_ATAS_PER_TRANSACTION`] ATAs.
+    /// The payer must sign every transaction.
+    pub batches: Vec<Vec<Instruction>>,
+}
+
+/// The emptied ATAs of a wallet to close, batched into transactions.
+#[derive(Debug)]
+pub struct AccountClosurePlan {
+    /// ATAs holding no tokens, closed by `batches`.
+    pub closable: Vec<AssociatedTokenAccount>,
+
+    /// ATAs that still hold tokens, e.g. because their swap hasn't landed, and are left open.
+    pub not_empty: Vec<AssociatedTokenAccount>,
+
+    /// Mints for which the wallet has no ATA, or that don't exist on the cluster.
+    pub missing: Vec<String>,
+
+    /// One instruction set per transaction, each closing up to [`MAX_CLOSES_PER_TRANSACTION`]
+    /// accounts. The wallet must sign every transaction.
+    pub batches: Vec<Vec<Instruction>>,
+}
+
+/// Derives the associated token account of `wallet` for `mint`.
+pub fn associated_token_address(
+    wallet: &str,
+    mint: &str,
+    token_program: &str,
+) -> Result<String, JupiterClientError> {
+    let seeds = [
+        decode_pubkey(wallet)?,
+        decode_pubkey(token_program)?,
+        decode_pubkey(mint)?,
+    ];
+    let program_id = decode_pubkey(ASSOCIATED_TOKEN_PROGRAM_ID)?;
+
+    for bump in (0..=u8::MAX).rev() {
+        let mut hasher = Sha256::new();
+        for seed in &seeds {
+            hasher.update(seed);
+        }
+        hasher.update([bump]);
+        hasher.update(program_id);
+        hasher.update(b"ProgramDerivedAddress");
+        let address: [u8; 32] = hasher.finalize().into();
+
+        // a program address must not be a valid ed25519 public key
+        if curve25519_dalek::edwards::CompressedEdwardsY(address)
+            .decompress()
+            .is_none()
+        {
+            return Ok(bs58::encode(address).into_string());
+        }
+    }
+
+    Err(JupiterClientError::InvalidPubkey(format!(
+        "no associated token address for {wallet} and {mint}"
+    )))
+}
+
+/// Builds an idempotent `CreateAssociatedTokenAccount` instruction, which succeeds even if
+/// the account already exists.
+pub fn create_associated_token_account_idempotent(
+    payer: &str,
+    wallet: &str,
+    mint: &str,
+    token_program: &str,
+) -> Result<Instruction, JupiterClientError> {
+    let address = associated_token_address(wallet, mint, token_program)?;
+    let meta = |pubkey: &str, is_signer, is_writable| AccountMeta {
+        pubkey: pubkey.to_string(),
+        is_signer,
+        is_writable,
+    };
+
+    Ok(Instruction {
+        program_id: ASSOCIATED_TOKEN_PROGRAM_ID.to_string(),
+        accounts: vec![
+            meta(payer, true, true),
+            meta(&address, false, true),
+            meta(wallet, false, false),
+            meta(mint, false, false),
+            meta(SYSTEM_PROGRAM_ID, false, false),
+            meta(token_program, false, false),
+        ],
+        // base64 of the `CreateIdempotent` discriminator, [1]
+        data: "AQ==".to_string(),
+    })
+}
+
+/// Builds a token program `CloseAccount` instruction, sending the rent of the empty `account`
+/// to `destination`.
+pub fn close_token_account(
+    account: &str,
+    destination: &str,
+    owner: &str,
+    token_program: &str,
+) -> Result<Instruction, JupiterClientError> {
+    for pubkey in [account, destination, owner, token_program] {
+        decode_pubkey(pubkey)?;
+    }
+    let meta = |pubkey: &str, is_signer, is_writable| AccountMeta {
+        pubkey: pubkey.to_string(),
+        is_signer,
+        is_writable,
+    };
+
+    Ok(Instruction {
+        program_id: token_program.to_string(),
+        accounts: vec![
+            meta(account, false, true),
+            meta(destination, false, true),
+            meta(owner, true, false),
+        ],
+        // base64 of the `CloseAccount` discriminator, [9]
+        data: "CQ==".to_string(),
+    })
+}
+
+/// Looks up which ATAs of `wallet` for `mints` are missing on `rpc_url` and batches the
+/// instructions that create them, paid by `payer`.
+///
+/// Uses its own HTTP client so the Jupiter API key is never sent to the RPC.
+///
+/// # Example
+/// ```
+/// let plan = plan_ata_prewarm(&rpc_url, &wallet, &wallet, &[USDC_MINT, JUP_MINT, BONK_MINT]).await?;
+/// for instructions in plan.batches {
+///     let tx = build_and_sign(instructions, &keypair, recent_blockhash);
+///     rpc.send_and_confirm_transaction(&tx).await?;
+/// }
+///
+/// // every later swap can now skip Jupiter's account lookups
+/// let swap = SwapRequest::new(&wallet, &wallet, quote).skip_user_account_rpc_calls(true);
+/// ```
+pub async fn plan_ata_prewarm(
+    rpc_url: &str,
+    payer: &str,
+    wallet: &str,
+    mints: &[&str],
+) -> Result<AtaPrewarmPlan, JupiterClientError> {
+    let client = Client::new();
+
+    let mut plan = AtaPrewarmPlan {
+        existing: Vec::new(),
+        missing: Vec::new(),
+        unknown_mints: Vec::new(),
+        batches: Vec::new(),
+    };
+
+    let mut candidates = Vec::new();
+    let owners = fetch_owners(&client, rpc_url, mints).await?;
+    for (mint, owner) in mints.iter().zip(owners) {
+        match owner.as_deref() {
+            Some(program @ (TOKEN_PROGRAM_ID | TOKEN_2022_PROGRAM_ID)) => {
+                candidates.push(AssociatedTokenAccount {
+                    mint: mint.to_string(),
+                    address: associated_token_address(wallet, mint, program)?,
+                    token_program: program.to_string(),
+                })
+            }
+            _ => plan.unknown_mints.push(mint.to_string()),
+        }
+    }
+
+    let addresses: Vec<&str> = candidates.iter().map(|a| a.address.as_str()).collect();
+    let owners = fetch_owners(&client, rpc_url, &addresses).await?;
+    for (account, owner) in candidates.into_iter().zip(owners) {
+        match owner {
+            Some(_) => plan.existing.push(account),
+            None => plan.missing.push(account),
+        }
+    }
+
+    for chunk in plan.missing.chunks(MAX_ATAS_PER_TRANSACTION) {
+        plan.batches.push(
+            chunk
+                .iter()
+                .map(|account| {
+                    create_associated_token_account_idempotent(
+                        payer,
+                        wallet,
+                        &account.mint,
+                        &account.token_program,
+                    )
+                })
+                .collect::<Result<_, _>>()?,
+        );
+    }
+
+    Ok(plan)
+}
+
+/// Looks up the ATAs of `wallet` for `mints` on `rpc_url` and batches the instructions closing
+/// those that hold no tokens, returning their rent to `wallet`.
+///
+/// Balances are read from the cluster rather than trusted from a swap report, so an account is
+/// only closed once the swap emptying it has landed. Call it after your swaps confirmed, e.g.
+/// with [`DustSweepReport::swept_mints`](crate::types::DustSweepReport::swept_mints). Token-2022
+/// accounts still holding withheld transfer fees are empty but can't be closed until the fees
+/// are harvested, so their transactions fail.
+///
+/// Uses its own HTTP client so the Jupiter API key is never sent to the RPC.
+///
+/// # Example
+/// ```
+/// let report = api.sweep_dust(&wallet, &config, send).await?;
+/// wait_for_confirmations(&report.swept).await?;
+///
+/// let plan = plan_account_closure(&rpc_url, &wallet, &report.swept_mints()).await?;
+/// for instructions in plan.batches {
+///     let tx = build_and_sign(instructions, &keypair, recent_blockhash);
+///     rpc.send_and_confirm_transaction(&tx).await?;
+/// }
+/// ```
+pub async fn plan_account_closure(
+    rpc_url: &str,
+    wallet: &str,
+    mints: &[&str],
+) -> Result<AccountClosurePlan, JupiterClientError> {
+    let client = Client::new();
+
+    let mut plan = AccountClosurePlan {
+        closable: Vec::new(),
+        not_empty: Vec::new(),
+        missing: Vec::new(),
+        batches: Vec::new(),
+    };
+
+    let mut candidates = Vec::new();
+    let owners = fetch_owners(&client, rpc_url, mints).await?;
+    for (mint, owner) in mints.iter().zip(owners) {
+        match owner.as_deref() {
+            Some(program @ (TOKEN_PROGRAM_ID | TOKEN_2022_PROGRAM_ID)) => {
+                candidates.push(AssociatedTokenAccount {
+                    mint: mint.to_string(),
+                    address: associated_token_address(wallet, mint, program)?,
+                    token_program: program.to_string(),
+                })
+            }
+            _ => plan.missing.push(mint.to_string()),
+        }
+    }
+
+    let addresses: Vec<&str> = candidates.iter().map(|a| a.address.as_str()).collect();
+    let accounts = fetch_accounts(&client, rpc_url, &addresses, Some(TOKEN_AMOUNT_SLICE)).await?;
+    for (account, info) in candidates.into_iter().zip(accounts) {
+        match info.map(|info| token_amount(&info)) {
+            Some(Ok(0)) => plan.closable.push(account),
+            Some(Ok(_)) => plan.not_empty.push(account),
+            Some(Err(e)) => return Err(e),
+            None => plan.missing.push(account.mint),
+        }
+    }
+
+    for chunk in plan.closable.chunks(MAX_CLOSES_PER_TRANSACTION) {
+        plan.batches.push(
+            chunk
+                .iter()
+                .map(|account| {
+                    close_token_account(&account.address, wallet, wallet, &account.token_program)
+                })
+                .collect::<Result<_, _>>()?,
+        );
+    }
+
+    Ok(plan)
+}
+
+/// The size in bytes of a new associated token account for each of `mints` on `rpc_url`.
+///
+/// Token program accounts are always 165 bytes. Token-2022 ATAs carry the immutable owner
+/// extension plus the account extensions the mint's own extensions require, e.g. a withheld
+/// fee amount for transfer fee mints, and cost more rent. Mints that don't exist are left out.
+///
+/// Uses its own HTTP client so the Jupiter API key is never sent to the RPC.
+pub async fn token_account_sizes(
+    rpc_url: &str,
+    mints: &[&str],
+) -> Result<HashMap<String, u64>, JupiterClientError> {
+    let accounts = fetch_accounts(&Client::new(), rpc_url, mints, None).await?;
+
+    let mut sizes = HashMap::new();
+    for (mint, account) in mints.iter().zip(accounts) {
+        let size = match account {
+            Some(account) if account.owner == TOKEN_2022_PROGRAM_ID => {
+                token_2022_account_size(&account.decode_data()?)
+            }
+            Some(account) if account.owner == TOKEN_PROGRAM_ID => TOKEN_ACCOUNT_SIZE,
+            _ => continue,
+        };
+        sizes.insert(mint.to_string(), size);
+    }
+    Ok(sizes)
+}
+
+/// Token-2022 extension types, numbered as in the program.
+const TRANSFER_FEE_CONFIG: u16 = 1;
+const NON_TRANSFERABLE: u16 = 9;
+const TRANSFER_HOOK: u16 = 14;
+const CONFIDENTIAL_TRANSFER_FEE_CONFIG: u16 = 16;
+const PAUSABLE_CONFIG: u16 = 26;
+
+/// The size of an ATA of a Token-2022 mint with the given account data.
+///
+/// Mirrors `GetAccountDataSize` as called by the associated token program: a 165-byte base,
+/// an account type byte, then a 4-byte type and length header per extension.
+fn token_2022_account_size(mint: &[u8]) -> u64 {
+    // the immutable owner extension, which the associated token program always requests
+    let mut extensions = 4;
+
+    // mint extensions start after the base padded to 165 bytes and the account type byte
+    let mut tlv = mint
+        .get(TOKEN_ACCOUNT_SIZE as usize + 1..)
+        .unwrap_or_default();
+    while let [t0, t1, l0, l1, rest @ ..] = tlv {
+        let length = u16::from_le_bytes([*l0, *l1]) as usize;
+        extensions += match u16::from_le_bytes([*t0, *t1]) {
+            // transfer fee amount: the withheld amount
+            TRANSFER_FEE_CONFIG => 4 + 8,
+            // non-transferable account, paused account
+            NON_TRANSFERABLE | PAUSABLE_CONFIG => 4,
+            // transfer hook account: the transferring flag
+            TRANSFER_HOOK => 4 + 1,
+            // confidential transfer fee amount: the encrypted withheld amount
+            CONFIDENTIAL_TRANSFER_FEE_CONFIG => 4 + 64,
+            _ => 0,
+        };
+        tlv = rest.get(length..).unwrap_or_default();
+    }
+
+    let size = TOKEN_ACCOUNT_SIZE + 1 + extensions;
+    // sizes equal to a multisig account's get two bytes of padding to tell them apart
+    if size == 355 { size + 2 } else { size }
+}
+
+pub(crate) fn decode_pubkey(pubkey: &str) -> Result<[u8; 32], JupiterClientError> {
+    let mut bytes = [0u8; 32];
+    match bs58::decode(pubkey).onto(&mut bytes) {
+        Ok(32) => Ok(bytes),
+        _ => Err(JupiterClientError::InvalidPubkey(pubkey.to_string())),
+    }
+}
+
+#[derive(Deserialize)]
+struct MultipleAccountsResponse {
+    result: MultipleAccountsResult,
+}
+
+#[derive(Deserialize)]
+struct MultipleAccountsResult {
+    value: Vec<Option<RpcAccount>>,
+}
+
+#[derive(Deserialize)]
+struct RpcAccount {
+    owner: String,
+
+    /// The requested data slice and its encoding, always base64.
+    data: (String, String),
+}
+
+/// Offset and length of the amount in a token account, the same for Token and Token-2022.
+const TOKEN_AMOUNT_SLICE: (usize, usize) = (64, 8);
+
+impl RpcAccount {
+    fn decode_data(&self) -> Result<Vec<u8>, JupiterClientError> {
+        base64::engine::general_purpose::STANDARD
+            .decode(&self.data.0)
+            .map_err(|e| JupiterClientError::deserialization(e.to_string()))
+    }
+}
+
+/// Reads the little-endian amount from an account fetched with [`TOKEN_AMOUNT_SLICE`].
+fn token_amount(account: &RpcAccount) -> Result<u64, JupiterClientError> {
+    let amount: [u8; 8] = account.decode_data()?.try_into().map_err(|_| {
+        JupiterClientError::deserialization("token account data too short".to_string())
+    })?;
+    Ok(u64::from_le_bytes(amount))
+}
+
+/// The owner program of every account in `pubkeys`, or `None` for accounts that don't exist.
+async fn fetch_owners(
+    client: &Client,
+    rpc_url: &str,
+    pubkeys: &[&str],
+) -> Result<Vec<Option<String>>, JupiterClientError> {
+    let accounts = fetch_accounts(client, rpc_url, pubkeys, Some((0, 0))).await?;
+    Ok(accounts.into_iter().map(|a| a.map(|a| a.owner)).collect())
+}
+
+/// Every account in `pubkeys` with the `(offset, length)` slice of its data, or all of it
+/// without a slice, or `None` for accounts that don't exist.
+async fn fetch_accounts(
+    client: &Client,
+    rpc_url: &str,
+    pubkeys: &[&str],
+    slice: Option<(usize, usize)>,
+) -> Result<Vec<Option<RpcAccount>>, JupiterClientError> {
+    let mut config = serde_json::json!({ "encoding": "base64" });
+    if let Some((offset, length)) = slice {
+        config["dataSlice"] = serde_json::json!({ "offset": offset, "length": length });
+    }
+
+    let mut accounts = Vec::with_capacity(pubkeys.len());
+    for chunk in pubkeys.chunks(MAX_ACCOUNTS_PER_RPC_CALL) {
+        let response = client
+            .post(rpc_url)
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "getMultipleAccounts",
+                "params": [chunk, config],
+            }))
+            .send()
+            .await?;
+
+        let response = handle_response(response).await?;
+
+        match response.json::<MultipleAccountsResponse>().await {
+            Ok(body) => accounts.extend(body.result.value),
+            Err(e) => return Err(JupiterClientError::deserialization(e.to_string())),
+        }
+    }
+    Ok(accounts)
+}
@@ -0,0 +1,254 @@
+//! Structured audit records of the swap lifecycle, delivered to pluggable sinks.
+//!
+//! Register sinks with [`JupiterClient::with_audit_sink`](crate::JupiterClient::with_audit_sink).
+//! Quotes, swap transactions and swap instructions are recorded automatically, including
+//! failures and policy denials. Steps that happen outside the SDK, such as submitting a signed
+//! transaction or observing its confirmations, are recorded with
+//! [`JupiterClient::audit`](crate::JupiterClient::audit) so the whole lifecycle lands in the
+//! same sink.
+
+use std::{
+    fmt,
+    io::Write,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    rt::{SystemTime, UNIX_EPOCH},
+    types::{QuoteRequest, QuoteResponse, SwapRequest},
+};
+
+/// The lifecycle step an [`AuditRecord`] describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AuditOperation {
+    Quote,
+    SwapTransaction,
+    SwapInstructions,
+    /// A signed transaction was sent to the cluster.
+    Submission,
+    /// A confirmation status was observed for a submitted transaction.
+    Confirmation,
+}
+
+impl AuditOperation {
+    /// The stable name of the operation, as serialized.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditOperation::Quote => "quote",
+            AuditOperation::SwapTransaction => "swapTransaction",
+            AuditOperation::SwapInstructions => "swapInstructions",
+            AuditOperation::Submission => "submission",
+            AuditOperation::Confirmation => "confirmation",
+        }
+    }
+}
+
+/// A serializable record of a single lifecycle step.
+///
+/// Fields that don't apply to the step are left as `None` and omitted from the serialized form.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecord {
+    pub operation: AuditOperation,
+
+    /// Unix timestamp in milliseconds at which the step started.
+    pub timestamp_ms: u64,
+
+    /// How long the step took, in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+
+    /// The query parameters sent to `/quote`, after client defaults were applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote_request: Option<serde_json::Value>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote: Option<QuoteResponse>,
+
+    /// The payload sent to `/swap` or `/swap-instructions`, after client defaults were applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swap_request: Option<SwapRequest>,
+
+    /// Transaction signature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmations: Option<u64>,
+
+    /// Raw output amount that actually landed, as observed on chain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_amount: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditRecord {
+    /// Creates a record for `operation` stamped with the current time.
+    ///
+    /// # Example
+    /// ```
+    /// client.audit(
+    ///     AuditRecord::new(AuditOperation::Submission)
+    ///         .signature(signature.to_string())
+    ///         .duration(sent_at.elapsed()),
+    /// );
+    /// ```
+    pub fn new(operation: AuditOperation) -> Self {
+        Self {
+            operation,
+            timestamp_ms: now_ms(),
+            duration_ms: None,
+            quote_request: None,
+            quote: None,
+            swap_request: None,
+            signature: None,
+            confirmations: None,
+            out_amount: None,
+            error: None,
+        }
+    }
+
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration_ms = Some(duration.as_millis() as u64);
+        self
+    }
+
+    pub fn quote_request(mut self, request: &QuoteRequest) -> Self {
+        self.quote_request = serde_json::to_value(request).ok();
+        self
+    }
+
+    pub fn quote(mut self, quote: QuoteResponse) -> Self {
+        self.quote = Some(quote);
+        self
+    }
+
+    pub fn swap_request(mut self, request: SwapRequest) -> Self {
+        self.swap_request = Some(request);
+        self
+    }
+
+    pub fn signature(mut self, signature: impl Into<String>) -> Self {
+        self.signature = Some(signature.into());
+        self
+    }
+
+    pub fn confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = Some(confirmations);
+        self
+    }
+
+    pub fn out_amount(mut self, out_amount: u64) -> Self {
+        self.out_amount = Some(out_amount);
+        self
+    }
+
+    pub fn error(mut self, error: impl ToString) -> Self {
+        self.error = Some(error.to_string());
+        self
+    }
+}
+
+pub(crate) fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// A destination for [`AuditRecord`]s, e.g. a compliance log or a database table.
+///
+/// Implemented for any `Fn(&AuditRecord)`, so closures can be registered directly.
+///
+/// # Example
+/// ```
+/// let client = JupiterClient::new("https://lite-api.jup.ag")
+///     .with_audit_sink(|record: &AuditRecord| {
+///         db.insert_audit_record(serde_json::to_value(record).unwrap());
+///     });
+/// ```
+pub trait AuditSink: Send + Sync {
+    fn record(&self, record: &AuditRecord);
+
+    /// Pushes buffered records to their destination. Called by
+    /// [`JupiterClient::shutdown`](crate::JupiterClient::shutdown).
+    fn flush(&self) {}
+}
+
+impl<F> AuditSink for F
+where
+    F: Fn(&AuditRecord) + Send + Sync,
+{
+    fn record(&self, record: &AuditRecord) {
+        self(record)
+    }
+}
+
+/// Writes every record as a single line of JSON.
+///
+/// # Example
+/// ```
+/// let file = std::fs::File::create("audit.jsonl")?;
+/// let client = JupiterClient::new("https://lite-api.jup.ag")
+///     .with_audit_sink(JsonLinesSink::new(file));
+/// ```
+pub struct JsonLinesSink<W>(Mutex<W>);
+
+impl<W: Write + Send> JsonLinesSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self(Mutex::new(writer))
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.0.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<W: Write + Send> AuditSink for JsonLinesSink<W> {
+    fn record(&self, record: &AuditRecord) {
+        let Ok(line) = serde_json::to_string(record) else {
+            return;
+        };
+        let mut writer = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = writeln!(writer, "{line}");
+    }
+
+    fn flush(&self) {
+        let _ = self.0.lock().unwrap_or_else(|e| e.into_inner()).flush();
+    }
+}
+
+/// The audit sinks registered on a client.
+#[derive(Clone, Default)]
+pub(crate) struct AuditSinks(pub(crate) Vec<Arc<dyn AuditSink>>);
+
+impl AuditSinks {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn emit(&self, record: &AuditRecord) {
+        for sink in &self.0 {
+            sink.record(record);
+        }
+    }
+
+    pub(crate) fn flush(&self) {
+        for sink in &self.0 {
+            sink.flush();
+        }
+    }
+}
+
+impl fmt::Debug for AuditSinks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AuditSinks({})", self.0.len())
+    }
+}
@@ -0,0 +1,75 @@
+//! Groups nearby trade sizes into buckets, trading quote accuracy for cache hits.
+//!
+//! The quote cache keys requests by [`AmountBucketing::bucket`], so a `1_000_300` and a
+//! `1_000_700` lamport quote can share an entry; see
+//! [`JupiterClient::with_bucketed_quote_cache`](crate::JupiterClient::with_bucketed_quote_cache).
+//! The same function is public for bucketing sizes in your own analytics.
+
+use std::{fmt, sync::Arc};
+
+/// How raw amounts are grouped. Every amount maps to the lower bound of its bucket.
+///
+/// # Example
+/// ```
+/// let bucketing = AmountBucketing::Log { buckets_per_decade: 10 };
+/// assert_eq!(bucketing.bucket(1_000_000), 1_000_000);
+/// assert_eq!(bucketing.bucket(1_100_000), 1_000_000);
+/// assert_eq!(bucketing.bucket(1_300_000), 1_258_925);
+/// ```
+#[derive(Clone, Default)]
+pub enum AmountBucketing {
+    /// Every amount is its own bucket.
+    #[default]
+    Exact,
+
+    /// Logarithmic buckets, `buckets_per_decade` per power of ten, so the relative error is
+    /// the same for small and large trades. 10 buckets per decade keep amounts within ~26%.
+    Log { buckets_per_decade: u32 },
+
+    /// Buckets of a fixed raw width, e.g. `1_000_000` for 1 USDC.
+    FixedStep(u64),
+
+    /// Your own mapping from an amount to its bucket.
+    Custom(Arc<dyn Fn(u64) -> u64 + Send + Sync>),
+}
+
+impl AmountBucketing {
+    pub fn custom(bucket: impl Fn(u64) -> u64 + Send + Sync + 'static) -> Self {
+        AmountBucketing::Custom(Arc::new(bucket))
+    }
+
+    /// The bucket `amount` falls in, identified by its lower bound.
+    pub fn bucket(&self, amount: u64) -> u64 {
+        match self {
+            AmountBucketing::Exact => amount,
+            AmountBucketing::Log { buckets_per_decade } => {
+                if amount == 0 || *buckets_per_decade == 0 {
+                    return amount;
+                }
+                let per_decade = *buckets_per_decade as f64;
+                let index = ((amount as f64).log10() * per_decade + 1e-9).floor();
+                let lower = 10f64.powf(index / per_decade).round() as u64;
+                lower.min(amount)
+            }
+            AmountBucketing::FixedStep(step) => match step {
+                0 => amount,
+                step => amount / step * step,
+            },
+            AmountBucketing::Custom(bucket) => bucket(amount),
+        }
+    }
+}
+
+impl fmt::Debug for AmountBucketing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AmountBucketing::Exact => write!(f, "Exact"),
+            AmountBucketing::Log { buckets_per_decade } => f
+                .debug_struct("Log")
+                .field("buckets_per_decade", buckets_per_decade)
+                .finish(),
+            AmountBucketing::FixedStep(step) => f.debug_tuple("FixedStep").field(step).finish(),
+            AmountBucketing::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
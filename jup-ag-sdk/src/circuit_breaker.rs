@@ -0,0 +1,135 @@
+//! A per-endpoint circuit breaker that stops calling a degraded endpoint for a while.
+//!
+//! Enable with [`JupiterClient::with_circuit_breaker`](crate::JupiterClient::with_circuit_breaker).
+//! After `failure_threshold` consecutive failed calls to an endpoint, its circuit opens and
+//! calls fail immediately with
+//! [`JupiterClientError::CircuitOpen`](crate::JupiterClientError::CircuitOpen) for `cooldown`.
+//! The next call after that is let through as a probe: success closes the circuit, failure
+//! opens it for another cooldown.
+//!
+//! Transport errors and `5xx` responses count as failures. `4xx` responses, including `429`,
+//! don't: they say the request was wrong or too frequent, not that the endpoint is down.
+
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use reqwest::StatusCode;
+
+use crate::{JupiterClientError, clock::Instant};
+
+/// The state of an endpoint's circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls go through.
+    Closed,
+
+    /// Calls fail fast until the cooldown ends.
+    Open,
+
+    /// The cooldown ended and a single probe call decides whether to close the circuit.
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Circuit {
+    Closed { consecutive_failures: u32 },
+    Open { until: Instant },
+    HalfOpen { probe_started: Instant },
+}
+
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    circuits: Mutex<HashMap<String, Circuit>>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            circuits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Lets a call to `endpoint` through, or fails with [`JupiterClientError::CircuitOpen`].
+    pub(crate) fn acquire(&self, endpoint: &str, now: Instant) -> Result<(), JupiterClientError> {
+        let mut circuits = self.circuits.lock().unwrap_or_else(|e| e.into_inner());
+        let circuit = circuits
+            .entry(endpoint.to_string())
+            .or_insert(Circuit::Closed {
+                consecutive_failures: 0,
+            });
+
+        match *circuit {
+            Circuit::Closed { .. } => Ok(()),
+            Circuit::Open { until } if now >= until => {
+                *circuit = Circuit::HalfOpen { probe_started: now };
+                Ok(())
+            }
+            // a probe that never reported back, e.g. because its future was dropped
+            Circuit::HalfOpen { probe_started }
+                if now.saturating_duration_since(probe_started) >= self.cooldown =>
+            {
+                *circuit = Circuit::HalfOpen { probe_started: now };
+                Ok(())
+            }
+            Circuit::Open { .. } | Circuit::HalfOpen { .. } => {
+                Err(JupiterClientError::CircuitOpen {
+                    endpoint: endpoint.to_string(),
+                    request_id: None,
+                })
+            }
+        }
+    }
+
+    /// Records the outcome of a call let through by [`acquire`](Self::acquire).
+    pub(crate) fn record<T>(
+        &self,
+        endpoint: &str,
+        result: &Result<T, JupiterClientError>,
+        now: Instant,
+    ) {
+        let failed = match result {
+            Ok(_) => false,
+            Err(JupiterClientError::RequestError { .. }) => true,
+            Err(JupiterClientError::ApiError { status, .. }) => {
+                status.is_server_error() && *status != StatusCode::NOT_IMPLEMENTED
+            }
+            Err(_) => false,
+        };
+
+        let mut circuits = self.circuits.lock().unwrap_or_else(|e| e.into_inner());
+        let circuit = circuits
+            .entry(endpoint.to_string())
+            .or_insert(Circuit::Closed {
+                consecutive_failures: 0,
+            });
+
+        *circuit = match (*circuit, failed) {
+            (_, false) => Circuit::Closed {
+                consecutive_failures: 0,
+            },
+            (
+                Circuit::Closed {
+                    consecutive_failures,
+                },
+                true,
+            ) if consecutive_failures + 1 < self.failure_threshold => Circuit::Closed {
+                consecutive_failures: consecutive_failures + 1,
+            },
+            (_, true) => Circuit::Open {
+                until: now + self.cooldown,
+            },
+        };
+    }
+
+    pub(crate) fn state(&self, endpoint: &str, now: Instant) -> CircuitState {
+        let circuits = self.circuits.lock().unwrap_or_else(|e| e.into_inner());
+        match circuits.get(endpoint) {
+            None | Some(Circuit::Closed { .. }) => CircuitState::Closed,
+            Some(Circuit::Open { until }) if now < *until => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+}
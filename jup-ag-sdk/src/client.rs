@@ -1,6 +1,28 @@
+use std::collections::HashMap;
+
 use reqwest::{
     Client,
-    header::{HeaderMap, HeaderValue},
+    header::{self, HeaderMap, HeaderValue},
+};
+
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    JupiterClientError,
+    audit::{AuditRecord, AuditSink, AuditSinks},
+    bucketing::AmountBucketing,
+    circuit_breaker::{CircuitBreaker, CircuitState},
+    clock::{Clock, SystemClock},
+    metrics::{MetricsSink, MetricsSinks},
+    middleware::{Middleware, Middlewares},
+    policy::{Approval, Policies, SigningIntent, SigningPolicy},
+    quote_cache::QuoteCache,
+    rate_limit::{RateLimitMode, RateLimiter},
+    retry::RetryPolicy,
+    shutdown::Shutdown,
+    types::{DexEnum, QuoteRequest, QuoteResponse, RequestDefaults, Slippage, SwapRequest},
+    venue_health::{VenueFailure, VenueHealth, VenueHealthMonitor},
+    warnings::{WarningSink, WarningSinks},
 };
 
 /// `JupiterClient` is a client wrapper to interact with the Jupiter Aggregator APIs.
@@ -9,6 +31,210 @@ use reqwest::{
 pub struct JupiterClient {
     pub client: Client,
     pub base_url: String,
+    config: ClientConfig,
+    defaults: RequestDefaults,
+    pair_defaults: HashMap<(String, String), RequestDefaults>,
+    policies: Policies,
+    audit_sinks: AuditSinks,
+    middlewares: Middlewares,
+    metrics_sinks: MetricsSinks,
+    warning_sinks: WarningSinks,
+    api_version: ApiVersion,
+    retry_policy: RetryPolicy,
+    swap_build_retry: Option<RetryPolicy>,
+    /// Set on the per-call copy that builds swaps, see [`Self::with_swap_build_retries`].
+    idempotent_posts: bool,
+    rate_limiter: Option<(Arc<RateLimiter>, RateLimitMode)>,
+    quote_cache: Option<Arc<QuoteCache>>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    venue_health: Option<Arc<VenueHealthMonitor>>,
+    clock: Arc<dyn Clock>,
+    max_slippage: Slippage,
+    venue_allowlist: Option<Vec<DexEnum>>,
+    max_response_size: Option<usize>,
+    capture_raw_body: bool,
+    call_opts: CallOpts,
+    shutdown: Shutdown,
+}
+
+/// The version of the Swap API served behind `base_url`.
+///
+/// Decides how endpoint URLs are built, and where versions diverge, how requests are shaped.
+/// New versions are added as Jupiter ships them, so match with a wildcard arm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ApiVersion {
+    /// The legacy v6 API at `{base_url}/v6/quote`, as served by `quote-api.jup.ag` and
+    /// self-hosted deployments.
+    V6,
+
+    /// `{base_url}/swap/v1/quote`, as served by `lite-api.jup.ag` and `api.jup.ag`.
+    #[default]
+    V1,
+}
+
+impl ApiVersion {
+    /// The path segment the swap endpoints live under.
+    pub fn swap_path(&self) -> &'static str {
+        match self {
+            ApiVersion::V6 => "v6",
+            ApiVersion::V1 => "swap/v1",
+        }
+    }
+}
+
+/// The `User-Agent` sent by default, e.g. `jup-ag-sdk/0.2.0`.
+pub const USER_AGENT: &str = concat!("jup-ag-sdk/", env!("CARGO_PKG_VERSION"));
+
+/// The header carrying the ID of each call, generated unless set with
+/// [`CallOpts::request_id`].
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Settings used to (re)build the underlying `reqwest::Client`.
+///
+/// Kept on the client so that every `with_*` method can rebuild the HTTP client
+/// without dropping settings applied earlier in the chain.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+struct ClientConfig {
+    api_key: Option<String>,
+    http2_prior_knowledge: bool,
+    http2_initial_stream_window_size: Option<u32>,
+    http2_initial_connection_window_size: Option<u32>,
+    http2_adaptive_window: bool,
+    http2_keep_alive_interval: Option<Duration>,
+    http2_keep_alive_timeout: Option<Duration>,
+    http2_keep_alive_while_idle: bool,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    tcp_keepalive: Option<Duration>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    default_headers: HeaderMap,
+    /// Appended to [`USER_AGENT`] to identify the integrator, e.g. `my-wallet/1.4`.
+    user_agent_suffix: Option<String>,
+    proxy: Option<ProxyConfig>,
+    /// The HTTP client was injected rather than built from this config, so headers are
+    /// attached per request and transport settings are left to the caller.
+    custom_client: bool,
+}
+
+/// An egress proxy every request is routed through.
+#[derive(Debug, Clone)]
+struct ProxyConfig {
+    url: String,
+    basic_auth: Option<(String, String)>,
+}
+
+impl ClientConfig {
+    fn build_client(&self) -> Client {
+        self.try_build_client()
+            .expect("Failed to build client with API key")
+    }
+
+    /// The headers sent with every request.
+    fn headers(&self) -> Result<HeaderMap, JupiterClientError> {
+        let mut headers = self.default_headers.clone();
+        if let Some(api_key) = &self.api_key {
+            headers.insert("x-api-key", HeaderValue::from_str(api_key)?);
+        }
+        if !headers.contains_key(header::USER_AGENT) {
+            let user_agent = match &self.user_agent_suffix {
+                Some(suffix) => format!("{USER_AGENT} {suffix}"),
+                None => USER_AGENT.to_string(),
+            };
+            headers.insert(header::USER_AGENT, HeaderValue::from_str(&user_agent)?);
+        }
+        headers.insert("Accept", "application/json".parse().unwrap());
+        headers.insert("Content-Type", "application/json".parse().unwrap());
+        Ok(headers)
+    }
+
+    fn try_build_client(&self) -> Result<Client, JupiterClientError> {
+        let builder = Client::builder().default_headers(self.headers()?);
+
+        // timeouts, HTTP/2 tuning and proxies are not available on reqwest's wasm (fetch) backend
+        #[cfg(not(target_arch = "wasm32"))]
+        let builder = match self.proxy()? {
+            Some(proxy) => self.apply_native_settings(builder).proxy(proxy),
+            None => self.apply_native_settings(builder),
+        };
+
+        Ok(builder.build()?)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn apply_native_settings(&self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if let Some(size) = self.http2_initial_stream_window_size {
+            builder = builder.http2_initial_stream_window_size(size);
+        }
+        if let Some(size) = self.http2_initial_connection_window_size {
+            builder = builder.http2_initial_connection_window_size(size);
+        }
+        if self.http2_adaptive_window {
+            builder = builder.http2_adaptive_window(true);
+        }
+        if let Some(interval) = self.http2_keep_alive_interval {
+            builder = builder
+                .http2_keep_alive_interval(interval)
+                .http2_keep_alive_while_idle(self.http2_keep_alive_while_idle);
+        }
+        if let Some(timeout) = self.http2_keep_alive_timeout {
+            builder = builder.http2_keep_alive_timeout(timeout);
+        }
+
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(interval);
+        }
+
+        builder
+    }
+
+    /// Whether any setting that only applies to a client built by the SDK is set.
+    fn has_transport_settings(&self) -> bool {
+        self.timeout.is_some()
+            || self.connect_timeout.is_some()
+            || self.http2_prior_knowledge
+            || self.http2_initial_stream_window_size.is_some()
+            || self.http2_initial_connection_window_size.is_some()
+            || self.http2_adaptive_window
+            || self.http2_keep_alive_interval.is_some()
+            || self.http2_keep_alive_timeout.is_some()
+            || self.pool_idle_timeout.is_some()
+            || self.pool_max_idle_per_host.is_some()
+            || self.tcp_keepalive.is_some()
+            || self.proxy.is_some()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn proxy(&self) -> Result<Option<reqwest::Proxy>, JupiterClientError> {
+        let Some(config) = &self.proxy else {
+            return Ok(None);
+        };
+        let mut proxy = reqwest::Proxy::all(&config.url).map_err(|e| {
+            JupiterClientError::ConfigError(format!("invalid proxy URL {}: {e}", config.url))
+        })?;
+        if let Some((username, password)) = &config.basic_auth {
+            proxy = proxy.basic_auth(username, password);
+        }
+        Ok(Some(proxy))
+    }
 }
 
 impl JupiterClient {
@@ -24,24 +250,88 @@ impl JupiterClient {
     /// let api = JupiterClient::new("https://lite-api.jup.ag");
     /// ```
     pub fn new(base_url: &str) -> Self {
-        let mut headers = HeaderMap::new();
-        headers.insert("Accept", "application/json".parse().unwrap());
-        headers.insert("Content-Type", "application/json".parse().unwrap());
+        let config = ClientConfig::default();
+        Self::from_parts(base_url, config.build_client(), config)
+    }
 
-        let client = Client::builder()
-            .default_headers(headers)
-            .build()
-            .expect("Failed to build client with API key");
+    /// Creates a client that sends every request through `client`, e.g. one tuned with a
+    /// custom connection pool, proxy, DNS resolver or TLS settings.
+    ///
+    /// The API key and default headers are attached to each request, so `client` needs no
+    /// Jupiter-specific setup. Transport settings such as timeouts and the HTTP/2 options
+    /// are taken from `client` as is; the `with_http2_*` methods have no effect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let http = reqwest::Client::builder()
+    ///     .pool_max_idle_per_host(64)
+    ///     .dns_resolver(Arc::new(my_resolver))
+    ///     .build()?;
+    /// let api = JupiterClient::with_http_client("https://api.jup.ag", http).with_api_key("your-api-key");
+    /// ```
+    pub fn with_http_client(base_url: &str, client: Client) -> Self {
+        let config = ClientConfig {
+            custom_client: true,
+            ..ClientConfig::default()
+        };
+        Self::from_parts(base_url, client, config)
+    }
+
+    /// Returns a [`JupiterClientBuilder`] for configuring the API key, tier, timeouts and
+    /// default headers up front.
+    pub fn builder() -> JupiterClientBuilder {
+        JupiterClientBuilder::new()
+    }
 
-        // let client = Client::new();
+    /// Builds a client from the `JUPITER_*` environment variables, see
+    /// [`JupiterClientBuilder::from_env`]. With none set, it targets the Lite tier.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // JUPITER_API_KEY=... JUPITER_TIMEOUT_MS=2000 ./my-bot
+    /// let api = JupiterClient::from_env()?;
+    /// ```
+    pub fn from_env() -> Result<Self, JupiterClientError> {
+        JupiterClientBuilder::from_env()?.build()
+    }
+
+    fn from_parts(base_url: &str, client: Client, config: ClientConfig) -> Self {
         JupiterClient {
             client,
             base_url: base_url.to_string(),
+            config,
+            defaults: RequestDefaults::default(),
+            pair_defaults: HashMap::new(),
+            policies: Policies::default(),
+            audit_sinks: AuditSinks::default(),
+            middlewares: Middlewares::default(),
+            metrics_sinks: MetricsSinks::default(),
+            warning_sinks: WarningSinks::default(),
+            api_version: ApiVersion::default(),
+            retry_policy: RetryPolicy::disabled(),
+            swap_build_retry: None,
+            idempotent_posts: false,
+            rate_limiter: None,
+            quote_cache: None,
+            circuit_breaker: None,
+            venue_health: None,
+            clock: Arc::new(SystemClock),
+            max_slippage: Slippage::DEFAULT_MAX,
+            venue_allowlist: None,
+            max_response_size: None,
+            capture_raw_body: true,
+            call_opts: CallOpts::default(),
+            shutdown: Shutdown::default(),
         }
     }
 
     /// Returns a new JupiterClient with the API key set in headers.
     ///
+    /// A client targeting [`ApiTier::Lite`] is moved to [`ApiTier::Pro`], since the key is
+    /// only honored there. Custom base URLs are kept as is.
+    ///
     /// # Arguments
     ///
     /// * `api_key` - your api key, you can get one from here `https://portal.jup.ag/onboard`.
@@ -51,27 +341,783 @@ impl JupiterClient {
     /// ```
     /// let api = JupiterClient::new("https://api.jup.ag").with_api_key('your-api-key');
     /// ```
-    pub fn with_api_key(self, api_key: &str) -> Self {
-        let mut headers = HeaderMap::new();
-        headers.insert("x-api-key", HeaderValue::from_str(api_key).unwrap());
-        headers.insert("Accept", "application/json".parse().unwrap());
-        headers.insert("Content-Type", "application/json".parse().unwrap());
+    pub fn with_api_key(mut self, api_key: &str) -> Self {
+        self.config.api_key = Some(api_key.to_string());
+        if self.tier() == Some(ApiTier::Lite) {
+            self.base_url = ApiTier::Pro.base_url().to_string();
+        }
+        self.rebuild()
+    }
 
-        let client = Client::builder()
-            .default_headers(headers)
-            .build()
-            .expect("Failed to build client with API key");
+    /// The hosted tier this client targets, or `None` for a custom base URL.
+    pub fn tier(&self) -> Option<ApiTier> {
+        ApiTier::from_base_url(&self.base_url)
+    }
 
-        JupiterClient {
-            client,
-            base_url: self.base_url,
+    /// Returns a copy of this client targeting `tier`, for overriding the tier of a single call.
+    ///
+    /// The copy shares the connection pool, rate limiter, caches and every other setting
+    /// with this client, so it is cheap to create per call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::builder().api_key("your-api-key").build()?;
+    ///
+    /// // spare the paid quota for a low-priority request
+    /// let tokens = api.for_tier(ApiTier::Lite).get_recent_tokens().await?;
+    /// ```
+    pub fn for_tier(&self, tier: ApiTier) -> Self {
+        Self {
+            base_url: tier.base_url().to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of this client that applies `opts` to every call made through it, e.g.
+    /// to send a request id or a partner token with a single call.
+    ///
+    /// Like [`for_tier`](Self::for_tier), the copy shares everything else with this client.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let opts = CallOpts::new().header("x-request-id", "4f1c...");
+    /// let swap = api.for_call(&opts).get_swap_transaction(&request).await?;
+    ///
+    /// // compare production with a canary deployment
+    /// let canary = CallOpts::new().base_url("https://jupiter-canary.internal");
+    /// let expected = api.get_quote(&request).await?;
+    /// let actual = api.for_call(&canary).get_quote(&request).await?;
+    /// ```
+    pub fn for_call(&self, opts: &CallOpts) -> Self {
+        Self {
+            base_url: opts
+                .base_url_override()
+                .unwrap_or(&self.base_url)
+                .to_string(),
+            call_opts: opts.clone(),
+            ..self.clone()
+        }
+    }
+
+    /// Forces HTTP/2 without ALPN negotiation (prior knowledge).
+    ///
+    /// Every request to `base_url` is multiplexed over a single HTTP/2 connection,
+    /// which removes per-request connection setup when firing many quotes at once.
+    /// Only enable this against endpoints known to speak HTTP/2.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://api.jup.ag").with_http2_prior_knowledge();
+    /// ```
+    pub fn with_http2_prior_knowledge(mut self) -> Self {
+        self.config.http2_prior_knowledge = true;
+        self.rebuild()
+    }
+
+    /// Sets the HTTP/2 `SETTINGS_INITIAL_WINDOW_SIZE` used for stream-level flow control.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - window size in bytes. Ignored when adaptive flow control is enabled.
+    pub fn with_http2_initial_stream_window_size(mut self, size: u32) -> Self {
+        self.config.http2_initial_stream_window_size = Some(size);
+        self.rebuild()
+    }
+
+    /// Sets the max connection-level flow control window for HTTP/2.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - window size in bytes. Ignored when adaptive flow control is enabled.
+    pub fn with_http2_initial_connection_window_size(mut self, size: u32) -> Self {
+        self.config.http2_initial_connection_window_size = Some(size);
+        self.rebuild()
+    }
+
+    /// Enables HTTP/2 adaptive flow control (BDP based window sizing).
+    ///
+    /// Overrides the initial stream and connection window sizes.
+    pub fn with_http2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.config.http2_adaptive_window = enabled;
+        self.rebuild()
+    }
+
+    /// Sets the [`RequestDefaults`] applied to every quote and swap request.
+    ///
+    /// Fields set explicitly on a request always take precedence over these defaults.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_defaults(RequestDefaults::new().slippage_bps(50).max_accounts(40));
+    /// ```
+    pub fn with_defaults(mut self, defaults: RequestDefaults) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// Returns the [`RequestDefaults`] applied to every quote and swap request.
+    pub fn defaults(&self) -> &RequestDefaults {
+        &self.defaults
+    }
+
+    /// Overrides the client defaults for swaps between `mint_a` and `mint_b`, in either direction.
+    ///
+    /// Covers slippage, max accounts and venue selection through
+    /// [`dexes`](RequestDefaults::dexes) and [`exclude_dexes`](RequestDefaults::exclude_dexes).
+    /// Fields set on the request still win; fields left unset in the override fall back to
+    /// the client-wide [`RequestDefaults`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // keep stablecoin swaps tight while the client default stays at 1%
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_defaults(RequestDefaults::new().slippage_bps(100))
+    ///     .with_pair_defaults(USDC_MINT, USDT_MINT, RequestDefaults::new().slippage_bps(5));
+    /// ```
+    pub fn with_pair_defaults(
+        mut self,
+        mint_a: &str,
+        mint_b: &str,
+        defaults: RequestDefaults,
+    ) -> Self {
+        self.pair_defaults
+            .insert(pair_key(mint_a, mint_b), defaults);
+        self
+    }
+
+    /// Returns the defaults registered for the pair, if any.
+    pub fn pair_defaults(&self, mint_a: &str, mint_b: &str) -> Option<&RequestDefaults> {
+        self.pair_defaults.get(&pair_key(mint_a, mint_b))
+    }
+
+    /// Fills unset quote fields from the pair override, then from the client defaults, and
+    /// restricts routing to the venue allowlist.
+    pub(crate) fn resolve_quote_request(&self, params: &QuoteRequest) -> QuoteRequest {
+        let mut params = match self.pair_defaults(&params.input_mint, &params.output_mint) {
+            Some(pair) => self.defaults.apply_to_quote(&pair.apply_to_quote(params)),
+            None => self.defaults.apply_to_quote(params),
+        };
+        if let (Some(monitor), None) = (&self.venue_health, &params.dexes) {
+            let unhealthy = monitor.excluded(self.clock.now());
+            if !unhealthy.is_empty() {
+                let excluded = params.exclude_dexes.get_or_insert_with(Vec::new);
+                for dex in unhealthy {
+                    if !excluded.contains(&dex) {
+                        excluded.push(dex);
+                    }
+                }
+            }
+        }
+        if let (Some(allowlist), None) = (&self.venue_allowlist, &params.dexes) {
+            let excluded = params.exclude_dexes.take().unwrap_or_default();
+            params.dexes = Some(
+                allowlist
+                    .iter()
+                    .filter(|dex| !excluded.contains(dex))
+                    .cloned()
+                    .collect(),
+            );
+        }
+        params
+    }
+
+    /// Restricts every quote to `venues`, failing when Jupiter can't or doesn't honor it.
+    ///
+    /// Quotes are sent with `dexes` set to the allowlist, minus any `exclude_dexes` of the
+    /// request. Requests that name their own `dexes` must stay within the allowlist. Every
+    /// returned route plan is checked as well, so a quote through any other venue fails with
+    /// [`JupiterClientError::VenueNotAllowed`] instead of reaching a swap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_venue_allowlist(vec![DexEnum::Whirlpool, DexEnum::MeteoraDlmm, DexEnum::RaydiumClmm]);
+    /// ```
+    pub fn with_venue_allowlist(mut self, venues: Vec<DexEnum>) -> Self {
+        self.venue_allowlist = Some(venues);
+        self
+    }
+
+    /// Caps the body size of the large endpoints: token lists, token search and wallet
+    /// balances. Unlimited by default.
+    ///
+    /// Bodies are read in chunks and abandoned with [`JupiterClientError::ResponseTooLarge`]
+    /// as soon as they pass `bytes`, or before reading when the announced `Content-Length`
+    /// already does, so a pathological response can't exhaust a memory-constrained process.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag").with_max_response_size(8 << 20);
+    /// ```
+    pub fn with_max_response_size(mut self, bytes: usize) -> Self {
+        self.max_response_size = Some(bytes);
+        self
+    }
+
+    /// Whether a [`JupiterClientError::DeserializationError`] carries the raw body that
+    /// failed to parse. On by default, which helps diagnose API changes; turn it off when
+    /// errors are logged somewhere that shouldn't see response bodies.
+    ///
+    /// Responses are parsed straight from their bytes either way; the body is only decoded
+    /// to text once parsing failed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag").with_raw_body_capture(false);
+    /// ```
+    pub fn with_raw_body_capture(mut self, capture: bool) -> Self {
+        self.capture_raw_body = capture;
+        self
+    }
+
+    pub(crate) fn check_venues(&self, params: &QuoteRequest) -> Result<(), JupiterClientError> {
+        let (Some(allowlist), Some(dexes)) = (&self.venue_allowlist, &params.dexes) else {
+            return Ok(());
+        };
+        if let Some(dex) = dexes.iter().find(|dex| !allowlist.contains(dex)) {
+            return Err(JupiterClientError::VenueNotAllowed(dex.to_string()));
+        }
+        if dexes.is_empty() {
+            return Err(JupiterClientError::ConfigError(
+                "every allowlisted venue is excluded".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that every hop of `quote` went through a venue `params` allowed.
+    pub(crate) fn check_route_venues(
+        &self,
+        params: &QuoteRequest,
+        quote: &QuoteResponse,
+    ) -> Result<(), JupiterClientError> {
+        let Some(allowlist) = &self.venue_allowlist else {
+            return Ok(());
+        };
+        let allowlist = params.dexes.as_ref().unwrap_or(allowlist);
+        match quote
+            .route_plan
+            .iter()
+            .find(|step| !allowlist.contains(&DexEnum::from(step.swap_info.label.as_str())))
+        {
+            Some(step) => Err(JupiterClientError::VenueNotAllowed(
+                step.swap_info.label.clone(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Sets the highest slippage a quote may request. Default: [`Slippage::DEFAULT_MAX`], 10%.
+    ///
+    /// Quotes above it fail with [`JupiterClientError::SlippageTooHigh`] before anything is
+    /// sent, catching fat-fingered configs such as `5000` meant as 0.5%. Raise it explicitly
+    /// for long-tail tokens that really need more.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag").with_max_slippage("25%".parse()?);
+    /// ```
+    pub fn with_max_slippage(mut self, max_slippage: Slippage) -> Self {
+        self.max_slippage = max_slippage;
+        self
+    }
+
+    pub(crate) fn check_slippage(&self, params: &QuoteRequest) -> Result<(), JupiterClientError> {
+        match params.slippage_bps {
+            Some(bps) if bps > self.max_slippage.bps() => {
+                Err(JupiterClientError::SlippageTooHigh {
+                    bps,
+                    max_bps: self.max_slippage.bps(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Fills unset swap fields from the pair override, then from the client defaults.
+    pub(crate) fn resolve_swap_request(&self, data: &SwapRequest) -> SwapRequest {
+        let quote = &data.quote_response;
+        match self.pair_defaults(&quote.input_mint, &quote.output_mint) {
+            Some(pair) => self.defaults.apply_to_swap(&pair.apply_to_swap(data)),
+            None => self.defaults.apply_to_swap(data),
+        }
+    }
+
+    /// Registers a custody [`SigningPolicy`] checked before any unsigned transaction is returned.
+    ///
+    /// Policies run in registration order for swap transactions, swap instructions and Ultra
+    /// orders; the first denial fails the call with [`JupiterClientError::PolicyViolation`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_policy(MaxSlippageBps(100))
+    ///     .with_policy(MintAllowlist(allowed_mints));
+    /// ```
+    pub fn with_policy<P: SigningPolicy + 'static>(mut self, policy: P) -> Self {
+        self.policies.0.push(Arc::new(policy));
+        self
+    }
+
+    /// Checks `intent` against every registered policy.
+    ///
+    /// Called automatically by the swap, Ultra, trigger and recurring methods; exposed for
+    /// flows that build transactions elsewhere.
+    pub fn check_signing_intent(&self, intent: &SigningIntent) -> Result<(), JupiterClientError> {
+        match self.policies.check(intent) {
+            Ok(_) => Ok(()),
+            Err(reason) => Err(JupiterClientError::PolicyViolation(reason)),
+        }
+    }
+
+    /// Checks the intent built by `intent` against the registered policies, building it only
+    /// if there are any, so a description that can't be read only fails clients with policies.
+    ///
+    /// The returned [`Approval`] should be released if the transaction isn't built after all.
+    pub(crate) fn check_policies(
+        &self,
+        intent: impl FnOnce() -> Result<SigningIntent, JupiterClientError>,
+    ) -> Result<Approval, JupiterClientError> {
+        if self.policies.is_empty() {
+            return Ok(Approval::default());
+        }
+        self.policies
+            .check(&intent()?)
+            .map_err(JupiterClientError::PolicyViolation)
+    }
+
+    /// Sets the [`ApiVersion`] of the swap endpoints (quote, swap, swap-instructions and
+    /// program-id-to-label).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("http://localhost:8080").with_api_version(ApiVersion::V6);
+    /// // GET http://localhost:8080/v6/quote
+    /// let quote = api.get_quote(&request).await?;
+    /// ```
+    pub fn with_api_version(mut self, api_version: ApiVersion) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    /// Returns the [`ApiVersion`] of the swap endpoints.
+    pub fn api_version(&self) -> ApiVersion {
+        self.api_version
+    }
+
+    /// Returns the full URL of a swap endpoint, e.g. `quote`, under the configured [`ApiVersion`].
+    pub(crate) fn swap_url(&self, endpoint: &str) -> String {
+        format!(
+            "{}/{}/{}",
+            self.base_url,
+            self.api_version.swap_path(),
+            endpoint
+        )
+    }
+
+    /// Retries transient failures according to `policy`. Requests are not retried by default.
+    ///
+    /// `GET` endpoints such as `/quote` are retried on timeouts and `5xx` responses; `POST`
+    /// endpoints only when the connection could not be established. `429` responses are
+    /// retried for every endpoint, waiting as long as `Retry-After` asks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag").with_retry_policy(RetryPolicy::new(5));
+    /// ```
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Retries failed `/swap` and `/swap-instructions` builds according to `policy`, resending
+    /// the same quote payload, in place of the client's retry policy.
+    ///
+    /// Building a swap is a pure function of the quote and has no side effects, so unlike
+    /// other `POST`s it is retried on timeouts and `5xx` responses too. Nothing that submits
+    /// a transaction, such as Ultra's execute, is ever retried this way.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag").with_swap_build_retries(
+    ///     RetryPolicy::new(3).max_backoff(Duration::from_secs(1)),
+    /// );
+    /// ```
+    pub fn with_swap_build_retries(mut self, policy: RetryPolicy) -> Self {
+        self.swap_build_retry = Some(policy);
+        self
+    }
+
+    /// Limits every request made by this client, and its clones, to `requests_per_second`
+    /// with bursts of up to `burst` requests.
+    ///
+    /// Every endpoint method waits for the limiter before sending, or fails with
+    /// [`JupiterClientError::RateLimitExceeded`] in [`RateLimitMode::FailFast`]. Retries count
+    /// against the limit too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // the free tier allows 60 requests per minute
+    /// let api = JupiterClient::new("https://lite-api.jup.ag").with_rate_limit(1.0, 5, RateLimitMode::Queue);
+    /// ```
+    pub fn with_rate_limit(
+        mut self,
+        requests_per_second: f64,
+        burst: u32,
+        mode: RateLimitMode,
+    ) -> Self {
+        self.rate_limiter = Some((Arc::new(RateLimiter::new(requests_per_second, burst)), mode));
+        self
+    }
+
+    /// Waits for, or in fail-fast mode checks, the client's rate limiter.
+    pub(crate) async fn acquire_rate_limit(&self) -> Result<(), JupiterClientError> {
+        match &self.rate_limiter {
+            Some((limiter, RateLimitMode::Queue)) => limiter.acquire_on(self.clock.as_ref()).await,
+            Some((limiter, RateLimitMode::FailFast))
+                if !limiter.try_acquire_on(self.clock.as_ref()) =>
+            {
+                return Err(JupiterClientError::RateLimitExceeded);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Fails fast on an endpoint after `failure_threshold` consecutive failed calls, for
+    /// `cooldown`, then probes it with a single call before letting traffic through again.
+    ///
+    /// Circuits are tracked per endpoint path, so a degraded `/quote` doesn't block `/swap`.
+    /// A call counts once, after its retries. Clones of the client share the circuits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_circuit_breaker(5, Duration::from_secs(10));
+    /// ```
+    pub fn with_circuit_breaker(mut self, failure_threshold: u32, cooldown: Duration) -> Self {
+        self.circuit_breaker = Some(Arc::new(CircuitBreaker::new(failure_threshold, cooldown)));
+        self
+    }
+
+    /// Reads the time and waits through `clock` instead of the system clock, for quote cache
+    /// TTLs, circuit breaker cooldowns, rate limiting, retry backoff and scheduling, see
+    /// [`clock`](crate::clock).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let clock = Arc::new(ManualClock::new());
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_clock(clock.clone())
+    ///     .with_circuit_breaker(5, Duration::from_secs(10));
+    /// clock.advance(Duration::from_secs(10)); // ends any cooldown
+    /// ```
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub(crate) fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
+    /// The circuit state of `endpoint`, a path relative to the base URL such as
+    /// `swap/v1/quote`. Always [`CircuitState::Closed`] without a circuit breaker.
+    pub fn circuit_state(&self, endpoint: &str) -> CircuitState {
+        match &self.circuit_breaker {
+            Some(breaker) => breaker.state(endpoint, self.clock.now()),
+            None => CircuitState::Closed,
+        }
+    }
+
+    /// Tracks failures per venue and excludes a venue from quotes for `cooldown` after
+    /// `failure_threshold` failures without a fill in between, see
+    /// [`venue_health`](crate::venue_health). A threshold of 0 is treated as 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_venue_health(3, Duration::from_secs(300));
+    /// ```
+    pub fn with_venue_health(mut self, failure_threshold: u32, cooldown: Duration) -> Self {
+        self.venue_health = Some(Arc::new(VenueHealthMonitor::new(
+            failure_threshold,
+            cooldown,
+        )));
+        self
+    }
+
+    /// Records that the swap built from `quote` landed, crediting every venue on its route
+    /// and resetting their failure streaks. Does nothing without
+    /// [`with_venue_health`](Self::with_venue_health).
+    pub fn record_fill(&self, quote: &QuoteResponse) {
+        if let Some(monitor) = &self.venue_health {
+            monitor.record_fill(quote);
+        }
+    }
+
+    /// Records a failure inside `venue`'s hop, e.g. the venue of a
+    /// [`SimulationDiagnosis`](crate::types::SimulationDiagnosis). Does nothing without
+    /// [`with_venue_health`](Self::with_venue_health).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let diagnosis = diagnose_simulation(result.err.as_ref(), &result.logs, &quote);
+    /// if let Some(venue) = &diagnosis.venue {
+    ///     api.record_venue_failure(venue, VenueFailure::Simulation);
+    /// }
+    /// ```
+    pub fn record_venue_failure(&self, venue: &str, failure: VenueFailure) {
+        if let Some(monitor) = &self.venue_health {
+            monitor.record_failure(venue, failure, self.clock.now());
+        }
+    }
+
+    /// Quote, fill and failure counts of every venue seen so far, ordered by label. Empty
+    /// without [`with_venue_health`](Self::with_venue_health).
+    pub fn venue_health(&self) -> Vec<VenueHealth> {
+        match &self.venue_health {
+            Some(monitor) => monitor.report(self.clock.now()),
+            None => Vec::new(),
+        }
+    }
+
+    /// Venues currently kept out of quotes after repeated failures.
+    pub fn unhealthy_venues(&self) -> Vec<DexEnum> {
+        match &self.venue_health {
+            Some(monitor) => monitor.excluded(self.clock.now()),
+            None => Vec::new(),
+        }
+    }
+
+    pub(crate) fn record_quoted_venues(&self, quote: &QuoteResponse) {
+        if let Some(monitor) = &self.venue_health {
+            monitor.record_quote(quote);
+        }
+    }
+
+    /// Registers an [`AuditSink`] that receives a record of every quote and swap build.
+    ///
+    /// Records carry the request sent after defaults were applied, the response or error,
+    /// and timings. Sinks are called in registration order on the calling task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_audit_sink(JsonLinesSink::new(std::fs::File::create("audit.jsonl")?));
+    /// ```
+    pub fn with_audit_sink<S: AuditSink + 'static>(mut self, sink: S) -> Self {
+        self.audit_sinks.0.push(Arc::new(sink));
+        self
+    }
+
+    /// Sends `record` to every registered audit sink.
+    ///
+    /// Use this for lifecycle steps performed outside the client, such as submitting the
+    /// signed transaction or observing its confirmations.
+    pub fn audit(&self, record: AuditRecord) {
+        self.audit_sinks.emit(&record);
+    }
+
+    /// Registers a [`Middleware`] called around every HTTP request, across all endpoints.
+    ///
+    /// Middleware runs in registration order for every attempt, including retries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://api.jup.ag")
+    ///     .with_middleware(BearerAuth(token_cache))
+    ///     .with_middleware(RequestLogger);
+    /// ```
+    pub fn with_middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.middlewares.0.push(Arc::new(middleware));
+        self
+    }
+
+    /// Registers a [`MetricsSink`] that receives the endpoint, status and duration of every
+    /// HTTP attempt.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_metrics_sink(PrometheusSink::new(&registry));
+    /// ```
+    pub fn with_metrics_sink<S: MetricsSink + 'static>(mut self, sink: S) -> Self {
+        self.metrics_sinks.0.push(Arc::new(sink));
+        self
+    }
+
+    /// Registers a [`WarningSink`] that receives non-fatal anomalies, such as unknown venue
+    /// labels, response fields filled with placeholders, or quotes computed on a lagging slot.
+    ///
+    /// Only freshly fetched quotes are checked, not cache hits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_warning_sink(|warning: &ClientWarning| log::warn!("{warning}"));
+    /// ```
+    pub fn with_warning_sink<S: WarningSink + 'static>(mut self, sink: S) -> Self {
+        self.warning_sinks.sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// Caches quotes for `ttl`, so identical requests within that window are answered
+    /// without another call to `/quote`.
+    ///
+    /// Meant for high-frequency display quoting; keep the TTL short (e.g. 500ms), since a
+    /// cached quote ages just like any other. Requests are compared after defaults are
+    /// applied, ignoring DEX list order. Concurrent identical requests that miss the cache
+    /// are all sent. Clones of the client share the cache.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_quote_cache(Duration::from_millis(500));
+    /// ```
+    pub fn with_quote_cache(self, ttl: Duration) -> Self {
+        self.with_bucketed_quote_cache(ttl, AmountBucketing::Exact)
+    }
+
+    /// Like [`with_quote_cache`](Self::with_quote_cache), but requests whose amounts fall in
+    /// the same bucket share a cached quote.
+    ///
+    /// Coarser buckets raise the hit rate at the cost of accuracy: a hit returns the quote
+    /// of whichever amount in the bucket was quoted first, so its `in_amount` and
+    /// `out_amount` may differ from the request. Use it for display quoting, not for swaps.
+    /// The client's own swap builders, e.g. [`execute_batch`](Self::execute_batch),
+    /// [`sweep_dust`](Self::sweep_dust) and
+    /// [`convert_for_withdrawal`](Self::convert_for_withdrawal), only reuse a cached quote
+    /// of exactly their amount.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag").with_bucketed_quote_cache(
+    ///     Duration::from_secs(1),
+    ///     AmountBucketing::Log { buckets_per_decade: 20 },
+    /// );
+    /// ```
+    pub fn with_bucketed_quote_cache(mut self, ttl: Duration, bucketing: AmountBucketing) -> Self {
+        self.quote_cache = Some(Arc::new(QuoteCache::new(ttl, bucketing)));
+        self
+    }
+
+    /// Drops every cached quote, e.g. right after a swap lands and pool state changed.
+    pub fn clear_quote_cache(&self) {
+        if let Some(cache) = &self.quote_cache {
+            cache.clear();
+        }
+    }
+
+    /// Shares `shutdown` with this client, so one handle stops several clients and
+    /// resubmission loops together. Clones of the client share it as well.
+    ///
+    /// Once the handle is triggered, new calls fail with
+    /// [`JupiterClientError::ShuttingDown`] while calls already in flight run to completion.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let shutdown = Shutdown::new();
+    /// let lite = JupiterClient::new("https://lite-api.jup.ag").with_shutdown(shutdown.clone());
+    /// let pro = JupiterClient::new("https://api.jup.ag").with_shutdown(shutdown.clone());
+    ///
+    /// shutdown.trigger();
+    /// shutdown.drained().await;
+    /// ```
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Stops the client: triggers its [`Shutdown`] handle, waits for in-flight calls and
+    /// resubmission loops sharing the handle to finish, then flushes the audit sinks.
+    pub async fn shutdown(&self) {
+        self.shutdown.trigger();
+        self.shutdown.drained().await;
+        self.audit_sinks.flush();
+    }
+
+    /// Whether any audit sink is registered, so callers can skip building records.
+    pub(crate) fn is_audited(&self) -> bool {
+        !self.audit_sinks.is_empty()
+    }
+
+    /// Rebuilds the HTTP client from the config, unless it was injected.
+    fn rebuild(mut self) -> Self {
+        if !self.config.custom_client {
+            self.client = self.config.build_client();
+        }
+        self
+    }
+
+    /// Headers to attach to each request, needed only when the HTTP client was injected
+    /// and therefore lacks the client's default headers.
+    pub(crate) fn per_request_headers(&self) -> Result<Option<HeaderMap>, JupiterClientError> {
+        if self.config.custom_client {
+            self.config.headers().map(Some)
+        } else {
+            Ok(None)
         }
     }
 }
 
+fn pair_key(mint_a: &str, mint_b: &str) -> (String, String) {
+    if mint_a <= mint_b {
+        (mint_a.to_string(), mint_b.to_string())
+    } else {
+        (mint_b.to_string(), mint_a.to_string())
+    }
+}
+
+mod builder;
+mod call_opts;
+pub use builder::{ApiTier, JupiterClientBuilder};
+pub use call_opts::CallOpts;
+
 // Include all the API method implementations
+mod batch_execution;
+mod dust_sweep;
+mod enrich;
+mod fee_attribution;
+mod health;
+mod holdings;
+mod portfolio;
+mod price_api;
 mod recurring_api;
 mod swap_api;
+mod swap_preview;
 mod token_api;
+mod token_snapshot;
+mod transport;
 mod trigger_api;
 mod ultra_api;
+mod vet_token;
+mod withdrawal;
+
+pub use trigger_api::TriggerOrderPages;
@@ -0,0 +1,118 @@
+use std::{future::Future, time::Duration};
+
+use super::JupiterClient;
+use crate::{
+    error::JupiterClientError,
+    policy::PolicyDecision,
+    rt,
+    types::{
+        BatchExecutionConfig, BatchExecutionReport, BatchSwap, BatchSwapOutcome, BatchSwapResult,
+        SwapRequest, SwapResponse, batch_execution::execution_order,
+    },
+};
+
+impl JupiterClient {
+    /// Executes independent swaps, e.g. for different wallets or pairs, in priority order
+    /// within a wall-clock budget.
+    ///
+    /// Swaps are started highest [`priority`](BatchSwap::priority) first, then fastest
+    /// [`decay`](BatchSwap::decay_bps_per_sec), with at most `config.max_concurrency` in
+    /// progress. Each is quoted, built and handed to `send`, which signs and submits it and
+    /// returns the signature. Every call goes through the client's rate limiter, so a limiter
+    /// shared with other work keeps the batch within the key's limits.
+    ///
+    /// A swap with a [`predicate`](BatchSwap::predicate) is checked once built, right before
+    /// `send`, and reported as aborted when the predicate denies it.
+    ///
+    /// Once `config.budget` has elapsed, or the client's
+    /// [`CallOpts::deadline`](super::CallOpts::deadline) has passed, swaps that haven't started
+    /// are skipped. Swaps in progress are never abandoned by the budget, since their
+    /// transaction may already be on its way, but their calls still fail at the deadline.
+    /// Failures are reported per swap instead of aborting the batch.
+    ///
+    /// # Example
+    /// ```
+    /// let swaps = vec![
+    ///     BatchSwap::new("liquidation", &hot_wallet, liquidation).priority(10),
+    ///     BatchSwap::new("arb", &arb_wallet, arb).priority(5).decay_bps_per_sec(20.0),
+    ///     BatchSwap::new("rebalance", &treasury, rebalance),
+    /// ];
+    /// let config = BatchExecutionConfig::new(Duration::from_secs(5));
+    /// let report = api
+    ///     .execute_batch(swaps, &config, |swap, tx| {
+    ///         let signer = signers[&swap.wallet].clone();
+    ///         async move { sign_and_send(&signer, tx).await }
+    ///     })
+    ///     .await;
+    /// for result in &report.results {
+    ///     println!("{}: {:?}", result.id, result.outcome);
+    /// }
+    /// ```
+    pub async fn execute_batch<F, Fut>(
+        &self,
+        mut swaps: Vec<BatchSwap>,
+        config: &BatchExecutionConfig,
+        send: F,
+    ) -> BatchExecutionReport
+    where
+        F: Fn(&BatchSwap, SwapResponse) -> Fut,
+        Fut: Future<Output = Result<String, JupiterClientError>>,
+    {
+        execution_order(&mut swaps);
+
+        let started = self.clock.now();
+        let send = &send;
+        let runs = swaps
+            .iter()
+            .map(|swap| async move {
+                let started_after = self.clock.now().saturating_duration_since(started);
+                if started_after >= config.budget || !self.within_deadline(Duration::ZERO) {
+                    return BatchSwapResult {
+                        id: swap.id.clone(),
+                        outcome: BatchSwapOutcome::Skipped,
+                        started_after: None,
+                    };
+                }
+
+                let result = async {
+                    let quote = self.get_swap_quote(&swap.request).await?;
+                    let expected_out = quote.out_amount.parse::<u64>().unwrap_or_default();
+                    let tx = self
+                        .get_swap_transaction(&SwapRequest::new(
+                            &swap.wallet,
+                            &swap.wallet,
+                            quote.clone(),
+                        ))
+                        .await?;
+                    if let Some(predicate) = &swap.predicate {
+                        if let PolicyDecision::Deny(reason) =
+                            predicate.evaluate(self, &swap.request, &quote).await
+                        {
+                            return Ok(BatchSwapOutcome::Aborted { reason });
+                        }
+                    }
+                    let signature = send(swap, tx).await?;
+                    Ok::<_, JupiterClientError>(BatchSwapOutcome::Executed {
+                        signature,
+                        expected_out,
+                    })
+                }
+                .await;
+
+                BatchSwapResult {
+                    id: swap.id.clone(),
+                    outcome: result.unwrap_or_else(|e| BatchSwapOutcome::Failed {
+                        reason: e.to_string(),
+                    }),
+                    started_after: Some(started_after),
+                }
+            })
+            .collect();
+
+        let results = rt::join_bounded(runs, config.max_concurrency).await;
+        BatchExecutionReport {
+            results,
+            elapsed: self.clock.now().saturating_duration_since(started),
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use std::time::Duration;
+
+use reqwest::{
+    Client, Url,
+    header::{HeaderName, HeaderValue},
+};
+
+use super::{ApiVersion, ClientConfig, JupiterClient, ProxyConfig};
+use crate::JupiterClientError;
+
+/// The hosted Jupiter API tiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiTier {
+    /// The free, rate-limited tier at `https://lite-api.jup.ag`. No API key needed.
+    Lite,
+
+    /// The paid tier at `https://api.jup.ag`. Requires an API key.
+    Pro,
+}
+
+impl ApiTier {
+    pub fn base_url(&self) -> &'static str {
+        match self {
+            ApiTier::Lite => "https://lite-api.jup.ag",
+            ApiTier::Pro => "https://api.jup.ag",
+        }
+    }
+
+    /// The tier served at `base_url`, or `None` for any other deployment.
+    pub fn from_base_url(base_url: &str) -> Option<Self> {
+        match base_url.trim_end_matches('/') {
+            "https://lite-api.jup.ag" => Some(ApiTier::Lite),
+            "https://api.jup.ag" => Some(ApiTier::Pro),
+            _ => None,
+        }
+    }
+}
+
+/// Builds a [`JupiterClient`], validating the configuration up front.
+///
+/// When neither a base URL nor a tier is set, the client targets [`ApiTier::Pro`] if an API
+/// key is given and [`ApiTier::Lite`] otherwise.
+///
+/// For sub-second re-quoting, keep the TLS connection warm: connections idle for longer than
+/// [`pool_idle_timeout`](Self::pool_idle_timeout) (90s by default) are closed and the next
+/// quote pays a fresh handshake.
+///
+/// # Example
+///
+/// ```
+/// let api = JupiterClient::builder()
+///     .api_key("your-api-key")
+///     .timeout(Duration::from_secs(5))
+///     .default_header("x-request-source", "my-bot")
+///     .build()?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct JupiterClientBuilder {
+    base_url: Option<String>,
+    tier: Option<ApiTier>,
+    api_key: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    default_headers: Vec<(String, String)>,
+    user_agent_suffix: Option<String>,
+    api_version: ApiVersion,
+    http_client: Option<Client>,
+    proxy: Option<ProxyConfig>,
+    /// Connection pool and HTTP/2 settings, merged into the built client's config.
+    transport: ClientConfig,
+}
+
+impl JupiterClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts from the `JUPITER_*` environment variables, so deployments can reconfigure the
+    /// client without code changes. Unset and empty variables are ignored; anything set on
+    /// the returned builder afterwards takes precedence.
+    ///
+    /// | Variable | Builder method |
+    /// |---|---|
+    /// | `JUPITER_API_KEY` | [`api_key`](Self::api_key) |
+    /// | `JUPITER_BASE_URL` | [`base_url`](Self::base_url) |
+    /// | `JUPITER_TIER` (`lite` or `pro`) | [`tier`](Self::tier) |
+    /// | `JUPITER_API_VERSION` (`v1` or `v6`) | [`api_version`](Self::api_version) |
+    /// | `JUPITER_TIMEOUT_MS` | [`timeout`](Self::timeout) |
+    /// | `JUPITER_CONNECT_TIMEOUT_MS` | [`connect_timeout`](Self::connect_timeout) |
+    /// | `JUPITER_PROXY` | [`proxy`](Self::proxy) |
+    ///
+    /// # Errors
+    /// [`JupiterClientError::ConfigError`] naming the variable when a value can't be parsed.
+    pub fn from_env() -> Result<Self, JupiterClientError> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let invalid = |name: &str, value: &str| {
+            JupiterClientError::ConfigError(format!("invalid {name}: {value:?}"))
+        };
+        let millis = |name: &str| -> Result<Option<Duration>, JupiterClientError> {
+            var(name)
+                .map(|value| {
+                    value
+                        .trim()
+                        .parse()
+                        .map(Duration::from_millis)
+                        .map_err(|_| invalid(name, &value))
+                })
+                .transpose()
+        };
+
+        let mut builder = Self::new();
+        builder.api_key = var("JUPITER_API_KEY");
+        builder.base_url = var("JUPITER_BASE_URL");
+        builder.timeout = millis("JUPITER_TIMEOUT_MS")?;
+        builder.connect_timeout = millis("JUPITER_CONNECT_TIMEOUT_MS")?;
+        if let Some(tier) = var("JUPITER_TIER") {
+            builder.tier = Some(match tier.trim().to_ascii_lowercase().as_str() {
+                "lite" => ApiTier::Lite,
+                "pro" => ApiTier::Pro,
+                _ => return Err(invalid("JUPITER_TIER", &tier)),
+            });
+        }
+        if let Some(version) = var("JUPITER_API_VERSION") {
+            builder.api_version = match version.trim().to_ascii_lowercase().as_str() {
+                "v1" => ApiVersion::V1,
+                "v6" => ApiVersion::V6,
+                _ => return Err(invalid("JUPITER_API_VERSION", &version)),
+            };
+        }
+        if let Some(proxy) = var("JUPITER_PROXY") {
+            builder = builder.proxy(proxy);
+        }
+
+        Ok(builder)
+    }
+
+    /// Sets a custom base URL, e.g. a self-hosted deployment. Takes precedence over [`tier`](Self::tier).
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Targets one of the hosted API tiers.
+    pub fn tier(mut self, tier: ApiTier) -> Self {
+        self.tier = Some(tier);
+        self
+    }
+
+    /// Sets the `x-api-key` header sent with every request.
+    ///
+    /// You can get one from `https://portal.jup.ag/onboard`.
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Sets the total timeout of every request, from connecting until the body is read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for establishing a connection.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Closes pooled connections idle for longer than `timeout`. reqwest's default is 90s.
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.transport.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Keeps at most `max` idle connections per host in the pool.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.transport.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Sends TCP keepalive probes every `interval`, so middleboxes don't drop idle connections.
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.transport.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Forces HTTP/2 without ALPN negotiation. See
+    /// [`JupiterClient::with_http2_prior_knowledge`].
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.transport.http2_prior_knowledge = true;
+        self
+    }
+
+    /// Sets the HTTP/2 stream and connection flow control windows, in bytes.
+    pub fn http2_window_sizes(mut self, stream: u32, connection: u32) -> Self {
+        self.transport.http2_initial_stream_window_size = Some(stream);
+        self.transport.http2_initial_connection_window_size = Some(connection);
+        self
+    }
+
+    /// Enables HTTP/2 adaptive flow control, overriding [`http2_window_sizes`](Self::http2_window_sizes).
+    pub fn http2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.transport.http2_adaptive_window = enabled;
+        self
+    }
+
+    /// Sends HTTP/2 PINGs every `interval`, closing the connection if one isn't acknowledged
+    /// within `timeout`. With `while_idle`, pings are also sent when no request is in flight,
+    /// which keeps the connection alive between bursts of quotes.
+    pub fn http2_keep_alive(
+        mut self,
+        interval: Duration,
+        timeout: Duration,
+        while_idle: bool,
+    ) -> Self {
+        self.transport.http2_keep_alive_interval = Some(interval);
+        self.transport.http2_keep_alive_timeout = Some(timeout);
+        self.transport.http2_keep_alive_while_idle = while_idle;
+        self
+    }
+
+    /// Adds a header sent with every request.
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Appends `suffix` to the default `User-Agent`, e.g. `jup-ag-sdk/0.2.0 my-wallet/1.4`,
+    /// so Jupiter can attribute traffic to your integration.
+    ///
+    /// A `User-Agent` set with [`default_header`](Self::default_header) replaces the whole value.
+    pub fn user_agent_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.user_agent_suffix = Some(suffix.into());
+        self
+    }
+
+    /// Routes every request through an egress proxy, e.g. `http://proxy.internal:3128`.
+    ///
+    /// `socks5://` and `socks5h://` URLs require the `socks` feature. Not supported on wasm,
+    /// where the browser's proxy settings apply.
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(ProxyConfig {
+            url: url.into(),
+            basic_auth: None,
+        });
+        self
+    }
+
+    /// Sets the credentials for the proxy configured with [`proxy`](Self::proxy).
+    pub fn proxy_basic_auth(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        if let Some(proxy) = &mut self.proxy {
+            proxy.basic_auth = Some((username.into(), password.into()));
+        }
+        self
+    }
+
+    /// Sends requests through a pre-built `reqwest::Client` instead of building one.
+    ///
+    /// Configure timeouts, pooling and HTTP/2 on `client` itself; combining this with any of
+    /// the builder's transport settings fails the build.
+    pub fn http_client(mut self, client: Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Sets the [`ApiVersion`] of the swap endpoints.
+    pub fn api_version(mut self, api_version: ApiVersion) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    /// Validates the configuration and builds the client.
+    ///
+    /// # Errors
+    /// * the base URL is not an absolute `http`/`https` URL.
+    /// * the Pro tier is selected without an API key, or the API key is empty.
+    /// * a header name or value is invalid.
+    /// * timeouts, pool or HTTP/2 settings or a proxy are set together with an injected HTTP client.
+    /// * the proxy URL is invalid or uses an unsupported scheme.
+    pub fn build(self) -> Result<JupiterClient, JupiterClientError> {
+        let tier = self.tier.unwrap_or(match self.api_key {
+            Some(_) => ApiTier::Pro,
+            None => ApiTier::Lite,
+        });
+
+        if self
+            .api_key
+            .as_deref()
+            .is_some_and(|key| key.trim().is_empty())
+        {
+            return Err(JupiterClientError::ConfigError(
+                "API key must not be empty".to_string(),
+            ));
+        }
+        if self.base_url.is_none() && tier == ApiTier::Pro && self.api_key.is_none() {
+            return Err(JupiterClientError::ConfigError(
+                "the Pro tier requires an API key".to_string(),
+            ));
+        }
+
+        let base_url = self.base_url.unwrap_or_else(|| tier.base_url().to_string());
+        let url = Url::parse(&base_url)
+            .map_err(|e| JupiterClientError::ConfigError(format!("invalid base URL: {e}")))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(JupiterClientError::ConfigError(format!(
+                "base URL must use http or https, got {}",
+                url.scheme()
+            )));
+        }
+
+        let mut config = ClientConfig {
+            api_key: self.api_key,
+            timeout: self.timeout,
+            connect_timeout: self.connect_timeout,
+            custom_client: self.http_client.is_some(),
+            user_agent_suffix: self.user_agent_suffix,
+            proxy: self.proxy,
+            ..self.transport
+        };
+        if config.custom_client && config.has_transport_settings() {
+            return Err(JupiterClientError::ConfigError(
+                "timeouts, pooling, HTTP/2 and proxies must be set on the injected reqwest::Client"
+                    .to_string(),
+            ));
+        }
+        for (name, value) in &self.default_headers {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                JupiterClientError::ConfigError(format!("invalid header name {name}: {e}"))
+            })?;
+            config
+                .default_headers
+                .insert(name, HeaderValue::from_str(value)?);
+        }
+
+        let client = match self.http_client {
+            Some(client) => {
+                // surfaces invalid API keys now rather than on the first request
+                config.headers()?;
+                client
+            }
+            None => config.try_build_client()?,
+        };
+
+        Ok(
+            JupiterClient::from_parts(base_url.trim_end_matches('/'), client, config)
+                .with_api_version(self.api_version),
+        )
+    }
+}
@@ -0,0 +1,173 @@
+use std::{fmt, future::Future, pin::Pin, sync::Arc};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use super::REQUEST_ID_HEADER;
+use crate::{JupiterClientError, rt::Instant};
+
+type CancelSignal = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Options applied to individual calls, through [`JupiterClient::for_call`](super::JupiterClient::for_call).
+///
+/// # Example
+///
+/// ```
+/// let opts = CallOpts::new()
+///     .request_id(job.id.to_string())
+///     .header("x-partner-token", &tenant.partner_token);
+/// let quote = api.for_call(&opts).get_quote(&request).await?;
+/// ```
+#[derive(Clone, Default)]
+pub struct CallOpts {
+    headers: Vec<(String, String)>,
+    base_url: Option<String>,
+    cancel: Option<Arc<dyn Fn() -> CancelSignal + Send + Sync>>,
+    deadline: Option<Instant>,
+}
+
+impl CallOpts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a header sent with the call, replacing a client-wide header of the same name.
+    ///
+    /// Names and values are validated when the call is made; invalid ones fail the call
+    /// with [`JupiterClientError::ConfigError`] or [`JupiterClientError::HeaderError`].
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sends the call with `id` as its [`REQUEST_ID_HEADER`] instead of a generated one, e.g.
+    /// to reuse an ID from your own logs or to keep it stable when retrying a job.
+    ///
+    /// The ID is attached to every HTTP request made by the call, including retries, and to
+    /// the errors Jupiter answers with, see [`JupiterClientError::request_id`].
+    pub fn request_id(self, id: impl Into<String>) -> Self {
+        self.header(REQUEST_ID_HEADER, id)
+    }
+
+    /// Sends the call to `base_url` instead of the client's, e.g. a canary or self-hosted
+    /// deployment that should answer one specific quote.
+    ///
+    /// The URL must serve the client's [`ApiVersion`](super::ApiVersion).
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Aborts each call as soon as the future returned by `cancel` completes, failing it
+    /// with [`JupiterClientError::Cancelled`].
+    ///
+    /// `cancel` is called once per HTTP call. The in-flight request is dropped, closing its
+    /// connection instead of waiting for the response, and pending retries are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let (skip_tx, skip_rx) = tokio::sync::watch::channel(false);
+    /// let opts = CallOpts::new().cancel_on(move || {
+    ///     let mut skip_rx = skip_rx.clone();
+    ///     async move {
+    ///         let _ = skip_rx.wait_for(|skip| *skip).await;
+    ///     }
+    /// });
+    /// ```
+    pub fn cancel_on<F, Fut>(mut self, cancel: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.cancel = Some(Arc::new(move || Box::pin(cancel())));
+        self
+    }
+
+    /// Aborts each call once `token` is cancelled. See [`cancel_on`](Self::cancel_on).
+    ///
+    /// Requires the `cancellation-token` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let token = CancellationToken::new();
+    /// let quote = api
+    ///     .for_call(&CallOpts::new().cancellation_token(token.child_token()))
+    ///     .get_quote(&request);
+    ///
+    /// // elsewhere, once the strategy decides to skip the trade
+    /// token.cancel();
+    /// ```
+    #[cfg(feature = "cancellation-token")]
+    pub fn cancellation_token(self, token: tokio_util::sync::CancellationToken) -> Self {
+        self.cancel_on(move || token.clone().cancelled_owned())
+    }
+
+    /// Fails every call still running at `deadline` with
+    /// [`JupiterClientError::DeadlineExceeded`], as measured by the client's
+    /// [`Clock`](crate::clock::Clock).
+    ///
+    /// The deadline is shared by every HTTP call made through the client, so one deadline
+    /// bounds a whole composite operation: each call, its retries and rate-limit waits, and
+    /// the waits between calls. Calls started after the deadline fail without being sent, and
+    /// retries whose backoff would end past it are given up right away.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let opts = CallOpts::new().deadline(Instant::now() + Duration::from_millis(800));
+    /// let api = api.for_call(&opts);
+    /// let stability = api.sample_route_stability(&request, 4, Duration::from_millis(150)).await?;
+    /// let quote = api.get_quote(&request).await?;
+    /// ```
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+
+    pub(crate) fn header_map(&self) -> Result<HeaderMap, JupiterClientError> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                JupiterClientError::ConfigError(format!("invalid header name {name}: {e}"))
+            })?;
+            headers.insert(name, HeaderValue::from_str(value)?);
+        }
+        Ok(headers)
+    }
+
+    pub(crate) fn request_id_override(&self) -> Option<&str> {
+        self.headers
+            .iter()
+            .rev()
+            .find(|(name, _)| name.eq_ignore_ascii_case(REQUEST_ID_HEADER))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub(crate) fn base_url_override(&self) -> Option<&str> {
+        self.base_url.as_deref()
+    }
+
+    pub(crate) fn cancel_signal(&self) -> Option<CancelSignal> {
+        self.cancel.as_ref().map(|cancel| cancel())
+    }
+
+    pub(crate) fn deadline_at(&self) -> Option<Instant> {
+        self.deadline
+    }
+}
+
+impl fmt::Debug for CallOpts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallOpts")
+            .field("headers", &self.headers)
+            .field("base_url", &self.base_url)
+            .field("cancellable", &self.cancel.is_some())
+            .field("deadline", &self.deadline)
+            .finish()
+    }
+}
@@ -0,0 +1,100 @@
+use std::future::Future;
+
+use super::JupiterClient;
+use crate::{
+    error::JupiterClientError,
+    types::{
+        DustSweepConfig, DustSweepReport, QuoteRequest, SkippedToken, SwapRequest, SwapResponse,
+        SweptToken, select_dust,
+    },
+};
+
+impl JupiterClient {
+    /// Converts every balance worth less than `config.usd_threshold` into `config.target_mint`.
+    ///
+    /// Lists the wallet's balances, prices them, then quotes and builds one swap per dust
+    /// balance, swapping the whole balance. Each unsigned transaction is handed to `send`, which
+    /// signs and submits it and returns the signature. Failures of individual tokens are
+    /// reported as skipped instead of aborting the sweep.
+    ///
+    /// Token accounts are not closed by the sweep, as its swaps may not have landed yet. Once
+    /// they are confirmed, [`plan_account_closure`](crate::ata::plan_account_closure) with
+    /// [`DustSweepReport::swept_mints`] builds the instructions closing the emptied accounts
+    /// and reclaiming their rent.
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet to sweep, which also signs every swap.
+    /// * `config` - The target mint, USD threshold and swap settings.
+    /// * `send` - Signs and submits a swap transaction, returning its signature.
+    ///
+    /// # Example
+    /// ```
+    /// let config = DustSweepConfig::new(USDC_MINT, 1.0).slippage_bps(300);
+    /// let report = api
+    ///     .sweep_dust(&wallet, &config, |swap| async move { sign_and_send(swap).await })
+    ///     .await?;
+    /// println!("swept ${:.2} into USDC", report.total_usd_swept);
+    /// ```
+    pub async fn sweep_dust<F, Fut>(
+        &self,
+        wallet: &str,
+        config: &DustSweepConfig,
+        mut send: F,
+    ) -> Result<DustSweepReport, JupiterClientError>
+    where
+        F: FnMut(SwapResponse) -> Fut,
+        Fut: Future<Output = Result<String, JupiterClientError>>,
+    {
+        let holdings = self
+            .get_wallet_holdings(wallet, &config.holdings_source)
+            .await?;
+
+        let mints: Vec<String> = holdings.token_mints().map(str::to_string).collect();
+        let prices = self.get_prices(&mints).await?;
+
+        let (candidates, skipped) = select_dust(&holdings, &prices, config);
+        let mut report = DustSweepReport {
+            skipped,
+            ..DustSweepReport::default()
+        };
+
+        for candidate in candidates {
+            let mut request =
+                QuoteRequest::new(&candidate.mint, &config.target_mint, candidate.amount);
+            if let Some(slippage_bps) = config.slippage_bps {
+                request = request.slippage_bps(slippage_bps);
+            }
+
+            let result = async {
+                let quote = self.get_swap_quote(&request).await?;
+                let expected_out = quote.out_amount.parse::<u64>().unwrap_or_default();
+                let swap = self
+                    .get_swap_transaction(&SwapRequest::new(wallet, wallet, quote))
+                    .await?;
+                let signature = send(swap).await?;
+                Ok::<_, JupiterClientError>((expected_out, signature))
+            }
+            .await;
+
+            match result {
+                Ok((expected_out, signature)) => {
+                    report.total_usd_swept += candidate.usd_value;
+                    report.total_expected_out += expected_out;
+                    report.swept.push(SweptToken {
+                        mint: candidate.mint,
+                        amount: candidate.amount,
+                        usd_value: candidate.usd_value,
+                        expected_out,
+                        signature,
+                    });
+                }
+                Err(e) => report.skipped.push(SkippedToken {
+                    mint: candidate.mint,
+                    reason: e.to_string(),
+                }),
+            }
+        }
+
+        Ok(report)
+    }
+}
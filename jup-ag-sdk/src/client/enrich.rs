@@ -0,0 +1,35 @@
+use super::JupiterClient;
+use crate::{
+    error::JupiterClientError,
+    types::{EnrichedQuote, QuoteResponse},
+};
+
+impl JupiterClient {
+    /// Resolves symbols, decimals, logos and UI amounts for the input, output and every hop
+    /// of `quote`.
+    ///
+    /// Looks up the metadata of every mint on the route in a single Token API call.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::MissingTokenMetadata`] when the Token API doesn't know a mint.
+    ///
+    /// # Example
+    /// ```
+    /// let quote = api.get_quote(&request).await?;
+    /// let enriched = api.enrich(&quote).await?;
+    /// for hop in &enriched.hops {
+    ///     println!(
+    ///         "{}: {} {} → {} {}",
+    ///         hop.label,
+    ///         hop.input.ui_amount,
+    ///         hop.input.token.symbol,
+    ///         hop.output.ui_amount,
+    ///         hop.output.token.symbol,
+    ///     );
+    /// }
+    /// ```
+    pub async fn enrich(&self, quote: &QuoteResponse) -> Result<EnrichedQuote, JupiterClientError> {
+        let tokens = self.get_token_metas(&EnrichedQuote::mints(quote)).await?;
+        EnrichedQuote::new(quote, &tokens)
+    }
+}
@@ -0,0 +1,29 @@
+use super::JupiterClient;
+use crate::{
+    error::JupiterClientError,
+    types::{FeeAttribution, QuoteResponse},
+};
+
+impl JupiterClient {
+    /// Normalizes the per-hop and platform fees of `quote` into one figure, in USD, in the
+    /// output mint and in bps of the output, so routes can be compared on cost.
+    ///
+    /// Prices every fee mint and the output mint in a single Price API call.
+    ///
+    /// # Example
+    /// ```
+    /// let direct = api.get_quote(&request.clone().only_direct_routes(true)).await?;
+    /// let routed = api.get_quote(&request).await?;
+    /// for quote in [&direct, &routed] {
+    ///     let fees = api.attribute_fees(quote).await?;
+    ///     println!("{:.1} bps (${:.4}) in fees", fees.total_bps, fees.total_usd);
+    /// }
+    /// ```
+    pub async fn attribute_fees(
+        &self,
+        quote: &QuoteResponse,
+    ) -> Result<FeeAttribution, JupiterClientError> {
+        let prices = self.get_prices(&FeeAttribution::mints(quote)).await?;
+        Ok(FeeAttribution::new(quote, &prices))
+    }
+}
@@ -0,0 +1,56 @@
+use reqwest::StatusCode;
+
+use super::JupiterClient;
+use crate::{JupiterClientError, retry::RetryPolicy, rt::Instant, types::HealthReport};
+
+impl JupiterClient {
+    /// Pings the cheap `program-id-to-label` endpoint and reports whether Jupiter is
+    /// reachable and how long it took, e.g. to gate startup or back a readiness probe.
+    ///
+    /// The probe is sent once, without retries and bypassing the circuit breaker, so it
+    /// reflects the current state of the API. It never fails; errors are reported in the
+    /// [`HealthReport`].
+    ///
+    /// # Example
+    /// ```
+    /// let health = api.health().await;
+    /// if !health.is_healthy() {
+    ///     eprintln!("jupiter unavailable after {:?}: {:?}", health.latency, health.error);
+    /// }
+    /// ```
+    pub async fn health(&self) -> HealthReport {
+        let probe = JupiterClient {
+            retry_policy: RetryPolicy::disabled(),
+            circuit_breaker: None,
+            ..self.clone()
+        };
+
+        let started = Instant::now();
+        let result = probe
+            .send(probe.client.get(probe.swap_url("program-id-to-label")))
+            .await;
+        let latency = started.elapsed();
+
+        match result {
+            Ok(response) => HealthReport {
+                reachable: true,
+                status: Some(response.status()),
+                latency,
+                error: None,
+            },
+            Err(e) => {
+                let status = match &e {
+                    JupiterClientError::ApiError { status, .. } => Some(*status),
+                    JupiterClientError::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+                    _ => None,
+                };
+                HealthReport {
+                    reachable: status.is_some(),
+                    status,
+                    latency,
+                    error: Some(e.to_string()),
+                }
+            }
+        }
+    }
+}
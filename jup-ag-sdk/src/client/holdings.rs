@@ -0,0 +1,126 @@
+use reqwest::Client;
+use serde::Deserialize;
+
+use super::JupiterClient;
+use crate::{
+    ata::{TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    error::JupiterClientError,
+    submission::rpc_call,
+    types::{Holding, HoldingsSource, NATIVE_SOL, WalletHoldings},
+};
+
+#[derive(Deserialize)]
+struct RpcValue<T> {
+    value: T,
+}
+
+#[derive(Deserialize)]
+struct TokenAccount {
+    account: TokenAccountData,
+}
+
+#[derive(Deserialize)]
+struct TokenAccountData {
+    data: ParsedData,
+}
+
+#[derive(Deserialize)]
+struct ParsedData {
+    parsed: ParsedAccount,
+}
+
+#[derive(Deserialize)]
+struct ParsedAccount {
+    info: TokenAccountInfo,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenAccountInfo {
+    mint: String,
+    state: String,
+    token_amount: TokenAmount,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenAmount {
+    amount: String,
+    decimals: u8,
+    ui_amount: Option<f64>,
+}
+
+impl JupiterClient {
+    /// Lists every balance of `wallet`, native SOL included.
+    ///
+    /// This is the account scan behind [`sweep_dust`](Self::sweep_dust) and
+    /// [`convert_for_withdrawal`](Self::convert_for_withdrawal); use it directly to feed
+    /// your own rebalancing logic.
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet whose balances are listed.
+    /// * `source` - Jupiter's Ultra balances endpoint, or a Solana RPC.
+    ///
+    /// # Example
+    /// ```
+    /// let holdings = api
+    ///     .get_wallet_holdings(&wallet, &HoldingsSource::Rpc(rpc_url.clone()))
+    ///     .await?;
+    /// for holding in &holdings.holdings {
+    ///     println!("{}: {}", holding.mint, holding.ui_amount);
+    /// }
+    /// ```
+    pub async fn get_wallet_holdings(
+        &self,
+        wallet: &str,
+        source: &HoldingsSource,
+    ) -> Result<WalletHoldings, JupiterClientError> {
+        match source {
+            HoldingsSource::Ultra => Ok(WalletHoldings::from_balances(
+                wallet,
+                self.get_token_balances(wallet).await?,
+            )),
+            HoldingsSource::Rpc(rpc_url) => fetch_rpc_holdings(rpc_url, wallet).await,
+        }
+    }
+}
+
+async fn fetch_rpc_holdings(
+    rpc_url: &str,
+    wallet: &str,
+) -> Result<WalletHoldings, JupiterClientError> {
+    let client = Client::new();
+
+    let lamports: RpcValue<u64> =
+        rpc_call(&client, rpc_url, "getBalance", serde_json::json!([wallet])).await?;
+    let mut holdings = vec![Holding {
+        mint: NATIVE_SOL.to_string(),
+        amount: lamports.value,
+        ui_amount: lamports.value as f64 / 1e9,
+        decimals: Some(9),
+        is_frozen: false,
+    }];
+
+    for program_id in [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+        let accounts: RpcValue<Vec<TokenAccount>> = rpc_call(
+            &client,
+            rpc_url,
+            "getTokenAccountsByOwner",
+            serde_json::json!([wallet, { "programId": program_id }, { "encoding": "jsonParsed" }]),
+        )
+        .await?;
+
+        holdings.extend(accounts.value.into_iter().map(|account| {
+            let info = account.account.data.parsed.info;
+            Holding {
+                mint: info.mint,
+                amount: info.token_amount.amount.parse().unwrap_or_default(),
+                ui_amount: info.token_amount.ui_amount.unwrap_or_default(),
+                decimals: Some(info.token_amount.decimals),
+                is_frozen: info.state == "frozen",
+            }
+        }));
+    }
+
+    Ok(WalletHoldings::from_holdings(wallet, holdings))
+}
@@ -0,0 +1,34 @@
+use super::JupiterClient;
+use crate::{
+    audit::now_ms,
+    error::JupiterClientError,
+    portfolio::PortfolioSnapshot,
+    types::{HoldingsSource, NATIVE_SOL, holdings::WSOL_MINT},
+};
+
+impl JupiterClient {
+    /// Values the holdings of `wallet` at current Jupiter prices.
+    ///
+    /// Reads the balances from `source`, then prices every held mint in Price API batches.
+    /// Use a [`PortfolioTracker`](crate::portfolio::PortfolioTracker) to persist snapshots and
+    /// report P&L over them.
+    ///
+    /// # Example
+    /// ```
+    /// let snapshot = api.portfolio_snapshot(&wallet, &HoldingsSource::Ultra).await?;
+    /// println!("${:.2}", snapshot.total_usd());
+    /// ```
+    pub async fn portfolio_snapshot(
+        &self,
+        wallet: &str,
+        source: &HoldingsSource,
+    ) -> Result<PortfolioSnapshot, JupiterClientError> {
+        let holdings = self.get_wallet_holdings(wallet, source).await?;
+        let mut mints: Vec<String> = holdings.token_mints().map(str::to_string).collect();
+        if holdings.amount(NATIVE_SOL) > 0 && !mints.iter().any(|mint| mint == WSOL_MINT) {
+            mints.push(WSOL_MINT.to_string());
+        }
+        let prices = self.get_prices(&mints).await?;
+        Ok(PortfolioSnapshot::new(&holdings, &prices, now_ms()))
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use super::JupiterClient;
+use crate::{
+    error::JupiterClientError,
+    types::{Price, TokenPrice, TokenPriceRequest},
+};
+
+/// The Price API v2 response, which reports unpriced mints as `null`.
+#[derive(Deserialize)]
+struct ExtraInfoResponse {
+    data: HashMap<String, Option<TokenPrice>>,
+}
+
+/// The Price API accepts at most 50 ids per request.
+const PRICE_BATCH_SIZE: usize = 50;
+
+impl JupiterClient {
+    /// Returns the USD spot price of `mints` from Price API v3, keyed by mint.
+    ///
+    /// Any number of mints can be passed; they're requested in batches of 50. Mints without a
+    /// reliable price are missing from the map.
+    ///
+    /// # Example
+    /// ```
+    /// let prices = api.get_prices(&[SOL_MINT.to_string(), JUP_MINT.to_string()]).await?;
+    /// if let Some(sol) = prices.get(SOL_MINT) {
+    ///     println!("SOL ${:.2} ({:+.2}% 24h)", sol.usd_price, sol.price_change_24h);
+    /// }
+    /// ```
+    pub async fn get_prices(
+        &self,
+        mints: &[String],
+    ) -> Result<HashMap<String, Price>, JupiterClientError> {
+        let mut prices = HashMap::new();
+        for batch in mints.chunks(PRICE_BATCH_SIZE) {
+            prices.extend(self.get_tokens_price(batch).await?);
+        }
+        Ok(prices)
+    }
+
+    /// Returns the USD price of `mints` with the Price API's extra info: confidence level,
+    /// buy and sell depth, and the last swapped and quoted prices, keyed by mint.
+    ///
+    /// Use it to decide whether a price is trustworthy before sizing a trade on it. Only the
+    /// Price API v2 serves `showExtraInfo`. Mints are requested in batches of 50; mints
+    /// without a price are missing from the map.
+    ///
+    /// # Example
+    /// ```
+    /// let prices = api.get_prices_with_extra_info(&[mint.to_string()]).await?;
+    /// let trusted = prices[mint].extra_info.as_ref().is_some_and(|info| {
+    ///     info.confidence_level >= ConfidenceLevel::Medium
+    ///         && info
+    ///             .depth
+    ///             .as_ref()
+    ///             .and_then(|d| d.sell_price_impact_ratio.as_ref())
+    ///             .and_then(|d| d.impact_at(1000))
+    ///             .is_some_and(|impact| impact < 0.01)
+    /// });
+    /// ```
+    pub async fn get_prices_with_extra_info(
+        &self,
+        mints: &[String],
+    ) -> Result<HashMap<String, TokenPrice>, JupiterClientError> {
+        let mut prices = HashMap::new();
+        for batch in mints.chunks(PRICE_BATCH_SIZE) {
+            let params = TokenPriceRequest::new(batch).with_show_extra_info(true);
+            let response = self
+                .send(
+                    self.client
+                        .get(format!("{}/price/v2", self.base_url))
+                        .query(&params),
+                )
+                .await?;
+
+            let batch = self.read_json::<ExtraInfoResponse>(response).await?;
+            prices.extend(
+                batch
+                    .data
+                    .into_iter()
+                    .filter_map(|(mint, price)| Some((mint, price?))),
+            );
+        }
+        Ok(prices)
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     JupiterClientError,
-    error::handle_response,
+    policy::SigningIntent,
     types::{
         CancelRecurringOrderRequest, CreateRecurringOrderRequest, ExecuteRecurringRequest,
         ExecuteRecurringResponse, GetRecurringOrders, PriceDeposit, PriceWithdraw, RecurringOrders,
@@ -13,28 +13,27 @@ use super::JupiterClient;
 impl JupiterClient {
     /// Sends a request to create a new recurring order.
     ///
-    /// Returns a base64-encoded unsigned transaction to be signed and a request _id.
+    /// Returns a base64-encoded unsigned transaction to be signed and a request _id. The order
+    /// is [validated](CreateRecurringOrderRequest::validate) first, so requests Jupiter would
+    /// reject fail without a round trip.
     pub async fn create_recurring_order(
         &self,
         data: &CreateRecurringOrderRequest,
     ) -> Result<RecurringResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/recurring/v1/createOrder", self.base_url))
-            .json(data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
+        data.validate()?;
+        let approval = self.check_policies(|| Ok(SigningIntent::from_recurring(data)))?;
+        let created = async {
+            let response = self
+                .send(
+                    self.client
+                        .post(format!("{}/recurring/v1/createOrder", self.base_url))
+                        .json(data),
+                )
+                .await?;
+
+            self.read_json::<RecurringResponse>(response).await
         };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<RecurringResponse>().await {
-            Ok(resp) => Ok(resp),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        created.await.inspect_err(|_| approval.release())
     }
 
     /// Request for a base64-encoded unsigned recurring order cancellation transaction
@@ -42,71 +41,70 @@ impl JupiterClient {
         &self,
         data: &CancelRecurringOrderRequest,
     ) -> Result<RecurringResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/recurring/v1/cancelOrder", self.base_url))
-            .json(data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<RecurringResponse>().await {
-            Ok(resp) => Ok(resp),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        let response = self
+            .send(
+                self.client
+                    .post(format!("{}/recurring/v1/cancelOrder", self.base_url))
+                    .json(data),
+            )
+            .await?;
+
+        self.read_json::<RecurringResponse>(response).await
     }
 
-    /// Request for a base64-encoded unsigned price-based recurring order deposit transaction
+    /// Request for a base64-encoded unsigned price-based recurring order deposit transaction.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::InvalidPubkey`] or [`JupiterClientError::InvalidRecurringOrder`]
+    /// when [`PriceDeposit::validate`] fails, before anything is sent.
+    ///
+    /// # Example
+    /// ```rust
+    /// let response = client
+    ///     .price_deposit_recurring(&PriceDeposit::new(50_000_000, order_key, wallet))
+    ///     .await?;
+    /// let signed = sign_transaction(response.transaction);
+    /// client
+    ///     .execute_recurring_order(&ExecuteRecurringRequest::new(response.request_id, signed))
+    ///     .await?
+    ///     .into_result()?;
+    /// ```
     pub async fn price_deposit_recurring(
         &self,
         data: &PriceDeposit,
     ) -> Result<RecurringResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/recurring/v1/priceDeposit", self.base_url))
-            .json(data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<RecurringResponse>().await {
-            Ok(resp) => Ok(resp),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        data.validate()?;
+        let response = self
+            .send(
+                self.client
+                    .post(format!("{}/recurring/v1/priceDeposit", self.base_url))
+                    .json(data),
+            )
+            .await?;
+
+        self.read_json::<RecurringResponse>(response).await
     }
 
-    /// Request for a base64-encoded unsigned price-based recurring order withdrawal transaction
+    /// Request for a base64-encoded unsigned price-based recurring order withdrawal
+    /// transaction. [`PriceWithdraw::all`] drains the whole balance of one token.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::InvalidPubkey`] or [`JupiterClientError::InvalidRecurringOrder`]
+    /// when [`PriceWithdraw::validate`] fails, before anything is sent.
     pub async fn price_withdraw_recurring(
         &self,
         data: &PriceWithdraw,
     ) -> Result<RecurringResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/recurring/v1/priceWithdraw", self.base_url))
-            .json(data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<RecurringResponse>().await {
-            Ok(resp) => Ok(resp),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        data.validate()?;
+        let response = self
+            .send(
+                self.client
+                    .post(format!("{}/recurring/v1/priceWithdraw", self.base_url))
+                    .json(data),
+            )
+            .await?;
+
+        self.read_json::<RecurringResponse>(response).await
     }
 
     /// execute a recurring order
@@ -114,23 +112,15 @@ impl JupiterClient {
         &self,
         data: &ExecuteRecurringRequest,
     ) -> Result<ExecuteRecurringResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/recurring/v1/execute", self.base_url))
-            .json(data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<ExecuteRecurringResponse>().await {
-            Ok(resp) => Ok(resp),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        let response = self
+            .send(
+                self.client
+                    .post(format!("{}/recurring/v1/execute", self.base_url))
+                    .json(data),
+            )
+            .await?;
+
+        self.read_json::<ExecuteRecurringResponse>(response).await
     }
 
     /// Request for the active or historical orders associated to the provided account
@@ -138,22 +128,14 @@ impl JupiterClient {
         &self,
         data: &GetRecurringOrders,
     ) -> Result<RecurringOrders, JupiterClientError> {
-        let response = match self
-            .client
-            .get(format!("{}/recurring/v1/getRecurringOrders", self.base_url))
-            .query(&data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<RecurringOrders>().await {
-            Ok(orders) => Ok(orders),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        let response = self
+            .send(
+                self.client
+                    .get(format!("{}/recurring/v1/getRecurringOrders", self.base_url))
+                    .query(&data),
+            )
+            .await?;
+
+        self.read_json::<RecurringOrders>(response).await
     }
 }
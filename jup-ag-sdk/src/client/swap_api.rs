@@ -1,7 +1,25 @@
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    time::Duration,
+};
+
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+
 use super::JupiterClient;
 use crate::{
-    error::{JupiterClientError, handle_response},
-    types::{QuoteRequest, QuoteResponse, SwapInstructions, SwapRequest, SwapResponse},
+    ata::decode_pubkey,
+    audit::{AuditOperation, AuditRecord},
+    error::JupiterClientError,
+    policy::{IntentSource, SigningIntent},
+    rt::{self, Instant},
+    types::{
+        DexEnum, MidPrice, Profile, QuoteQuery, QuoteRequest, QuoteResponse, QuoteTemplate,
+        RouteStability, SizeSuggestion, SlippageComparison, SwapInstructions, SwapRequest,
+        SwapResponse, failing_program_id,
+    },
+    venue_health::VenueFailure,
 };
 
 impl JupiterClient {
@@ -29,32 +47,181 @@ impl JupiterClient {
     /// let req = QuoteRequest::new(inputMint, outputMint, amount);
     /// let quote = api.get_quote(req).await?;
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        name = "jupiter.quote",
+        skip_all,
+        err,
+        fields(
+            endpoint = "quote",
+            input_mint = %params.input_mint,
+            output_mint = %params.output_mint,
+            amount = params.amount,
+            status = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+        ),
+    ))]
     pub async fn get_quote(
         &self,
         params: &QuoteRequest,
     ) -> Result<QuoteResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .get(format!("{}/swap/v1/quote", &self.base_url))
-            .query(&params)
-            .send()
+        let params = self.resolve_quote_request(params);
+        self.check_slippage(&params)?;
+        self.check_venues(&params)?;
+        self.get_resolved_quote(params, false).await
+    }
+
+    /// Like [`get_quote`](Self::get_quote) for quotes a transaction is built from: a cached
+    /// quote is only reused when it quotes exactly the requested amount, never a bucketed one
+    /// of another amount in the same bucket.
+    pub(crate) async fn get_swap_quote(
+        &self,
+        params: &QuoteRequest,
+    ) -> Result<QuoteResponse, JupiterClientError> {
+        let params = self.resolve_quote_request(params);
+        self.check_slippage(&params)?;
+        self.check_venues(&params)?;
+        self.get_resolved_quote(params, true).await
+    }
+
+    /// Like [`get_quote`](Self::get_quote), but always fetches a fresh quote, bypassing the
+    /// quote cache and the audit sinks.
+    pub(crate) async fn refresh_quote(
+        &self,
+        params: &QuoteRequest,
+    ) -> Result<QuoteResponse, JupiterClientError> {
+        let params = self.resolve_quote_request(params);
+        self.check_slippage(&params)?;
+        self.check_venues(&params)?;
+        let quote = self.fetch_quote(params.query()).await?;
+        self.check_route_venues(&params, &quote)?;
+        Ok(quote)
+    }
+
+    /// Resolves and validates `params` once, returning a template that can be quoted for
+    /// any amount with [`get_quote_from_template`](Self::get_quote_from_template).
+    ///
+    /// The `amount` of `params` is ignored.
+    ///
+    /// # Errors
+    /// * [`JupiterClientError::InvalidPubkey`] when a mint is not a valid public key.
+    /// * [`JupiterClientError::SlippageTooHigh`] when the resolved slippage exceeds the client's maximum.
+    ///
+    /// # Example
+    /// ```
+    /// let template = api.quote_template(QuoteRequest::new(SOL_MINT, USDC_MINT, 0).slippage_bps(50))?;
+    /// loop {
+    ///     let quote = api.get_quote_from_template(&template, next_size()).await?;
+    ///     // ...
+    /// }
+    /// ```
+    pub fn quote_template(
+        &self,
+        params: QuoteRequest,
+    ) -> Result<QuoteTemplate, JupiterClientError> {
+        decode_pubkey(&params.input_mint)?;
+        decode_pubkey(&params.output_mint)?;
+        let params = self.resolve_quote_request(&params);
+        self.check_slippage(&params)?;
+        self.check_venues(&params)?;
+        Ok(QuoteTemplate::new(params))
+    }
+
+    /// Like [`get_quote`](Self::get_quote) for `template` stamped with `amount`, skipping
+    /// defaults resolution and validation already done by [`quote_template`](Self::quote_template).
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        name = "jupiter.quote",
+        skip_all,
+        err,
+        fields(
+            endpoint = "quote",
+            input_mint = %template.input_mint(),
+            output_mint = %template.output_mint(),
+            amount = amount,
+            status = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+        ),
+    ))]
+    pub async fn get_quote_from_template(
+        &self,
+        template: &QuoteTemplate,
+        amount: u64,
+    ) -> Result<QuoteResponse, JupiterClientError> {
+        if self.quote_cache.is_none() && !self.is_audited() {
+            // nothing keeps the request, so quote straight from the template's borrowed query
+            return self
+                .fetch_checked_quote(template.params(), template.query(amount))
+                .await;
+        }
+        self.get_resolved_quote(template.request(amount), false)
             .await
+    }
+
+    /// Quotes a request that already went through defaults and validation. With
+    /// `exact_amount`, cached quotes of another amount in the same bucket are skipped.
+    async fn get_resolved_quote(
+        &self,
+        params: QuoteRequest,
+        exact_amount: bool,
+    ) -> Result<QuoteResponse, JupiterClientError> {
+        if let Some(quote) = self
+            .quote_cache
+            .as_ref()
+            .and_then(|c| c.get(&params, self.clock.now(), exact_amount))
         {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+            return Ok(quote);
+        }
 
-        let response = handle_response(response).await?;
+        let record = self
+            .is_audited()
+            .then(|| AuditRecord::new(AuditOperation::Quote).quote_request(&params));
+        let started = Instant::now();
 
-        let text = response.text().await?;
+        let result = self.fetch_checked_quote(&params, params.query()).await;
 
-        match serde_json::from_str::<QuoteResponse>(&text) {
-            Ok(quote_response) => Ok(quote_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(format!(
-                "Failed to deserialize QuoteResponse: {}. Response text: {}",
-                e, text
-            ))),
+        if let Some(record) = record {
+            let record = match &result {
+                Ok(quote) => record.quote(quote.clone()),
+                Err(e) => record.error(e),
+            };
+            self.audit(record.duration(started.elapsed()));
+        }
+        if let (Ok(quote), Some(cache)) = (&result, &self.quote_cache) {
+            cache.insert(&params, quote, self.clock.now());
         }
+
+        result
+    }
+
+    /// Quotes every request in `params` concurrently, with at most `max_concurrency` in flight.
+    ///
+    /// Results are returned in the order of `params`, each with its own error, so one pair
+    /// that can't be routed doesn't fail the batch. Defaults, the quote cache, retries and
+    /// the rate limiter apply to each request as with [`get_quote`](Self::get_quote). A
+    /// `max_concurrency` of 0 is treated as 1.
+    ///
+    /// # Example
+    /// ```
+    /// let requests: Vec<QuoteRequest> = holdings
+    ///     .iter()
+    ///     .map(|(mint, amount)| QuoteRequest::new(mint, USDC_MINT, *amount))
+    ///     .collect();
+    /// for (request, quote) in requests.iter().zip(api.get_quotes(&requests, 8).await) {
+    ///     match quote {
+    ///         Ok(quote) => println!("{} is worth {} USDC", request.input_mint, quote.out_amount),
+    ///         Err(e) => println!("{} can't be valued: {e}", request.input_mint),
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_quotes(
+        &self,
+        params: &[QuoteRequest],
+        max_concurrency: usize,
+    ) -> Vec<Result<QuoteResponse, JupiterClientError>> {
+        let quotes = params
+            .iter()
+            .map(|request| self.get_quote(request))
+            .collect();
+        rt::join_bounded(quotes, max_concurrency).await
     }
 
     /// Fetches a swap transaction from Jupiter's `/swap` endpoint.
@@ -70,27 +237,32 @@ impl JupiterClient {
     /// let payload = SwapRequest::new("YourPubKey...", quote);
     /// let swap_transaction = api.get_swap_transaction(payload).await?;
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        name = "jupiter.swap",
+        skip_all,
+        err,
+        fields(
+            endpoint = "swap",
+            input_mint = %data.quote_response.input_mint,
+            output_mint = %data.quote_response.output_mint,
+            amount = %data.quote_response.in_amount,
+            status = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+        ),
+    ))]
     pub async fn get_swap_transaction(
         &self,
         data: &SwapRequest,
     ) -> Result<SwapResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/swap/v1/swap", self.base_url))
-            .json(&data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<SwapResponse>().await {
-            Ok(swap_response) => Ok(swap_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        let data = self.resolve_swap_request(data);
+        self.audited_swap(AuditOperation::SwapTransaction, &data, async {
+            let approval =
+                self.check_policies(|| SigningIntent::from_swap(IntentSource::Swap, &data))?;
+            self.post_swap("swap", &data)
+                .await
+                .inspect_err(|_| approval.release())
+        })
+        .await
     }
 
     /// Fetches a swap transaction from Jupiter's `/swap` endpoint.
@@ -106,26 +278,451 @@ impl JupiterClient {
     /// let payload = SwapRequest::new("YourPubKey...", quote);
     /// let swap_instructions = api.get_swap_instructions(payload).await?;
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        name = "jupiter.swap-instructions",
+        skip_all,
+        err,
+        fields(
+            endpoint = "swap-instructions",
+            input_mint = %data.quote_response.input_mint,
+            output_mint = %data.quote_response.output_mint,
+            amount = %data.quote_response.in_amount,
+            status = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+        ),
+    ))]
     pub async fn get_swap_instructions(
         &self,
         data: &SwapRequest,
     ) -> Result<SwapInstructions, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/swap/v1/swap-instructions", self.base_url))
-            .json(&data)
-            .send()
+        let data = self.resolve_swap_request(data);
+        self.audited_swap(AuditOperation::SwapInstructions, &data, async {
+            let approval = self.check_policies(|| {
+                SigningIntent::from_swap(IntentSource::SwapInstructions, &data)
+            })?;
+            self.post_swap("swap-instructions", &data)
+                .await
+                .inspect_err(|_| approval.release())
+        })
+        .await
+    }
+
+    /// Derives a mid price and spread for a pair by quoting a small size in both directions.
+    ///
+    /// Sells `base_amount` of the base mint, then quotes buying it back with the proceeds,
+    /// so both sides are measured at the same notional. Useful for long-tail tokens the Price
+    /// API doesn't cover.
+    ///
+    /// # Arguments
+    /// * `base_mint` - The mint being priced.
+    /// * `quote_mint` - The mint the price is expressed in, e.g. USDC.
+    /// * `base_amount` - Raw probe size; keep it small so price impact doesn't dominate the spread.
+    ///
+    /// # Example
+    /// ```
+    /// let price = api.get_mid_price(BONK_MINT, USDC_MINT, 1_000_000_000).await?;
+    /// println!("mid {} USDC, spread {:.1} bps", price.ui_mid(5, 6), price.spread_bps);
+    /// ```
+    pub async fn get_mid_price(
+        &self,
+        base_mint: &str,
+        quote_mint: &str,
+        base_amount: u64,
+    ) -> Result<MidPrice, JupiterClientError> {
+        let sell_base = self
+            .get_quote(&QuoteRequest::new(base_mint, quote_mint, base_amount))
+            .await?;
+
+        let quote_amount = sell_base
+            .out_amount
+            .parse::<u64>()
+            .map_err(|e| JupiterClientError::deserialization(format!("invalid out amount: {e}")))?;
+        let buy_base = self
+            .get_quote(&QuoteRequest::new(quote_mint, base_mint, quote_amount))
+            .await?;
+
+        MidPrice::from_quotes(&sell_base, &buy_base)
+    }
+
+    /// Quotes `params` `samples` times, `interval` apart, and scores how stable the route and
+    /// output amount are.
+    ///
+    /// Executors can prefer pairs and sizes with a high [`RouteStability::score`] for large
+    /// fills, where a route flipping between quote and landing costs the most.
+    ///
+    /// Under a [`CallOpts::deadline`](super::CallOpts::deadline), fails with
+    /// [`JupiterClientError::DeadlineExceeded`] as soon as the remaining samples can't fit.
+    ///
+    /// # Example
+    /// ```
+    /// let stability = api
+    ///     .sample_route_stability(&req, 5, Duration::from_millis(400))
+    ///     .await?;
+    /// if stability.score < 0.8 {
+    ///     println!("route unstable: {} routes in {} samples", stability.distinct_routes, stability.samples);
+    /// }
+    /// ```
+    pub async fn sample_route_stability(
+        &self,
+        params: &QuoteRequest,
+        samples: usize,
+        interval: Duration,
+    ) -> Result<RouteStability, JupiterClientError> {
+        let mut quotes = Vec::with_capacity(samples);
+        for i in 0..samples {
+            if i > 0 {
+                self.sleep_within_deadline(interval).await?;
+            }
+            quotes.push(self.get_quote(params).await?);
+        }
+
+        RouteStability::from_quotes(&quotes).ok_or_else(|| {
+            JupiterClientError::ConfigError("route stability needs at least one sample".to_string())
+        })
+    }
+
+    /// Fetches `params` once with a fixed `slippage_bps` and once with dynamic slippage, to
+    /// see which worst case each mode would have shipped.
+    ///
+    /// Run it over a sample of your typical pairs and sizes to decide which mode to default
+    /// to. The two quotes are fetched one after the other, so part of the difference may be
+    /// market movement.
+    ///
+    /// # Example
+    /// ```
+    /// let comparison = api.compare_slippage_modes(&req, 50).await?;
+    /// println!(
+    ///     "dynamic picked {} bps, worst case {:+.1} bps vs fixed",
+    ///     comparison.dynamic_slippage_bps(),
+    ///     comparison.dynamic_improvement_bps(),
+    /// );
+    /// ```
+    pub async fn compare_slippage_modes(
+        &self,
+        params: &QuoteRequest,
+        slippage_bps: u16,
+    ) -> Result<SlippageComparison, JupiterClientError> {
+        let fixed = params
+            .clone()
+            .dynamic_slippage(false)
+            .slippage_bps(slippage_bps);
+        let mut dynamic = params.clone().dynamic_slippage(true);
+        dynamic.slippage_bps = None;
+
+        let fixed = self.get_quote(&fixed).await?;
+        let dynamic = self.get_quote(&dynamic).await?;
+        Ok(SlippageComparison::new(fixed, dynamic))
+    }
+
+    /// Binary-searches the largest size of `params` whose price impact stays within
+    /// `max_price_impact_pct`, e.g. to suggest a "max recommended size" in an order form.
+    ///
+    /// Compared against `price_impact_pct` exactly as reported by Jupiter. Sizes Jupiter can't
+    /// route at all count as exceeding the threshold. Uses at most 13 quotes; the first one
+    /// checks the requested size as is.
+    ///
+    /// # Example
+    /// ```
+    /// let suggestion = api.suggest_max_size(&req, 1.0).await?;
+    /// if suggestion.is_liquidity_limited() {
+    ///     println!("max recommended size: {}", suggestion.max_amount);
+    /// }
+    /// ```
+    pub async fn suggest_max_size(
+        &self,
+        params: &QuoteRequest,
+        max_price_impact_pct: f64,
+    ) -> Result<SizeSuggestion, JupiterClientError> {
+        const MAX_SIZE_PROBES: u32 = 12;
+
+        let mut suggestion = SizeSuggestion {
+            requested_amount: params.amount,
+            max_amount: 0,
+            quote: None,
+            quotes_used: 0,
+        };
+
+        let (mut low, mut high) = (0, params.amount);
+        let mut amount = params.amount;
+        while suggestion.quotes_used <= MAX_SIZE_PROBES && amount > low {
+            let mut request = params.clone();
+            request.amount = amount;
+            suggestion.quotes_used += 1;
+
+            let within = match self.get_quote(&request).await {
+                Ok(quote) => {
+                    let within = quote
+                        .price_impact_pct
+                        .parse::<f64>()
+                        .is_ok_and(|impact| impact <= max_price_impact_pct);
+                    if within {
+                        suggestion.max_amount = amount;
+                        suggestion.quote = Some(quote);
+                    }
+                    within
+                }
+                Err(JupiterClientError::ApiError {
+                    status: StatusCode::BAD_REQUEST,
+                    ..
+                }) => false,
+                Err(e) => return Err(e),
+            };
+
+            if within {
+                low = amount;
+            } else {
+                high = amount;
+            }
+            amount = low + (high - low) / 2;
+        }
+
+        Ok(suggestion)
+    }
+
+    /// Fetches a quote with the options bundled by `profile` filled into unset fields.
+    ///
+    /// Fields set on `params` win over the profile, which wins over the client defaults.
+    ///
+    /// # Example
+    /// ```
+    /// let quote = api.get_quote_with_profile(&req, &Profile::Aggressive).await?;
+    /// ```
+    pub async fn get_quote_with_profile(
+        &self,
+        params: &QuoteRequest,
+        profile: &Profile,
+    ) -> Result<QuoteResponse, JupiterClientError> {
+        self.get_quote(&profile.defaults().apply_to_quote(params))
+            .await
+    }
+
+    /// Fetches a swap transaction with the options bundled by `profile` filled into unset fields.
+    pub async fn get_swap_transaction_with_profile(
+        &self,
+        data: &SwapRequest,
+        profile: &Profile,
+    ) -> Result<SwapResponse, JupiterClientError> {
+        self.get_swap_transaction(&profile.defaults().apply_to_swap(data))
+            .await
+    }
+
+    /// Fetches swap instructions with the options bundled by `profile` filled into unset fields.
+    pub async fn get_swap_instructions_with_profile(
+        &self,
+        data: &SwapRequest,
+        profile: &Profile,
+    ) -> Result<SwapInstructions, JupiterClientError> {
+        self.get_swap_instructions(&profile.defaults().apply_to_swap(data))
             .await
+    }
+
+    /// Returns the program id to venue label registry used by the routing engine.
+    ///
+    /// The labels map onto [`DexEnum`](crate::types::DexEnum) values and can be used
+    /// with [`QuoteRequest::exclude_program_ids`] to block venues by program id.
+    ///
+    /// # Jupiter API Reference
+    ///
+    /// - [Program ID to Label Endpoint](https://dev.jup.ag/docs/api/swap-api/program-id-to-label)
+    ///
+    /// # Example
+    /// ```
+    /// let labels = api.get_program_id_to_label().await?;
+    /// println!("{:?}", labels.get("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"));
+    /// ```
+    pub async fn get_program_id_to_label(
+        &self,
+    ) -> Result<HashMap<String, String>, JupiterClientError> {
+        let response = self
+            .send(self.client.get(self.swap_url("program-id-to-label")))
+            .await?;
+
+        self.read_json::<HashMap<String, String>>(response).await
+    }
+
+    /// Checks that every venue in `dexes` is served by the routing engine, according to
+    /// [`get_program_id_to_label`](Self::get_program_id_to_label).
+    ///
+    /// The API ignores unknown labels in `dexes` and `excludeDexes`, so a misspelled
+    /// [`DexEnum::Other`](crate::types::DexEnum::Other) or a retired venue silently widens or
+    /// narrows routing. Validate configured venue lists once at startup.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::UnknownDex`] with the first label missing from the registry.
+    ///
+    /// # Example
+    /// ```
+    /// let venues = vec![DexEnum::Whirlpool, DexEnum::Other("Meteora DAMM v2".into())];
+    /// api.validate_dexes(&venues).await?;
+    /// let request = request.dexes(venues);
+    /// ```
+    pub async fn validate_dexes(&self, dexes: &[DexEnum]) -> Result<(), JupiterClientError> {
+        let labels = self.get_program_id_to_label().await?;
+        let known: HashSet<&str> = labels.values().map(String::as_str).collect();
+        match dexes
+            .iter()
+            .find(|dex| !known.contains(dex.to_string().as_str()))
         {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
+            Some(dex) => Err(JupiterClientError::UnknownDex(dex.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Quotes and builds a swap transaction, retrying around AMMs that fail in simulation.
+    ///
+    /// After each build the transaction is handed to `simulate`. If it fails inside a specific
+    /// AMM hop, the failing program is mapped back to its DEX label through
+    /// [`get_program_id_to_label`](Self::get_program_id_to_label), the venue is added to
+    /// `exclude_dexes`, and the swap is re-quoted. This turns a hard failure into a
+    /// degraded-but-successful fill. With [`with_venue_health`](Self::with_venue_health),
+    /// each failure also counts towards the venue's health.
+    ///
+    /// # Arguments
+    /// * `params` - The initial [`QuoteRequest`].
+    /// * `build_swap` - Builds the [`SwapRequest`] for each fresh quote.
+    /// * `simulate` - Simulates the transaction, returning the program logs on failure.
+    /// * `max_retries` - How many venues may be excluded before giving up.
+    ///
+    /// # Returns
+    /// The first [`SwapResponse`] that simulated successfully, or
+    /// [`JupiterClientError::SimulationError`] when retries are exhausted or the failure can't
+    /// be attributed to an AMM.
+    ///
+    /// # Example
+    /// ```
+    /// let swap = api
+    ///     .get_swap_transaction_excluding_failing_amms(
+    ///         &quote_request,
+    ///         |quote| SwapRequest::new(wallet, wallet, quote),
+    ///         |swap| simulate_with_rpc(&rpc, swap.swap_transaction.clone()),
+    ///         2,
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn get_swap_transaction_excluding_failing_amms<B, S, Fut>(
+        &self,
+        params: &QuoteRequest,
+        build_swap: B,
+        mut simulate: S,
+        max_retries: usize,
+    ) -> Result<SwapResponse, JupiterClientError>
+    where
+        B: Fn(QuoteResponse) -> SwapRequest,
+        S: FnMut(&SwapResponse) -> Fut,
+        Fut: Future<Output = Result<(), Vec<String>>>,
+    {
+        let mut request = params.clone();
+        let mut labels: Option<HashMap<String, String>> = None;
+        let mut retries = 0;
+
+        loop {
+            let quote = self.get_swap_quote(&request).await?;
+            let swap = self.get_swap_transaction(&build_swap(quote)).await?;
+
+            let logs = match simulate(&swap).await {
+                Ok(()) => return Ok(swap),
+                Err(logs) => logs,
+            };
+
+            if retries >= max_retries {
+                return Err(JupiterClientError::SimulationError(format!(
+                    "retries exhausted after excluding {retries} venue(s): {}",
+                    logs.join("\n")
+                )));
+            }
+
+            let Some(program_id) = failing_program_id(&logs) else {
+                return Err(JupiterClientError::SimulationError(logs.join("\n")));
+            };
+
+            if labels.is_none() {
+                labels = Some(self.get_program_id_to_label().await?);
+            }
+            let labels = labels.as_ref().unwrap();
+
+            let Some(label) = labels.get(program_id) else {
+                return Err(JupiterClientError::SimulationError(format!(
+                    "failing program {program_id} is not a routable venue: {}",
+                    logs.join("\n")
+                )));
+            };
+            self.record_venue_failure(label, VenueFailure::Simulation);
+
+            request = request.exclude_program_ids(&[program_id], labels)?;
+            retries += 1;
+        }
+    }
+
+    async fn fetch_quote(
+        &self,
+        query: QuoteQuery<'_>,
+    ) -> Result<QuoteResponse, JupiterClientError> {
+        let response = self
+            .send(self.client.get(self.swap_url("quote")).query(&query))
+            .await?;
+
+        self.read_json::<QuoteResponse>(response).await
+    }
+
+    /// Fetches `query`, a query of `params`, checks the route's venues and reports the quote
+    /// to the warning sinks and venue health.
+    async fn fetch_checked_quote(
+        &self,
+        params: &QuoteRequest,
+        query: QuoteQuery<'_>,
+    ) -> Result<QuoteResponse, JupiterClientError> {
+        let quote = self.fetch_quote(query).await?;
+        self.check_route_venues(params, &quote)?;
+        if !self.warning_sinks.is_empty() {
+            self.warning_sinks.check_quote(&quote);
+        }
+        self.record_quoted_venues(&quote);
+        Ok(quote)
+    }
+
+    async fn post_swap<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        data: &SwapRequest,
+    ) -> Result<T, JupiterClientError> {
+        let request = self.client.post(self.swap_url(endpoint)).json(data);
+        let response = match self.swap_build_retry {
+            Some(policy) => {
+                JupiterClient {
+                    retry_policy: policy,
+                    idempotent_posts: true,
+                    ..self.clone()
+                }
+                .send(request)
+                .await?
+            }
+            None => self.send(request).await?,
         };
 
-        let response = handle_response(response).await?;
+        self.read_json::<T>(response).await
+    }
 
-        match response.json::<SwapInstructions>().await {
-            Ok(swap_instructions) => Ok(swap_instructions),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
+    /// Runs `build` and records the swap request and its outcome with the audit sinks.
+    async fn audited_swap<T>(
+        &self,
+        operation: AuditOperation,
+        data: &SwapRequest,
+        build: impl Future<Output = Result<T, JupiterClientError>>,
+    ) -> Result<T, JupiterClientError> {
+        let record = self
+            .is_audited()
+            .then(|| AuditRecord::new(operation).swap_request(data.clone()));
+        let started = Instant::now();
+
+        let result = build.await;
+
+        if let Some(mut record) = record {
+            if let Err(e) = &result {
+                record = record.error(e);
+            }
+            self.audit(record.duration(started.elapsed()));
         }
+
+        result
     }
 }
@@ -0,0 +1,102 @@
+use super::JupiterClient;
+use crate::{
+    ata::token_account_sizes,
+    error::JupiterClientError,
+    types::{
+        AccountSetup, HoldingsSource, QuoteResponse, SwapPreview, SwapRequest, SwapResponse,
+        holdings::WSOL_MINT,
+    },
+};
+
+impl JupiterClient {
+    /// Builds the confirm-screen summary of a swap: amounts with symbols and USD values,
+    /// venues, price impact, fees and, when `swap` is given, network cost and expiry.
+    ///
+    /// Looks up the metadata of every mint involved in a single Token API call.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::MissingTokenMetadata`] when the Token API doesn't know a mint.
+    ///
+    /// # Example
+    /// ```
+    /// let quote = api.get_quote(&request).await?;
+    /// let swap = api.get_swap_transaction(&SwapRequest::new(&wallet, &wallet, quote.clone())).await?;
+    ///
+    /// let preview = api.preview_swap(&quote, Some(&swap)).await?;
+    /// println!("Pay {} {}", preview.input.ui_amount, preview.input.symbol);
+    /// println!("You will {}", preview.worst_case);
+    /// println!("via {}", preview.venues.join(" → "));
+    /// ```
+    pub async fn preview_swap(
+        &self,
+        quote: &QuoteResponse,
+        swap: Option<&SwapResponse>,
+    ) -> Result<SwapPreview, JupiterClientError> {
+        let tokens = self
+            .get_token_metas(&preview_mints(quote, swap.is_some()))
+            .await?;
+        SwapPreview::new(quote, swap, &tokens)
+    }
+
+    /// Like [`preview_swap`](Self::preview_swap) for the quote of `request`, adding the
+    /// token accounts the swap creates for the wallet and their rent, see [`AccountSetup`].
+    ///
+    /// The wallet's token accounts are read from `source`. Prefer
+    /// [`HoldingsSource::Rpc`], which also reports empty accounts and sizes the rent of
+    /// Token-2022 accounts by their extensions.
+    ///
+    /// # Example
+    /// ```
+    /// let request = SwapRequest::new(&wallet, &wallet, quote);
+    /// let preview = api
+    ///     .preview_swap_for_wallet(&request, None, &HoldingsSource::Rpc(rpc_url))
+    ///     .await?;
+    /// let setup = preview.account_setup.as_ref().unwrap();
+    /// if setup.creates_accounts() {
+    ///     println!("Opens {} account(s) for {:?} USD of rent", setup.missing_accounts.len(), preview.account_rent_usd);
+    /// }
+    /// let request = request.skip_user_account_rpc_calls(setup.can_skip_account_checks());
+    /// ```
+    pub async fn preview_swap_for_wallet(
+        &self,
+        request: &SwapRequest,
+        swap: Option<&SwapResponse>,
+        source: &HoldingsSource,
+    ) -> Result<SwapPreview, JupiterClientError> {
+        let quote = &request.quote_response;
+        let tokens = self.get_token_metas(&preview_mints(quote, true)).await?;
+        let holdings = self
+            .get_wallet_holdings(&request.user_public_key, source)
+            .await?;
+        let mut setup = AccountSetup::new(request, &holdings);
+        if let HoldingsSource::Rpc(rpc_url) = source {
+            if !setup.rented_accounts.is_empty() {
+                let mints: Vec<&str> = setup.rented_accounts.iter().map(String::as_str).collect();
+                let sizes = token_account_sizes(rpc_url, &mints).await?;
+                setup = setup.with_account_sizes(&sizes);
+            }
+        }
+        Ok(SwapPreview::new(quote, swap, &tokens)?.with_account_setup(setup, &tokens))
+    }
+}
+
+/// The mints a preview needs metadata for, with wrapped SOL when network or rent costs are
+/// valued in it.
+fn preview_mints(quote: &QuoteResponse, with_sol: bool) -> Vec<String> {
+    let mut mints = vec![quote.input_mint.clone(), quote.output_mint.clone()];
+    let fee_mints = quote
+        .route_plan
+        .iter()
+        .filter(|step| step.swap_info.fee_amount != "0")
+        .map(|step| &step.swap_info.fee_mint)
+        .chain(quote.platform_fee.iter().flat_map(|fee| &fee.fee_mint));
+    for mint in fee_mints {
+        if !mints.contains(mint) {
+            mints.push(mint.clone());
+        }
+    }
+    if with_sol && !mints.iter().any(|mint| mint == WSOL_MINT) {
+        mints.push(WSOL_MINT.to_string());
+    }
+    mints
+}
@@ -2,14 +2,81 @@ use std::collections::HashMap;
 
 use super::JupiterClient;
 use crate::{
-    error::{JupiterClientError, handle_response},
+    error::JupiterClientError,
     types::{
-        Category, Interval, NewTokens, Price, TokenInfo, TokenInfoResponse, TokenPriceRequest,
-        TokenPriceResponse,
+        Category, Interval, NewTokens, Price, TokenInfo, TokenInfoResponse, TokenMeta,
+        TokenPriceRequest, TokenPriceResponse, TokenTag,
     },
 };
 
+/// The search endpoint accepts at most this many mints per request.
+const SEARCH_BATCH_SIZE: usize = 100;
+
 impl JupiterClient {
+    /// Looks up the display metadata of `mints`, keyed by mint.
+    ///
+    /// Mints the Token API doesn't know are missing from the map.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let tokens = client.get_token_metas(&[USDC_MINT.to_string()]).await?;
+    /// println!("{} decimals", tokens[USDC_MINT].decimals);
+    /// ```
+    pub async fn get_token_metas(
+        &self,
+        mints: &[String],
+    ) -> Result<HashMap<String, TokenMeta>, JupiterClientError> {
+        let mut metas = HashMap::new();
+        for batch in mints.chunks(SEARCH_BATCH_SIZE) {
+            for info in self.token_search(batch).await? {
+                if mints.contains(&info.id) {
+                    metas.insert(info.id.clone(), TokenMeta::from(&info));
+                }
+            }
+        }
+        Ok(metas)
+    }
+
+    /// Looks up the metadata of a single mint: symbol, name, decimals, logo, tags, token
+    /// program, authorities and market stats.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::MissingTokenMetadata`] when the Token API doesn't know the mint.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let jup = client.get_token("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN").await?;
+    /// println!("{} has {} decimals", jup.symbol, jup.decimals);
+    /// ```
+    pub async fn get_token(&self, mint: &str) -> Result<TokenInfo, JupiterClientError> {
+        self.token_search(&[mint.to_string()])
+            .await?
+            .into_iter()
+            .find(|info| info.id == mint)
+            .ok_or_else(|| JupiterClientError::MissingTokenMetadata(mint.to_string()))
+    }
+
+    /// Searches tokens by symbol, name or mint address, e.g. to resolve `"JUP"` or `"bonk"`
+    /// to a mint.
+    ///
+    /// Results come in the Token API's ranking, at most 20 of them. Several tokens may share
+    /// a symbol; check `is_verified` and `organic_score` before trusting a match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let bonk = client
+    ///     .search_tokens("bonk")
+    ///     .await?
+    ///     .into_iter()
+    ///     .find(|token| token.symbol.eq_ignore_ascii_case("bonk") && token.is_verified == Some(true));
+    /// ```
+    pub async fn search_tokens(&self, query: &str) -> Result<Vec<TokenInfo>, JupiterClientError> {
+        self.token_search(&[query.trim().to_string()]).await
+    }
+
     /// search for a token and its information by its symbol, name or mint address
     ///
     /// Limit to 100 mint addresses in query
@@ -43,23 +110,15 @@ impl JupiterClient {
     ) -> Result<Vec<TokenInfo>, JupiterClientError> {
         let query_params = vec![("query", mints.join(","))];
 
-        let response = match self
-            .client
-            .get(format!("{}/tokens/v2/search", self.base_url))
-            .query(&query_params)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<Vec<TokenInfo>>().await {
-            Ok(data) => Ok(data),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        let response = self
+            .send(
+                self.client
+                    .get(format!("{}/tokens/v2/search", self.base_url))
+                    .query(&query_params),
+            )
+            .await?;
+
+        self.read_json::<Vec<TokenInfo>>(response).await
     }
 
     /// Returns a list of mints with specified tag(s) along with their metadata.
@@ -78,23 +137,34 @@ impl JupiterClient {
     ) -> Result<Vec<TokenInfo>, JupiterClientError> {
         let query_params = vec![("query", tags.join(","))];
 
-        let response = match self
-            .client
-            .get(format!("{}/tokens/v2/tag", self.base_url))
-            .query(&query_params)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<Vec<TokenInfo>>().await {
-            Ok(mints) => Ok(mints),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        let response = self
+            .send(
+                self.client
+                    .get(format!("{}/tokens/v2/tag", self.base_url))
+                    .query(&query_params),
+            )
+            .await?;
+
+        self.read_json::<Vec<TokenInfo>>(response).await
+    }
+
+    /// Returns every token carrying `tag` with its metadata, e.g. to restrict routing to
+    /// verified tokens.
+    ///
+    /// # Example
+    /// ```
+    /// let verified: HashSet<String> = client
+    ///     .get_tokens_by_tag(TokenTag::Verified)
+    ///     .await?
+    ///     .into_iter()
+    ///     .map(|token| token.id)
+    ///     .collect();
+    /// ```
+    pub async fn get_tokens_by_tag(
+        &self,
+        tag: TokenTag,
+    ) -> Result<Vec<TokenInfo>, JupiterClientError> {
+        self.get_mints_by_tags(&[tag.to_string()]).await
     }
 
     /// Returns a list of mints and their information for the given category and time interval.
@@ -118,8 +188,15 @@ impl JupiterClient {
     ///   Must be between 1 and 100 inclusive if provided.  
     ///   ```
     ///   let tokens = client
-    ///    .get_mints_by_category(Category::TopTrending, Interval::OneHour, None)
+    ///    .get_tokens_by_category(Category::TopTrending, Interval::OneHour, None)
     ///    .await.expect("failed to get tokens");
+    ///
+    ///   // every feed, e.g. for a discovery bot
+    ///   for category in Category::ALL {
+    ///       for interval in Interval::ALL {
+    ///           let tokens = client.get_tokens_by_category(category, interval, Some(20)).await?;
+    ///       }
+    ///   }
     ///   ```
     pub async fn get_tokens_by_category(
         &self,
@@ -135,17 +212,9 @@ impl JupiterClient {
             request = request.query(&[("limit", limit)]);
         }
 
-        let response = match request.send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        let response = self.send(request).await?;
 
-        let response = handle_response(response).await?;
-
-        match response.json::<Vec<TokenInfo>>().await {
-            Ok(mints) => Ok(mints),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.read_json::<Vec<TokenInfo>>(response).await
     }
 
     /// Returns an vec of mints that recently had their first created pool
@@ -153,17 +222,9 @@ impl JupiterClient {
     pub async fn get_recent_tokens(&self) -> Result<Vec<TokenInfo>, JupiterClientError> {
         let url = format!("{}/tokens/v2/recent", self.base_url);
 
-        let response = match self.client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
+        let response = self.send(self.client.get(&url)).await?;
 
-        match response.json::<Vec<TokenInfo>>().await {
-            Ok(mints) => Ok(mints),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.read_json::<Vec<TokenInfo>>(response).await
     }
 
     /// Returns prices of specified tokens.
@@ -185,23 +246,15 @@ impl JupiterClient {
     ) -> Result<HashMap<String, Price>, JupiterClientError> {
         let query_params = vec![("ids", mints.join(","))];
 
-        let response = match self
-            .client
-            .get(format!("{}/price/v3", self.base_url))
-            .query(&query_params)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<HashMap<String, Price>>().await {
-            Ok(token_price) => Ok(token_price),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        let response = self
+            .send(
+                self.client
+                    .get(format!("{}/price/v3", self.base_url))
+                    .query(&query_params),
+            )
+            .await?;
+
+        self.read_json::<HashMap<String, Price>>(response).await
     }
 
     #[deprecated(note = "This endpoint is deprecated. use `get_tokens_price` instead")]
@@ -233,26 +286,18 @@ impl JupiterClient {
         &self,
         params: &TokenPriceRequest,
     ) -> Result<TokenPriceResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .get(format!("{}/price/v2", self.base_url))
-            .query(&params)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<TokenPriceResponse>().await {
-            Ok(token_price) => Ok(token_price),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        let response = self
+            .send(
+                self.client
+                    .get(format!("{}/price/v2", self.base_url))
+                    .query(&params),
+            )
+            .await?;
+
+        self.read_json::<TokenPriceResponse>(response).await
     }
 
-    #[deprecated]
+    #[deprecated(note = "This endpoint is deprecated. use `get_token` instead")]
     /// Returns the specified mint address's token information and metadata.
     ///
     /// ```
@@ -266,17 +311,9 @@ impl JupiterClient {
         mint_address: &str,
     ) -> Result<TokenInfoResponse, JupiterClientError> {
         let url = format!("{}/tokens/v1/token/{}", self.base_url, mint_address);
-        let response = match self.client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
+        let response = self.send(self.client.get(&url)).await?;
 
-        match response.json::<TokenInfoResponse>().await {
-            Ok(token_info) => Ok(token_info),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.read_json::<TokenInfoResponse>(response).await
     }
 
     #[deprecated]
@@ -289,17 +326,9 @@ impl JupiterClient {
             "{}/tokens/v1/market/{}/mints",
             self.base_url, market_address
         );
-        let response = match self.client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
+        let response = self.send(self.client.get(&url)).await?;
 
-        match response.json::<Vec<String>>().await {
-            Ok(mints) => Ok(mints),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.read_json::<Vec<String>>(response).await
     }
 
     #[deprecated]
@@ -307,17 +336,9 @@ impl JupiterClient {
     /// This endpoint returns greater than 32MB amount of data. May take a while to complete.
     pub async fn get_tradable_mints(&self) -> Result<Vec<String>, JupiterClientError> {
         let url = format!("{}/tokens/v1/mints/tradable", self.base_url);
-        let response = match self.client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
+        let response = self.send(self.client.get(&url)).await?;
 
-        match response.json::<Vec<String>>().await {
-            Ok(mints) => Ok(mints),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.read_json::<Vec<String>>(response).await
     }
 
     #[deprecated(note = "This fn is deprecated. Use `get_recent_tokens` instead.")]
@@ -338,17 +359,9 @@ impl JupiterClient {
                 url.push_str(&format!("?offset={}", o));
             }
         }
-        let response = match self.client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
+        let response = self.send(self.client.get(&url)).await?;
 
-        match response.json::<Vec<NewTokens>>().await {
-            Ok(tokens) => Ok(tokens),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.read_json::<Vec<NewTokens>>(response).await
     }
 
     #[deprecated]
@@ -358,16 +371,8 @@ impl JupiterClient {
     pub async fn get_all_tokens(&self) -> Result<Vec<TokenInfoResponse>, JupiterClientError> {
         let url = format!("{}/tokens/v1/all", self.base_url);
 
-        let response = match self.client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
+        let response = self.send(self.client.get(&url)).await?;
 
-        match response.json::<Vec<TokenInfoResponse>>().await {
-            Ok(tokens) => Ok(tokens),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.read_json::<Vec<TokenInfoResponse>>(response).await
     }
 }
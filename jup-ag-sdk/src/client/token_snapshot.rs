@@ -0,0 +1,24 @@
+use super::JupiterClient;
+use crate::{error::JupiterClientError, token_snapshot::TokenSnapshot, types::TokenTag};
+
+impl JupiterClient {
+    /// Snapshots the tokens carrying `tag`, e.g. the verified set, for diffing against later
+    /// snapshots with [`TokenSnapshot::diff`].
+    ///
+    /// # Example
+    /// ```
+    /// let before = api.snapshot_tokens(TokenTag::Verified).await?;
+    /// tokio::time::sleep(Duration::from_secs(3600)).await;
+    /// let after = api.snapshot_tokens(TokenTag::Verified).await?;
+    /// for change in before.diff(&after) {
+    ///     println!("{change:?}");
+    /// }
+    /// ```
+    pub async fn snapshot_tokens(
+        &self,
+        tag: TokenTag,
+    ) -> Result<TokenSnapshot, JupiterClientError> {
+        let tokens = self.get_tokens_by_tag(tag).await?;
+        Ok(TokenSnapshot::from_tokens(&tokens))
+    }
+}
@@ -0,0 +1,374 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use serde::de::DeserializeOwned;
+
+use reqwest::{Method, Request, RequestBuilder, Response, StatusCode, Url, header::HeaderValue};
+
+use super::{JupiterClient, REQUEST_ID_HEADER};
+use crate::{
+    ata::decode_pubkey,
+    error::{JupiterClientError, handle_response},
+    metrics::RequestMetric,
+    middleware::{RequestParts, ResponseParts},
+    retry::{is_retryable_error, is_retryable_status, retry_after},
+    rt::{self, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// A random 16-hex-digit ID for a call. Unique per process through the counter, and across
+/// processes with overwhelming probability.
+fn new_request_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    format!("{:016x}", hasher.finish())
+}
+
+/// The ID a response's request was sent with, kept in its extensions so errors reading the
+/// body can be tagged with it.
+#[derive(Clone)]
+struct RequestId(String);
+
+/// Reads the body of `response`, failing as soon as it exceeds `limit` bytes.
+async fn read_limited(response: Response, limit: usize) -> Result<Vec<u8>, JupiterClientError> {
+    let too_large = || JupiterClientError::ResponseTooLarge {
+        limit,
+        request_id: None,
+    };
+    if response
+        .content_length()
+        .is_some_and(|length| length > limit as u64)
+    {
+        return Err(too_large());
+    }
+
+    // the fetch backend can't stream bodies, so wasm reads it whole before checking
+    #[cfg(target_arch = "wasm32")]
+    {
+        let body = response.bytes().await?;
+        if body.len() > limit {
+            return Err(too_large());
+        }
+        Ok(body.to_vec())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let mut response = response;
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > limit {
+                return Err(too_large());
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+}
+
+impl JupiterClient {
+    /// Sends `request` once the circuit breaker and rate limiter allow it, retrying transient
+    /// failures according to the client's retry policy, and turns non-success statuses into
+    /// [`JupiterClientError::ApiError`] or [`JupiterClientError::RateLimited`].
+    ///
+    /// Every error raised once the `x-request-id` header is set carries the ID, and so do the
+    /// errors of [`read_json`](Self::read_json) reading the response.
+    pub(crate) async fn send(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<Response, JupiterClientError> {
+        if self.shutdown.is_triggered() {
+            return Err(JupiterClientError::ShuttingDown);
+        }
+        let _in_flight = self.shutdown.enter();
+        let request_id = match self.call_opts.request_id_override() {
+            Some(id) => id.to_string(),
+            None => new_request_id(),
+        };
+        let mut request = request.build()?;
+        request
+            .headers_mut()
+            .insert(REQUEST_ID_HEADER, HeaderValue::from_str(&request_id)?);
+
+        let bounded = async {
+            let Some(deadline) = self.call_opts.deadline_at() else {
+                return self.send_guarded(request).await;
+            };
+            let remaining = deadline.saturating_duration_since(self.clock.now());
+            if remaining.is_zero() {
+                return Err(JupiterClientError::DeadlineExceeded { request_id: None });
+            }
+            // a real timer, since a manual clock completes its sleeps immediately; the clock
+            // still decides how long is left, and retries check it again
+            rt::race(self.send_guarded(request), rt::sleep(remaining))
+                .await
+                .unwrap_or(Err(JupiterClientError::DeadlineExceeded {
+                    request_id: None,
+                }))
+        };
+        let result = match self.call_opts.cancel_signal() {
+            // a cancelled call is dropped before the circuit breaker records it, so it counts
+            // neither as a success nor as a failure
+            Some(cancel) => rt::race(bounded, cancel)
+                .await
+                .unwrap_or(Err(JupiterClientError::Cancelled)),
+            None => bounded.await,
+        };
+        match result {
+            Ok(mut response) => {
+                response.extensions_mut().insert(RequestId(request_id));
+                Ok(response)
+            }
+            Err(e) => Err(e.with_request_id(&request_id)),
+        }
+    }
+
+    /// Waits `duration` between the calls of a composite operation, failing with
+    /// [`JupiterClientError::DeadlineExceeded`] instead when the wait would end past the
+    /// call's [`deadline`](super::CallOpts::deadline).
+    pub(crate) async fn sleep_within_deadline(
+        &self,
+        duration: Duration,
+    ) -> Result<(), JupiterClientError> {
+        if !self.within_deadline(duration) {
+            return Err(JupiterClientError::DeadlineExceeded { request_id: None });
+        }
+        self.clock.sleep(duration).await;
+        Ok(())
+    }
+
+    /// Whether `wait` from now still ends before the call's deadline, if it has one.
+    pub(crate) fn within_deadline(&self, wait: Duration) -> bool {
+        self.call_opts
+            .deadline_at()
+            .is_none_or(|deadline| self.clock.now() + wait < deadline)
+    }
+
+    async fn send_guarded(&self, request: Request) -> Result<Response, JupiterClientError> {
+        let Some(breaker) = &self.circuit_breaker else {
+            return self.send_with_retries(request).await;
+        };
+        let endpoint = self.endpoint_name(request.url());
+        breaker.acquire(&endpoint, self.clock.now())?;
+        let result = self.send_with_retries(request).await;
+        breaker.record(&endpoint, &result, self.clock.now());
+        result
+    }
+
+    async fn send_with_retries(
+        &self,
+        mut request: Request,
+    ) -> Result<Response, JupiterClientError> {
+        if let Some(headers) = self.per_request_headers()? {
+            for (name, value) in &headers {
+                if !request.headers().contains_key(name) {
+                    request.headers_mut().insert(name, value.clone());
+                }
+            }
+        }
+        if !self.call_opts.is_empty() {
+            request.headers_mut().extend(self.call_opts.header_map()?);
+        }
+        let idempotent = request.method() == Method::GET || self.idempotent_posts;
+        let policy = self.retry_policy;
+
+        let mut attempt = 1;
+        loop {
+            self.acquire_rate_limit().await?;
+
+            let retries_left = attempt < policy.max_attempts;
+            // bodies built with `.json()` and `.query()` are always cloneable
+            let Some(next) = request.try_clone().filter(|_| retries_left) else {
+                let response = self.execute(request, attempt).await?;
+                return handle_response(response).await;
+            };
+
+            let delay = match self.execute(next, attempt).await {
+                // a 429 was rejected before processing, so it is safe to retry for any method
+                Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => {
+                    match retry_after(response.headers()) {
+                        Some(wait) if wait > policy.max_backoff => {
+                            return handle_response(response).await;
+                        }
+                        wait => wait.unwrap_or_default().max(policy.backoff(attempt)),
+                    }
+                }
+                Ok(response) if idempotent && is_retryable_status(response.status()) => {
+                    policy.backoff(attempt)
+                }
+                Ok(response) => return handle_response(response).await,
+                Err(e) if is_retryable_error(&e, idempotent) => policy.backoff(attempt),
+                Err(e) => return Err(e.into()),
+            };
+
+            self.sleep_within_deadline(delay).await?;
+            attempt += 1;
+        }
+    }
+
+    /// Reads a JSON body within the client's
+    /// [`max_response_size`](Self::with_max_response_size), if it has one, and parses it
+    /// once from its bytes.
+    ///
+    /// On failure the error carries the raw body, unless
+    /// [`raw body capture`](Self::with_raw_body_capture) is off, and the ID of the request
+    /// the response answers.
+    pub(crate) async fn read_json<T: DeserializeOwned>(
+        &self,
+        response: Response,
+    ) -> Result<T, JupiterClientError> {
+        let request_id = response.extensions().get::<RequestId>().cloned();
+        let result = self.parse_json(response).await;
+        match request_id {
+            Some(RequestId(id)) => result.map_err(|e| e.with_request_id(&id)),
+            None => result,
+        }
+    }
+
+    async fn parse_json<T: DeserializeOwned>(
+        &self,
+        response: Response,
+    ) -> Result<T, JupiterClientError> {
+        let (limited, whole);
+        let body: &[u8] = match self.max_response_size {
+            Some(limit) => {
+                limited = read_limited(response, limit).await?;
+                &limited
+            }
+            None => {
+                whole = response.bytes().await?;
+                &whole
+            }
+        };
+        serde_json::from_slice(body).map_err(|e| {
+            JupiterClientError::deserialization(if self.capture_raw_body {
+                format!("{e}. Response text: {}", String::from_utf8_lossy(body))
+            } else {
+                e.to_string()
+            })
+        })
+    }
+
+    /// Executes a single attempt, running the registered middleware around it.
+    async fn execute(&self, mut request: Request, attempt: u32) -> reqwest::Result<Response> {
+        // labelled before middleware can rewrite the URL, so metrics stay per endpoint
+        let endpoint = (!self.metrics_sinks.is_empty()).then(|| self.endpoint_name(request.url()));
+        self.middlewares
+            .on_request(&mut RequestParts::new(&mut request, attempt));
+
+        let method = request.method().clone();
+        let url = request.url().clone();
+        let started = Instant::now();
+        match self.client.execute(request).await {
+            Ok(response) => {
+                let elapsed = started.elapsed();
+                // fills the fields declared by the instrumented endpoint methods, if any
+                #[cfg(feature = "tracing")]
+                tracing::Span::current()
+                    .record("status", response.status().as_u16())
+                    .record("latency_ms", elapsed.as_millis() as u64);
+
+                self.middlewares.on_response(&ResponseParts {
+                    method: &method,
+                    url: &url,
+                    status: response.status(),
+                    headers: response.headers(),
+                    elapsed,
+                    attempt,
+                });
+                self.emit_metric(endpoint, method, Some(response.status()), elapsed, attempt);
+                Ok(response)
+            }
+            Err(e) => {
+                self.middlewares.on_error(&e);
+                self.emit_metric(endpoint, method, None, started.elapsed(), attempt);
+                Err(e)
+            }
+        }
+    }
+
+    fn emit_metric(
+        &self,
+        endpoint: Option<String>,
+        method: Method,
+        status: Option<StatusCode>,
+        duration: Duration,
+        attempt: u32,
+    ) {
+        let Some(endpoint) = endpoint else {
+            return;
+        };
+        self.metrics_sinks.emit(&RequestMetric {
+            endpoint,
+            method,
+            status,
+            duration,
+            attempt,
+        });
+    }
+
+    /// The path of `url` relative to the base URL, with wallet, mint and market addresses
+    /// replaced by placeholders so it stays usable as a metric label and breaker key.
+    fn endpoint_name(&self, url: &Url) -> String {
+        let path = url
+            .path()
+            .strip_prefix(base_path(&self.base_url))
+            .unwrap_or(url.path())
+            .trim_start_matches('/');
+        template_path(path)
+    }
+}
+
+/// Endpoints taking path parameters, with the name of each parameter.
+const PATH_TEMPLATES: [&str; 3] = [
+    "ultra/v1/balances/{address}",
+    "tokens/v1/token/{mint}",
+    "tokens/v1/market/{market}/mints",
+];
+
+/// The path component of `base_url`, without a trailing slash, e.g. `/jupiter` for
+/// `https://proxy.example.com/jupiter/`.
+fn base_path(base_url: &str) -> &str {
+    let rest = base_url
+        .split_once("://")
+        .map_or(base_url, |(_, rest)| rest);
+    rest.find('/')
+        .map_or("", |start| &rest[start..])
+        .trim_end_matches('/')
+}
+
+/// Matches `path` against [`PATH_TEMPLATES`]. Paths of other endpoints are kept, except for
+/// segments holding a public key, which become `{pubkey}`.
+fn template_path(path: &str) -> String {
+    let segments: Vec<&str> = path.split('/').collect();
+    for template in PATH_TEMPLATES {
+        let mut parts = template.split('/');
+        let matches = parts.clone().count() == segments.len()
+            && parts
+                .by_ref()
+                .zip(&segments)
+                .all(|(part, segment)| part.starts_with('{') || part == *segment);
+        if matches {
+            return template.to_string();
+        }
+    }
+
+    segments
+        .iter()
+        .map(|segment| match decode_pubkey(segment) {
+            Ok(_) => "{pubkey}",
+            Err(_) => segment,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
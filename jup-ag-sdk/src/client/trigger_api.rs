@@ -1,9 +1,10 @@
 use crate::{
     JupiterClientError,
-    error::handle_response,
+    policy::SigningIntent,
     types::{
         CancelTriggerOrder, CancelTriggerOrders, CreateTriggerOrder, ExecuteTriggerOrder,
         ExecuteTriggerOrderResponse, GetTriggerOrders, OrderResponse, TriggerResponse,
+        trigger::Order,
     },
 };
 
@@ -22,6 +23,10 @@ impl JupiterClient {
     ///   - `order: String` - Base-58 account which is the Trigger Order account
     ///   - `code: u8` - Response code
     ///
+    /// # Errors
+    /// [`JupiterClientError::InvalidTriggerOrder`] or [`JupiterClientError::InvalidPubkey`] when
+    /// [`CreateTriggerOrder::validate`] fails, before anything is sent.
+    ///
     /// # Example
     /// ```rust
     /// use jupiter_client::types::CreateTriggerOrder;
@@ -34,33 +39,31 @@ impl JupiterClient {
     ///     1000000000, // 1 SOL (in lamports)
     ///     400000000,  // 400 JUP (in smallest unit)
     /// )
-    /// .slippage_bps("50") // 0.5% slippage
-    /// .expired_at("1704067200"); // Unix timestamp
+    /// .slippage(Slippage::from_bps(50)) // 0.5% slippage
+    /// .expires_in(Duration::from_secs(24 * 60 * 60))
+    /// .platform_fee("YourReferralTokenAccount...", 20);
     ///
     /// let response = client.create_trigger_order(&create_order).await?;
-    /// println!("Order created with ID: {}", response.request_id);
+    /// println!("Order {:?} created with ID: {}", response.order, response.request_id);
     /// ```
     pub async fn create_trigger_order(
         &self,
         data: &CreateTriggerOrder,
     ) -> Result<TriggerResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/trigger/v1/createOrder", self.base_url))
-            .json(&data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        data.validate()?;
+        let approval = self.check_policies(|| SigningIntent::from_trigger(data))?;
+        let created = async {
+            let response = self
+                .send(
+                    self.client
+                        .post(format!("{}/trigger/v1/createOrder", self.base_url))
+                        .json(&data),
+                )
+                .await?;
 
-        let response = handle_response(response).await?;
-
-        match response.json::<TriggerResponse>().await {
-            Ok(create_order_response) => Ok(create_order_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+            self.read_json::<TriggerResponse>(response).await
+        };
+        created.await.inspect_err(|_| approval.release())
     }
 
     /// Executes a trigger(create, cancel) order by submitting the signed transaction
@@ -71,7 +74,10 @@ impl JupiterClient {
     ///   - `signed_transaction: String` - The base-58 signed transaction
     ///
     /// # Returns
-    /// * `Result<TriggerResponse, JupiterClientError>` - Success returns TriggerResponse with execution details
+    /// * `Result<ExecuteTriggerOrderResponse, JupiterClientError>` - The execution outcome. A
+    ///   transaction that failed to land still returns `Ok`; check
+    ///   [`is_success`](ExecuteTriggerOrderResponse::is_success) or use
+    ///   [`into_result`](ExecuteTriggerOrderResponse::into_result)
     ///
     /// # Example
     /// ```rust
@@ -83,30 +89,23 @@ impl JupiterClient {
     ///     &signed_tx
     /// );
     ///
-    /// let response = client.execute_trigger_order(&execute_order).await?;
-    /// println!("Order executed successfully");
+    /// let response = client.execute_trigger_order(&execute_order).await?.into_result()?;
+    /// println!("Order executed successfully: {}", response.signature);
     /// ```
     pub async fn execute_trigger_order(
         &self,
         data: &ExecuteTriggerOrder,
     ) -> Result<ExecuteTriggerOrderResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/trigger/v1/execute", self.base_url))
-            .json(&data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        let response = self
+            .send(
+                self.client
+                    .post(format!("{}/trigger/v1/execute", self.base_url))
+                    .json(&data),
+            )
+            .await?;
 
-        let response = handle_response(response).await?;
-
-        match response.json::<ExecuteTriggerOrderResponse>().await {
-            Ok(execute_order_response) => Ok(execute_order_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.read_json::<ExecuteTriggerOrderResponse>(response)
+            .await
     }
 
     /// Request for a base64-encoded unsigned trigger order cancellation transaction
@@ -137,35 +136,32 @@ impl JupiterClient {
         &self,
         data: &CancelTriggerOrder,
     ) -> Result<TriggerResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/trigger/v1/cancelOrder", self.base_url))
-            .json(&data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        data.validate()?;
+        let response = self
+            .send(
+                self.client
+                    .post(format!("{}/trigger/v1/cancelOrder", self.base_url))
+                    .json(&data),
+            )
+            .await?;
 
-        let response = handle_response(response).await?;
-
-        match response.json::<TriggerResponse>().await {
-            Ok(cancel_order_response) => Ok(cancel_order_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.read_json::<TriggerResponse>(response).await
     }
 
-    /// Cancels multiple trigger orders in a single transaction
+    /// Cancels multiple trigger orders, batched [`CancelTriggerOrders::ORDERS_PER_TRANSACTION`]
+    /// orders per transaction
     ///
     /// # Arguments
     /// * `data` - `&CancelTriggerOrders` - Contains:
     ///   - `maker: String` - Maker wallet address
-    ///   - `order: Vec<String>` - Vector of Base-58 trigger order account addresses
+    ///   - `order: Vec<String>` - Vector of Base-58 trigger order account addresses, every open
+    ///     order of the maker when empty
     ///   - `compute_unit_price: Option<String>` - Priority fee in microlamports (optional)
     ///
     /// # Returns
-    /// * `Result<TriggerResponse, JupiterClientError>` - Returns unsigned batch cancellation transaction
+    /// * `Result<TriggerResponse, JupiterClientError>` - Returns the unsigned batch cancellation
+    ///   transactions, see [`TriggerResponse::unsigned_transactions`]. Sign each and execute it
+    ///   with the same `request_id`
     ///
     /// # Example
     /// ```rust
@@ -181,30 +177,28 @@ impl JupiterClient {
     ///     compute_unit_price: Some("1000".to_string()), // 1000 microlamports
     /// };
     ///
-    /// // Get unsigned batch cancellation transaction
+    /// // Get unsigned batch cancellation transactions
     /// let cancel_response = client.cancel_trigger_orders(&cancel_orders).await?;
+    /// for transaction in cancel_response.unsigned_transactions() {
+    ///     let signed_tx = sign_transaction(transaction);
+    ///     let execute = ExecuteTriggerOrder::new(&cancel_response.request_id, &signed_tx);
+    ///     client.execute_trigger_order(&execute).await?.into_result()?;
+    /// }
     /// ```
     pub async fn cancel_trigger_orders(
         &self,
         data: &CancelTriggerOrders,
     ) -> Result<TriggerResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/trigger/v1/cancelOrders", self.base_url))
-            .json(&data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
+        data.validate()?;
+        let response = self
+            .send(
+                self.client
+                    .post(format!("{}/trigger/v1/cancelOrders", self.base_url))
+                    .json(&data),
+            )
+            .await?;
 
-        match response.json::<TriggerResponse>().await {
-            Ok(cancel_order_response) => Ok(cancel_order_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.read_json::<TriggerResponse>(response).await
     }
 
     /// Retrieves existing trigger orders for a user wallet
@@ -258,22 +252,81 @@ impl JupiterClient {
         &self,
         data: &GetTriggerOrders,
     ) -> Result<OrderResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .get(format!("{}/trigger/v1/getTriggerOrders", self.base_url))
-            .query(&data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        let response = self
+            .send(
+                self.client
+                    .get(format!("{}/trigger/v1/getTriggerOrders", self.base_url))
+                    .query(&data),
+            )
+            .await?;
+
+        self.read_json::<OrderResponse>(response).await
+    }
 
-        let response = handle_response(response).await?;
+    /// Pages through the trigger orders matching `data`, starting at its `page` or the first
+    /// one, fetching each page on demand.
+    ///
+    /// # Example
+    /// ```rust
+    /// // reconcile local state with every resting order
+    /// let mut pages = client.trigger_orders(&GetTriggerOrders::new(wallet, OrderStatus::Active));
+    /// while let Some(page) = pages.next_page().await {
+    ///     for order in page?.orders {
+    ///         println!("{} is resting", order.order_key);
+    ///     }
+    /// }
+    ///
+    /// // or all at once
+    /// let history = client
+    ///     .trigger_orders(&GetTriggerOrders::new(wallet, OrderStatus::History))
+    ///     .collect_orders()
+    ///     .await?;
+    /// ```
+    pub fn trigger_orders(&self, data: &GetTriggerOrders) -> TriggerOrderPages<'_> {
+        let next_page = data
+            .page
+            .as_deref()
+            .and_then(|page| page.parse().ok())
+            .unwrap_or(1);
+        TriggerOrderPages {
+            client: self,
+            params: data.clone(),
+            next_page: Some(next_page),
+        }
+    }
+}
+
+/// The pages of trigger orders returned by [`JupiterClient::trigger_orders`].
+#[derive(Debug)]
+pub struct TriggerOrderPages<'a> {
+    client: &'a JupiterClient,
+    params: GetTriggerOrders,
+    next_page: Option<u32>,
+}
+
+impl TriggerOrderPages<'_> {
+    /// Fetches the next page, or returns `None` after the last one. A failed page ends the
+    /// iteration after its error is returned.
+    pub async fn next_page(&mut self) -> Option<Result<OrderResponse, JupiterClientError>> {
+        let page = self.next_page.take()?;
+        let params = self.params.clone().page_number(page);
+        let result = self.client.get_trigger_orders(&params).await;
+        if let Ok(response) = &result {
+            let page = response.page.max(page);
+            // an empty page ends the iteration even if `totalPages` says otherwise
+            if page < response.total_pages && !response.orders.is_empty() {
+                self.next_page = Some(page + 1);
+            }
+        }
+        Some(result)
+    }
 
-        match response.json::<OrderResponse>().await {
-            Ok(orders) => Ok(orders),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
+    /// Fetches every remaining page and returns their orders, in page order.
+    pub async fn collect_orders(mut self) -> Result<Vec<Order>, JupiterClientError> {
+        let mut orders = Vec::new();
+        while let Some(page) = self.next_page().await {
+            orders.extend(page?.orders);
         }
+        Ok(orders)
     }
 }
@@ -1,5 +1,6 @@
 use crate::{
-    error::{JupiterClientError, handle_response},
+    error::JupiterClientError,
+    policy::SigningIntent,
     types::{
         Router, Shield, TokenBalancesResponse, TokenInfo, UltraExecuteOrderRequest,
         UltraExecuteOrderResponse, UltraOrderRequest, UltraOrderResponse,
@@ -27,30 +28,37 @@ impl JupiterClient {
     /// # Example
     ///
     /// ```
-    /// let req = UltraOrderRequest::new("inputMint", "outputMint", 1_000_000_000);
+    /// let req = UltraOrderRequest::new("inputMint", "outputMint", 1_000_000_000)
+    ///     .add_taker("taker wallet address")
+    ///     .add_referral_account("referral account address")
+    ///     .add_referral_fee(50);
     /// let order = api.get_ultra_order(&req).await?;
     /// ```
+    ///
+    /// Without a taker, the order carries a quote but no transaction.
+    ///
+    /// Referral parameters are checked with [`UltraOrderRequest::validate`] before anything is
+    /// sent.
     pub async fn get_ultra_order(
         &self,
         params: &UltraOrderRequest,
     ) -> Result<UltraOrderResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .get(format!("{}/ultra/v1/order", self.base_url))
-            .query(&params)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<UltraOrderResponse>().await {
-            Ok(ultra_order_response) => Ok(ultra_order_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
+        params.validate()?;
+        let response = self
+            .send(
+                self.client
+                    .get(format!("{}/ultra/v1/order", self.base_url))
+                    .query(&params),
+            )
+            .await?;
+
+        let order = self.read_json::<UltraOrderResponse>(response).await?;
+
+        if order.transaction.is_some() {
+            self.check_policies(|| SigningIntent::from_ultra(&order))?;
         }
+
+        Ok(order)
     }
 
     /// Executes a signed swap order using Jupiter's Ultra API.
@@ -73,28 +81,31 @@ impl JupiterClient {
     /// ```
     /// let req = UltraExecuteOrderRequest::new(signed_tx, request_id);
     /// let res = api.ultra_execute_order(&req).await?;
+    /// match res.execute_code() {
+    ///     UltraExecuteCode::Success => {
+    ///         println!("landed {:?}, {:?} bps slippage", res.signature, res.realized_slippage_bps(&order));
+    ///     }
+    ///     code if code.is_retryable() => println!("{code:?}, fetch a new order"),
+    ///     code => println!("failed: {code:?} {:?}", res.error),
+    /// }
     /// ```
+    ///
+    /// A swap that failed on-chain is still `Ok`, with
+    /// [`Status::Failed`](crate::types::Status::Failed) and a non-zero
+    /// [`code`](UltraExecuteOrderResponse::code).
     pub async fn ultra_execute_order(
         &self,
         data: &UltraExecuteOrderRequest,
     ) -> Result<UltraExecuteOrderResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/ultra/v1/execute", self.base_url))
-            .json(&data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<UltraExecuteOrderResponse>().await {
-            Ok(swap_response) => Ok(swap_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        let response = self
+            .send(
+                self.client
+                    .post(format!("{}/ultra/v1/execute", self.base_url))
+                    .json(&data),
+            )
+            .await?;
+
+        self.read_json::<UltraExecuteOrderResponse>(response).await
     }
 
     /// Fetches token balances for a given wallet address using Jupiter's Ultra API.
@@ -123,22 +134,14 @@ impl JupiterClient {
         &self,
         address: &str,
     ) -> Result<TokenBalancesResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .get(format!("{}/ultra/v1/balances/{}", self.base_url, address))
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<TokenBalancesResponse>().await {
-            Ok(token_balances) => Ok(token_balances),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        let response = self
+            .send(
+                self.client
+                    .get(format!("{}/ultra/v1/balances/{}", self.base_url, address)),
+            )
+            .await?;
+
+        self.read_json::<TokenBalancesResponse>(response).await
     }
 
     /// Fetches token safety information for given mints using Jupiter's Ultra Shield API.
@@ -151,7 +154,7 @@ impl JupiterClient {
     ///
     /// # Returns
     ///
-    /// * `Ok(Shield)` containing token safety metadata.
+    /// * `Ok(Shield)` with the typed warnings of each flagged mint.
     /// * `Err` if the request or deserialization fails.
     ///
     /// # Jupiter API Reference
@@ -165,29 +168,30 @@ impl JupiterClient {
     ///     "So11111111111111111111111111111111111111112".to_string(),
     ///     "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
     /// ];
-    /// let shield_info = client.shield(&mints).await?;
-    /// println!("{:#?}", shield_info);
+    /// let shield = client.get_shield(&mints).await?;
+    /// for mint in &mints {
+    ///     if shield.is_critical(mint) || shield.has_warning(mint, &WarningType::NotSellable) {
+    ///         return Err(format!("{mint} is flagged").into());
+    ///     }
+    /// }
     /// ```
-    pub async fn shield(&self, mints: &[String]) -> Result<Shield, JupiterClientError> {
+    pub async fn get_shield(&self, mints: &[String]) -> Result<Shield, JupiterClientError> {
         let query_params = vec![("mints", mints.join(","))];
 
-        let response = match self
-            .client
-            .get(format!("{}/ultra/v1/shield", self.base_url))
-            .query(&query_params)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        let response = self
+            .send(
+                self.client
+                    .get(format!("{}/ultra/v1/shield", self.base_url))
+                    .query(&query_params),
+            )
+            .await?;
 
-        let response = handle_response(response).await?;
+        self.read_json::<Shield>(response).await
+    }
 
-        match response.json::<Shield>().await {
-            Ok(token_balances) => Ok(token_balances),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+    #[deprecated(note = "renamed to `get_shield`")]
+    pub async fn shield(&self, mints: &[String]) -> Result<Shield, JupiterClientError> {
+        self.get_shield(mints).await
     }
 
     /// search for a token and its information by its symbol, name or mint address
@@ -222,42 +226,26 @@ impl JupiterClient {
     ) -> Result<Vec<TokenInfo>, JupiterClientError> {
         let query_params = vec![("query", mints.join(","))];
 
-        let response = match self
-            .client
-            .get(format!("{}/ultra/v1/search", self.base_url))
-            .query(&query_params)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
+        let response = self
+            .send(
+                self.client
+                    .get(format!("{}/ultra/v1/search", self.base_url))
+                    .query(&query_params),
+            )
+            .await?;
 
-        match response.json::<Vec<TokenInfo>>().await {
-            Ok(data) => Ok(data),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.read_json::<Vec<TokenInfo>>(response).await
     }
 
     /// Request for the list of routers available in the routing engine of Ultra, which is Juno
     pub async fn routers(&self) -> Result<Vec<Router>, JupiterClientError> {
-        let response = match self
-            .client
-            .get(format!("{}/ultra/v1/order/routers", self.base_url))
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        response
-            .json::<Vec<Router>>()
-            .await
-            .map_err(|e| JupiterClientError::DeserializationError(e.to_string()))
+        let response = self
+            .send(
+                self.client
+                    .get(format!("{}/ultra/v1/order/routers", self.base_url)),
+            )
+            .await?;
+
+        self.read_json::<Vec<Router>>(response).await
     }
 }
@@ -0,0 +1,62 @@
+use super::JupiterClient;
+use crate::{
+    error::JupiterClientError,
+    types::{QuoteRequest, TokenInfo, TokenVetting, holdings::WSOL_MINT},
+};
+
+impl JupiterClient {
+    /// Vets `mint` before it's shown or traded: merges its Token API metadata, its Shield
+    /// warnings and whether Jupiter can route out of it into one [`TokenVetting`].
+    ///
+    /// Tradability is probed by quoting one whole token into SOL. A probe that Jupiter
+    /// rejects counts as untradable; transport and server errors are returned as errors.
+    ///
+    /// # Example
+    /// ```
+    /// let vetting = api.vet_token(mint).await?;
+    /// match &vetting.verdict {
+    ///     TokenVerdict::Tradable => {}
+    ///     TokenVerdict::Warning(reasons) => println!("careful: {}", reasons.join(", ")),
+    ///     TokenVerdict::Blocked(reasons) => return Err(reasons.join(", ").into()),
+    /// }
+    /// ```
+    pub async fn vet_token(&self, mint: &str) -> Result<TokenVetting, JupiterClientError> {
+        let mints = [mint.to_string()];
+
+        let info = self
+            .token_search(&mints)
+            .await?
+            .into_iter()
+            .find(|info| info.id == mint);
+        let warnings = self
+            .get_shield(&mints)
+            .await?
+            .warnings
+            .remove(mint)
+            .unwrap_or_default();
+        let tradable = match &info {
+            Some(info) => self.probe_tradable(info).await?,
+            None => false,
+        };
+
+        Ok(TokenVetting::new(mint, info.as_ref(), warnings, tradable))
+    }
+
+    async fn probe_tradable(&self, info: &TokenInfo) -> Result<bool, JupiterClientError> {
+        if info.id == WSOL_MINT {
+            return Ok(true);
+        }
+
+        let amount = 10u64.checked_pow(info.decimals as u32).unwrap_or(u64::MAX);
+        match self
+            .get_quote(&QuoteRequest::new(&info.id, WSOL_MINT, amount))
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(JupiterClientError::ApiError { status, .. }) if status.is_client_error() => {
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use super::JupiterClient;
+use crate::{
+    error::JupiterClientError,
+    types::{
+        QuoteGetSwapModeEnum, QuoteRequest, SwapRequest, WithdrawalConversion, WithdrawalRequest,
+    },
+};
+
+impl JupiterClient {
+    /// Builds an `ExactOut` swap that delivers exactly `request.amount` of `request.output_mint`
+    /// to `request.destination_token_account`, funded from `wallet`.
+    ///
+    /// Funding mints are tried in order of preference; the first one whose balance covers the
+    /// quoted maximum input, slippage included, is used. Funding mints equal to the output mint
+    /// are skipped since they need a plain transfer rather than a swap.
+    ///
+    /// After the transaction lands, check the received amount with
+    /// [`WithdrawalConversion::verify`].
+    ///
+    /// # Errors
+    /// [`JupiterClientError::InsufficientFunds`] when no funding mint can cover the withdrawal.
+    ///
+    /// # Example
+    /// ```
+    /// let conversion = api.convert_for_withdrawal(&hot_wallet, &request).await?;
+    /// let signature = sign_and_send(&conversion.swap).await?;
+    /// conversion.verify(received_amount(&signature).await?)?;
+    /// ```
+    pub async fn convert_for_withdrawal(
+        &self,
+        wallet: &str,
+        request: &WithdrawalRequest,
+    ) -> Result<WithdrawalConversion, JupiterClientError> {
+        let holdings = self
+            .get_wallet_holdings(wallet, &request.holdings_source)
+            .await?;
+        let mut reasons = Vec::new();
+
+        for funding_mint in &request.funding_mints {
+            if *funding_mint == request.output_mint {
+                continue;
+            }
+
+            let balance = holdings.amount(funding_mint);
+            if balance == 0 {
+                reasons.push(format!("{funding_mint}: no balance"));
+                continue;
+            }
+
+            let mut quote_request =
+                QuoteRequest::new(funding_mint, &request.output_mint, request.amount)
+                    .swap_mode(QuoteGetSwapModeEnum::ExactOut);
+            if let Some(slippage_bps) = request.slippage_bps {
+                quote_request = quote_request.slippage_bps(slippage_bps);
+            }
+
+            let quote = match self.get_swap_quote(&quote_request).await {
+                Ok(quote) => quote,
+                Err(e) => {
+                    reasons.push(format!("{funding_mint}: {e}"));
+                    continue;
+                }
+            };
+
+            // for ExactOut quotes the threshold is the maximum input
+            let max_in_amount = quote
+                .other_amount_threshold
+                .parse::<u64>()
+                .unwrap_or(u64::MAX);
+            if max_in_amount > balance {
+                reasons.push(format!(
+                    "{funding_mint}: needs up to {max_in_amount}, has {balance}"
+                ));
+                continue;
+            }
+
+            let swap_request = SwapRequest::new(wallet, wallet, quote.clone())
+                .destination_token_account(request.destination_token_account.clone());
+            let swap = self.get_swap_transaction(&swap_request).await?;
+
+            return Ok(WithdrawalConversion {
+                funding_mint: funding_mint.clone(),
+                max_in_amount,
+                expected_out_amount: request.amount,
+                destination_token_account: request.destination_token_account.clone(),
+                quote,
+                swap,
+            });
+        }
+
+        Err(JupiterClientError::InsufficientFunds(reasons.join("; ")))
+    }
+}
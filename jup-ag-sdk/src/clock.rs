@@ -0,0 +1,106 @@
+//! Time sources for the client's time-dependent logic.
+//!
+//! A [`JupiterClient`](crate::JupiterClient) reads the time and waits through its [`Clock`]
+//! for quote cache TTLs, circuit breaker cooldowns, rate limiting, retry backoff and the
+//! scheduling of batches and sampled quotes. Latencies reported to audit and metrics sinks
+//! are always measured on the system clock.
+//!
+//! The default [`SystemClock`] uses the platform timers. Swap in a [`ManualClock`] to
+//! fast-forward time deterministically in tests, or implement [`Clock`] for environments
+//! with their own time source.
+//!
+//! # Example
+//! ```
+//! let clock = Arc::new(ManualClock::new());
+//! let api = JupiterClient::new("https://lite-api.jup.ag")
+//!     .with_clock(clock.clone())
+//!     .with_quote_cache(Duration::from_secs(1));
+//!
+//! api.get_quote(&request).await?; // fetched
+//! api.get_quote(&request).await?; // cached
+//! clock.advance(Duration::from_secs(2));
+//! api.get_quote(&request).await?; // fetched again
+//! ```
+
+use std::{fmt, future::Future, pin::Pin, sync::Mutex, time::Duration};
+
+use crate::rt;
+pub use crate::rt::Instant;
+
+/// The future returned by [`Clock::sleep`].
+#[cfg(not(target_arch = "wasm32"))]
+pub type Sleep = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// The future returned by [`Clock::sleep`].
+#[cfg(target_arch = "wasm32")]
+pub type Sleep = Pin<Box<dyn Future<Output = ()>>>;
+
+/// A source of time, see the [module docs](self).
+pub trait Clock: fmt::Debug + Send + Sync {
+    fn now(&self) -> Instant;
+
+    /// Completes once `duration` has passed on this clock.
+    fn sleep(&self, duration: Duration) -> Sleep;
+}
+
+/// The platform clock and timers.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        Box::pin(rt::sleep(duration))
+    }
+}
+
+/// A clock that only moves when told to.
+///
+/// [`sleep`](Clock::sleep) advances the clock by the requested duration and completes
+/// immediately, so backoffs, rate limits and schedules run instantly while observing the
+/// time they would have taken.
+#[derive(Debug)]
+pub struct ManualClock {
+    origin: Instant,
+    elapsed: Mutex<Duration>,
+}
+
+impl ManualClock {
+    /// A clock stopped at the current instant.
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+            elapsed: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap_or_else(|e| e.into_inner()) += duration;
+    }
+
+    /// How far the clock has moved since it was created.
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.origin + self.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        self.advance(duration);
+        Box::pin(std::future::ready(()))
+    }
+}
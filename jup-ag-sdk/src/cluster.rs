@@ -0,0 +1,70 @@
+//! Guards against broadcasting Jupiter transactions to the wrong cluster.
+//!
+//! Jupiter routes only exist on mainnet-beta, so a transaction built by the SDK is meaningless
+//! on devnet or testnet. Call [`ensure_mainnet_rpc`] before sending to catch misconfigured
+//! RPC endpoints, or opt into the check with
+//! [`ResubmitConfig::require_mainnet`](crate::submission::ResubmitConfig::require_mainnet) and
+//! [`JitoBundleSubmitter::require_mainnet`](crate::jito::JitoBundleSubmitter::require_mainnet).
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+use reqwest::Client;
+
+use crate::{JupiterClientError, submission::rpc_call};
+
+/// Genesis hash of mainnet-beta.
+pub const MAINNET_BETA_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+
+/// Fails with [`JupiterClientError::ClusterMismatch`] unless `genesis_hash` is mainnet-beta's.
+pub fn ensure_mainnet_genesis(genesis_hash: &str) -> Result<(), JupiterClientError> {
+    if genesis_hash == MAINNET_BETA_GENESIS_HASH {
+        Ok(())
+    } else {
+        Err(JupiterClientError::ClusterMismatch(
+            genesis_hash.to_string(),
+        ))
+    }
+}
+
+/// Genesis hashes already fetched, keyed by RPC URL. A node never changes cluster, so each
+/// URL is queried once per process.
+fn genesis_hashes() -> &'static Mutex<HashMap<String, String>> {
+    static HASHES: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    HASHES.get_or_init(Default::default)
+}
+
+/// Queries `getGenesisHash` on `rpc_url` and fails unless it is a mainnet-beta node.
+///
+/// The hash is cached per URL, so only the first call for an RPC costs a request. Uses its own
+/// HTTP client so the Jupiter API key is never sent to the RPC.
+///
+/// # Example
+/// ```
+/// ensure_mainnet_rpc(&rpc_url).await?;
+/// rpc.send_transaction(&signed_transaction).await?;
+/// ```
+pub async fn ensure_mainnet_rpc(rpc_url: &str) -> Result<(), JupiterClientError> {
+    let cached = genesis_hashes().lock().unwrap().get(rpc_url).cloned();
+    let genesis_hash = match cached {
+        Some(genesis_hash) => genesis_hash,
+        None => {
+            let genesis_hash: String = rpc_call(
+                &Client::new(),
+                rpc_url,
+                "getGenesisHash",
+                serde_json::json!([]),
+            )
+            .await?;
+            genesis_hashes()
+                .lock()
+                .unwrap()
+                .insert(rpc_url.to_string(), genesis_hash.clone());
+            genesis_hash
+        }
+    };
+
+    ensure_mainnet_genesis(&genesis_hash)
+}
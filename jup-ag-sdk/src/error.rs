@@ -1,28 +1,258 @@
+use std::time::Duration;
+
 use reqwest::{Response, StatusCode};
 
+use crate::retry::retry_after;
+
+/// Errors returned by the client.
+///
+/// Errors raised once a call to Jupiter has been tagged carry its `x-request-id` in a
+/// `request_id` field, also read through [`request_id`](Self::request_id). It is `None` for
+/// errors raised outside such a call, e.g. by RPC requests or local decoding.
 #[derive(Debug, thiserror::Error)]
 pub enum JupiterClientError {
-    #[error("Request failed: {0}")]
-    RequestError(#[from] reqwest::Error),
+    #[error("Request failed: {source}{}", suffix(.request_id))]
+    RequestError {
+        source: reqwest::Error,
+        request_id: Option<String>,
+    },
 
     #[error("Invalid header value: {0}")]
     HeaderError(#[from] reqwest::header::InvalidHeaderValue),
 
-    #[error("API returned error: {0}, Status Code: {1}")]
-    ApiError(String, StatusCode),
+    #[error("API returned error: {body}, Status Code: {status}{}", suffix(.request_id))]
+    ApiError {
+        body: String,
+        status: StatusCode,
+        request_id: Option<String>,
+    },
+
+    /// Jupiter answered `429 Too Many Requests`. `retry_after` is how long it asked to wait,
+    /// if it said.
+    #[error("Rate limited by the API, retry after {retry_after:?}{}", suffix(.request_id))]
+    RateLimited {
+        retry_after: Option<Duration>,
+        request_id: Option<String>,
+    },
+
+    #[error("Failed to deserialize response: {message}{}", suffix(.request_id))]
+    DeserializationError {
+        message: String,
+        request_id: Option<String>,
+    },
+
+    #[error("Program id {0} not found in the program-id-to-label registry")]
+    UnknownProgramId(String),
+
+    /// A venue label isn't served by the routing engine, see
+    /// [`JupiterClient::validate_dexes`](crate::JupiterClient::validate_dexes).
+    #[error("Dex {0} not found in the program-id-to-label registry")]
+    UnknownDex(String),
+
+    #[error("Invalid instruction data: {0}")]
+    InstructionDataError(String),
+
+    #[error("Transaction simulation failed: {0}")]
+    SimulationError(String),
+
+    #[error("Signing policy violation: {0}")]
+    PolicyViolation(String),
+
+    #[error("Invalid client configuration: {0}")]
+    ConfigError(String),
+
+    #[error("Invalid slippage: {0}")]
+    InvalidSlippage(String),
+
+    /// The request's slippage exceeds the client's maximum, see
+    /// [`JupiterClient::with_max_slippage`](crate::JupiterClient::with_max_slippage).
+    #[error("Slippage of {bps} bps exceeds the maximum of {max_bps} bps")]
+    SlippageTooHigh { bps: u16, max_bps: u16 },
+
+    /// A request or route used a venue outside the client's allowlist, see
+    /// [`JupiterClient::with_venue_allowlist`](crate::JupiterClient::with_venue_allowlist).
+    #[error("Venue {0} is not in the allowlist")]
+    VenueNotAllowed(String),
+
+    /// A response body exceeded the client's limit, see
+    /// [`JupiterClient::with_max_response_size`](crate::JupiterClient::with_max_response_size).
+    #[error("Response body exceeds the limit of {limit} bytes{}", suffix(.request_id))]
+    ResponseTooLarge {
+        limit: usize,
+        request_id: Option<String>,
+    },
+
+    #[error("Client-side rate limit exceeded")]
+    RateLimitExceeded,
+
+    #[error("Circuit open for endpoint {endpoint}{}", suffix(.request_id))]
+    CircuitOpen {
+        endpoint: String,
+        request_id: Option<String>,
+    },
+
+    /// The call was aborted through [`CallOpts::cancel_on`](crate::client::CallOpts::cancel_on).
+    #[error("Request cancelled")]
+    Cancelled,
+
+    /// The call ran past its [`CallOpts::deadline`](crate::client::CallOpts::deadline), or a
+    /// [`ResubmitConfig::deadline`](crate::submission::ResubmitConfig::deadline).
+    #[error("Deadline exceeded{}", suffix(.request_id))]
+    DeadlineExceeded { request_id: Option<String> },
+
+    /// The client's [`Shutdown`](crate::shutdown::Shutdown) handle was triggered.
+    #[error("Client is shutting down")]
+    ShuttingDown,
+
+    #[error("No funding token can cover the request: {0}")]
+    InsufficientFunds(String),
+
+    #[error("Fill mismatch: expected {expected}, received {received}")]
+    FillMismatch { expected: u64, received: u64 },
+
+    #[error("Unknown tenant: {0}")]
+    UnknownTenant(String),
+
+    #[error("RPC is not connected to mainnet-beta: genesis hash {0}")]
+    ClusterMismatch(String),
+
+    #[error("No token metadata for mint {0}")]
+    MissingTokenMetadata(String),
 
-    #[error("Failed to deserialize response: {0}")]
-    DeserializationError(String),
+    #[error("Invalid public key: {0}")]
+    InvalidPubkey(String),
+
+    /// A trigger order that Jupiter would reject, caught before it's sent, see
+    /// [`CreateTriggerOrder::validate`](crate::types::CreateTriggerOrder::validate).
+    #[error("Invalid trigger order: {0}")]
+    InvalidTriggerOrder(String),
+
+    /// A recurring order that Jupiter would reject, caught before it's sent, see
+    /// [`CreateRecurringOrderRequest::validate`](crate::types::CreateRecurringOrderRequest::validate).
+    #[error("Invalid recurring order: {0}")]
+    InvalidRecurringOrder(String),
+
+    /// Ultra referral parameters Jupiter would reject, see
+    /// [`UltraReferral::new`](crate::types::UltraReferral::new).
+    #[error("Invalid referral: {0}")]
+    InvalidReferral(String),
+
+    /// Reading or writing persisted state failed, e.g. a
+    /// [`SnapshotStore`](crate::portfolio::SnapshotStore).
+    #[error("Storage error: {0}")]
+    StorageError(#[from] std::io::Error),
+
+    /// `/trigger/v1/execute` answered, but the transaction didn't land, see
+    /// [`ExecuteTriggerOrderResponse::into_result`](crate::types::ExecuteTriggerOrderResponse::into_result).
+    #[error("Trigger execution failed with code {code}: {error}")]
+    TriggerExecutionFailed { code: i32, error: String },
+
+    /// `/recurring/v1/execute` answered, but the transaction didn't land, see
+    /// [`ExecuteRecurringResponse::into_result`](crate::types::ExecuteRecurringResponse::into_result).
+    #[error("Recurring execution failed: {0}")]
+    RecurringExecutionFailed(String),
+
+    #[error("No transaction in the bundle pays a Jito tip account")]
+    MissingJitoTip,
+
+    #[error("Jito block engine error: {0}")]
+    JitoError(String),
+
+    #[error("RPC error: {0}")]
+    RpcError(String),
+
+    #[error("Transaction with message hash {0} was already confirmed")]
+    DuplicateSubmission(String),
+}
+
+impl JupiterClientError {
+    /// The `x-request-id` of the call that failed, if the error was raised during one.
+    ///
+    /// Quote it when filing a support ticket so the failure can be found in Jupiter's logs.
+    ///
+    /// # Example
+    /// ```
+    /// match api.get_quote(&request).await {
+    ///     Err(e @ JupiterClientError::ApiError { status, .. }) if status.is_client_error() => {
+    ///         println!("rejected, request ID {:?}", e.request_id());
+    ///     }
+    ///     other => { /* ... */ }
+    /// }
+    /// ```
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            JupiterClientError::RequestError { request_id, .. }
+            | JupiterClientError::ApiError { request_id, .. }
+            | JupiterClientError::RateLimited { request_id, .. }
+            | JupiterClientError::DeserializationError { request_id, .. }
+            | JupiterClientError::ResponseTooLarge { request_id, .. }
+            | JupiterClientError::CircuitOpen { request_id, .. }
+            | JupiterClientError::DeadlineExceeded { request_id } => request_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// A [`DeserializationError`](Self::DeserializationError) raised outside a call to Jupiter.
+    pub(crate) fn deserialization(message: impl Into<String>) -> Self {
+        JupiterClientError::DeserializationError {
+            message: message.into(),
+            request_id: None,
+        }
+    }
+
+    /// Tags an error raised during the call sent with `id`, unless it already carries an ID.
+    pub(crate) fn with_request_id(mut self, id: &str) -> Self {
+        match &mut self {
+            JupiterClientError::RequestError { request_id, .. }
+            | JupiterClientError::ApiError { request_id, .. }
+            | JupiterClientError::RateLimited { request_id, .. }
+            | JupiterClientError::DeserializationError { request_id, .. }
+            | JupiterClientError::ResponseTooLarge { request_id, .. }
+            | JupiterClientError::CircuitOpen { request_id, .. }
+            | JupiterClientError::DeadlineExceeded { request_id } => {
+                request_id.get_or_insert_with(|| id.to_string());
+            }
+            _ => {}
+        }
+        self
+    }
+}
+
+impl From<reqwest::Error> for JupiterClientError {
+    fn from(source: reqwest::Error) -> Self {
+        JupiterClientError::RequestError {
+            source,
+            request_id: None,
+        }
+    }
+}
+
+/// Renders the request ID appended to the messages of tagged errors.
+fn suffix(request_id: &Option<String>) -> String {
+    match request_id {
+        Some(id) => format!(", Request ID: {id}"),
+        None => String::new(),
+    }
 }
 
 pub async fn handle_response(response: Response) -> Result<Response, JupiterClientError> {
+    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        return Err(JupiterClientError::RateLimited {
+            retry_after: retry_after(response.headers()),
+            request_id: None,
+        });
+    }
     if !response.status().is_success() {
         let status = response.status();
         let error_text = response
             .text()
             .await
             .unwrap_or_else(|_| "Unable to get error details".to_string());
-        return Err(JupiterClientError::ApiError(error_text, status));
+        return Err(JupiterClientError::ApiError {
+            body: error_text,
+            status,
+            request_id: None,
+        });
     }
     Ok(response)
 }
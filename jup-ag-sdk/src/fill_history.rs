@@ -0,0 +1,228 @@
+//! Reporting queries over recorded fills.
+//!
+//! A fill is a successful [`AuditOperation::Confirmation`] record that carries the quote the
+//! transaction was built from, e.g.
+//!
+//! ```
+//! client.audit(
+//!     AuditRecord::new(AuditOperation::Confirmation)
+//!         .signature(signature.to_string())
+//!         .quote(quote)
+//!         .out_amount(received),
+//! );
+//! ```
+//!
+//! Load them back from a [`JsonLinesSink`](crate::audit::JsonLinesSink) file with
+//! [`FillHistory::from_json_lines`], or from any other store with
+//! [`FillHistory::from_records`], and query by pair or day, for realized slippage or for fee
+//! totals.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{self, BufRead},
+};
+
+use crate::{
+    audit::{AuditOperation, AuditRecord},
+    types::QuoteResponse,
+};
+
+const MS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+/// A confirmed swap, as recorded in the audit log.
+#[derive(Debug, Clone)]
+pub struct Fill {
+    pub signature: String,
+
+    /// Unix timestamp in milliseconds of the confirmation record.
+    pub timestamp_ms: u64,
+
+    pub quote: QuoteResponse,
+
+    /// Raw output amount that landed, when it was recorded.
+    pub out_amount: Option<u64>,
+}
+
+impl Fill {
+    pub fn input_mint(&self) -> &str {
+        &self.quote.input_mint
+    }
+
+    pub fn output_mint(&self) -> &str {
+        &self.quote.output_mint
+    }
+
+    /// Days since the Unix epoch, in UTC.
+    pub fn day(&self) -> u64 {
+        self.timestamp_ms / MS_PER_DAY
+    }
+
+    /// How far the landed output fell short of the quoted one, in bps. Negative when the fill
+    /// beat the quote. `None` when no output amount was recorded.
+    pub fn realized_slippage_bps(&self) -> Option<f64> {
+        let quoted = self.quote.out_amount.parse::<u64>().ok()?;
+        let realized = self.out_amount?;
+        if quoted == 0 {
+            return None;
+        }
+        Some((quoted as f64 - realized as f64) / quoted as f64 * 10_000.0)
+    }
+
+    /// Fees paid on the fill, by mint: every hop's venue fee plus the platform fee.
+    pub fn fees(&self) -> HashMap<String, u64> {
+        let mut fees = HashMap::new();
+        for step in &self.quote.route_plan {
+            let info = &step.swap_info;
+            let amount = info.fee_amount.parse::<u64>().unwrap_or_default();
+            if amount > 0 {
+                *fees.entry(info.fee_mint.clone()).or_default() += amount;
+            }
+        }
+        if let Some(fee) = &self.quote.platform_fee {
+            let amount = fee.amount.parse::<u64>().unwrap_or_default();
+            // taken from the output on ExactIn and the input on ExactOut
+            if amount > 0 {
+                *fees
+                    .entry(self.quote.worst_case_mint().to_string())
+                    .or_default() += amount;
+            }
+        }
+        fees
+    }
+}
+
+/// Summary of realized slippage over a set of fills, in bps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlippageStats {
+    /// Fills with a recorded output amount.
+    pub count: usize,
+    pub mean_bps: f64,
+    pub median_bps: f64,
+
+    /// The worst shortfall.
+    pub max_bps: f64,
+}
+
+/// Fills loaded from audit records, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct FillHistory {
+    fills: Vec<Fill>,
+}
+
+impl FillHistory {
+    /// Collects the fills in `records`.
+    ///
+    /// Records of other operations, failed confirmations and confirmations without a quote or
+    /// signature are skipped. When a signature is confirmed more than once, the latest record
+    /// wins.
+    pub fn from_records(records: impl IntoIterator<Item = AuditRecord>) -> Self {
+        let mut by_signature: HashMap<String, Fill> = HashMap::new();
+        for record in records {
+            if record.operation != AuditOperation::Confirmation || record.error.is_some() {
+                continue;
+            }
+            let (Some(signature), Some(quote)) = (record.signature, record.quote) else {
+                continue;
+            };
+            let fill = Fill {
+                signature: signature.clone(),
+                timestamp_ms: record.timestamp_ms,
+                quote,
+                out_amount: record.out_amount,
+            };
+            match by_signature.get(&signature) {
+                Some(seen) if seen.timestamp_ms > fill.timestamp_ms => {}
+                _ => {
+                    by_signature.insert(signature, fill);
+                }
+            }
+        }
+
+        let mut fills: Vec<Fill> = by_signature.into_values().collect();
+        fills.sort_by(|a, b| (a.timestamp_ms, &a.signature).cmp(&(b.timestamp_ms, &b.signature)));
+        Self { fills }
+    }
+
+    /// Reads the records written by a [`JsonLinesSink`](crate::audit::JsonLinesSink).
+    ///
+    /// # Errors
+    /// Fails with [`io::ErrorKind::InvalidData`] on a line that isn't an audit record.
+    pub fn from_json_lines(reader: impl BufRead) -> io::Result<Self> {
+        let mut records = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            records.push(serde_json::from_str::<AuditRecord>(&line)?);
+        }
+        Ok(Self::from_records(records))
+    }
+
+    pub fn fills(&self) -> &[Fill] {
+        &self.fills
+    }
+
+    /// Fills that swapped `input_mint` into `output_mint`.
+    pub fn by_pair(&self, input_mint: &str, output_mint: &str) -> Vec<&Fill> {
+        self.fills
+            .iter()
+            .filter(|fill| fill.input_mint() == input_mint && fill.output_mint() == output_mint)
+            .collect()
+    }
+
+    /// Fills grouped by UTC day, see [`Fill::day`].
+    pub fn by_day(&self) -> BTreeMap<u64, Vec<&Fill>> {
+        let mut days: BTreeMap<u64, Vec<&Fill>> = BTreeMap::new();
+        for fill in &self.fills {
+            days.entry(fill.day()).or_default().push(fill);
+        }
+        days
+    }
+
+    /// Realized slippage over every fill with a recorded output amount.
+    pub fn slippage_stats(&self) -> Option<SlippageStats> {
+        slippage_stats(self.fills.iter())
+    }
+
+    /// Realized slippage over the fills of one pair.
+    pub fn pair_slippage_stats(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+    ) -> Option<SlippageStats> {
+        slippage_stats(self.by_pair(input_mint, output_mint).into_iter())
+    }
+
+    /// Total fees paid, by mint, see [`Fill::fees`].
+    pub fn fee_totals(&self) -> HashMap<String, u64> {
+        let mut totals = HashMap::new();
+        for fill in &self.fills {
+            for (mint, amount) in fill.fees() {
+                *totals.entry(mint).or_default() += amount;
+            }
+        }
+        totals
+    }
+}
+
+fn slippage_stats<'a>(fills: impl Iterator<Item = &'a Fill>) -> Option<SlippageStats> {
+    let mut bps: Vec<f64> = fills.filter_map(Fill::realized_slippage_bps).collect();
+    if bps.is_empty() {
+        return None;
+    }
+    bps.sort_by(f64::total_cmp);
+
+    let count = bps.len();
+    let median_bps = if count % 2 == 0 {
+        (bps[count / 2 - 1] + bps[count / 2]) / 2.0
+    } else {
+        bps[count / 2]
+    };
+    Some(SlippageStats {
+        count,
+        mean_bps: bps.iter().sum::<f64>() / count as f64,
+        median_bps,
+        max_bps: bps[count - 1],
+    })
+}
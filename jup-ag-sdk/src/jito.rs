@@ -0,0 +1,257 @@
+//! MEV-protected submission through Jito bundles.
+//!
+//! A transaction broadcast through a public RPC is forwarded to leaders where searchers can
+//! see and sandwich it. [`JitoBundleSubmitter`] only ever talks to a Jito block engine, refuses
+//! bundles that don't tip a Jito tip account, and reports whether the bundle landed.
+//!
+//! Build the swap with a tip, e.g. with
+//! [`prioritization_fee_jito_tip`](crate::types::SwapRequest::prioritization_fee_jito_tip), so
+//! Jupiter adds the tip transfer to the transaction.
+
+use std::{sync::OnceLock, time::Duration};
+
+use reqwest::Client;
+use serde::{Deserialize, de::DeserializeOwned};
+
+use crate::{
+    JupiterClientError,
+    error::handle_response,
+    rt::{self, Instant},
+    wire::{TRANSACTION_BUFFER_LEN, decode_transaction, static_account_keys},
+};
+
+/// The mainnet block engine.
+pub const MAINNET_BLOCK_ENGINE_URL: &str = "https://mainnet.block-engine.jito.wtf";
+
+/// Accounts a bundle can pay its tip to.
+pub const JITO_TIP_ACCOUNTS: [&str; 8] = [
+    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+    "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+    "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+    "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+    "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+    "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+    "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+    "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+];
+
+/// The state of a submitted bundle, as reported by `getInflightBundleStatuses`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleOutcome {
+    /// The bundle landed in `slot`.
+    Landed { slot: u64 },
+
+    /// The bundle was dropped or failed simulation; none of its transactions landed.
+    Failed,
+
+    /// Still pending when the wait timed out. It may yet land until its blockhash expires.
+    Pending,
+}
+
+/// Submits signed transactions exclusively through a Jito block engine.
+///
+/// Uses its own HTTP client so the Jupiter API key is never sent to the block engine.
+///
+/// # Example
+/// ```
+/// let swap = SwapRequest::new(&wallet, &wallet, quote).prioritization_fee_jito_tip(100_000);
+/// let response = api.get_swap_transaction(&swap).await?;
+/// let signed = sign(&response.swap_transaction, &keypair);
+///
+/// let jito = JitoBundleSubmitter::new(MAINNET_BLOCK_ENGINE_URL);
+/// let bundle_id = jito.send_bundle(&[signed]).await?;
+/// match jito.wait_for_bundle(&bundle_id, Duration::from_secs(30), Duration::from_secs(1)).await? {
+///     BundleOutcome::Landed { slot } => println!("landed in slot {slot}"),
+///     outcome => println!("not landed: {outcome:?}"),
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct JitoBundleSubmitter {
+    client: Client,
+    block_engine_url: String,
+    require_mainnet: bool,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct InflightStatuses {
+    value: Vec<InflightStatus>,
+}
+
+#[derive(Deserialize)]
+struct InflightStatus {
+    status: String,
+    landed_slot: Option<u64>,
+}
+
+impl JitoBundleSubmitter {
+    pub fn new(block_engine_url: &str) -> Self {
+        Self {
+            client: Client::new(),
+            block_engine_url: block_engine_url.trim_end_matches('/').to_string(),
+            require_mainnet: false,
+        }
+    }
+
+    /// Refuses to send bundles unless the block engine is one of Jito's mainnet endpoints,
+    /// `mainnet.block-engine.jito.wtf` or a regional `*.mainnet.block-engine.jito.wtf`.
+    ///
+    /// Block engines don't answer `getGenesisHash`, so unlike
+    /// [`ResubmitConfig::require_mainnet`](crate::submission::ResubmitConfig::require_mainnet)
+    /// the check goes by host name, and rejects self-hosted proxies in front of mainnet.
+    pub fn require_mainnet(mut self, require: bool) -> Self {
+        self.require_mainnet = require;
+        self
+    }
+
+    /// Sends up to 5 signed, base64-encoded transactions as a single bundle and returns its id.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::MissingJitoTip`] if no transaction references a Jito tip account,
+    /// and [`JupiterClientError::ConfigError`] if [`require_mainnet`](Self::require_mainnet)
+    /// is set for a block engine that isn't mainnet's, both checked before anything is sent.
+    pub async fn send_bundle(&self, transactions: &[String]) -> Result<String, JupiterClientError> {
+        if self.require_mainnet && !is_mainnet_block_engine(&self.block_engine_url) {
+            return Err(JupiterClientError::ConfigError(format!(
+                "{} is not a mainnet block engine",
+                self.block_engine_url
+            )));
+        }
+
+        let mut tipped = false;
+        for transaction in transactions {
+            tipped |= jito_tip_account(transaction)?.is_some();
+        }
+        if !tipped {
+            return Err(JupiterClientError::MissingJitoTip);
+        }
+
+        self.call(
+            "sendBundle",
+            serde_json::json!([transactions, { "encoding": "base64" }]),
+        )
+        .await
+    }
+
+    /// Returns the current state of a bundle submitted in the last five minutes.
+    pub async fn get_bundle_outcome(
+        &self,
+        bundle_id: &str,
+    ) -> Result<BundleOutcome, JupiterClientError> {
+        let statuses: InflightStatuses = self
+            .call(
+                "getInflightBundleStatuses",
+                serde_json::json!([[bundle_id]]),
+            )
+            .await?;
+
+        Ok(match statuses.value.first() {
+            Some(InflightStatus {
+                status,
+                landed_slot: Some(slot),
+            }) if status == "Landed" => BundleOutcome::Landed { slot: *slot },
+            Some(InflightStatus { status, .. }) if status == "Failed" || status == "Invalid" => {
+                BundleOutcome::Failed
+            }
+            _ => BundleOutcome::Pending,
+        })
+    }
+
+    /// Polls the bundle every `poll_interval` until it lands, fails, or `timeout` elapses.
+    pub async fn wait_for_bundle(
+        &self,
+        bundle_id: &str,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<BundleOutcome, JupiterClientError> {
+        let started = Instant::now();
+        loop {
+            let outcome = self.get_bundle_outcome(bundle_id).await?;
+            if outcome != BundleOutcome::Pending || started.elapsed() + poll_interval > timeout {
+                return Ok(outcome);
+            }
+            rt::sleep(poll_interval).await;
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, JupiterClientError> {
+        let response = self
+            .client
+            .post(format!("{}/api/v1/bundles", self.block_engine_url))
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))
+            .send()
+            .await?;
+
+        let response = handle_response(response).await?;
+
+        let body = match response.json::<RpcResponse<T>>().await {
+            Ok(body) => body,
+            Err(e) => return Err(JupiterClientError::deserialization(e.to_string())),
+        };
+        match (body.result, body.error) {
+            (_, Some(error)) => Err(JupiterClientError::JitoError(error.message)),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(JupiterClientError::JitoError(format!(
+                "empty response to {method}"
+            ))),
+        }
+    }
+}
+
+fn is_mainnet_block_engine(url: &str) -> bool {
+    let host = reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_default();
+    host == "mainnet.block-engine.jito.wtf" || host.ends_with(".mainnet.block-engine.jito.wtf")
+}
+
+/// [`JITO_TIP_ACCOUNTS`] as raw keys, decoded once.
+fn tip_account_keys() -> &'static [[u8; 32]; 8] {
+    static KEYS: OnceLock<[[u8; 32]; 8]> = OnceLock::new();
+    KEYS.get_or_init(|| {
+        JITO_TIP_ACCOUNTS.map(|tip| {
+            let mut key = [0u8; 32];
+            bs58::decode(tip)
+                .onto(&mut key)
+                .expect("tip accounts are valid pubkeys");
+            key
+        })
+    })
+}
+
+/// Returns the Jito tip account referenced by a base64-encoded transaction, if any.
+///
+/// Looks at the static account keys of legacy and v0 messages; a tip transfer always lists
+/// the tip account there.
+pub fn jito_tip_account(transaction: &str) -> Result<Option<&'static str>, JupiterClientError> {
+    let mut buffer = [0; TRANSACTION_BUFFER_LEN];
+    let bytes = decode_transaction(transaction, &mut buffer)?;
+    let keys = static_account_keys(bytes)
+        .ok_or_else(|| JupiterClientError::deserialization("malformed transaction".to_string()))?;
+
+    Ok(JITO_TIP_ACCOUNTS
+        .into_iter()
+        .zip(tip_account_keys())
+        .find(|(_, key)| keys.clone().any(|k| k == key.as_slice()))
+        .map(|(tip, _)| tip))
+}
@@ -7,6 +7,13 @@
 //! - Access Ultra API features (orders, balances, shield)
 //! - Fetch token prices and router information
 //!
+//! The client also compiles for `wasm32-unknown-unknown`, using reqwest's fetch backend, so it
+//! can run in browser dApps and wasm workers. Timeouts and HTTP/2 tuning are native-only and
+//! ignored there.
+//!
+//! TLS comes from the platform library by default (the `native-tls` feature). For static musl
+//! binaries or images without OpenSSL, disable default features and enable `rustls`.
+//!
 //! ## Example
 //!
 //! ```rust
@@ -30,9 +37,38 @@
 //! }
 //! ```
 
-pub use client::JupiterClient;
+pub use client::{JupiterClient, JupiterClientBuilder};
 pub use error::JupiterClientError;
 
+pub mod ata;
+pub mod audit;
+pub mod bucketing;
+pub mod circuit_breaker;
 pub mod client;
+pub mod clock;
+pub mod cluster;
 pub mod error;
+pub mod fill_history;
+pub mod jito;
+pub mod metrics;
+pub mod middleware;
+pub mod policy;
+pub mod pool;
+pub mod portfolio;
+pub mod predicate;
+#[cfg(feature = "program-test")]
+pub mod program_test;
+mod quote_cache;
+pub mod rate_limit;
+pub mod retry;
+mod rt;
+pub mod shutdown;
+pub mod submission;
+pub mod token_snapshot;
+#[cfg(feature = "tracing")]
+pub mod tracing_bridge;
+pub mod trigger_grid;
 pub mod types;
+pub mod venue_health;
+pub mod warnings;
+mod wire;
@@ -0,0 +1,86 @@
+//! Per-request metrics delivered to pluggable sinks.
+//!
+//! Register sinks with [`JupiterClient::with_metrics_sink`](crate::JupiterClient::with_metrics_sink).
+//! Every HTTP attempt, on every endpoint and including retries, produces one
+//! [`RequestMetric`], ready to feed counters and latency histograms.
+
+use std::{fmt, sync::Arc, time::Duration};
+
+use reqwest::{Method, StatusCode};
+
+/// The outcome of a single HTTP attempt.
+#[derive(Debug, Clone)]
+pub struct RequestMetric {
+    /// The endpoint path relative to the base URL, without query, e.g. `swap/v1/quote` or
+    /// `ultra/v1/order`. Path parameters are templated, e.g. `ultra/v1/balances/{address}`,
+    /// so it is low-cardinality and can be used as a metric label.
+    pub endpoint: String,
+
+    pub method: Method,
+
+    /// The response status, or `None` when the request failed before a response arrived.
+    pub status: Option<StatusCode>,
+
+    /// Time from sending the request until the response headers arrived or the request failed.
+    pub duration: Duration,
+
+    /// 1 for the first attempt, incremented on every retry.
+    pub attempt: u32,
+}
+
+impl RequestMetric {
+    /// Whether the attempt failed, either at the transport level or with a non-success status.
+    pub fn is_error(&self) -> bool {
+        !self.status.is_some_and(|status| status.is_success())
+    }
+}
+
+/// A destination for [`RequestMetric`]s, e.g. a Prometheus registry or a StatsD client.
+///
+/// Implemented for any `Fn(&RequestMetric)`, so closures can be registered directly. Sinks are
+/// called on the request path, so keep them cheap.
+///
+/// # Example
+/// ```
+/// let client = JupiterClient::new("https://lite-api.jup.ag")
+///     .with_metrics_sink(|metric: &RequestMetric| {
+///         let status = metric.status.map_or("error".to_string(), |s| s.as_u16().to_string());
+///         REQUEST_LATENCY
+///             .with_label_values(&[&metric.endpoint, &status])
+///             .observe(metric.duration.as_secs_f64());
+///     });
+/// ```
+pub trait MetricsSink: Send + Sync {
+    fn record(&self, metric: &RequestMetric);
+}
+
+impl<F> MetricsSink for F
+where
+    F: Fn(&RequestMetric) + Send + Sync,
+{
+    fn record(&self, metric: &RequestMetric) {
+        self(metric)
+    }
+}
+
+/// The metrics sinks registered on a client.
+#[derive(Clone, Default)]
+pub(crate) struct MetricsSinks(pub(crate) Vec<Arc<dyn MetricsSink>>);
+
+impl MetricsSinks {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn emit(&self, metric: &RequestMetric) {
+        for sink in &self.0 {
+            sink.record(metric);
+        }
+    }
+}
+
+impl fmt::Debug for MetricsSinks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MetricsSinks({})", self.0.len())
+    }
+}
@@ -0,0 +1,131 @@
+//! Request/response hooks applied uniformly to every endpoint.
+//!
+//! Register middleware with [`JupiterClient::with_middleware`](crate::JupiterClient::with_middleware).
+//! Hooks run for every HTTP attempt, including retries, right before the request is sent and
+//! right after the response headers arrive, so they see exactly what goes over the wire.
+
+use std::{fmt, sync::Arc, time::Duration};
+
+use reqwest::{Method, Request, StatusCode, Url, header::HeaderMap};
+
+/// A request about to be sent, open to mutation.
+#[derive(Debug)]
+pub struct RequestParts<'a> {
+    request: &'a mut Request,
+    attempt: u32,
+}
+
+impl<'a> RequestParts<'a> {
+    pub(crate) fn new(request: &'a mut Request, attempt: u32) -> Self {
+        Self { request, attempt }
+    }
+
+    pub fn method(&self) -> &Method {
+        self.request.method()
+    }
+
+    pub fn url(&self) -> &Url {
+        self.request.url()
+    }
+
+    /// Rewrites the URL, e.g. to route through a gateway.
+    pub fn url_mut(&mut self) -> &mut Url {
+        self.request.url_mut()
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        self.request.headers()
+    }
+
+    /// Adds, replaces or removes headers, e.g. to inject a short-lived auth token.
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        self.request.headers_mut()
+    }
+
+    /// The request body, if it was built from JSON.
+    pub fn body(&self) -> Option<&[u8]> {
+        self.request.body().and_then(|body| body.as_bytes())
+    }
+
+    /// 1 for the first attempt, incremented on every retry.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+}
+
+/// The response headers of a request, before the body is read.
+#[derive(Debug)]
+pub struct ResponseParts<'a> {
+    pub method: &'a Method,
+
+    /// The URL that was requested, after any rewrite by middleware.
+    pub url: &'a Url,
+
+    pub status: StatusCode,
+    pub headers: &'a HeaderMap,
+
+    /// Time from sending the request until the response headers arrived.
+    pub elapsed: Duration,
+
+    pub attempt: u32,
+}
+
+/// Hooks called around every HTTP request the client sends.
+///
+/// Every method has a no-op default, so implement only the ones you need.
+///
+/// # Example
+/// ```
+/// struct BearerAuth(TokenCache);
+///
+/// impl Middleware for BearerAuth {
+///     fn on_request(&self, request: &mut RequestParts) {
+///         let value = format!("Bearer {}", self.0.current());
+///         request.headers_mut().insert(AUTHORIZATION, value.parse().unwrap());
+///     }
+///
+///     fn on_response(&self, response: &ResponseParts) {
+///         log::debug!("{} {} -> {} in {:?}", response.method, response.url, response.status, response.elapsed);
+///     }
+/// }
+///
+/// let client = JupiterClient::new("https://api.jup.ag").with_middleware(BearerAuth(cache));
+/// ```
+pub trait Middleware: Send + Sync {
+    fn on_request(&self, _request: &mut RequestParts) {}
+
+    fn on_response(&self, _response: &ResponseParts) {}
+
+    /// Called when the request fails before a response arrives, e.g. on connection errors.
+    fn on_error(&self, _error: &reqwest::Error) {}
+}
+
+/// The middleware registered on a client.
+#[derive(Clone, Default)]
+pub(crate) struct Middlewares(pub(crate) Vec<Arc<dyn Middleware>>);
+
+impl Middlewares {
+    pub(crate) fn on_request(&self, request: &mut RequestParts) {
+        for middleware in &self.0 {
+            middleware.on_request(request);
+        }
+    }
+
+    pub(crate) fn on_response(&self, response: &ResponseParts) {
+        for middleware in &self.0 {
+            middleware.on_response(response);
+        }
+    }
+
+    pub(crate) fn on_error(&self, error: &reqwest::Error) {
+        for middleware in &self.0 {
+            middleware.on_error(error);
+        }
+    }
+}
+
+impl fmt::Debug for Middlewares {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Middlewares({})", self.0.len())
+    }
+}
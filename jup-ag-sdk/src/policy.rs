@@ -0,0 +1,566 @@
+//! Custody policy hooks evaluated before an unsigned transaction is handed back for signing.
+//!
+//! Register policies with [`JupiterClient::with_policy`](crate::JupiterClient::with_policy).
+//! Every swap transaction, swap instruction set, Ultra order carrying a transaction, trigger
+//! order and recurring order is described as a [`SigningIntent`] and checked against each
+//! policy; the first denial is returned as
+//! [`JupiterClientError::PolicyViolation`](crate::JupiterClientError::PolicyViolation).
+//!
+//! Policies fail closed: a transaction whose amounts can't be read is denied rather than
+//! checked as zero.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    str::FromStr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use crate::{
+    JupiterClientError,
+    clock::{Clock, Instant, SystemClock},
+    types::{
+        CreateRecurringOrderRequest, CreateTriggerOrder, OrderParams, QuoteGetSwapModeEnum,
+        RoutePlanItem, SwapRequest, UltraOrderResponse,
+    },
+};
+
+/// Which flow produced the transaction about to be signed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntentSource {
+    Swap,
+    SwapInstructions,
+    Ultra,
+    Trigger,
+    Recurring,
+}
+
+/// A description of what an unsigned transaction will do once signed.
+#[derive(Debug, Clone)]
+pub struct SigningIntent {
+    pub source: IntentSource,
+
+    /// The wallet that will sign, if known.
+    pub user: Option<String>,
+
+    pub input_mint: String,
+    pub output_mint: String,
+
+    /// Raw input amount.
+    pub in_amount: u64,
+
+    /// The most input the transaction can spend: `other_amount_threshold` for an ExactOut
+    /// swap, whose input is only quoted, otherwise `in_amount`.
+    pub max_in_amount: u64,
+
+    /// Raw quoted output amount. The taking amount of a trigger order, zero for a recurring
+    /// order.
+    pub out_amount: u64,
+
+    /// Worst-case amount after slippage.
+    pub other_amount_threshold: u64,
+
+    pub slippage_bps: u16,
+
+    /// Whether Jupiter picks the slippage when it builds the transaction, in which case
+    /// `slippage_bps` doesn't bound it.
+    pub dynamic_slippage: bool,
+
+    pub price_impact_pct: f64,
+
+    /// The account collecting integrator fees, if any.
+    pub fee_account: Option<String>,
+
+    /// The token account receiving the output, when it isn't the user's own.
+    pub destination_token_account: Option<String>,
+
+    /// Venue labels of every hop in the route. Empty for trigger and recurring orders.
+    pub venues: Vec<String>,
+}
+
+impl SigningIntent {
+    /// Describes the transaction built for a [`SwapRequest`].
+    ///
+    /// # Errors
+    /// [`JupiterClientError::PolicyViolation`] when an amount of the quote can't be read.
+    pub fn from_swap(
+        source: IntentSource,
+        request: &SwapRequest,
+    ) -> Result<Self, JupiterClientError> {
+        let quote = &request.quote_response;
+        let in_amount = parse("inAmount", &quote.in_amount)?;
+        let other_amount_threshold = parse("otherAmountThreshold", &quote.other_amount_threshold)?;
+        Ok(Self {
+            source,
+            user: Some(request.user_public_key.clone()),
+            input_mint: quote.input_mint.clone(),
+            output_mint: quote.output_mint.clone(),
+            in_amount,
+            max_in_amount: max_in_amount(&quote.swap_mode, in_amount, other_amount_threshold),
+            out_amount: parse("outAmount", &quote.out_amount)?,
+            other_amount_threshold,
+            slippage_bps: quote.slippage_bps,
+            dynamic_slippage: request.dynamic_slippage == Some(true),
+            price_impact_pct: parse("priceImpactPct", &quote.price_impact_pct)?,
+            fee_account: request.fee_account.clone(),
+            destination_token_account: request.destination_token_account.clone(),
+            venues: venues(&quote.route_plan),
+        })
+    }
+
+    /// Describes the transaction carried by an Ultra order.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::PolicyViolation`] when an amount of the order can't be read.
+    pub fn from_ultra(order: &UltraOrderResponse) -> Result<Self, JupiterClientError> {
+        let in_amount = parse("inAmount", &order.in_amount)?;
+        let other_amount_threshold = parse("otherAmountThreshold", &order.other_amount_threshold)?;
+        Ok(Self {
+            source: IntentSource::Ultra,
+            user: order.taker.clone(),
+            input_mint: order.input_mint.clone(),
+            output_mint: order.output_mint.clone(),
+            in_amount,
+            max_in_amount: max_in_amount(&order.swap_mode, in_amount, other_amount_threshold),
+            out_amount: parse("outAmount", &order.out_amount)?,
+            other_amount_threshold,
+            slippage_bps: parse("slippageBps", &order.slippage_bps.to_string())?,
+            dynamic_slippage: false,
+            price_impact_pct: parse("priceImpactPct", &order.price_impact_pct)?,
+            fee_account: None,
+            destination_token_account: None,
+            venues: venues(&order.route_plan),
+        })
+    }
+
+    /// Describes the transaction creating a trigger order, which sells `making_amount` for at
+    /// least `taking_amount`.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::InvalidTriggerOrder`] when an amount can't be read, or
+    /// [`JupiterClientError::PolicyViolation`] when the slippage can't.
+    pub fn from_trigger(order: &CreateTriggerOrder) -> Result<Self, JupiterClientError> {
+        let making_amount = order.making_amount()?;
+        let taking_amount = order.taking_amount()?;
+        Ok(Self {
+            source: IntentSource::Trigger,
+            user: Some(order.maker.clone()),
+            input_mint: order.input_mint.clone(),
+            output_mint: order.output_mint.clone(),
+            in_amount: making_amount,
+            max_in_amount: making_amount,
+            out_amount: taking_amount,
+            other_amount_threshold: taking_amount,
+            slippage_bps: match &order.params.slippage_bps {
+                Some(slippage_bps) => parse("slippageBps", slippage_bps)?,
+                None => 0,
+            },
+            dynamic_slippage: false,
+            price_impact_pct: 0.0,
+            fee_account: order.fee_account.clone(),
+            destination_token_account: None,
+            venues: Vec::new(),
+        })
+    }
+
+    /// Describes the transaction creating a recurring order, spending its whole input amount
+    /// or deposit.
+    pub fn from_recurring(order: &CreateRecurringOrderRequest) -> Self {
+        let in_amount = match &order.params {
+            OrderParams::TimeWrapper { time } => time.in_amount,
+            OrderParams::PriceWrapper { price } => price.deposit_amount,
+        };
+        Self {
+            source: IntentSource::Recurring,
+            user: Some(order.user.clone()),
+            input_mint: order.input_mint.clone(),
+            output_mint: order.output_mint.clone(),
+            in_amount,
+            max_in_amount: in_amount,
+            out_amount: 0,
+            other_amount_threshold: 0,
+            slippage_bps: 0,
+            dynamic_slippage: false,
+            price_impact_pct: 0.0,
+            fee_account: None,
+            destination_token_account: None,
+            venues: Vec::new(),
+        }
+    }
+}
+
+/// Parses a field of the transaction's description, denying the transaction if it can't be
+/// read rather than checking it as zero.
+fn parse<T: FromStr>(field: &str, value: &str) -> Result<T, JupiterClientError> {
+    value.parse().map_err(|_| {
+        JupiterClientError::PolicyViolation(format!("{field} {value:?} can't be checked"))
+    })
+}
+
+fn max_in_amount(
+    swap_mode: &QuoteGetSwapModeEnum,
+    in_amount: u64,
+    other_amount_threshold: u64,
+) -> u64 {
+    match swap_mode {
+        QuoteGetSwapModeEnum::ExactIn => in_amount,
+        QuoteGetSwapModeEnum::ExactOut => other_amount_threshold,
+    }
+}
+
+fn venues(route_plan: &[RoutePlanItem]) -> Vec<String> {
+    route_plan
+        .iter()
+        .map(|step| step.swap_info.label.clone())
+        .collect()
+}
+
+/// The outcome of evaluating a [`SigningPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyDecision {
+    Allow,
+    Deny(String),
+}
+
+/// A custody rule checked before a transaction is returned for signing.
+///
+/// Implemented for any `Fn(&SigningIntent) -> PolicyDecision`, so closures can be registered directly.
+///
+/// # Example
+/// ```
+/// let client = JupiterClient::new("https://lite-api.jup.ag")
+///     .with_policy(MaxSlippageBps(100))
+///     .with_policy(|intent: &SigningIntent| {
+///         if intent.max_in_amount > 10_000_000_000 {
+///             PolicyDecision::Deny("swap larger than 10 SOL".to_string())
+///         } else {
+///             PolicyDecision::Allow
+///         }
+///     });
+/// ```
+pub trait SigningPolicy: Send + Sync {
+    fn evaluate(&self, intent: &SigningIntent) -> PolicyDecision;
+
+    /// Evaluates `intent` the way the client does before returning a transaction, recording
+    /// it if allowed. Returns the [`Reservation`] for what was recorded, or the denial reason.
+    ///
+    /// Defaults to [`evaluate`](Self::evaluate), recording nothing. Only stateful policies
+    /// such as [`DailyVolumeCap`] need to override it.
+    fn reserve(&self, intent: &SigningIntent) -> Result<Option<Reservation>, String> {
+        match self.evaluate(intent) {
+            PolicyDecision::Allow => Ok(None),
+            PolicyDecision::Deny(reason) => Err(reason),
+        }
+    }
+
+    /// Undoes what [`reserve`](Self::reserve) recorded under `reservation`, when a later policy
+    /// denies the intent or the transaction can't be built.
+    fn release(&self, _reservation: Reservation) {}
+}
+
+/// Identifies what a stateful [`SigningPolicy`] recorded for one approval, so
+/// [`release`](SigningPolicy::release) undoes exactly that record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Reservation(pub u64);
+
+impl<F> SigningPolicy for F
+where
+    F: Fn(&SigningIntent) -> PolicyDecision + Send + Sync,
+{
+    fn evaluate(&self, intent: &SigningIntent) -> PolicyDecision {
+        self(intent)
+    }
+}
+
+/// Denies any transaction whose slippage tolerance exceeds the given bps.
+///
+/// Swaps requesting [dynamic slippage](SigningIntent::dynamic_slippage) are denied too, since
+/// their tolerance is only chosen once the transaction is built.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxSlippageBps(pub u16);
+
+impl SigningPolicy for MaxSlippageBps {
+    fn evaluate(&self, intent: &SigningIntent) -> PolicyDecision {
+        if intent.dynamic_slippage {
+            PolicyDecision::Deny(format!(
+                "dynamic slippage can't be held to the {} bps limit",
+                self.0
+            ))
+        } else if intent.slippage_bps > self.0 {
+            PolicyDecision::Deny(format!(
+                "slippage {} bps exceeds the {} bps limit",
+                intent.slippage_bps, self.0
+            ))
+        } else {
+            PolicyDecision::Allow
+        }
+    }
+}
+
+/// Denies any transaction whose price impact exceeds the given value.
+///
+/// Compared against `price_impact_pct` exactly as reported by Jupiter.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxPriceImpactPct(pub f64);
+
+impl SigningPolicy for MaxPriceImpactPct {
+    fn evaluate(&self, intent: &SigningIntent) -> PolicyDecision {
+        if intent.price_impact_pct > self.0 {
+            PolicyDecision::Deny(format!(
+                "price impact {} exceeds the {} limit",
+                intent.price_impact_pct, self.0
+            ))
+        } else {
+            PolicyDecision::Allow
+        }
+    }
+}
+
+/// Denies any transaction that spends or receives a mint outside the allowlist.
+#[derive(Debug, Clone)]
+pub struct MintAllowlist(pub HashSet<String>);
+
+impl SigningPolicy for MintAllowlist {
+    fn evaluate(&self, intent: &SigningIntent) -> PolicyDecision {
+        for mint in [&intent.input_mint, &intent.output_mint] {
+            if !self.0.contains(mint) {
+                return PolicyDecision::Deny(format!("mint {mint} is not allowlisted"));
+            }
+        }
+        PolicyDecision::Allow
+    }
+}
+
+/// Denies any transaction that can spend more of its input mint than the mint's cap, in raw
+/// units, checking the [`max_in_amount`](SigningIntent::max_in_amount). Mints without a cap
+/// are denied.
+///
+/// # Example
+/// ```
+/// // at most 10 SOL or 1,000 USDC per swap
+/// let policy = MaxNotional([(SOL_MINT.to_string(), 10_000_000_000), (USDC_MINT.to_string(), 1_000_000_000)].into());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaxNotional(pub HashMap<String, u64>);
+
+impl SigningPolicy for MaxNotional {
+    fn evaluate(&self, intent: &SigningIntent) -> PolicyDecision {
+        match self.0.get(&intent.input_mint) {
+            None => PolicyDecision::Deny(format!("mint {} has no notional cap", intent.input_mint)),
+            Some(&cap) if intent.max_in_amount > cap => PolicyDecision::Deny(format!(
+                "notional {} of {} exceeds the {cap} cap",
+                intent.max_in_amount, intent.input_mint
+            )),
+            Some(_) => PolicyDecision::Allow,
+        }
+    }
+}
+
+/// Denies any transaction swapping a pair outside the set, given as `(input, output)` mints.
+/// A pair only allows the direction it's listed in.
+#[derive(Debug, Clone)]
+pub struct AllowedPairs(pub HashSet<(String, String)>);
+
+impl SigningPolicy for AllowedPairs {
+    fn evaluate(&self, intent: &SigningIntent) -> PolicyDecision {
+        let pair = (intent.input_mint.clone(), intent.output_mint.clone());
+        if self.0.contains(&pair) {
+            PolicyDecision::Allow
+        } else {
+            PolicyDecision::Deny(format!(
+                "pair {} -> {} is not allowed",
+                intent.input_mint, intent.output_mint
+            ))
+        }
+    }
+}
+
+/// Denies any transaction whose output goes to an account outside the whitelist.
+///
+/// List explicit destination token accounts, and the wallets whose own token accounts may
+/// receive output. A transaction with no destination and no known user is denied.
+#[derive(Debug, Clone)]
+pub struct DestinationWhitelist(pub HashSet<String>);
+
+impl SigningPolicy for DestinationWhitelist {
+    fn evaluate(&self, intent: &SigningIntent) -> PolicyDecision {
+        let destination = match (&intent.destination_token_account, &intent.user) {
+            (Some(account), _) | (None, Some(account)) => account,
+            (None, None) => {
+                return PolicyDecision::Deny("the output's destination is unknown".to_string());
+            }
+        };
+        if self.0.contains(destination) {
+            PolicyDecision::Allow
+        } else {
+            PolicyDecision::Deny(format!("destination {destination} is not whitelisted"))
+        }
+    }
+}
+
+/// Caps how much of each input mint is spent over any 24 hours, in raw units, counting each
+/// transaction's [`max_in_amount`](SigningIntent::max_in_amount). Mints without a cap are
+/// denied.
+///
+/// Volume is counted when a transaction is approved for signing, whether or not it's signed
+/// or lands, so the cap errs on the side of denying; it's released again if the transaction
+/// can't be built. [`evaluate`](SigningPolicy::evaluate) only checks the cap. Share one
+/// instance (it's cheap to clone) across clients signing for the same book.
+///
+/// # Example
+/// ```
+/// let cap = DailyVolumeCap::new([(USDC_MINT.to_string(), 50_000_000_000)].into());
+/// let api = JupiterClient::new("https://lite-api.jup.ag").with_policy(cap.clone());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DailyVolumeCap {
+    caps: HashMap<String, u64>,
+    clock: Arc<dyn Clock>,
+    spent: Arc<Mutex<HashMap<String, Window>>>,
+}
+
+/// The amounts approved for a mint and their reservations, oldest first.
+type Window = VecDeque<(Instant, u64, Reservation)>;
+
+/// Numbers reservations, so they're unique across every cap sharing a process.
+static NEXT_RESERVATION: AtomicU64 = AtomicU64::new(0);
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+impl DailyVolumeCap {
+    /// Caps each mint of `caps` at its amount per 24 hours.
+    pub fn new(caps: HashMap<String, u64>) -> Self {
+        Self {
+            caps,
+            clock: Arc::new(SystemClock),
+            spent: Arc::default(),
+        }
+    }
+
+    /// Reads the time from `clock` instead of the system clock, e.g. the client's
+    /// [`ManualClock`](crate::clock::ManualClock) in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// How much of `mint` was approved over the last 24 hours.
+    pub fn spent(&self, mint: &str) -> u64 {
+        let now = self.clock.now();
+        let mut spent = self.spent.lock().unwrap_or_else(|e| e.into_inner());
+        spent
+            .get_mut(mint)
+            .map_or(0, |window| window_total(window, now))
+    }
+
+    /// Checks `intent` against its mint's cap, recording it under a new reservation if
+    /// `record` is set.
+    fn admit(&self, intent: &SigningIntent, record: bool) -> Result<Option<Reservation>, String> {
+        let Some(&cap) = self.caps.get(&intent.input_mint) else {
+            return Err(format!(
+                "mint {} has no daily volume cap",
+                intent.input_mint
+            ));
+        };
+        let now = self.clock.now();
+        let mut spent = self.spent.lock().unwrap_or_else(|e| e.into_inner());
+        let window = spent.entry(intent.input_mint.clone()).or_default();
+        let total = window_total(window, now);
+        if total.saturating_add(intent.max_in_amount) > cap {
+            return Err(format!(
+                "{} of {} on top of {total} today exceeds the {cap} daily cap",
+                intent.max_in_amount, intent.input_mint
+            ));
+        }
+        if !record {
+            return Ok(None);
+        }
+        // reserved now, so concurrent approvals can't overshoot the cap together
+        let reservation = Reservation(NEXT_RESERVATION.fetch_add(1, Ordering::Relaxed));
+        window.push_back((now, intent.max_in_amount, reservation));
+        Ok(Some(reservation))
+    }
+}
+
+/// Drops the entries of `window` older than a day and sums the rest.
+fn window_total(window: &mut Window, now: Instant) -> u64 {
+    while window
+        .front()
+        .is_some_and(|(at, _, _)| now.saturating_duration_since(*at) >= DAY)
+    {
+        window.pop_front();
+    }
+    window
+        .iter()
+        .fold(0u64, |total, (_, amount, _)| total.saturating_add(*amount))
+}
+
+impl SigningPolicy for DailyVolumeCap {
+    fn evaluate(&self, intent: &SigningIntent) -> PolicyDecision {
+        match self.admit(intent, false) {
+            Ok(_) => PolicyDecision::Allow,
+            Err(reason) => PolicyDecision::Deny(reason),
+        }
+    }
+
+    fn reserve(&self, intent: &SigningIntent) -> Result<Option<Reservation>, String> {
+        self.admit(intent, true)
+    }
+
+    fn release(&self, reservation: Reservation) {
+        let mut spent = self.spent.lock().unwrap_or_else(|e| e.into_inner());
+        for window in spent.values_mut() {
+            window.retain(|(_, _, reserved)| *reserved != reservation);
+        }
+    }
+}
+
+/// The policies registered on a client.
+#[derive(Clone, Default)]
+pub(crate) struct Policies(pub(crate) Vec<Arc<dyn SigningPolicy>>);
+
+impl Policies {
+    /// Reserves the intent with every policy, returning the first denial reason if any
+    /// rejects it. The reservations made before the denial are released.
+    pub(crate) fn check(&self, intent: &SigningIntent) -> Result<Approval, String> {
+        let mut approval = Approval::default();
+        for policy in &self.0 {
+            match policy.reserve(intent) {
+                Ok(Some(reservation)) => approval.0.push((policy.clone(), reservation)),
+                Ok(None) => {}
+                Err(reason) => {
+                    approval.release();
+                    return Err(reason);
+                }
+            }
+        }
+        Ok(approval)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// The reservations made by the policies that approved an intent.
+#[derive(Default)]
+pub(crate) struct Approval(Vec<(Arc<dyn SigningPolicy>, Reservation)>);
+
+impl Approval {
+    /// Releases every reservation, for a transaction that won't be returned for signing.
+    pub(crate) fn release(self) {
+        for (policy, reservation) in self.0 {
+            policy.release(reservation);
+        }
+    }
+}
+
+impl fmt::Debug for Policies {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Policies({})", self.0.len())
+    }
+}
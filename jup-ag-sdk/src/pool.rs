@@ -0,0 +1,216 @@
+//! A pool of [`JupiterClient`]s keyed by tenant, for platforms swapping on behalf of many
+//! customers with their own API keys.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use crate::{JupiterClient, JupiterClientError, rate_limit::RateLimitMode, rt::Instant};
+
+/// How a tenant's client is set up.
+#[derive(Debug, Clone)]
+pub struct TenantConfig {
+    pub api_key: String,
+
+    /// Requests per second and burst size, if the tenant should be rate limited. Applies to
+    /// every request the tenant's client sends, see [`JupiterClient::with_rate_limit`].
+    pub rate_limit: Option<(f64, u32)>,
+}
+
+impl TenantConfig {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            rate_limit: None,
+        }
+    }
+
+    /// Limits the tenant to `requests_per_second`, allowing bursts of up to `burst` requests.
+    pub fn rate_limit(mut self, requests_per_second: f64, burst: u32) -> Self {
+        self.rate_limit = Some((requests_per_second, burst));
+        self
+    }
+}
+
+/// Request counters of a single tenant.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TenantMetrics {
+    pub requests: u64,
+    pub errors: u64,
+
+    /// Time spent in calls, including waits on the tenant's rate limit.
+    pub total_latency: Duration,
+}
+
+impl TenantMetrics {
+    pub fn average_latency(&self) -> Option<Duration> {
+        (self.requests > 0).then(|| self.total_latency / self.requests as u32)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    requests: AtomicU64,
+    errors: AtomicU64,
+    latency_micros: AtomicU64,
+}
+
+#[derive(Debug)]
+struct Tenant {
+    client: JupiterClient,
+    counters: Counters,
+}
+
+/// Manages one [`JupiterClient`] per tenant, each with its own API key, rate limit and metrics.
+///
+/// # Example
+/// ```
+/// let pool = ClientPool::new("https://api.jup.ag");
+/// pool.add_tenant("acme", TenantConfig::new("acme-api-key").rate_limit(10.0, 20))?;
+///
+/// let quote = pool
+///     .execute("acme", |client| async move { client.get_quote(&request).await })
+///     .await?;
+///
+/// let metrics = pool.metrics("acme").unwrap();
+/// println!("{} requests, {} errors", metrics.requests, metrics.errors);
+/// ```
+#[derive(Debug)]
+pub struct ClientPool {
+    base_url: String,
+    tenants: RwLock<HashMap<String, Arc<Tenant>>>,
+}
+
+impl ClientPool {
+    /// Creates an empty pool whose clients target `base_url`.
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+            tenants: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Adds or replaces a tenant, resetting its metrics.
+    pub fn add_tenant(
+        &self,
+        tenant_id: impl Into<String>,
+        config: TenantConfig,
+    ) -> Result<(), JupiterClientError> {
+        let client = JupiterClient::builder()
+            .base_url(&self.base_url)
+            .api_key(config.api_key)
+            .build()?;
+        self.insert(tenant_id.into(), client, config.rate_limit);
+        Ok(())
+    }
+
+    /// Adds or replaces a tenant with a client you configured yourself. A `rate_limit`
+    /// replaces any the client already had.
+    pub fn add_tenant_client(
+        &self,
+        tenant_id: impl Into<String>,
+        client: JupiterClient,
+        rate_limit: Option<(f64, u32)>,
+    ) {
+        self.insert(tenant_id.into(), client, rate_limit);
+    }
+
+    fn insert(&self, tenant_id: String, client: JupiterClient, rate_limit: Option<(f64, u32)>) {
+        let client = match rate_limit {
+            Some((rps, burst)) => client.with_rate_limit(rps, burst, RateLimitMode::Queue),
+            None => client,
+        };
+        let tenant = Tenant {
+            client,
+            counters: Counters::default(),
+        };
+        self.tenants
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(tenant_id, Arc::new(tenant));
+    }
+
+    /// Removes a tenant, returning whether it existed.
+    pub fn remove_tenant(&self, tenant_id: &str) -> bool {
+        self.tenants
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(tenant_id)
+            .is_some()
+    }
+
+    /// Ids of every tenant in the pool.
+    pub fn tenants(&self) -> Vec<String> {
+        self.tenants
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the tenant's client, bypassing the pool's metrics. The tenant's rate limit
+    /// still applies.
+    pub fn client(&self, tenant_id: &str) -> Option<JupiterClient> {
+        self.tenant(tenant_id).map(|tenant| tenant.client.clone())
+    }
+
+    /// Returns a snapshot of the tenant's metrics.
+    pub fn metrics(&self, tenant_id: &str) -> Option<TenantMetrics> {
+        self.tenant(tenant_id).map(|tenant| TenantMetrics {
+            requests: tenant.counters.requests.load(Ordering::Relaxed),
+            errors: tenant.counters.errors.load(Ordering::Relaxed),
+            total_latency: Duration::from_micros(
+                tenant.counters.latency_micros.load(Ordering::Relaxed),
+            ),
+        })
+    }
+
+    /// Runs `call` with the tenant's client and records it in the tenant's metrics. Every
+    /// request `call` sends waits for the tenant's rate limit.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::UnknownTenant`] if the tenant was never added, otherwise whatever
+    /// `call` returns.
+    pub async fn execute<T, F, Fut>(
+        &self,
+        tenant_id: &str,
+        call: F,
+    ) -> Result<T, JupiterClientError>
+    where
+        F: FnOnce(JupiterClient) -> Fut,
+        Fut: Future<Output = Result<T, JupiterClientError>>,
+    {
+        let tenant = self
+            .tenant(tenant_id)
+            .ok_or_else(|| JupiterClientError::UnknownTenant(tenant_id.to_string()))?;
+
+        let started = Instant::now();
+        let result = call(tenant.client.clone()).await;
+
+        let counters = &tenant.counters;
+        counters.requests.fetch_add(1, Ordering::Relaxed);
+        counters
+            .latency_micros
+            .fetch_add(started.elapsed().as_micros() as u64, Ordering::Relaxed);
+        if result.is_err() {
+            counters.errors.fetch_add(1, Ordering::Relaxed);
+        }
+
+        result
+    }
+
+    fn tenant(&self, tenant_id: &str) -> Option<Arc<Tenant>> {
+        self.tenants
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(tenant_id)
+            .cloned()
+    }
+}
@@ -0,0 +1,450 @@
+//! Periodic valuations of a wallet and profit and loss over them.
+//!
+//! A [`PortfolioTracker`] values the wallet's holdings with Jupiter prices and persists each
+//! [`PortfolioSnapshot`] to a [`SnapshotStore`]. [`PnlReport::new`] then combines the stored
+//! snapshots with the recorded fills of a [`FillHistory`] into realized and unrealized P&L,
+//! per token and in total:
+//!
+//! ```
+//! let store = Arc::new(JsonLinesSnapshotStore::new("portfolio.jsonl"));
+//! let tracker = PortfolioTracker::new(api.clone(), &treasury, store);
+//! tokio::spawn({
+//!     let tracker = tracker.clone();
+//!     async move { tracker.run(Duration::from_secs(3600), &shutdown).await }
+//! });
+//!
+//! // later
+//! let fills = FillHistory::from_json_lines(BufReader::new(File::open("audit.jsonl")?))?;
+//! if let Some(report) = tracker.pnl(&fills)? {
+//!     println!("realized ${:.2}, unrealized ${:.2}", report.realized_usd, report.unrealized_usd);
+//! }
+//! ```
+//!
+//! Costs are averaged per token. Holdings of the first snapshot are valued at its prices, and
+//! each fill after it moves cost from the sold token to the bought one at the fill's USD value.
+//! Tokens that arrive otherwise, e.g. by transfer, have no cost basis and no P&L.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    JupiterClient,
+    error::JupiterClientError,
+    fill_history::FillHistory,
+    rt,
+    shutdown::Shutdown,
+    types::{HoldingsSource, NATIVE_SOL, Price, WalletHoldings, holdings::WSOL_MINT},
+};
+
+/// The value of one holding in a [`PortfolioSnapshot`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Position {
+    /// The mint, or [`NATIVE_SOL`] for the native SOL balance.
+    pub mint: String,
+
+    /// Raw amount.
+    pub amount: u64,
+
+    pub ui_amount: f64,
+
+    /// `None` when Jupiter has no reliable price for the mint.
+    pub usd_price: Option<f64>,
+
+    pub usd_value: Option<f64>,
+}
+
+/// A wallet's holdings valued at a point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioSnapshot {
+    pub wallet: String,
+
+    /// Unix timestamp in milliseconds of when the holdings were read.
+    pub taken_at_ms: u64,
+
+    /// Non-zero holdings, sorted by mint.
+    pub positions: Vec<Position>,
+}
+
+impl PortfolioSnapshot {
+    /// Values `holdings` with `prices`, keyed by mint. Native SOL is valued at the wrapped SOL
+    /// price.
+    pub fn new(
+        holdings: &WalletHoldings,
+        prices: &HashMap<String, Price>,
+        taken_at_ms: u64,
+    ) -> Self {
+        let positions = holdings
+            .holdings
+            .iter()
+            .filter(|holding| holding.amount > 0)
+            .map(|holding| {
+                let usd_price = prices.get(price_mint(&holding.mint)).map(|p| p.usd_price);
+                Position {
+                    mint: holding.mint.clone(),
+                    amount: holding.amount,
+                    ui_amount: holding.ui_amount,
+                    usd_price,
+                    usd_value: usd_price.map(|price| price * holding.ui_amount),
+                }
+            })
+            .collect();
+        Self {
+            wallet: holdings.wallet.clone(),
+            taken_at_ms,
+            positions,
+        }
+    }
+
+    /// The position in `mint`. Wrapped SOL resolves to the native SOL balance.
+    pub fn get(&self, mint: &str) -> Option<&Position> {
+        self.positions
+            .iter()
+            .find(|position| price_mint(&position.mint) == price_mint(mint))
+    }
+
+    /// Total USD value of the priced positions.
+    pub fn total_usd(&self) -> f64 {
+        self.positions.iter().filter_map(|p| p.usd_value).sum()
+    }
+}
+
+/// The mint prices and cost bases are kept under: native SOL counts as wrapped SOL.
+fn price_mint(mint: &str) -> &str {
+    if mint == NATIVE_SOL { WSOL_MINT } else { mint }
+}
+
+/// Where a [`PortfolioTracker`] persists its snapshots, e.g. a file or a database table.
+pub trait SnapshotStore: Send + Sync {
+    fn save(&self, snapshot: &PortfolioSnapshot) -> io::Result<()>;
+
+    /// The snapshots of `wallet`, oldest first.
+    fn load(&self, wallet: &str) -> io::Result<Vec<PortfolioSnapshot>>;
+}
+
+/// Keeps snapshots in memory, e.g. for tests or short-lived processes.
+#[derive(Debug, Default)]
+pub struct MemorySnapshotStore(Mutex<Vec<PortfolioSnapshot>>);
+
+impl MemorySnapshotStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SnapshotStore for MemorySnapshotStore {
+    fn save(&self, snapshot: &PortfolioSnapshot) -> io::Result<()> {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(snapshot.clone());
+        Ok(())
+    }
+
+    fn load(&self, wallet: &str) -> io::Result<Vec<PortfolioSnapshot>> {
+        let snapshots = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(sorted(
+            snapshots.iter().filter(|s| s.wallet == wallet).cloned(),
+        ))
+    }
+}
+
+/// Appends every snapshot to a file as a single line of JSON.
+#[derive(Debug, Clone)]
+pub struct JsonLinesSnapshotStore {
+    path: PathBuf,
+}
+
+impl JsonLinesSnapshotStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl SnapshotStore for JsonLinesSnapshotStore {
+    fn save(&self, snapshot: &PortfolioSnapshot) -> io::Result<()> {
+        let line = serde_json::to_string(snapshot)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{line}")
+    }
+
+    /// # Errors
+    /// Fails with [`io::ErrorKind::InvalidData`] on a line that isn't a snapshot. A missing
+    /// file holds no snapshots.
+    fn load(&self, wallet: &str) -> io::Result<Vec<PortfolioSnapshot>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut snapshots = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let snapshot: PortfolioSnapshot = serde_json::from_str(&line)?;
+            if snapshot.wallet == wallet {
+                snapshots.push(snapshot);
+            }
+        }
+        Ok(sorted(snapshots))
+    }
+}
+
+fn sorted(snapshots: impl IntoIterator<Item = PortfolioSnapshot>) -> Vec<PortfolioSnapshot> {
+    let mut snapshots: Vec<_> = snapshots.into_iter().collect();
+    snapshots.sort_by_key(|s| s.taken_at_ms);
+    snapshots
+}
+
+/// The P&L of one token over a [`PnlReport`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPnl {
+    /// Raw amount held at the end of the period.
+    pub amount: u64,
+
+    /// USD cost of the amount still held with a known cost, averaged over its purchases.
+    pub cost_basis_usd: f64,
+
+    /// USD value at the end of the period. `None` when the token has no price.
+    pub market_value_usd: Option<f64>,
+
+    /// Gains locked in by selling the token, net of its cost.
+    pub realized_usd: f64,
+
+    /// Gains of the held amount over its cost. `None` when the token has no price.
+    pub unrealized_usd: Option<f64>,
+}
+
+/// Realized and unrealized P&L between the first and last snapshot of a wallet.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PnlReport {
+    pub from_ms: u64,
+    pub to_ms: u64,
+
+    /// Per token, keyed by mint. Native SOL is reported under the wrapped SOL mint.
+    pub tokens: BTreeMap<String, TokenPnl>,
+
+    pub realized_usd: f64,
+    pub unrealized_usd: f64,
+
+    /// Fills within the period without a USD value, left out of the P&L.
+    pub unvalued_fills: usize,
+}
+
+#[derive(Default)]
+struct Lot {
+    amount: u64,
+    cost_usd: f64,
+    realized_usd: f64,
+}
+
+impl PnlReport {
+    /// Computes the P&L of `snapshots`, one wallet's in any order, and the `fills` made
+    /// between the first and the last of them.
+    ///
+    /// Fills are valued at their quote's `swapUsdValue`; those without one are counted in
+    /// [`unvalued_fills`](Self::unvalued_fills) and skipped. Returns `None` without
+    /// snapshots.
+    pub fn new(snapshots: &[PortfolioSnapshot], fills: &FillHistory) -> Option<Self> {
+        let first = snapshots.iter().min_by_key(|s| s.taken_at_ms)?;
+        let last = snapshots.iter().max_by_key(|s| s.taken_at_ms)?;
+
+        let mut lots: BTreeMap<String, Lot> = BTreeMap::new();
+        for position in &first.positions {
+            if let Some(value) = position.usd_value {
+                let lot = lots
+                    .entry(price_mint(&position.mint).to_string())
+                    .or_default();
+                lot.amount += position.amount;
+                lot.cost_usd += value;
+            }
+        }
+
+        let mut unvalued_fills = 0;
+        let period = first.taken_at_ms..=last.taken_at_ms;
+        for fill in fills
+            .fills()
+            .iter()
+            .filter(|f| period.contains(&f.timestamp_ms))
+        {
+            let Some(usd) = fill
+                .quote
+                .swap_usd_value
+                .as_deref()
+                .and_then(|value| value.parse::<f64>().ok())
+            else {
+                unvalued_fills += 1;
+                continue;
+            };
+            let sold = fill.quote.in_amount.parse::<u64>().unwrap_or_default();
+            let bought = fill
+                .out_amount
+                .unwrap_or_else(|| fill.quote.out_amount.parse().unwrap_or_default());
+
+            let lot = lots
+                .entry(price_mint(fill.input_mint()).to_string())
+                .or_default();
+            // only the part of the sale covered by a known cost realizes anything
+            let covered = sold.min(lot.amount);
+            if covered > 0 {
+                let cost = lot.cost_usd * covered as f64 / lot.amount as f64;
+                lot.realized_usd += usd * covered as f64 / sold as f64 - cost;
+                lot.cost_usd -= cost;
+                lot.amount -= covered;
+            }
+
+            let lot = lots
+                .entry(price_mint(fill.output_mint()).to_string())
+                .or_default();
+            lot.amount += bought;
+            lot.cost_usd += usd;
+        }
+
+        let mut report = PnlReport {
+            from_ms: first.taken_at_ms,
+            to_ms: last.taken_at_ms,
+            unvalued_fills,
+            ..Default::default()
+        };
+        let held = last
+            .positions
+            .iter()
+            .map(|position| price_mint(&position.mint).to_string());
+        let mints: Vec<String> = lots.keys().cloned().chain(held).collect();
+        for mint in mints {
+            if report.tokens.contains_key(&mint) {
+                continue;
+            }
+            let lot = lots.remove(&mint).unwrap_or_default();
+            let position = last.get(&mint);
+            let amount = position.map(|p| p.amount).unwrap_or_default();
+
+            // transfers out shrink the held amount below the tracked one; what arrived
+            // otherwise has no cost
+            let with_cost = amount.min(lot.amount);
+            let cost_basis_usd = match lot.amount {
+                0 => 0.0,
+                tracked => lot.cost_usd * with_cost as f64 / tracked as f64,
+            };
+            let market_value_usd = position.and_then(|p| p.usd_value);
+            let unrealized_usd = market_value_usd.map(|value| match amount {
+                0 => 0.0,
+                _ => value * with_cost as f64 / amount as f64 - cost_basis_usd,
+            });
+
+            report.realized_usd += lot.realized_usd;
+            report.unrealized_usd += unrealized_usd.unwrap_or_default();
+            report.tokens.insert(
+                mint,
+                TokenPnl {
+                    amount,
+                    cost_basis_usd,
+                    market_value_usd,
+                    realized_usd: lot.realized_usd,
+                    unrealized_usd,
+                },
+            );
+        }
+        Some(report)
+    }
+
+    pub fn total_usd(&self) -> f64 {
+        self.realized_usd + self.unrealized_usd
+    }
+}
+
+/// Snapshots a wallet's holdings into a [`SnapshotStore`], on demand or periodically, and
+/// reports P&L over the stored snapshots.
+#[derive(Clone)]
+pub struct PortfolioTracker {
+    client: JupiterClient,
+    wallet: String,
+    source: HoldingsSource,
+    store: Arc<dyn SnapshotStore>,
+}
+
+impl PortfolioTracker {
+    /// Tracks `wallet`, reading balances from Ultra. See [`source`](Self::source).
+    pub fn new(client: JupiterClient, wallet: &str, store: Arc<dyn SnapshotStore>) -> Self {
+        Self {
+            client,
+            wallet: wallet.to_string(),
+            source: HoldingsSource::Ultra,
+            store,
+        }
+    }
+
+    /// Reads balances from `source` instead, e.g. an RPC to include token decimals.
+    pub fn source(mut self, source: HoldingsSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Values the wallet's holdings now and saves the snapshot.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::StorageError`] when the store fails to save it.
+    pub async fn snapshot(&self) -> Result<PortfolioSnapshot, JupiterClientError> {
+        let snapshot = self
+            .client
+            .portfolio_snapshot(&self.wallet, &self.source)
+            .await?;
+        self.store.save(&snapshot)?;
+        Ok(snapshot)
+    }
+
+    /// Takes a snapshot every `interval` until `shutdown` is triggered.
+    ///
+    /// A failed snapshot is skipped and retried on the next tick, so a Jupiter outage leaves
+    /// a gap instead of stopping the tracker. Returns the number of snapshots taken.
+    pub async fn run(&self, interval: Duration, shutdown: &Shutdown) -> usize {
+        let mut taken = 0;
+        while !shutdown.is_triggered() {
+            if self.snapshot().await.is_ok() {
+                taken += 1;
+            }
+            let tick = self.client.clock().sleep(interval);
+            if rt::race(tick, shutdown.triggered()).await.is_none() {
+                break;
+            }
+        }
+        taken
+    }
+
+    /// The stored snapshots, oldest first.
+    pub fn snapshots(&self) -> io::Result<Vec<PortfolioSnapshot>> {
+        self.store.load(&self.wallet)
+    }
+
+    /// The P&L between the first and last stored snapshot, see [`PnlReport::new`]. `None`
+    /// until a snapshot was taken.
+    pub fn pnl(&self, fills: &FillHistory) -> io::Result<Option<PnlReport>> {
+        Ok(PnlReport::new(&self.snapshots()?, fills))
+    }
+}
+
+impl fmt::Debug for PortfolioTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PortfolioTracker")
+            .field("wallet", &self.wallet)
+            .field("source", &self.source)
+            .finish()
+    }
+}
@@ -0,0 +1,139 @@
+//! Last-look predicates checked right before a built swap is sent.
+//!
+//! Attach one to a [`BatchSwap`](crate::types::BatchSwap) with
+//! [`BatchSwap::predicate`](crate::types::BatchSwap::predicate). It runs after the swap was
+//! quoted and built, immediately before the transaction is handed to the send callback, and a
+//! [`PolicyDecision::Deny`] aborts the swap without sending anything.
+//!
+//! Predicates should fail closed: when the data they need can't be fetched, deny.
+
+use std::{future::Future, pin::Pin};
+
+use crate::{
+    JupiterClient,
+    policy::PolicyDecision,
+    types::{QuoteGetSwapModeEnum, QuoteRequest, QuoteResponse},
+};
+
+/// The future returned by [`ExecutionPredicate::evaluate`].
+#[cfg(not(target_arch = "wasm32"))]
+pub type PredicateFuture<'a> = Pin<Box<dyn Future<Output = PolicyDecision> + Send + 'a>>;
+
+/// The future returned by [`ExecutionPredicate::evaluate`].
+#[cfg(target_arch = "wasm32")]
+pub type PredicateFuture<'a> = Pin<Box<dyn Future<Output = PolicyDecision> + 'a>>;
+
+/// A condition an execution must still meet at the last moment before it's sent.
+///
+/// Implemented for any `Fn(&QuoteRequest, &QuoteResponse) -> impl Future<Output = PolicyDecision>`
+/// whose future owns its data, so closures can be attached directly.
+///
+/// # Example
+/// ```
+/// let oracle = oracle.clone();
+/// let swap = BatchSwap::new("arb", &wallet, request).predicate(
+///     move |_: &QuoteRequest, quote: &QuoteResponse| {
+///         let (oracle, out) = (oracle.clone(), quote.out_amount.clone());
+///         async move {
+///             match oracle.still_profitable(&out).await {
+///                 true => PolicyDecision::Allow,
+///                 false => PolicyDecision::Deny("spread closed".to_string()),
+///             }
+///         }
+///     },
+/// );
+/// ```
+pub trait ExecutionPredicate: Send + Sync {
+    /// Decides whether the swap built from `quote`, quoted for `request`, may still be sent.
+    fn evaluate<'a>(
+        &'a self,
+        client: &'a JupiterClient,
+        request: &'a QuoteRequest,
+        quote: &'a QuoteResponse,
+    ) -> PredicateFuture<'a>;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<F, Fut> ExecutionPredicate for F
+where
+    F: Fn(&QuoteRequest, &QuoteResponse) -> Fut + Send + Sync,
+    Fut: Future<Output = PolicyDecision> + Send + 'static,
+{
+    fn evaluate<'a>(
+        &'a self,
+        _client: &'a JupiterClient,
+        request: &'a QuoteRequest,
+        quote: &'a QuoteResponse,
+    ) -> PredicateFuture<'a> {
+        Box::pin(self(request, quote))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl<F, Fut> ExecutionPredicate for F
+where
+    F: Fn(&QuoteRequest, &QuoteResponse) -> Fut + Send + Sync,
+    Fut: Future<Output = PolicyDecision> + 'static,
+{
+    fn evaluate<'a>(
+        &'a self,
+        _client: &'a JupiterClient,
+        request: &'a QuoteRequest,
+        quote: &'a QuoteResponse,
+    ) -> PredicateFuture<'a> {
+        Box::pin(self(request, quote))
+    }
+}
+
+/// Re-quotes the request and denies the swap when the fresh quote is more than
+/// `max_shortfall_bps` worse than the quote the transaction was built from, e.g.
+/// `QuoteDrift(30)` for 0.3%.
+///
+/// An `ExactIn` quote is worse when its output fell, an `ExactOut` quote when its input rose.
+/// The fresh quote bypasses the client's quote cache. A better fresh quote never denies; a
+/// failed re-quote or an amount that can't be read does.
+#[derive(Debug, Clone, Copy)]
+pub struct QuoteDrift(pub u16);
+
+impl ExecutionPredicate for QuoteDrift {
+    fn evaluate<'a>(
+        &'a self,
+        client: &'a JupiterClient,
+        request: &'a QuoteRequest,
+        quote: &'a QuoteResponse,
+    ) -> PredicateFuture<'a> {
+        Box::pin(async move {
+            let fresh = match client.refresh_quote(request).await {
+                Ok(fresh) => fresh,
+                Err(e) => return PolicyDecision::Deny(format!("re-quote failed: {e}")),
+            };
+            // the side the route is free to move: output for ExactIn, input for ExactOut
+            let (built, now) = match quote.swap_mode {
+                QuoteGetSwapModeEnum::ExactIn => (&quote.out_amount, &fresh.out_amount),
+                QuoteGetSwapModeEnum::ExactOut => (&quote.in_amount, &fresh.in_amount),
+            };
+            let (Ok(built), Ok(now)) = (built.parse::<u64>(), now.parse::<u64>()) else {
+                return PolicyDecision::Deny(format!(
+                    "can't compare quoted amount {built:?} with {now:?}"
+                ));
+            };
+            if built == 0 {
+                return PolicyDecision::Deny("the built quote has a zero amount".to_string());
+            }
+            let worse_by = match quote.swap_mode {
+                QuoteGetSwapModeEnum::ExactIn => built.saturating_sub(now),
+                QuoteGetSwapModeEnum::ExactOut => now.saturating_sub(built),
+            };
+
+            let shortfall_bps = worse_by as f64 / built as f64 * 10_000.0;
+            if shortfall_bps > self.0 as f64 {
+                PolicyDecision::Deny(format!(
+                    "fresh quote is {shortfall_bps:.1} bps worse, above the {} bps limit",
+                    self.0
+                ))
+            } else {
+                PolicyDecision::Allow
+            }
+        })
+    }
+}
@@ -0,0 +1,336 @@
+//! Fixtures for executing composed swap transactions against a local bank before mainnet.
+//!
+//! Jupiter routes only exist on mainnet-beta, so replaying a composed transaction in
+//! `solana-program-test`, bankrun or `solana-test-validator` requires cloning every program and
+//! account it touches. [`ProgramTestFixture`] collects them from the swap instructions and your
+//! own companion instructions (memo, transfer, ...), [`ProgramTestFixture::load`] snapshots them
+//! from mainnet, and [`execute_transaction`] runs the composed transaction in the local bank
+//! started with those snapshots.
+//!
+//! Enabled with the `program-test` feature.
+
+use std::{collections::HashMap, io, path::Path};
+
+use base64::Engine;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    JupiterClientError,
+    submission::rpc_call,
+    types::{Instruction, SwapInstructions, simulation::INFRASTRUCTURE_PROGRAMS},
+};
+
+/// Owner of upgradeable programs, whose executable code lives in a separate program data account.
+const BPF_LOADER_UPGRADEABLE_ID: &str = "BPFLoaderUpgradeab1e11111111111111111111111";
+
+/// `getMultipleAccounts` accepts at most this many keys per call.
+const MAX_ACCOUNTS_PER_RPC_CALL: usize = 100;
+
+/// The programs and accounts a composed swap transaction needs in a local bank.
+///
+/// Builtin programs and sysvars are omitted since every local bank ships them.
+///
+/// # Example
+/// ```
+/// let labels = api.get_program_id_to_label().await?;
+/// let fixture = ProgramTestFixture::from_swap_instructions(&swap_instructions)
+///     .with_instruction(&memo_instruction)
+///     .with_program_labels(&labels);
+///
+/// // dump the fixture for a bankrun/program-test harness
+/// std::fs::write("fixture.json", serde_json::to_string_pretty(&fixture)?)?;
+///
+/// // or start a validator with everything cloned from mainnet
+/// Command::new("solana-test-validator")
+///     .args(fixture.test_validator_args("https://api.mainnet-beta.solana.com"))
+///     .spawn()?;
+///
+/// // or snapshot the accounts once and replay against them offline
+/// let loaded = fixture.load("https://api.mainnet-beta.solana.com").await?;
+/// Command::new("solana-test-validator")
+///     .args(loaded.write_account_files(Path::new("fixtures"))?)
+///     .spawn()?;
+/// let outcome = execute_transaction("http://127.0.0.1:8899", &signed_transaction).await?;
+/// assert_eq!(outcome.err, None, "{:#?}", outcome.logs);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramTestFixture {
+    /// Programs to load, e.g. the Jupiter aggregator and the AMMs on the route.
+    pub programs: Vec<String>,
+
+    /// Non-program accounts to clone from mainnet, e.g. pools, vaults and oracles.
+    pub accounts: Vec<String>,
+
+    /// Address lookup tables the transaction is compiled against.
+    pub address_lookup_tables: Vec<String>,
+
+    /// Accounts that must sign, and therefore be funded, in the local bank.
+    pub signers: Vec<String>,
+}
+
+impl ProgramTestFixture {
+    /// Collects every program and account referenced by `instructions`.
+    pub fn from_swap_instructions(instructions: &SwapInstructions) -> Self {
+        let mut fixture = Self::default();
+
+        let all = instructions
+            .compute_budget_instructions
+            .iter()
+            .flatten()
+            .chain(instructions.other_instructions.iter().flatten())
+            .chain(&instructions.setup_instructions)
+            .chain([&instructions.swap_instruction])
+            .chain(&instructions.cleanup_instruction);
+        for instruction in all {
+            fixture = fixture.with_instruction(instruction);
+        }
+
+        for table in &instructions.address_lookup_table_addresses {
+            push_unique(&mut fixture.address_lookup_tables, table);
+        }
+
+        fixture
+    }
+
+    /// Adds the program and accounts of one of your own instructions composed with the swap.
+    pub fn with_instruction(mut self, instruction: &Instruction) -> Self {
+        if !is_builtin(&instruction.program_id) {
+            self.accounts.retain(|a| a != &instruction.program_id);
+            push_unique(&mut self.programs, &instruction.program_id);
+        }
+
+        for meta in &instruction.accounts {
+            if meta.is_signer {
+                push_unique(&mut self.signers, &meta.pubkey);
+            } else if !is_builtin(&meta.pubkey) && !self.programs.contains(&meta.pubkey) {
+                push_unique(&mut self.accounts, &meta.pubkey);
+            }
+        }
+
+        self
+    }
+
+    /// Moves AMM programs invoked through CPI from `accounts` to `programs`.
+    ///
+    /// Route hops only reference AMM programs as plain accounts of the swap instruction;
+    /// `labels` is the map returned by [`get_program_id_to_label`](crate::JupiterClient::get_program_id_to_label).
+    pub fn with_program_labels(mut self, labels: &HashMap<String, String>) -> Self {
+        let (programs, accounts) = self
+            .accounts
+            .into_iter()
+            .partition::<Vec<_>, _>(|a| labels.contains_key(a));
+        self.accounts = accounts;
+        for program in &programs {
+            push_unique(&mut self.programs, program);
+        }
+        self
+    }
+
+    /// Arguments for `solana-test-validator` that clone the fixture from `rpc_url`.
+    pub fn test_validator_args(&self, rpc_url: &str) -> Vec<String> {
+        let mut args = vec!["--url".to_string(), rpc_url.to_string()];
+        for program in &self.programs {
+            args.push("--clone-upgradeable-program".to_string());
+            args.push(program.clone());
+        }
+        for account in self.accounts.iter().chain(&self.address_lookup_tables) {
+            args.push("--clone".to_string());
+            args.push(account.clone());
+        }
+        args
+    }
+
+    /// Snapshots every program, account, lookup table and signer of the fixture from `rpc_url`,
+    /// including the program data accounts of upgradeable programs.
+    ///
+    /// Uses its own HTTP client so the Jupiter API key is never sent to the RPC.
+    pub async fn load(&self, rpc_url: &str) -> Result<LoadedFixture, JupiterClientError> {
+        let client = Client::new();
+        let mut loaded = LoadedFixture::default();
+
+        let addresses: Vec<&str> = self
+            .programs
+            .iter()
+            .chain(&self.accounts)
+            .chain(&self.address_lookup_tables)
+            .chain(&self.signers)
+            .map(String::as_str)
+            .collect();
+        let snapshots = fetch_snapshots(&client, rpc_url, &addresses).await?;
+
+        let mut program_data = Vec::new();
+        for (pubkey, account) in addresses.iter().zip(snapshots) {
+            let Some(account) = account else {
+                loaded.missing.push(pubkey.to_string());
+                continue;
+            };
+            if let Some(address) = account.program_data_address()? {
+                program_data.push(address);
+            }
+            loaded.accounts.push(FixtureAccount {
+                pubkey: pubkey.to_string(),
+                account,
+            });
+        }
+
+        let addresses: Vec<&str> = program_data.iter().map(String::as_str).collect();
+        let snapshots = fetch_snapshots(&client, rpc_url, &addresses).await?;
+        for (pubkey, account) in addresses.iter().zip(snapshots) {
+            match account {
+                Some(account) => loaded.accounts.push(FixtureAccount {
+                    pubkey: pubkey.to_string(),
+                    account,
+                }),
+                None => loaded.missing.push(pubkey.to_string()),
+            }
+        }
+
+        Ok(loaded)
+    }
+}
+
+/// The accounts of a [`ProgramTestFixture`] as they are on the cluster they were loaded from.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadedFixture {
+    pub accounts: Vec<FixtureAccount>,
+
+    /// Addresses that don't exist on the cluster, e.g. token accounts the swap creates.
+    pub missing: Vec<String>,
+}
+
+impl LoadedFixture {
+    /// Writes each account to `dir/<pubkey>.json` and returns the `solana-test-validator`
+    /// arguments loading them.
+    ///
+    /// The files use the `solana account --output json` format, which bankrun's
+    /// `start(programs, accounts)` and `ProgramTest::add_account` harnesses can read as well.
+    pub fn write_account_files(&self, dir: &Path) -> io::Result<Vec<String>> {
+        std::fs::create_dir_all(dir)?;
+
+        let mut args = Vec::with_capacity(self.accounts.len() * 3);
+        for account in &self.accounts {
+            let path = dir.join(format!("{}.json", account.pubkey));
+            std::fs::write(&path, serde_json::to_vec_pretty(account)?)?;
+            args.push("--account".to_string());
+            args.push(account.pubkey.clone());
+            args.push(path.display().to_string());
+        }
+        Ok(args)
+    }
+}
+
+/// One account snapshot, serialized like `solana account --output json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixtureAccount {
+    pub pubkey: String,
+    pub account: AccountSnapshot,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSnapshot {
+    pub lamports: u64,
+
+    /// The account data and its encoding, always base64.
+    pub data: (String, String),
+    pub owner: String,
+    pub executable: bool,
+    pub rent_epoch: u64,
+
+    #[serde(default)]
+    pub space: u64,
+}
+
+impl AccountSnapshot {
+    /// The program data account of an upgradeable program, `None` for any other account.
+    fn program_data_address(&self) -> Result<Option<String>, JupiterClientError> {
+        if !self.executable || self.owner != BPF_LOADER_UPGRADEABLE_ID {
+            return Ok(None);
+        }
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(&self.data.0)
+            .map_err(|e| JupiterClientError::deserialization(e.to_string()))?;
+
+        // `UpgradeableLoaderState::Program { programdata_address }`: a u32 tag of 2, then the address
+        match data.get(..36) {
+            Some([2, 0, 0, 0, address @ ..]) => Ok(Some(bs58::encode(address).into_string())),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// The result of running a transaction in a local bank.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionOutcome {
+    /// The transaction error, e.g. `{"InstructionError":[2,{"Custom":6001}]}`, `None` on success.
+    /// Pass it to [`diagnose_simulation`](crate::types::simulation::diagnose_simulation) to map
+    /// a failure onto the route.
+    pub err: Option<serde_json::Value>,
+
+    #[serde(default)]
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
+
+/// Runs the base64-encoded `transaction` in the bank behind `rpc_url`, typically a
+/// `solana-test-validator` started with [`LoadedFixture::write_account_files`].
+///
+/// Signatures aren't verified and the blockhash is replaced with the bank's, so the transaction
+/// composed against mainnet can be replayed as is.
+pub async fn execute_transaction(
+    rpc_url: &str,
+    transaction: &str,
+) -> Result<ExecutionOutcome, JupiterClientError> {
+    let result: RpcValue<ExecutionOutcome> = rpc_call(
+        &Client::new(),
+        rpc_url,
+        "simulateTransaction",
+        serde_json::json!([transaction, {
+            "encoding": "base64",
+            "sigVerify": false,
+            "replaceRecentBlockhash": true,
+        }]),
+    )
+    .await?;
+    Ok(result.value)
+}
+
+#[derive(Deserialize)]
+struct RpcValue<T> {
+    value: T,
+}
+
+/// The full snapshot of every account in `pubkeys`, or `None` for accounts that don't exist.
+async fn fetch_snapshots(
+    client: &Client,
+    rpc_url: &str,
+    pubkeys: &[&str],
+) -> Result<Vec<Option<AccountSnapshot>>, JupiterClientError> {
+    let mut snapshots = Vec::with_capacity(pubkeys.len());
+    for chunk in pubkeys.chunks(MAX_ACCOUNTS_PER_RPC_CALL) {
+        let result: RpcValue<Vec<Option<AccountSnapshot>>> = rpc_call(
+            client,
+            rpc_url,
+            "getMultipleAccounts",
+            serde_json::json!([chunk, { "encoding": "base64" }]),
+        )
+        .await?;
+        snapshots.extend(result.value);
+    }
+    Ok(snapshots)
+}
+
+fn is_builtin(pubkey: &str) -> bool {
+    INFRASTRUCTURE_PROGRAMS.contains(&pubkey) || pubkey.starts_with("Sysvar")
+}
+
+fn push_unique(list: &mut Vec<String>, value: &str) {
+    if !list.iter().any(|v| v == value) {
+        list.push(value.to_string());
+    }
+}
@@ -0,0 +1,101 @@
+//! An in-memory cache deduplicating identical quote requests within a short TTL.
+//!
+//! Enabled with [`JupiterClient::with_quote_cache`](crate::JupiterClient::with_quote_cache).
+
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use crate::{
+    bucketing::AmountBucketing,
+    clock::Instant,
+    types::{QuoteGetSwapModeEnum, QuoteRequest, QuoteResponse},
+};
+
+/// Upper bound on cached quotes, so a client quoting many pairs can't grow without limit.
+const MAX_ENTRIES: usize = 1024;
+
+#[derive(Debug)]
+pub(crate) struct QuoteCache {
+    ttl: Duration,
+    bucketing: AmountBucketing,
+    /// Each quote with when it was cached and the amount it was requested for.
+    entries: Mutex<HashMap<String, (Instant, u64, QuoteResponse)>>,
+}
+
+impl QuoteCache {
+    pub(crate) fn new(ttl: Duration, bucketing: AmountBucketing) -> Self {
+        Self {
+            ttl,
+            bucketing,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The cached quote of `request`'s bucket. With `exact_amount`, only a quote requested
+    /// for the same amount is returned.
+    pub(crate) fn get(
+        &self,
+        request: &QuoteRequest,
+        now: Instant,
+        exact_amount: bool,
+    ) -> Option<QuoteResponse> {
+        let key = cache_key(request, &self.bucketing)?;
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(&key)
+            .filter(|(cached_at, _, _)| now.saturating_duration_since(*cached_at) < self.ttl)
+            .filter(|(_, amount, _)| !exact_amount || *amount == request.amount)
+            .map(|(_, _, quote)| quote.clone())
+    }
+
+    pub(crate) fn insert(&self, request: &QuoteRequest, quote: &QuoteResponse, now: Instant) {
+        let Some(key) = cache_key(request, &self.bucketing) else {
+            return;
+        };
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        entries.retain(|_, (cached_at, _, _)| now.saturating_duration_since(*cached_at) < self.ttl);
+        if entries.len() >= MAX_ENTRIES {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, (cached_at, _, _))| *cached_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, (now, request.amount, quote.clone()));
+    }
+
+    pub(crate) fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+/// A key under which requests Jupiter would answer identically collide.
+///
+/// DEX lists are order- and duplicate-insensitive, an unset swap mode is `ExactIn`, and
+/// `slippage_bps` is ignored when dynamic slippage overrides it, and amounts in the same
+/// bucket are equal.
+fn cache_key(request: &QuoteRequest, bucketing: &AmountBucketing) -> Option<String> {
+    let mut request = request.clone();
+    request.amount = bucketing.bucket(request.amount);
+
+    for dexes in [&mut request.dexes, &mut request.exclude_dexes]
+        .into_iter()
+        .flatten()
+    {
+        dexes.sort_by_cached_key(|dex| dex.to_string());
+        dexes.dedup_by_key(|dex| dex.to_string());
+    }
+    if request.swap_mode.is_none() {
+        request.swap_mode = Some(QuoteGetSwapModeEnum::ExactIn);
+    }
+    if request.dynamic_slippage == Some(true) {
+        request.slippage_bps = None;
+    }
+
+    serde_json::to_string(&request).ok()
+}
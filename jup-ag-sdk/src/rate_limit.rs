@@ -0,0 +1,116 @@
+//! A token-bucket rate limiter for keeping under Jupiter's per-key request limits.
+
+use std::{sync::Mutex, time::Duration};
+
+use crate::clock::{Clock, Instant, SystemClock};
+
+/// What a [`JupiterClient`](crate::JupiterClient) does when its rate limiter has no tokens left.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// Waits until a token is available.
+    #[default]
+    Queue,
+
+    /// Fails immediately with [`JupiterClientError::RateLimitExceeded`](crate::JupiterClientError::RateLimitExceeded).
+    FailFast,
+}
+
+/// A token bucket refilled at `requests_per_second`, holding at most `burst` tokens.
+///
+/// # Example
+/// ```
+/// // 10 requests per second, with bursts of up to 20
+/// let limiter = RateLimiter::new(10.0, 20);
+/// limiter.acquire().await;
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    requests_per_second: f64,
+    burst: u32,
+    state: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// Creates a full bucket.
+    ///
+    /// # Panics
+    /// If `requests_per_second` is not positive or `burst` is zero.
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        assert!(
+            requests_per_second > 0.0,
+            "requests_per_second must be positive"
+        );
+        assert!(burst > 0, "burst must be at least 1");
+
+        Self {
+            requests_per_second,
+            burst,
+            state: Mutex::new(Bucket {
+                tokens: burst as f64,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn requests_per_second(&self) -> f64 {
+        self.requests_per_second
+    }
+
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// Takes a token if one is available, without waiting.
+    pub fn try_acquire(&self) -> bool {
+        self.try_acquire_on(&SystemClock)
+    }
+
+    /// Waits until a token is available, then takes it.
+    pub async fn acquire(&self) {
+        self.acquire_on(&SystemClock).await
+    }
+
+    /// Like [`try_acquire`](Self::try_acquire), reading the time from `clock`.
+    pub(crate) fn try_acquire_on(&self, clock: &dyn Clock) -> bool {
+        self.reserve(false, clock.now()).is_zero()
+    }
+
+    /// Like [`acquire`](Self::acquire), waiting on `clock`.
+    pub(crate) async fn acquire_on(&self, clock: &dyn Clock) {
+        let wait = self.reserve(true, clock.now());
+        if !wait.is_zero() {
+            clock.sleep(wait).await;
+        }
+    }
+
+    /// Refills the bucket and takes a token, returning how long the caller must wait for it.
+    ///
+    /// When `queue` is false and no token is available, nothing is taken and a non-zero wait is
+    /// returned. When `queue` is true the token is borrowed from the future, so concurrent
+    /// callers are served in order.
+    fn reserve(&self, queue: bool, now: Instant) -> Duration {
+        let mut bucket = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * self.requests_per_second)
+            .min(self.burst as f64);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Duration::ZERO;
+        }
+
+        let wait = Duration::from_secs_f64((1.0 - bucket.tokens) / self.requests_per_second);
+        if queue {
+            bucket.tokens -= 1.0;
+        }
+        wait
+    }
+}
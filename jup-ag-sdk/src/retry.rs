@@ -0,0 +1,141 @@
+//! Opt-in retries with exponential backoff for transient failures.
+//!
+//! Enable with [`JupiterClient::with_retry_policy`](crate::JupiterClient::with_retry_policy).
+//! Idempotent `GET` requests are retried on timeouts and `5xx` responses. Other requests,
+//! such as the `/swap` build `POST`, are only retried when the connection could not be
+//! established, i.e. when the request never reached Jupiter, unless swap builds opt in with
+//! [`JupiterClient::with_swap_build_retries`](crate::JupiterClient::with_swap_build_retries).
+//!
+//! Every request is retried on `429`, which Jupiter returns before processing it. The wait
+//! honors `Retry-After` (or the rate-limit reset headers) when present; if the server asks for
+//! longer than [`RetryPolicy::max_backoff`], the call fails right away with
+//! [`JupiterClientError::RateLimited`](crate::JupiterClientError::RateLimited) instead.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use reqwest::{StatusCode, header::HeaderMap};
+
+use crate::rt::{SystemTime, UNIX_EPOCH};
+
+/// How many times, and how patiently, failed requests are retried.
+///
+/// # Example
+/// ```
+/// let api = JupiterClient::new("https://lite-api.jup.ag").with_retry_policy(
+///     RetryPolicy::new(4)
+///         .initial_backoff(Duration::from_millis(100))
+///         .max_backoff(Duration::from_secs(2)),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts per request, including the first one.
+    pub max_attempts: u32,
+
+    /// Backoff before the first retry; doubled for every following retry.
+    pub initial_backoff: Duration,
+
+    /// Upper bound of a single backoff.
+    pub max_backoff: Duration,
+
+    /// Randomizes each backoff between zero and its computed value ("full jitter"), so many
+    /// clients don't retry in lockstep.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy making up to `max_attempts` attempts with the default backoff.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Self::default()
+        }
+    }
+
+    /// A policy that never retries.
+    pub fn disabled() -> Self {
+        Self::new(1)
+    }
+
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// The backoff before retry number `retry` (starting at 1).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(31);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            let fraction = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+            backoff.mul_f64(fraction)
+        } else {
+            backoff
+        }
+    }
+}
+
+/// Whether a response status is worth retrying for an idempotent request.
+pub(crate) fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Whether a transport error is worth retrying.
+///
+/// Connection failures are always safe to retry; timeouts only for idempotent requests, since
+/// the server may already have processed the request.
+pub(crate) fn is_retryable_error(error: &reqwest::Error, idempotent: bool) -> bool {
+    #[cfg(not(target_arch = "wasm32"))]
+    if error.is_connect() {
+        return true;
+    }
+
+    idempotent && error.is_timeout()
+}
+
+/// How long the server asked to wait before retrying, from `Retry-After` or, failing that,
+/// the `x-ratelimit-reset` / `ratelimit-reset` headers.
+///
+/// Values are read as seconds to wait, except resets large enough to be a Unix timestamp.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let seconds = ["retry-after", "x-ratelimit-reset", "ratelimit-reset"]
+        .into_iter()
+        .find_map(|name| headers.get(name)?.to_str().ok()?.trim().parse::<f64>().ok())
+        .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)?;
+
+    // anything past 2001 is an absolute reset time rather than a delay
+    if seconds > 1e9 {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+        return Some(Duration::from_secs_f64(seconds).saturating_sub(now));
+    }
+    Some(Duration::from_secs_f64(seconds))
+}
@@ -0,0 +1,86 @@
+//! Timers and clocks that work both natively and on `wasm32-unknown-unknown`, where
+//! `std::time::Instant` and `SystemTime` panic and tokio timers are unavailable.
+
+use std::{
+    future::{Future, poll_fn},
+    pin::{Pin, pin},
+    task::Poll,
+    time::Duration,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::{Instant, SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+pub use web_time::{Instant, SystemTime, UNIX_EPOCH};
+
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(duration).await;
+
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// Runs `future` to completion, unless `cancel` completes first, in which case `future` is
+/// dropped and `None` returned.
+pub(crate) async fn race<T>(
+    future: impl Future<Output = T>,
+    cancel: impl Future<Output = ()>,
+) -> Option<T> {
+    let mut future = pin!(future);
+    let mut cancel = pin!(cancel);
+    poll_fn(|cx| {
+        if cancel.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(None);
+        }
+        future.as_mut().poll(cx).map(Some)
+    })
+    .await
+}
+
+/// Runs `futures` concurrently, polling at most `limit` at a time, and returns their outputs
+/// in input order.
+pub(crate) async fn join_bounded<F: Future>(futures: Vec<F>, limit: usize) -> Vec<F::Output> {
+    let limit = limit.max(1);
+    let mut futures: Vec<Option<Pin<Box<F>>>> =
+        futures.into_iter().map(|f| Some(Box::pin(f))).collect();
+    let mut outputs: Vec<Option<F::Output>> = futures.iter().map(|_| None).collect();
+    let mut active: Vec<usize> = Vec::new();
+    let mut next = 0;
+
+    poll_fn(|cx| {
+        loop {
+            while active.len() < limit && next < futures.len() {
+                active.push(next);
+                next += 1;
+            }
+
+            let mut finished = false;
+            active.retain(|&i| {
+                let Some(future) = futures[i].as_mut() else {
+                    return false;
+                };
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(output) => {
+                        outputs[i] = Some(output);
+                        futures[i] = None;
+                        finished = true;
+                        false
+                    }
+                    Poll::Pending => true,
+                }
+            });
+
+            if active.is_empty() && next == futures.len() {
+                return Poll::Ready(());
+            }
+            // freed slots are refilled right away; otherwise wait for a wake-up
+            if !finished {
+                return Poll::Pending;
+            }
+        }
+    })
+    .await;
+
+    outputs.into_iter().flatten().collect()
+}
@@ -0,0 +1,129 @@
+//! A shared handle for stopping the SDK's background work cleanly.
+//!
+//! One [`Shutdown`] can be registered on any number of clients with
+//! [`JupiterClient::with_shutdown`](crate::JupiterClient::with_shutdown) and passed to
+//! [`resubmit_until_landed`](crate::submission::resubmit_until_landed) through
+//! [`ResubmitConfig::shutdown`](crate::submission::ResubmitConfig::shutdown). Once triggered,
+//! new calls are rejected with [`JupiterClientError::ShuttingDown`](crate::JupiterClientError::ShuttingDown),
+//! resubmission loops stop broadcasting and only wait for the outcome of what they already
+//! sent, and [`Shutdown::drained`] resolves once all of that in-flight work has finished.
+
+use std::{
+    fmt,
+    future::poll_fn,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    task::{Poll, Waker},
+};
+
+/// A cloneable shutdown signal that also counts the work still in flight.
+///
+/// # Example
+/// ```
+/// let shutdown = Shutdown::new();
+/// let api = JupiterClient::new("https://lite-api.jup.ag").with_shutdown(shutdown.clone());
+///
+/// tokio::signal::ctrl_c().await?;
+/// api.shutdown().await; // triggers, drains in-flight calls and flushes audit sinks
+/// ```
+#[derive(Clone, Default)]
+pub struct Shutdown(Arc<Inner>);
+
+#[derive(Default)]
+struct Inner {
+    triggered: AtomicBool,
+    in_flight: AtomicUsize,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Signals every holder of this handle to stop. Idempotent.
+    pub fn trigger(&self) {
+        if !self.0.triggered.swap(true, Ordering::SeqCst) {
+            self.wake_all();
+        }
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        self.0.triggered.load(Ordering::SeqCst)
+    }
+
+    /// Number of calls and confirmation loops that have not finished yet.
+    pub fn in_flight(&self) -> usize {
+        self.0.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once [`trigger`](Self::trigger) is called.
+    pub async fn triggered(&self) {
+        self.wait_until(|| self.is_triggered()).await
+    }
+
+    /// Resolves once the handle is triggered and no work is in flight.
+    pub async fn drained(&self) {
+        self.wait_until(|| self.is_triggered() && self.in_flight() == 0)
+            .await
+    }
+
+    /// Marks a unit of work as in flight until the returned guard is dropped.
+    pub(crate) fn enter(&self) -> InFlightGuard {
+        self.0.in_flight.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard(self.clone())
+    }
+
+    async fn wait_until(&self, done: impl Fn() -> bool) {
+        poll_fn(|cx| {
+            if done() {
+                return Poll::Ready(());
+            }
+            {
+                let mut wakers = self.0.wakers.lock().unwrap_or_else(|e| e.into_inner());
+                if !wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                    wakers.push(cx.waker().clone());
+                }
+            }
+            // re-checked after registering, so a wake-up between the two is not lost
+            if done() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    fn wake_all(&self) {
+        let wakers = std::mem::take(&mut *self.0.wakers.lock().unwrap_or_else(|e| e.into_inner()));
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+impl fmt::Debug for Shutdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shutdown")
+            .field("triggered", &self.is_triggered())
+            .field("in_flight", &self.in_flight())
+            .finish()
+    }
+}
+
+/// Keeps a [`Shutdown`] from draining while alive.
+pub(crate) struct InFlightGuard(Shutdown);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let inner = &self.0.0;
+        if inner.in_flight.fetch_sub(1, Ordering::SeqCst) == 1
+            && inner.triggered.load(Ordering::SeqCst)
+        {
+            self.0.wake_all();
+        }
+    }
+}
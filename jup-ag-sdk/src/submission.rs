@@ -0,0 +1,455 @@
+//! Guards against submitting the same swap twice.
+//!
+//! Upstream retries (a job queue redelivering a message, a user double-clicking) can hand the
+//! same signed swap to the sender again after it already confirmed. [`SubmissionTracker`]
+//! remembers recently sent message hashes and flags or refuses such re-submissions.
+//!
+//! [`resubmit_until_landed`] keeps re-broadcasting a signed transaction until it confirms or
+//! its blockhash expires.
+
+use std::{collections::HashMap, fmt::Write, sync::Mutex, time::Duration};
+
+use reqwest::Client;
+use serde::{Deserialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    JupiterClientError,
+    cluster::ensure_mainnet_rpc,
+    error::handle_response,
+    rt::{self, Instant},
+    shutdown::Shutdown,
+    wire::{TRANSACTION_BUFFER_LEN, decode_transaction, first_signature, message_bytes},
+};
+
+/// What [`SubmissionTracker::register`] does with a transaction that already confirmed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Fails with [`JupiterClientError::DuplicateSubmission`].
+    #[default]
+    Refuse,
+
+    /// Returns [`SubmissionStatus::AlreadyConfirmed`] and leaves the decision to the caller.
+    Warn,
+}
+
+/// How a transaction relates to what was sent before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionStatus {
+    /// Not seen before.
+    New,
+
+    /// Sent before but not confirmed yet. Re-broadcasting it is harmless: the same message
+    /// can only land once.
+    InFlight,
+
+    /// Sent before and confirmed. Only returned with [`DuplicatePolicy::Warn`].
+    AlreadyConfirmed,
+}
+
+#[derive(Debug)]
+struct Submission {
+    sent_at: Instant,
+    confirmed: bool,
+}
+
+/// Tracks the message hashes of recently sent transactions.
+///
+/// Entries are forgotten after `ttl`; pick one longer than a blockhash stays valid (~90s),
+/// after which an old transaction can no longer land anyway.
+///
+/// # Example
+/// ```
+/// let tracker = SubmissionTracker::new(Duration::from_secs(120));
+///
+/// // fails with DuplicateSubmission if this swap already confirmed
+/// tracker.register(&signed_tx)?;
+/// rpc.send_and_confirm(&signed_tx).await?;
+/// tracker.mark_confirmed(&signed_tx)?;
+/// ```
+#[derive(Debug)]
+pub struct SubmissionTracker {
+    ttl: Duration,
+    policy: DuplicatePolicy,
+    submissions: Mutex<HashMap<String, Submission>>,
+}
+
+impl SubmissionTracker {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            policy: DuplicatePolicy::default(),
+            submissions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn policy(mut self, policy: DuplicatePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Records that the base64-encoded, signed `transaction` is about to be sent.
+    ///
+    /// # Errors
+    /// * [`JupiterClientError::DuplicateSubmission`] if it already confirmed and the policy is
+    ///   [`DuplicatePolicy::Refuse`].
+    /// * the transaction can't be decoded.
+    pub fn register(&self, transaction: &str) -> Result<SubmissionStatus, JupiterClientError> {
+        let hash = message_hash(transaction)?;
+        let mut submissions = self.submissions.lock().unwrap_or_else(|e| e.into_inner());
+        submissions.retain(|_, s| s.sent_at.elapsed() < self.ttl);
+
+        match submissions.get(&hash) {
+            Some(Submission {
+                confirmed: true, ..
+            }) => match self.policy {
+                DuplicatePolicy::Refuse => Err(JupiterClientError::DuplicateSubmission(hash)),
+                DuplicatePolicy::Warn => Ok(SubmissionStatus::AlreadyConfirmed),
+            },
+            Some(_) => Ok(SubmissionStatus::InFlight),
+            None => {
+                submissions.insert(
+                    hash,
+                    Submission {
+                        sent_at: Instant::now(),
+                        confirmed: false,
+                    },
+                );
+                Ok(SubmissionStatus::New)
+            }
+        }
+    }
+
+    /// Records that `transaction` confirmed, so later submissions of it are flagged.
+    pub fn mark_confirmed(&self, transaction: &str) -> Result<(), JupiterClientError> {
+        let hash = message_hash(transaction)?;
+        let mut submissions = self.submissions.lock().unwrap_or_else(|e| e.into_inner());
+        submissions
+            .entry(hash)
+            .or_insert(Submission {
+                sent_at: Instant::now(),
+                confirmed: true,
+            })
+            .confirmed = true;
+        Ok(())
+    }
+}
+
+/// Where and how often [`resubmit_until_landed`] broadcasts.
+#[derive(Debug, Clone)]
+pub struct ResubmitConfig {
+    /// RPCs the transaction is sent to on every round. Statuses are read from the first one.
+    pub rpc_urls: Vec<String>,
+
+    /// From the blockhash the transaction was signed with, e.g.
+    /// [`SwapResponse::last_valid_block_height`](crate::types::SwapResponse::last_valid_block_height).
+    pub last_valid_block_height: u64,
+
+    /// Time between broadcast rounds. Default: 2s.
+    pub interval: Duration,
+
+    /// Stops broadcasting once triggered, while still waiting for the outcome.
+    pub shutdown: Option<Shutdown>,
+
+    /// Gives up with [`JupiterClientError::DeadlineExceeded`] at this instant, bounding every
+    /// broadcast and status call. Default: none, waiting until the blockhash expires, which
+    /// only ends the loop while the status RPC answers.
+    pub deadline: Option<Instant>,
+
+    /// Checks that every RPC is a mainnet-beta node before the first broadcast. Default: false.
+    pub require_mainnet: bool,
+}
+
+impl ResubmitConfig {
+    pub fn new(rpc_urls: Vec<String>, last_valid_block_height: u64) -> Self {
+        Self {
+            rpc_urls,
+            last_valid_block_height,
+            interval: Duration::from_secs(2),
+            shutdown: None,
+            deadline: None,
+            require_mainnet: false,
+        }
+    }
+
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Ties the loop to `shutdown`: once triggered, the transaction is no longer re-broadcast
+    /// but its status is still polled until it confirms, fails or expires, and the handle
+    /// doesn't drain before then. A loop started after the trigger fails with
+    /// [`JupiterClientError::ShuttingDown`] without sending anything.
+    pub fn shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Stops waiting at `deadline`, e.g. to bound a whole swap flow by one deadline.
+    ///
+    /// The transaction may still land after the loop gave up, until its blockhash expires;
+    /// check its signature before rebuilding the swap.
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Fails with [`JupiterClientError::ClusterMismatch`] before sending anything if one of
+    /// the RPCs isn't connected to mainnet-beta, e.g. a devnet URL left in a config file.
+    ///
+    /// Uses [`ensure_mainnet_rpc`](crate::cluster::ensure_mainnet_rpc), so each URL is only
+    /// queried once per process.
+    pub fn require_mainnet(mut self, require: bool) -> Self {
+        self.require_mainnet = require;
+        self
+    }
+}
+
+/// How a resubmitted transaction ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LandingOutcome {
+    /// Confirmed without error in `slot`.
+    Confirmed { slot: u64 },
+
+    /// Landed in `slot` but failed; the fee was paid and nothing else happened.
+    Failed { slot: u64, error: String },
+
+    /// The blockhash expired at `block_height` before the transaction landed. It can no
+    /// longer land, so it is safe to rebuild and resend the swap.
+    Expired { block_height: u64 },
+}
+
+/// The result of [`resubmit_until_landed`].
+#[derive(Debug, Clone)]
+pub struct LandingReport {
+    /// Base58 signature of the transaction.
+    pub signature: String,
+
+    pub outcome: LandingOutcome,
+
+    /// Broadcast rounds performed.
+    pub rounds: u32,
+
+    /// Individual `sendTransaction` calls that an RPC rejected or that failed in transit.
+    pub failed_sends: u32,
+
+    /// `getSignatureStatuses` and `getBlockHeight` calls that failed. The loop carries on to
+    /// the next round after one, so an RPC that stays unreachable only ends it at the
+    /// [`deadline`](ResubmitConfig::deadline).
+    pub failed_polls: u32,
+
+    pub elapsed: Duration,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct SignatureStatuses {
+    value: Vec<Option<SignatureStatus>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignatureStatus {
+    slot: u64,
+    err: Option<serde_json::Value>,
+    confirmation_status: Option<String>,
+}
+
+/// Re-broadcasts the base64-encoded, signed `transaction` to every configured RPC each
+/// `interval` until it is confirmed, fails, or its blockhash expires.
+///
+/// Sends with `skipPreflight` and `maxRetries: 0`, leaving the retrying to this loop. Uses its
+/// own HTTP client so the Jupiter API key is never sent to the RPCs.
+///
+/// # Example
+/// ```
+/// let config = ResubmitConfig::new(vec![primary_rpc, backup_rpc], swap.last_valid_block_height)
+///     .interval(Duration::from_millis(500));
+/// let report = resubmit_until_landed(&signed_tx, &config).await?;
+/// match report.outcome {
+///     LandingOutcome::Confirmed { slot } => println!("{} landed in {slot}", report.signature),
+///     LandingOutcome::Expired { .. } => println!("expired, requote and retry"),
+///     LandingOutcome::Failed { error, .. } => println!("failed on-chain: {error}"),
+/// }
+/// ```
+pub async fn resubmit_until_landed(
+    transaction: &str,
+    config: &ResubmitConfig,
+) -> Result<LandingReport, JupiterClientError> {
+    let mut buffer = [0; TRANSACTION_BUFFER_LEN];
+    let bytes = decode_transaction(transaction, &mut buffer)?;
+    let signature = first_signature(bytes)
+        .map(|signature| bs58::encode(signature).into_string())
+        .ok_or_else(|| {
+            JupiterClientError::deserialization("transaction has no signature".to_string())
+        })?;
+    let Some(status_rpc) = config.rpc_urls.first() else {
+        return Err(JupiterClientError::ConfigError(
+            "at least one RPC URL is required".to_string(),
+        ));
+    };
+
+    if config.shutdown.as_ref().is_some_and(Shutdown::is_triggered) {
+        return Err(JupiterClientError::ShuttingDown);
+    }
+    let _in_flight = config.shutdown.as_ref().map(Shutdown::enter);
+
+    let client = Client::new();
+    let started = Instant::now();
+    let mut report = LandingReport {
+        signature,
+        outcome: LandingOutcome::Expired { block_height: 0 },
+        rounds: 0,
+        failed_sends: 0,
+        failed_polls: 0,
+        elapsed: Duration::ZERO,
+    };
+
+    let rounds = async {
+        if config.require_mainnet {
+            for rpc_url in &config.rpc_urls {
+                ensure_mainnet_rpc(rpc_url).await?;
+            }
+        }
+        loop {
+            let stopping = config.shutdown.as_ref().is_some_and(Shutdown::is_triggered);
+            if !stopping {
+                report.rounds += 1;
+            }
+            for rpc_url in config.rpc_urls.iter().filter(|_| !stopping) {
+                let sent = rpc_call::<serde_json::Value>(
+                    &client,
+                    rpc_url,
+                    "sendTransaction",
+                    serde_json::json!([
+                        transaction,
+                        { "encoding": "base64", "skipPreflight": true, "maxRetries": 0 }
+                    ]),
+                )
+                .await;
+                if sent.is_err() {
+                    report.failed_sends += 1;
+                }
+            }
+
+            rt::sleep(config.interval).await;
+
+            let statuses = rpc_call::<SignatureStatuses>(
+                &client,
+                status_rpc,
+                "getSignatureStatuses",
+                serde_json::json!([[report.signature]]),
+            )
+            .await;
+            let Ok(statuses) = statuses else {
+                report.failed_polls += 1;
+                continue;
+            };
+            if let Some(Some(status)) = statuses.value.into_iter().next() {
+                let landed = matches!(
+                    status.confirmation_status.as_deref(),
+                    Some("confirmed" | "finalized")
+                );
+                match status.err {
+                    Some(error) => {
+                        report.outcome = LandingOutcome::Failed {
+                            slot: status.slot,
+                            error: error.to_string(),
+                        };
+                        break;
+                    }
+                    None if landed => {
+                        report.outcome = LandingOutcome::Confirmed { slot: status.slot };
+                        break;
+                    }
+                    None => {}
+                }
+            }
+
+            let block_height = rpc_call::<u64>(
+                &client,
+                status_rpc,
+                "getBlockHeight",
+                serde_json::json!([{ "commitment": "confirmed" }]),
+            )
+            .await;
+            let Ok(block_height) = block_height else {
+                report.failed_polls += 1;
+                continue;
+            };
+            if block_height > config.last_valid_block_height {
+                report.outcome = LandingOutcome::Expired { block_height };
+                break;
+            }
+        }
+        Ok(())
+    };
+    match config.deadline {
+        Some(deadline) => {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            rt::race(rounds, rt::sleep(remaining)).await.unwrap_or(Err(
+                JupiterClientError::DeadlineExceeded { request_id: None },
+            ))?
+        }
+        None => rounds.await?,
+    }
+
+    report.elapsed = started.elapsed();
+    Ok(report)
+}
+
+pub(crate) async fn rpc_call<T: DeserializeOwned>(
+    client: &Client,
+    rpc_url: &str,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<T, JupiterClientError> {
+    let response = client
+        .post(rpc_url)
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        }))
+        .send()
+        .await?;
+
+    let response = handle_response(response).await?;
+
+    let body = match response.json::<RpcResponse<T>>().await {
+        Ok(body) => body,
+        Err(e) => return Err(JupiterClientError::deserialization(e.to_string())),
+    };
+    match (body.result, body.error) {
+        (Some(result), None) => Ok(result),
+        (_, error) => Err(JupiterClientError::RpcError(format!(
+            "{method} failed: {}",
+            error.unwrap_or_default()
+        ))),
+    }
+}
+
+/// The hex-encoded SHA-256 of a base64-encoded transaction's message.
+///
+/// Identical for every copy of the same swap regardless of how many times it was signed,
+/// since signatures are excluded.
+pub fn message_hash(transaction: &str) -> Result<String, JupiterClientError> {
+    let mut buffer = [0; TRANSACTION_BUFFER_LEN];
+    let bytes = decode_transaction(transaction, &mut buffer)?;
+    let message = message_bytes(bytes)
+        .filter(|m| !m.is_empty())
+        .ok_or_else(|| JupiterClientError::deserialization("malformed transaction".to_string()))?;
+
+    let mut hash = String::with_capacity(64);
+    for byte in Sha256::digest(message) {
+        let _ = write!(hash, "{byte:02x}");
+    }
+    Ok(hash)
+}
@@ -0,0 +1,152 @@
+//! Snapshots of a token list, diffed over time to monitor listings.
+//!
+//! Take a [`TokenSnapshot`] with
+//! [`JupiterClient::snapshot_tokens`](crate::JupiterClient::snapshot_tokens), persist it with
+//! [`TokenSnapshot::write_to`], and on the next run [`diff`](TokenSnapshot::diff) a fresh
+//! snapshot against the one read back with [`TokenSnapshot::read_from`]:
+//!
+//! ```
+//! let current = api.snapshot_tokens(TokenTag::Verified).await?;
+//! if let Ok(file) = std::fs::File::open("verified.json") {
+//!     let previous = TokenSnapshot::read_from(BufReader::new(file))?;
+//!     for change in previous.diff(&current) {
+//!         match change {
+//!             TokenChange::Listed { mint, symbol } => alert(&format!("{symbol} listed: {mint}")),
+//!             TokenChange::Delisted { mint, symbol } => alert(&format!("{symbol} delisted: {mint}")),
+//!             TokenChange::TagsChanged { .. } => {}
+//!         }
+//!     }
+//! }
+//! current.write_to(std::fs::File::create("verified.json")?)?;
+//! ```
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, Read, Write},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{audit::now_ms, types::TokenInfo};
+
+/// What a snapshot keeps of a token: enough to name it in change events and to track its tags.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotToken {
+    pub symbol: String,
+    pub name: String,
+
+    /// Token API tags, e.g. `"verified"` or `"lst"`.
+    pub tags: BTreeSet<String>,
+}
+
+/// A token list at a point in time, keyed by mint.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSnapshot {
+    /// Unix timestamp in milliseconds of when the list was fetched.
+    pub taken_at_ms: u64,
+
+    pub tokens: BTreeMap<String, SnapshotToken>,
+}
+
+/// A difference between two [`TokenSnapshot`]s.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum TokenChange {
+    /// The mint joined the list.
+    Listed { mint: String, symbol: String },
+
+    /// The mint left the list.
+    Delisted { mint: String, symbol: String },
+
+    /// The mint stayed on the list with different tags.
+    TagsChanged {
+        mint: String,
+        symbol: String,
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+}
+
+impl TokenSnapshot {
+    /// Snapshots `tokens`, timestamped now.
+    pub fn from_tokens<'a>(tokens: impl IntoIterator<Item = &'a TokenInfo>) -> Self {
+        Self {
+            taken_at_ms: now_ms(),
+            tokens: tokens
+                .into_iter()
+                .map(|token| {
+                    let snapshot = SnapshotToken {
+                        symbol: token.symbol.clone(),
+                        name: token.name.clone(),
+                        tags: token.tags.iter().cloned().collect(),
+                    };
+                    (token.id.clone(), snapshot)
+                })
+                .collect(),
+        }
+    }
+
+    /// Writes the snapshot as JSON.
+    pub fn write_to(&self, writer: impl Write) -> io::Result<()> {
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+
+    /// Reads a snapshot written by [`write_to`](Self::write_to).
+    ///
+    /// # Errors
+    /// Fails with [`io::ErrorKind::InvalidData`] when the input isn't a snapshot.
+    pub fn read_from(reader: impl Read) -> io::Result<Self> {
+        serde_json::from_reader(reader).map_err(io::Error::from)
+    }
+
+    /// The changes from this snapshot to `later`, ordered by mint: listings and delistings,
+    /// then tag changes of the tokens on both lists.
+    pub fn diff(&self, later: &TokenSnapshot) -> Vec<TokenChange> {
+        let mut membership = Vec::new();
+        let mut tags = Vec::new();
+
+        for (mint, token) in &self.tokens {
+            let Some(now) = later.tokens.get(mint) else {
+                membership.push(TokenChange::Delisted {
+                    mint: mint.clone(),
+                    symbol: token.symbol.clone(),
+                });
+                continue;
+            };
+            let added: Vec<String> = now.tags.difference(&token.tags).cloned().collect();
+            let removed: Vec<String> = token.tags.difference(&now.tags).cloned().collect();
+            if !added.is_empty() || !removed.is_empty() {
+                tags.push(TokenChange::TagsChanged {
+                    mint: mint.clone(),
+                    symbol: now.symbol.clone(),
+                    added,
+                    removed,
+                });
+            }
+        }
+        for (mint, token) in &later.tokens {
+            if !self.tokens.contains_key(mint) {
+                membership.push(TokenChange::Listed {
+                    mint: mint.clone(),
+                    symbol: token.symbol.clone(),
+                });
+            }
+        }
+
+        membership.sort_by(|a, b| a.mint().cmp(b.mint()));
+        membership.extend(tags);
+        membership
+    }
+}
+
+impl TokenChange {
+    pub fn mint(&self) -> &str {
+        match self {
+            Self::Listed { mint, .. }
+            | Self::Delisted { mint, .. }
+            | Self::TagsChanged { mint, .. } => mint,
+        }
+    }
+}
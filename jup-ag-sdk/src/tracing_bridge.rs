@@ -0,0 +1,99 @@
+//! Re-emits SDK lifecycle data as structured `tracing` events, so existing log pipelines
+//! (`tracing-subscriber` JSON layers, OpenTelemetry, ...) can consume it without custom glue.
+//!
+//! Enabled with the `tracing` feature.
+//!
+//! Field names are stable: new fields may be added, but existing ones are never renamed.
+//!
+//! | target                | fields                                                                                                                          |
+//! |-----------------------|---------------------------------------------------------------------------------------------------------------------------------|
+//! | `jup_ag_sdk::audit`   | `operation`, `timestamp_ms`, `duration_ms`, `input_mint`, `output_mint`, `in_amount`, `out_amount`, `signature`, `confirmations`, `error` |
+//! | `jup_ag_sdk::request` | `endpoint`, `method`, `status`, `duration_ms`, `attempt`, `error`                                                                 |
+//! | `jup_ag_sdk::warning` | `message`                                                                                                                       |
+//!
+//! Successful steps are logged at `INFO` (audit) or `DEBUG` (requests), failures and warnings at `WARN`.
+
+use crate::{
+    audit::{AuditRecord, AuditSink},
+    metrics::{MetricsSink, RequestMetric},
+    warnings::{ClientWarning, WarningSink},
+};
+
+/// An [`AuditSink`], [`MetricsSink`] and [`WarningSink`] that forwards everything to `tracing`.
+///
+/// # Example
+/// ```
+/// tracing_subscriber::fmt().json().init();
+///
+/// let client = JupiterClient::new("https://lite-api.jup.ag")
+///     .with_audit_sink(TracingSink)
+///     .with_metrics_sink(TracingSink)
+///     .with_warning_sink(TracingSink);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingSink;
+
+impl AuditSink for TracingSink {
+    fn record(&self, record: &AuditRecord) {
+        let quote = record
+            .quote
+            .as_ref()
+            .or(record.swap_request.as_ref().map(|r| &r.quote_response));
+
+        macro_rules! audit_event {
+            ($level:expr) => {
+                tracing::event!(
+                    target: "jup_ag_sdk::audit",
+                    $level,
+                    operation = record.operation.as_str(),
+                    timestamp_ms = record.timestamp_ms,
+                    duration_ms = record.duration_ms,
+                    input_mint = quote.map(|q| q.input_mint.as_str()),
+                    output_mint = quote.map(|q| q.output_mint.as_str()),
+                    in_amount = quote.map(|q| q.in_amount.as_str()),
+                    out_amount = quote.map(|q| q.out_amount.as_str()),
+                    signature = record.signature.as_deref(),
+                    confirmations = record.confirmations,
+                    error = record.error.as_deref(),
+                )
+            };
+        }
+
+        if record.error.is_some() {
+            audit_event!(tracing::Level::WARN);
+        } else {
+            audit_event!(tracing::Level::INFO);
+        }
+    }
+}
+
+impl MetricsSink for TracingSink {
+    fn record(&self, metric: &RequestMetric) {
+        macro_rules! request_event {
+            ($level:expr) => {
+                tracing::event!(
+                    target: "jup_ag_sdk::request",
+                    $level,
+                    endpoint = metric.endpoint.as_str(),
+                    method = metric.method.as_str(),
+                    status = metric.status.map(|s| s.as_u16()),
+                    duration_ms = metric.duration.as_millis() as u64,
+                    attempt = metric.attempt,
+                    error = metric.is_error(),
+                )
+            };
+        }
+
+        if metric.is_error() {
+            request_event!(tracing::Level::WARN);
+        } else {
+            request_event!(tracing::Level::DEBUG);
+        }
+    }
+}
+
+impl WarningSink for TracingSink {
+    fn warn(&self, warning: &ClientWarning) {
+        tracing::warn!(target: "jup_ag_sdk::warning", "{warning}");
+    }
+}
@@ -0,0 +1,442 @@
+//! Grids of trigger orders across a price range, the usual way to make a market with limit
+//! orders.
+//!
+//! A [`GridConfig`] spreads equally sized orders over a price range, and a [`TriggerGrid`]
+//! places them, keeps track of the order accounts Jupiter creates for them, and cancels the
+//! whole grid in one call:
+//!
+//! ```
+//! let sol = TokenMeta::new(SOL_MINT, "SOL", 9);
+//! let usdc = TokenMeta::new(USDC_MINT, "USDC", 6);
+//! // sell 0.5 SOL at each of 5 prices between 160 and 200 USDC, for a day
+//! let config = GridConfig::new(&wallet, GridSide::Sell, sol, usdc, 160.0..=200.0, 5, 500_000_000)
+//!     .expires_in(Duration::from_secs(24 * 60 * 60));
+//!
+//! let mut grid = TriggerGrid::new(api.clone(), config)?;
+//! grid.place(|tx| async move { sign_transaction(&keypair, tx) }).await?;
+//!
+//! // later
+//! for closed in grid.refresh().await? {
+//!     println!("order at {} closed", closed.level.price);
+//! }
+//! grid.cancel_all(|tx| async move { sign_transaction(&keypair, tx) }).await?;
+//! ```
+//!
+//! One grid quotes one side. Pair a buy grid below the market with a sell grid above it for
+//! both.
+
+use std::{future::Future, ops::RangeInclusive, time::Duration};
+
+use crate::{
+    JupiterClient,
+    error::JupiterClientError,
+    types::{
+        CancelTriggerOrders, CreateTriggerOrder, ExecuteTriggerOrder, GetTriggerOrders,
+        OrderStatus, Slippage, TokenMeta, trigger::Order,
+    },
+};
+
+/// Which side of the market a grid quotes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridSide {
+    /// Buy the base token with the quote token at each price.
+    Buy,
+
+    /// Sell the base token for the quote token at each price.
+    Sell,
+}
+
+/// How the prices of a grid are spread over its range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GridSpacing {
+    /// The same price difference between neighbouring levels.
+    #[default]
+    Arithmetic,
+
+    /// The same price ratio between neighbouring levels.
+    Geometric,
+}
+
+/// One order of a grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridLevel {
+    /// Decimal units of the quote token per unit of the base token.
+    pub price: f64,
+
+    /// Raw amount of the input token: base when selling, quote when buying.
+    pub making_amount: u64,
+
+    /// Raw amount of the output token: quote when selling, base when buying.
+    pub taking_amount: u64,
+}
+
+/// The orders of a grid: `levels` orders of `size_per_level` of the base token each, at prices
+/// from the start to the end of `price_range`.
+#[derive(Debug, Clone)]
+pub struct GridConfig {
+    pub maker: String,
+    pub side: GridSide,
+    pub base: TokenMeta,
+    pub quote: TokenMeta,
+    pub lower_price: f64,
+    pub upper_price: f64,
+    pub levels: u32,
+
+    /// Raw amount of the base token each order sells or buys.
+    pub size_per_level: u64,
+
+    pub spacing: GridSpacing,
+
+    /// How long each order rests before it expires. Default: until cancelled.
+    pub expiry: Option<Duration>,
+
+    pub slippage: Option<Slippage>,
+}
+
+impl GridConfig {
+    pub fn new(
+        maker: &str,
+        side: GridSide,
+        base: TokenMeta,
+        quote: TokenMeta,
+        price_range: RangeInclusive<f64>,
+        levels: u32,
+        size_per_level: u64,
+    ) -> Self {
+        let (lower_price, upper_price) = price_range.into_inner();
+        Self {
+            maker: maker.to_string(),
+            side,
+            base,
+            quote,
+            lower_price,
+            upper_price,
+            levels,
+            size_per_level,
+            spacing: GridSpacing::default(),
+            expiry: None,
+            slippage: None,
+        }
+    }
+
+    pub fn spacing(mut self, spacing: GridSpacing) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Expires each order `ttl` after it is placed.
+    pub fn expires_in(mut self, ttl: Duration) -> Self {
+        self.expiry = Some(ttl);
+        self
+    }
+
+    pub fn slippage(mut self, slippage: Slippage) -> Self {
+        self.slippage = Some(slippage);
+        self
+    }
+
+    /// The price of each level, from the lower to the upper end of the range.
+    pub fn prices(&self) -> Vec<f64> {
+        let steps = self.levels.saturating_sub(1).max(1) as f64;
+        (0..self.levels)
+            .map(|i| {
+                let t = i as f64 / steps;
+                match self.spacing {
+                    GridSpacing::Arithmetic => {
+                        self.lower_price + (self.upper_price - self.lower_price) * t
+                    }
+                    GridSpacing::Geometric => {
+                        self.lower_price * (self.upper_price / self.lower_price).powf(t)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// The amounts of each level, from the lowest price to the highest.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::InvalidTriggerOrder`] when the range isn't a positive, increasing
+    /// pair of prices, there are fewer than two levels, the size is zero, or a level's quote
+    /// amount rounds to zero or overflows.
+    pub fn grid_levels(&self) -> Result<Vec<GridLevel>, JupiterClientError> {
+        let invalid = JupiterClientError::InvalidTriggerOrder;
+        if !(self.lower_price.is_finite() && self.upper_price.is_finite())
+            || self.lower_price <= 0.0
+            || self.lower_price >= self.upper_price
+        {
+            return Err(invalid(format!(
+                "price range {}..={} must be positive and increasing",
+                self.lower_price, self.upper_price
+            )));
+        }
+        if self.levels < 2 {
+            return Err(invalid(format!(
+                "a grid needs at least 2 levels, got {}",
+                self.levels
+            )));
+        }
+        if self.size_per_level == 0 {
+            return Err(invalid("size per level must be positive".to_string()));
+        }
+
+        let decimals = self.quote.decimals as i32 - self.base.decimals as i32;
+        self.prices()
+            .into_iter()
+            .map(|price| {
+                let quote_amount =
+                    (self.size_per_level as f64 * price * 10f64.powi(decimals)).round();
+                if quote_amount < 1.0 || quote_amount >= u64::MAX as f64 {
+                    return Err(invalid(format!(
+                        "quote amount {quote_amount} at price {price} is out of range"
+                    )));
+                }
+                let quote_amount = quote_amount as u64;
+                let (making_amount, taking_amount) = match self.side {
+                    GridSide::Sell => (self.size_per_level, quote_amount),
+                    GridSide::Buy => (quote_amount, self.size_per_level),
+                };
+                Ok(GridLevel {
+                    price,
+                    making_amount,
+                    taking_amount,
+                })
+            })
+            .collect()
+    }
+
+    /// The `(input, output)` mints of the grid's orders.
+    pub fn mints(&self) -> (&str, &str) {
+        match self.side {
+            GridSide::Sell => (&self.base.mint, &self.quote.mint),
+            GridSide::Buy => (&self.quote.mint, &self.base.mint),
+        }
+    }
+
+    /// The trigger order of `level`, paid for by the maker. The expiry counts from now.
+    pub fn order(&self, level: &GridLevel) -> CreateTriggerOrder {
+        let (input_mint, output_mint) = self.mints();
+        let mut order = CreateTriggerOrder::new(
+            input_mint,
+            output_mint,
+            &self.maker,
+            &self.maker,
+            level.making_amount,
+            level.taking_amount,
+        );
+        if let Some(ttl) = self.expiry {
+            order = order.expires_in(ttl);
+        }
+        if let Some(slippage) = self.slippage {
+            order = order.slippage(slippage);
+        }
+        order
+    }
+}
+
+/// A placed order of a [`TriggerGrid`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridOrder {
+    pub level: GridLevel,
+
+    /// The trigger order account.
+    pub order_key: String,
+
+    /// Signature of the transaction that opened the order.
+    pub signature: String,
+}
+
+/// The orders of a [`GridConfig`], placed and tracked together.
+#[derive(Debug, Clone)]
+pub struct TriggerGrid {
+    client: JupiterClient,
+    config: GridConfig,
+    levels: Vec<GridLevel>,
+    orders: Vec<Option<GridOrder>>,
+
+    /// Order accounts created for a level whose execution didn't report success, which may
+    /// still have opened.
+    pending: Vec<Option<String>>,
+}
+
+impl TriggerGrid {
+    /// Lays out the grid without placing anything.
+    ///
+    /// # Errors
+    /// See [`GridConfig::grid_levels`].
+    pub fn new(client: JupiterClient, config: GridConfig) -> Result<Self, JupiterClientError> {
+        let levels = config.grid_levels()?;
+        Ok(Self {
+            client,
+            orders: vec![None; levels.len()],
+            pending: vec![None; levels.len()],
+            config,
+            levels,
+        })
+    }
+
+    pub fn config(&self) -> &GridConfig {
+        &self.config
+    }
+
+    pub fn levels(&self) -> &[GridLevel] {
+        &self.levels
+    }
+
+    /// The orders placed and not known to be closed, from the lowest price to the highest.
+    pub fn orders(&self) -> impl Iterator<Item = &GridOrder> {
+        self.orders.iter().flatten()
+    }
+
+    /// Whether every level has an open order.
+    pub fn is_placed(&self) -> bool {
+        self.orders.iter().all(Option::is_some)
+    }
+
+    /// Places an order at every level without one, signing each transaction with `sign`, which
+    /// takes and returns a base64-encoded transaction. Returns how many orders were placed.
+    ///
+    /// Orders are placed one at a time, from the lowest price. On an error the orders placed
+    /// so far stay tracked, and calling `place` again continues with the remaining levels.
+    ///
+    /// An order whose execution failed may still have opened, so before placing its level
+    /// again the next call looks it up among the maker's active orders, and tracks it if it's
+    /// there.
+    pub async fn place<F, Fut>(&mut self, sign: F) -> Result<usize, JupiterClientError>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<String, JupiterClientError>>,
+    {
+        if self.pending.iter().any(Option::is_some) {
+            let active = self.maker_orders(OrderStatus::Active).await?;
+            self.confirm_pending(&active);
+            // the rest never opened, or already closed
+            self.pending.iter_mut().for_each(|pending| *pending = None);
+        }
+
+        let mut placed = 0;
+        let slots = self.orders.iter_mut().zip(&mut self.pending);
+        for (level, (slot, pending)) in self.levels.iter().zip(slots) {
+            if slot.is_some() {
+                continue;
+            }
+            let response = self
+                .client
+                .create_trigger_order(&self.config.order(level))
+                .await?;
+            let order_key = response.order.clone().ok_or_else(|| {
+                JupiterClientError::deserialization(
+                    "createOrder response has no order account".to_string(),
+                )
+            })?;
+            *pending = Some(order_key.clone());
+            let signed = sign(response.transaction).await?;
+            let executed = self
+                .client
+                .execute_trigger_order(&ExecuteTriggerOrder::new(&response.request_id, &signed))
+                .await?
+                .into_result()?;
+            *pending = None;
+            *slot = Some(GridOrder {
+                level: level.clone(),
+                order_key,
+                signature: executed.signature,
+            });
+            placed += 1;
+        }
+        Ok(placed)
+    }
+
+    /// Tracks the pending orders found among the maker's `active` orders.
+    fn confirm_pending(&mut self, active: &[Order]) {
+        let slots = self.orders.iter_mut().zip(&mut self.pending);
+        for (level, (slot, pending)) in self.levels.iter().zip(slots) {
+            let Some(order) = pending
+                .as_ref()
+                .and_then(|key| active.iter().find(|a| a.order_key == *key))
+            else {
+                continue;
+            };
+            *slot = Some(GridOrder {
+                level: level.clone(),
+                order_key: order.order_key.clone(),
+                signature: order.open_tx.clone(),
+            });
+            *pending = None;
+        }
+    }
+
+    /// Checks the tracked orders against the maker's trigger orders and stops tracking those
+    /// that filled, expired or were cancelled elsewhere. Returns them; their levels are placed
+    /// again by the next [`place`](Self::place).
+    ///
+    /// An order only counts as closed once it shows up in the maker's order history. One
+    /// that's neither active nor in the history, e.g. because it was placed moments ago and
+    /// isn't indexed yet, stays tracked. Orders whose execution failed but that opened anyway
+    /// are tracked from here on.
+    pub async fn refresh(&mut self) -> Result<Vec<GridOrder>, JupiterClientError> {
+        if self.orders().next().is_none() && self.pending.iter().all(Option::is_none) {
+            return Ok(Vec::new());
+        }
+        let active = self.maker_orders(OrderStatus::Active).await?;
+        self.confirm_pending(&active);
+        let missing = |order: &GridOrder| !active.iter().any(|a| a.order_key == order.order_key);
+        if !self.orders().any(missing) {
+            return Ok(Vec::new());
+        }
+        let history = self.maker_orders(OrderStatus::History).await?;
+
+        let mut closed = Vec::new();
+        for slot in &mut self.orders {
+            let is_closed = slot
+                .as_ref()
+                .is_some_and(|order| history.iter().any(|h| h.order_key == order.order_key));
+            if is_closed {
+                closed.extend(slot.take());
+            }
+        }
+        Ok(closed)
+    }
+
+    /// Every order of the maker in the grid's direction with `status`.
+    async fn maker_orders(&self, status: OrderStatus) -> Result<Vec<Order>, JupiterClientError> {
+        let (input_mint, output_mint) = self.config.mints();
+        let request = GetTriggerOrders::new(&self.config.maker, status)
+            .input_mint(input_mint)
+            .output_mint(output_mint);
+        self.client.trigger_orders(&request).collect_orders().await
+    }
+
+    /// Cancels every tracked order in one request, signing each cancel transaction with
+    /// `sign`. Returns the signatures of the cancel transactions.
+    ///
+    /// The orders stay tracked until every transaction succeeded; after a failure,
+    /// [`refresh`](Self::refresh) finds which of them are still open.
+    pub async fn cancel_all<F, Fut>(&mut self, sign: F) -> Result<Vec<String>, JupiterClientError>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<String, JupiterClientError>>,
+    {
+        let order_keys: Vec<String> = self.orders().map(|o| o.order_key.clone()).collect();
+        if order_keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        let response = self
+            .client
+            .cancel_trigger_orders(&CancelTriggerOrders::new(&self.config.maker, order_keys))
+            .await?;
+
+        let mut signatures = Vec::new();
+        for transaction in response.unsigned_transactions() {
+            let signed = sign(transaction.to_string()).await?;
+            let executed = self
+                .client
+                .execute_trigger_order(&ExecuteTriggerOrder::new(&response.request_id, &signed))
+                .await?
+                .into_result()?;
+            signatures.push(executed.signature);
+        }
+        self.orders.iter_mut().for_each(|slot| *slot = None);
+        Ok(signatures)
+    }
+}
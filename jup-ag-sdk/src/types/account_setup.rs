@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{SwapRequest, WalletHoldings, holdings::WSOL_MINT};
+
+/// Size of a Token program account, and of a Token-2022 account without extensions.
+pub const TOKEN_ACCOUNT_SIZE: u64 = 165;
+
+/// Rent-exempt minimum of a 165-byte token account, in lamports.
+pub const TOKEN_ACCOUNT_RENT_LAMPORTS: u64 = rent_exempt_lamports(TOKEN_ACCOUNT_SIZE);
+
+/// Rent-exempt minimum of an account holding `space` bytes of data, in lamports.
+pub const fn rent_exempt_lamports(space: u64) -> u64 {
+    // 128 bytes of account metadata, at 3480 lamports per byte-year for two years
+    (128 + space) * 3480 * 2
+}
+
+/// The token accounts a swap needs that the wallet doesn't have yet.
+///
+/// Jupiter adds instructions creating them unless `skip_user_account_rpc_calls` is set, in
+/// which case the swap fails on the first missing one. Accounts are known from the wallet's
+/// holdings: a source that omits empty token accounts makes them look missing, which only
+/// errs on the side of letting Jupiter check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSetup {
+    /// Whether intermediate hops settle through Jupiter's shared program accounts, so the
+    /// wallet needs no token accounts for intermediate mints. Jupiter decides per route when
+    /// the request leaves `use_shared_accounts` unset, so only an explicit `true` counts.
+    pub shared_accounts: bool,
+
+    /// Mints whose token account the swap needs but the wallet doesn't have, in route order.
+    pub missing_accounts: Vec<String>,
+
+    /// The missing accounts that stay open after the swap and lock rent. Wrapped SOL accounts
+    /// are closed in the same transaction and cost nothing.
+    pub rented_accounts: Vec<String>,
+
+    /// Rent locked in `rented_accounts`, in lamports. Assumes 165-byte accounts until
+    /// [`with_account_sizes`](Self::with_account_sizes) supplies the sizes of Token-2022
+    /// accounts, whose extensions make them larger.
+    pub rent_lamports: u64,
+}
+
+impl AccountSetup {
+    /// Works out the accounts `request` needs from the token accounts in `holdings`.
+    ///
+    /// # Example
+    /// ```
+    /// let holdings = api.get_wallet_holdings(&wallet, &HoldingsSource::Rpc(rpc_url)).await?;
+    /// let setup = AccountSetup::new(&request, &holdings);
+    /// let request = request.skip_user_account_rpc_calls(setup.can_skip_account_checks());
+    /// ```
+    pub fn new(request: &SwapRequest, holdings: &WalletHoldings) -> Self {
+        let quote = &request.quote_response;
+        let wraps_sol = request.wrap_and_unwrap_sol != Some(false);
+        let shared_accounts = request.use_shared_accounts == Some(true);
+
+        let mut needed: Vec<&str> = Vec::new();
+        if quote.input_mint == WSOL_MINT && wraps_sol {
+            needed.push(WSOL_MINT);
+        }
+        if !shared_accounts {
+            for step in &quote.route_plan {
+                let mint = step.swap_info.output_mint.as_str();
+                if mint != quote.output_mint && !needed.contains(&mint) {
+                    needed.push(mint);
+                }
+            }
+        }
+        if request.destination_token_account.is_none() && !needed.contains(&&*quote.output_mint) {
+            needed.push(&quote.output_mint);
+        }
+
+        let missing_accounts: Vec<String> = needed
+            .into_iter()
+            .filter(|mint| !holdings.has_token_account(mint))
+            .map(str::to_string)
+            .collect();
+        let rented_accounts: Vec<String> = missing_accounts
+            .iter()
+            .filter(|mint| !(wraps_sol && *mint == WSOL_MINT))
+            .cloned()
+            .collect();
+
+        Self {
+            shared_accounts,
+            missing_accounts,
+            rent_lamports: rented_accounts.len() as u64 * TOKEN_ACCOUNT_RENT_LAMPORTS,
+            rented_accounts,
+        }
+    }
+
+    /// Prices the rent of each created account by its size in bytes, keyed by mint, as
+    /// returned by [`token_account_sizes`](crate::ata::token_account_sizes). Mints without an
+    /// entry keep the 165-byte size.
+    pub fn with_account_sizes(mut self, sizes: &HashMap<String, u64>) -> Self {
+        self.rent_lamports = self
+            .rented_accounts
+            .iter()
+            .map(|mint| rent_exempt_lamports(*sizes.get(mint).unwrap_or(&TOKEN_ACCOUNT_SIZE)))
+            .sum();
+        self
+    }
+
+    /// Whether Jupiter will add instructions creating accounts for the wallet.
+    pub fn creates_accounts(&self) -> bool {
+        !self.missing_accounts.is_empty()
+    }
+
+    /// Whether `skip_user_account_rpc_calls` is safe: every account the swap touches exists.
+    pub fn can_skip_account_checks(&self) -> bool {
+        !self.creates_accounts()
+    }
+}
@@ -0,0 +1,74 @@
+//! Deserializers for amount fields that Jupiter usually sends as strings, but that some
+//! endpoints and versions emit as bare JSON numbers.
+//!
+//! Integers up to `u64::MAX` (and down to `i64::MIN`) are kept exactly. Larger integers and
+//! decimals go through `f64` unless the `arbitrary-precision` feature is enabled, in which
+//! case the number's original digits are kept verbatim.
+
+use std::fmt;
+
+use serde::{
+    Deserialize, Deserializer,
+    de::{self, MapAccess, Visitor, value::MapAccessDeserializer},
+};
+
+struct AmountVisitor;
+
+impl<'de> Visitor<'de> for AmountVisitor {
+    type Value = String;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an amount as a string or a number")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<String, E> {
+        Ok(v.to_string())
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<String, E> {
+        Ok(v)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<String, E> {
+        Ok(v.to_string())
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<String, E> {
+        Ok(v.to_string())
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<String, E> {
+        Ok(v.to_string())
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<String, E> {
+        Ok(v.to_string())
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<String, E> {
+        Ok(v.to_string())
+    }
+
+    // with serde_json's `arbitrary_precision`, numbers arrive as a single-entry map holding
+    // their original digits, which `Number` knows how to read back
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<String, A::Error> {
+        serde_json::Number::deserialize(MapAccessDeserializer::new(map)).map(|n| n.to_string())
+    }
+}
+
+/// Reads an amount sent either as `"123"` or `123`.
+pub(crate) fn string_or_number<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    deserializer.deserialize_any(AmountVisitor)
+}
+
+/// Like [`string_or_number`], for optional amounts that may also be `null`.
+pub(crate) fn option_string_or_number<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    struct Amount(#[serde(deserialize_with = "string_or_number")] String);
+
+    Ok(Option::<Amount>::deserialize(deserializer)?.map(|Amount(amount)| amount))
+}
@@ -0,0 +1,165 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use super::QuoteRequest;
+use crate::predicate::ExecutionPredicate;
+
+/// One of the independent swaps executed by
+/// [`JupiterClient::execute_batch`](crate::JupiterClient::execute_batch).
+#[derive(Clone)]
+pub struct BatchSwap {
+    /// Your identifier for the swap, echoed in its [`BatchSwapResult`].
+    pub id: String,
+
+    /// The wallet that signs the swap and pays its fees.
+    pub wallet: String,
+
+    pub request: QuoteRequest,
+
+    /// Higher priorities run first. Default: 0.
+    pub priority: u32,
+
+    /// How fast the swap loses value while it waits, in bps per second, e.g. for arbitrage
+    /// or liquidation opportunities. Breaks ties between equal priorities: faster-decaying
+    /// swaps run first. Default: 0.
+    pub decay_bps_per_sec: f64,
+
+    /// Last-look check run after the swap is built and before it's sent, see
+    /// [`predicate`](crate::predicate).
+    pub predicate: Option<Arc<dyn ExecutionPredicate>>,
+}
+
+impl BatchSwap {
+    pub fn new(id: impl Into<String>, wallet: impl Into<String>, request: QuoteRequest) -> Self {
+        Self {
+            id: id.into(),
+            wallet: wallet.into(),
+            request,
+            priority: 0,
+            decay_bps_per_sec: 0.0,
+            predicate: None,
+        }
+    }
+
+    pub fn priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn decay_bps_per_sec(mut self, decay_bps_per_sec: f64) -> Self {
+        self.decay_bps_per_sec = decay_bps_per_sec;
+        self
+    }
+
+    /// Sends the swap only if `predicate` still allows it once built, e.g.
+    /// [`QuoteDrift`](crate::predicate::QuoteDrift) to abort when the market moved away.
+    pub fn predicate<P: ExecutionPredicate + 'static>(mut self, predicate: P) -> Self {
+        self.predicate = Some(Arc::new(predicate));
+        self
+    }
+}
+
+impl fmt::Debug for BatchSwap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchSwap")
+            .field("id", &self.id)
+            .field("wallet", &self.wallet)
+            .field("request", &self.request)
+            .field("priority", &self.priority)
+            .field("decay_bps_per_sec", &self.decay_bps_per_sec)
+            .field("predicate", &self.predicate.is_some())
+            .finish()
+    }
+}
+
+/// Settings for [`JupiterClient::execute_batch`](crate::JupiterClient::execute_batch).
+#[derive(Debug, Clone)]
+pub struct BatchExecutionConfig {
+    /// Wall-clock budget for the whole batch. Swaps that haven't started when it runs out
+    /// are skipped; swaps already in progress are finished.
+    pub budget: Duration,
+
+    /// At most this many swaps are in progress at once. Default: 4.
+    pub max_concurrency: usize,
+}
+
+impl BatchExecutionConfig {
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            max_concurrency: 4,
+        }
+    }
+
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+}
+
+/// What happened to a [`BatchSwap`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum BatchSwapOutcome {
+    /// The swap was built and handed to your send callback.
+    #[serde(rename_all = "camelCase")]
+    Executed {
+        signature: String,
+
+        /// Raw output amount quoted for the swap.
+        expected_out: u64,
+    },
+
+    /// Quoting, building or sending the swap failed.
+    Failed { reason: String },
+
+    /// The swap's [`predicate`](BatchSwap::predicate) denied it once built. Nothing was sent.
+    Aborted { reason: String },
+
+    /// The budget ran out before the swap started.
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSwapResult {
+    pub id: String,
+    pub outcome: BatchSwapOutcome,
+
+    /// When the swap started, relative to the start of the batch. `None` when skipped.
+    pub started_after: Option<Duration>,
+}
+
+/// The outcome of [`JupiterClient::execute_batch`](crate::JupiterClient::execute_batch), in
+/// execution order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchExecutionReport {
+    pub results: Vec<BatchSwapResult>,
+    pub elapsed: Duration,
+}
+
+impl BatchExecutionReport {
+    pub fn executed(&self) -> impl Iterator<Item = &BatchSwapResult> {
+        self.results
+            .iter()
+            .filter(|result| matches!(result.outcome, BatchSwapOutcome::Executed { .. }))
+    }
+
+    pub fn skipped(&self) -> impl Iterator<Item = &BatchSwapResult> {
+        self.results
+            .iter()
+            .filter(|result| result.outcome == BatchSwapOutcome::Skipped)
+    }
+}
+
+/// Sorts `swaps` into execution order: highest priority first, then fastest decay, keeping
+/// the given order otherwise.
+pub(crate) fn execution_order(swaps: &mut [BatchSwap]) {
+    swaps.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then(b.decay_bps_per_sec.total_cmp(&a.decay_bps_per_sec))
+    });
+}
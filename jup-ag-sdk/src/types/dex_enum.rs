@@ -1,6 +1,8 @@
-use serde::{Deserialize, Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// A routing venue, serialized as its Jupiter label, e.g. `"Meteora DLMM"`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
 pub enum DexEnum {
     Woofi,
     PumpFun,
@@ -51,6 +53,8 @@ pub enum DexEnum {
     OpenBook,
     Invariant,
     Guacswap,
+    /// Any venue label not modelled above, as returned by `/program-id-to-label`.
+    Other(String),
 }
 
 impl std::fmt::Display for DexEnum {
@@ -105,11 +109,113 @@ impl std::fmt::Display for DexEnum {
             DexEnum::OpenBook => "Openbook",
             DexEnum::Invariant => "Invariant",
             DexEnum::Guacswap => "Guacswap",
+            DexEnum::Other(label) => label.as_str(),
         };
         write!(f, "{label}")
     }
 }
 
+impl Serialize for DexEnum {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl From<String> for DexEnum {
+    fn from(label: String) -> Self {
+        match DexEnum::from(label.as_str()) {
+            DexEnum::Other(_) => DexEnum::Other(label),
+            dex => dex,
+        }
+    }
+}
+
+impl From<&str> for DexEnum {
+    /// Maps a Jupiter venue label (e.g. `"Meteora DLMM"`) to a `DexEnum`.
+    ///
+    /// Variant names (e.g. `"MeteoraDlmm"`), which earlier versions serialized instead of
+    /// labels, map to their variant too. Labels that are not modelled by a dedicated variant
+    /// become `DexEnum::Other`.
+    fn from(label: &str) -> Self {
+        match label {
+            "Woofi" => DexEnum::Woofi,
+            "Pump.fun" => DexEnum::PumpFun,
+            "Whirlpool" => DexEnum::Whirlpool,
+            "Virtuals" => DexEnum::Virtuals,
+            "Daos.fun" => DexEnum::DaosFun,
+            "Lifinity V2" => DexEnum::LifinityV2,
+            "Stabble Stable Swap" => DexEnum::StabbleStableSwap,
+            "Token Mill" => DexEnum::TokenMill,
+            "Meteora" => DexEnum::Meteora,
+            "Oasis" => DexEnum::Oasis,
+            "Aldrin" => DexEnum::Aldrin,
+            "GooseFX GAMMA" => DexEnum::GooseFxGamma,
+            "Perps" => DexEnum::Perps,
+            "SolFi" => DexEnum::SolFi,
+            "DexLab" => DexEnum::DexLab,
+            "Token Swap" => DexEnum::TokenSwap,
+            "ZeroFi" => DexEnum::ZeroFi,
+            "Cropper" => DexEnum::Cropper,
+            "Obric V2" => DexEnum::ObricV2,
+            "Stabble Weighted Swap" => DexEnum::StabbleWeightedSwap,
+            "Sanctum Infinity" => DexEnum::SanctumInfinity,
+            "Moonit" => DexEnum::Moonit,
+            "Sanctum" => DexEnum::Sanctum,
+            "Raydium CP" => DexEnum::RaydiumCp,
+            "Phoenix" => DexEnum::Phoenix,
+            "Pump.fun Amm" => DexEnum::PumpFunAmm,
+            "Saber" => DexEnum::Saber,
+            "Saber (Decimals)" => DexEnum::SaberDecimals,
+            "Raydium CLMM" => DexEnum::RaydiumClmm,
+            "1DEX" => DexEnum::Dex1,
+            "Penguin" => DexEnum::Penguin,
+            "Orca V2" => DexEnum::OrcaV2,
+            "FluxBeam" => DexEnum::FluxBeam,
+            "Raydium" => DexEnum::Raydium,
+            "Meteora DLMM" => DexEnum::MeteoraDlmm,
+            "Bonkswap" => DexEnum::Bonkswap,
+            "Solayer" => DexEnum::Solayer,
+            "StepN" => DexEnum::Stepn,
+            "Helium Network" => DexEnum::HeliumNetwork,
+            "Mercurial" => DexEnum::Mercurial,
+            "Perena" => DexEnum::Perena,
+            "Orca V1" => DexEnum::OrcaV1,
+            "Aldrin V2" => DexEnum::AldrinV2,
+            "Saros" => DexEnum::Saros,
+            "OpenBook V2" => DexEnum::OpenBookV2,
+            "Crema" => DexEnum::Crema,
+            "Openbook" => DexEnum::OpenBook,
+            "Invariant" => DexEnum::Invariant,
+            "Guacswap" => DexEnum::Guacswap,
+            // variant names that differ from their label
+            "PumpFun" => DexEnum::PumpFun,
+            "DaosFun" => DexEnum::DaosFun,
+            "LifinityV2" => DexEnum::LifinityV2,
+            "StabbleStableSwap" => DexEnum::StabbleStableSwap,
+            "TokenMill" => DexEnum::TokenMill,
+            "GooseFxGamma" => DexEnum::GooseFxGamma,
+            "TokenSwap" => DexEnum::TokenSwap,
+            "ObricV2" => DexEnum::ObricV2,
+            "StabbleWeightedSwap" => DexEnum::StabbleWeightedSwap,
+            "SanctumInfinity" => DexEnum::SanctumInfinity,
+            "RaydiumCp" => DexEnum::RaydiumCp,
+            "PumpFunAmm" => DexEnum::PumpFunAmm,
+            "SaberDecimals" => DexEnum::SaberDecimals,
+            "RaydiumClmm" => DexEnum::RaydiumClmm,
+            "Dex1" => DexEnum::Dex1,
+            "OrcaV2" => DexEnum::OrcaV2,
+            "MeteoraDlmm" => DexEnum::MeteoraDlmm,
+            "Stepn" => DexEnum::Stepn,
+            "HeliumNetwork" => DexEnum::HeliumNetwork,
+            "OrcaV1" => DexEnum::OrcaV1,
+            "AldrinV2" => DexEnum::AldrinV2,
+            "OpenBookV2" => DexEnum::OpenBookV2,
+            "OpenBook" => DexEnum::OpenBook,
+            other => DexEnum::Other(other.to_string()),
+        }
+    }
+}
+
 pub fn dex_vec_to_comma_string<S>(
     vec: &Option<Vec<DexEnum>>,
     serializer: S,
@@ -129,3 +235,33 @@ where
         None => serializer.serialize_none(),
     }
 }
+
+/// Deserializes an optional DEX list written either as an array of labels, as in JSON
+/// bodies and config files, or as the comma-separated string used in query strings.
+///
+/// Pairs with the default serialization, which writes an array, and with
+/// [`dex_vec_to_comma_string`] for query strings.
+pub fn deserialize_dex_list<'de, D>(deserializer: D) -> Result<Option<Vec<DexEnum>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum DexList {
+        Labels(Vec<DexEnum>),
+        Joined(String),
+    }
+
+    Ok(match Option::<DexList>::deserialize(deserializer)? {
+        Some(DexList::Labels(dexes)) => Some(dexes),
+        Some(DexList::Joined(joined)) => Some(
+            joined
+                .split(',')
+                .map(str::trim)
+                .filter(|label| !label.is_empty())
+                .map(DexEnum::from)
+                .collect(),
+        ),
+        None => None,
+    })
+}
@@ -0,0 +1,162 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::{HoldingsSource, NATIVE_SOL, Price, WalletHoldings};
+
+/// Settings for [`JupiterClient::sweep_dust`](crate::JupiterClient::sweep_dust).
+#[derive(Debug, Clone)]
+pub struct DustSweepConfig {
+    /// The mint every dust balance is converted into, e.g. SOL or USDC.
+    pub target_mint: String,
+
+    /// Balances worth less than this many USD are swept.
+    pub usd_threshold: f64,
+
+    /// Slippage tolerance for each swap. Falls back to the client defaults when unset.
+    pub slippage_bps: Option<u16>,
+
+    /// Mints that are never swept.
+    pub exclude_mints: HashSet<String>,
+
+    /// Where the wallet's balances are read from. Default: the Ultra balances endpoint.
+    pub holdings_source: HoldingsSource,
+}
+
+impl DustSweepConfig {
+    pub fn new(target_mint: impl Into<String>, usd_threshold: f64) -> Self {
+        Self {
+            target_mint: target_mint.into(),
+            usd_threshold,
+            slippage_bps: None,
+            exclude_mints: HashSet::new(),
+            holdings_source: HoldingsSource::default(),
+        }
+    }
+
+    pub fn slippage_bps(mut self, slippage_bps: u16) -> Self {
+        self.slippage_bps = Some(slippage_bps);
+        self
+    }
+
+    pub fn exclude_mint(mut self, mint: impl Into<String>) -> Self {
+        self.exclude_mints.insert(mint.into());
+        self
+    }
+
+    pub fn holdings_source(mut self, holdings_source: HoldingsSource) -> Self {
+        self.holdings_source = holdings_source;
+        self
+    }
+}
+
+/// A balance that was converted into the target mint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SweptToken {
+    pub mint: String,
+
+    /// Raw amount swapped, i.e. the whole balance.
+    pub amount: u64,
+
+    pub usd_value: f64,
+
+    /// Raw amount of the target mint quoted for the swap.
+    pub expected_out: u64,
+
+    /// Signature returned by your send callback.
+    pub signature: String,
+}
+
+/// A balance under the threshold that could not be swept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedToken {
+    pub mint: String,
+    pub reason: String,
+}
+
+/// The outcome of a dust sweep.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DustSweepReport {
+    pub swept: Vec<SweptToken>,
+    pub skipped: Vec<SkippedToken>,
+
+    /// Total USD value of the swept balances.
+    pub total_usd_swept: f64,
+
+    /// Total raw amount of the target mint quoted across all swaps.
+    pub total_expected_out: u64,
+}
+
+impl DustSweepReport {
+    /// Mints whose whole balance was sent to be swapped. Their token accounts are empty once
+    /// the swaps land; pass them to [`plan_account_closure`](crate::ata::plan_account_closure)
+    /// to close them.
+    pub fn swept_mints(&self) -> Vec<&str> {
+        self.swept.iter().map(|token| token.mint.as_str()).collect()
+    }
+}
+
+/// A balance selected for sweeping.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DustCandidate {
+    pub mint: String,
+    pub amount: u64,
+    pub usd_value: f64,
+}
+
+/// Picks the balances worth less than the threshold, sorted by mint.
+///
+/// Native SOL, the target mint, excluded mints, frozen accounts and empty balances are ignored.
+/// Balances without a USD price are reported as skipped rather than guessed at.
+///
+/// # Arguments
+/// * `holdings` - The wallet balances, as returned by `get_wallet_holdings`.
+/// * `prices` - USD prices keyed by mint, as returned by `get_tokens_price`.
+pub fn select_dust(
+    holdings: &WalletHoldings,
+    prices: &HashMap<String, Price>,
+    config: &DustSweepConfig,
+) -> (Vec<DustCandidate>, Vec<SkippedToken>) {
+    let mut candidates = Vec::new();
+    let mut skipped = Vec::new();
+
+    for balance in &holdings.holdings {
+        let mint = &balance.mint;
+        let amount = balance.amount;
+        if mint == NATIVE_SOL
+            || *mint == config.target_mint
+            || config.exclude_mints.contains(mint)
+            || amount == 0
+        {
+            continue;
+        }
+
+        let skip = |reason: &str| SkippedToken {
+            mint: mint.clone(),
+            reason: reason.to_string(),
+        };
+
+        if balance.is_frozen {
+            skipped.push(skip("token account is frozen"));
+            continue;
+        }
+        let Some(price) = prices.get(mint) else {
+            skipped.push(skip("no USD price available"));
+            continue;
+        };
+
+        let usd_value = balance.ui_amount * price.usd_price;
+        if usd_value < config.usd_threshold {
+            candidates.push(DustCandidate {
+                mint: mint.clone(),
+                amount,
+                usd_value,
+            });
+        }
+    }
+
+    (candidates, skipped)
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{QuoteResponse, TokenMeta, format_ui_amount};
+use crate::JupiterClientError;
+
+/// A raw amount paired with the metadata of its token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAmount {
+    pub token: TokenMeta,
+
+    /// Raw amount.
+    pub amount: u64,
+
+    /// The amount in decimal UI units, without trailing zeros.
+    pub ui_amount: String,
+}
+
+impl TokenAmount {
+    pub fn new(token: TokenMeta, amount: u64) -> Self {
+        Self {
+            ui_amount: format_ui_amount(amount, token.decimals),
+            token,
+            amount,
+        }
+    }
+
+    /// The USD value of the amount, if the token has a price.
+    pub fn usd_value(&self) -> Option<f64> {
+        self.token.usd_value(self.amount)
+    }
+}
+
+/// A single hop of an [`EnrichedQuote`] route.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnrichedHop {
+    pub label: String,
+    pub amm_key: String,
+
+    /// Share of the routed amount going through this hop.
+    pub percent: i32,
+
+    pub input: TokenAmount,
+    pub output: TokenAmount,
+
+    /// The venue fee, when the hop charges one.
+    pub fee: Option<TokenAmount>,
+}
+
+/// A quote with token metadata resolved for its input, output and every hop, ready for
+/// route displays.
+///
+/// Built by [`JupiterClient::enrich`](crate::JupiterClient::enrich), or with
+/// [`EnrichedQuote::new`] from metadata you already have.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnrichedQuote {
+    pub quote: QuoteResponse,
+    pub input: TokenAmount,
+    pub output: TokenAmount,
+    pub hops: Vec<EnrichedHop>,
+}
+
+impl EnrichedQuote {
+    /// Resolves every mint of `quote` against `tokens`, keyed by mint.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::MissingTokenMetadata`] when a mint has no entry in `tokens`.
+    pub fn new(
+        quote: &QuoteResponse,
+        tokens: &HashMap<String, TokenMeta>,
+    ) -> Result<Self, JupiterClientError> {
+        let amount = |mint: &str, raw: &str| {
+            let token = tokens
+                .get(mint)
+                .ok_or_else(|| JupiterClientError::MissingTokenMetadata(mint.to_string()))?;
+            Ok::<_, JupiterClientError>(TokenAmount::new(
+                token.clone(),
+                raw.parse().unwrap_or_default(),
+            ))
+        };
+
+        let hops = quote
+            .route_plan
+            .iter()
+            .map(|step| {
+                let info = &step.swap_info;
+                let fee = match info.fee_amount.parse::<u64>().unwrap_or_default() {
+                    0 => None,
+                    _ => Some(amount(&info.fee_mint, &info.fee_amount)?),
+                };
+                Ok(EnrichedHop {
+                    label: info.label.clone(),
+                    amm_key: info.amm_key.clone(),
+                    percent: step.percent,
+                    input: amount(&info.input_mint, &info.in_amount)?,
+                    output: amount(&info.output_mint, &info.out_amount)?,
+                    fee,
+                })
+            })
+            .collect::<Result<_, JupiterClientError>>()?;
+
+        Ok(Self {
+            quote: quote.clone(),
+            input: amount(&quote.input_mint, &quote.in_amount)?,
+            output: amount(&quote.output_mint, &quote.out_amount)?,
+            hops,
+        })
+    }
+
+    /// The mints a quote needs metadata for: input, output, intermediate tokens and fee mints.
+    pub fn mints(quote: &QuoteResponse) -> Vec<String> {
+        let mut mints = vec![quote.input_mint.clone(), quote.output_mint.clone()];
+        for step in &quote.route_plan {
+            let info = &step.swap_info;
+            let fee_mint =
+                (info.fee_amount.parse::<u64>().unwrap_or_default() > 0).then_some(&info.fee_mint);
+            for mint in [Some(&info.input_mint), Some(&info.output_mint), fee_mint]
+                .into_iter()
+                .flatten()
+            {
+                if !mints.contains(mint) {
+                    mints.push(mint.clone());
+                }
+            }
+        }
+        mints
+    }
+}
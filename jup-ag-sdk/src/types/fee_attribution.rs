@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Price, QuoteResponse};
+
+/// One fee of a route, valued in USD and in the output mint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributedFee {
+    /// The venue charging it, or `"Platform fee"` for the integrator fee.
+    pub label: String,
+    pub mint: String,
+
+    /// Raw amount of `mint`.
+    pub amount: u64,
+
+    pub usd_value: Option<f64>,
+
+    /// The fee converted into raw units of the quote's output mint. Exact when the fee is
+    /// charged in the output mint, otherwise converted through USD prices.
+    pub output_amount: Option<u64>,
+}
+
+/// Every fee of a quote normalized into one figure, so routes with different hops and fee
+/// mints can be compared on cost.
+///
+/// Built by [`JupiterClient::attribute_fees`](crate::JupiterClient::attribute_fees), or with
+/// [`FeeAttribution::new`] from prices you already have.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeAttribution {
+    pub output_mint: String,
+
+    /// Non-zero fees of every hop, then the platform fee.
+    pub fees: Vec<AttributedFee>,
+
+    /// Total USD value of the priced fees.
+    pub total_usd: f64,
+
+    /// Total of the fees that could be converted, in raw units of the output mint.
+    pub total_output_amount: u64,
+
+    /// [`total_output_amount`](Self::total_output_amount) relative to the quoted output, in
+    /// bps.
+    pub total_bps: f64,
+
+    /// Whether every fee could be priced and converted. When not, the totals understate the
+    /// cost of the route.
+    pub complete: bool,
+}
+
+impl FeeAttribution {
+    /// Values the fees of `quote` with `prices`, keyed by mint, as returned by
+    /// [`JupiterClient::get_prices`](crate::JupiterClient::get_prices).
+    ///
+    /// Fees in mints missing from `prices` are listed without a value and leave the
+    /// attribution incomplete.
+    pub fn new(quote: &QuoteResponse, prices: &HashMap<String, Price>) -> Self {
+        let output = prices.get(&quote.output_mint);
+        let attribute = |label: &str, mint: &str, amount: u64| {
+            let usd_value = prices
+                .get(mint)
+                .map(|price| amount as f64 / 10f64.powi(price.decimals as i32) * price.usd_price);
+            let output_amount = if mint == quote.output_mint {
+                Some(amount)
+            } else {
+                usd_value.zip(output).and_then(|(usd, output)| {
+                    (output.usd_price > 0.0).then(|| {
+                        (usd / output.usd_price * 10f64.powi(output.decimals as i32)).round() as u64
+                    })
+                })
+            };
+            AttributedFee {
+                label: label.to_string(),
+                mint: mint.to_string(),
+                amount,
+                usd_value,
+                output_amount,
+            }
+        };
+        let amount = |raw: &str| raw.parse::<u64>().unwrap_or_default();
+
+        let mut fees = Vec::new();
+        for step in &quote.route_plan {
+            let info = &step.swap_info;
+            let fee = amount(&info.fee_amount);
+            if fee > 0 {
+                fees.push(attribute(&info.label, &info.fee_mint, fee));
+            }
+        }
+        if let Some(platform_fee) = &quote.platform_fee {
+            let fee = amount(&platform_fee.amount);
+            let mint = platform_fee
+                .fee_mint
+                .as_deref()
+                .unwrap_or(&quote.output_mint);
+            if fee > 0 {
+                fees.push(attribute("Platform fee", mint, fee));
+            }
+        }
+
+        let total_output_amount = fees.iter().filter_map(|fee| fee.output_amount).sum();
+        let out_amount = amount(&quote.out_amount);
+        Self {
+            output_mint: quote.output_mint.clone(),
+            total_usd: fees.iter().filter_map(|fee| fee.usd_value).sum(),
+            total_output_amount,
+            total_bps: match out_amount {
+                0 => 0.0,
+                out => total_output_amount as f64 / out as f64 * 10_000.0,
+            },
+            complete: fees
+                .iter()
+                .all(|fee| fee.usd_value.is_some() && fee.output_amount.is_some()),
+            fees,
+        }
+    }
+
+    /// The mints whose prices are needed to attribute `quote`: the output mint and every
+    /// fee mint.
+    pub fn mints(quote: &QuoteResponse) -> Vec<String> {
+        let mut mints = vec![quote.output_mint.clone()];
+        let fee_mints = quote
+            .route_plan
+            .iter()
+            .filter(|step| step.swap_info.fee_amount.parse::<u64>().unwrap_or_default() > 0)
+            .map(|step| &step.swap_info.fee_mint)
+            .chain(quote.platform_fee.iter().flat_map(|fee| &fee.fee_mint));
+        for mint in fee_mints {
+            if !mints.contains(mint) {
+                mints.push(mint.clone());
+            }
+        }
+        mints
+    }
+}
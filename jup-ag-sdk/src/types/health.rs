@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+/// The result of [`JupiterClient::health`](crate::JupiterClient::health).
+#[derive(Debug, Clone)]
+pub struct HealthReport {
+    /// Whether Jupiter answered at all, with any status.
+    pub reachable: bool,
+
+    /// The status of the probe, when Jupiter answered.
+    pub status: Option<StatusCode>,
+
+    /// Time until the response headers arrived or the probe failed.
+    pub latency: Duration,
+
+    /// Why the probe failed, when it did.
+    pub error: Option<String>,
+}
+
+impl HealthReport {
+    /// Whether Jupiter answered the probe successfully, e.g. for a readiness probe.
+    pub fn is_healthy(&self) -> bool {
+        self.status.is_some_and(|status| status.is_success())
+    }
+}
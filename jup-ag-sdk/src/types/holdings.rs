@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+use super::TokenBalancesResponse;
+
+/// The key native SOL is reported under, as in the Ultra balances response.
+pub const NATIVE_SOL: &str = "SOL";
+
+/// Wrapped SOL mint, funded from the wallet's native SOL balance.
+pub(crate) const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// Where [`JupiterClient::get_wallet_holdings`](crate::JupiterClient::get_wallet_holdings)
+/// reads balances from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum HoldingsSource {
+    /// Jupiter's Ultra balances endpoint. Doesn't report decimals.
+    #[default]
+    Ultra,
+
+    /// A Solana RPC, scanning the wallet's Token and Token-2022 accounts.
+    Rpc(String),
+}
+
+/// A wallet's balance of one mint, summed over all of its token accounts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Holding {
+    /// The mint, or [`NATIVE_SOL`] for the native SOL balance.
+    pub mint: String,
+
+    /// Raw amount.
+    pub amount: u64,
+
+    pub ui_amount: f64,
+
+    /// Decimals of the mint, when the source reports them.
+    pub decimals: Option<u8>,
+
+    /// Whether a token account holding the balance is frozen.
+    pub is_frozen: bool,
+}
+
+/// Every balance of a wallet, sorted by mint.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletHoldings {
+    pub wallet: String,
+    pub holdings: Vec<Holding>,
+}
+
+impl WalletHoldings {
+    /// Builds holdings from an Ultra balances response.
+    pub fn from_balances(wallet: impl Into<String>, balances: TokenBalancesResponse) -> Self {
+        Self::from_holdings(
+            wallet,
+            balances.into_iter().map(|(mint, balance)| Holding {
+                mint,
+                amount: balance.amount.parse().unwrap_or_default(),
+                ui_amount: balance.ui_amount,
+                decimals: None,
+                is_frozen: balance.is_frozen,
+            }),
+        )
+    }
+
+    /// Builds holdings from individual balances, merging those of the same mint.
+    pub fn from_holdings(
+        wallet: impl Into<String>,
+        holdings: impl IntoIterator<Item = Holding>,
+    ) -> Self {
+        let mut merged: Vec<Holding> = Vec::new();
+        for holding in holdings {
+            match merged.iter_mut().find(|h| h.mint == holding.mint) {
+                Some(existing) => {
+                    existing.amount = existing.amount.saturating_add(holding.amount);
+                    existing.ui_amount += holding.ui_amount;
+                    existing.decimals = existing.decimals.or(holding.decimals);
+                    existing.is_frozen |= holding.is_frozen;
+                }
+                None => merged.push(holding),
+            }
+        }
+        merged.sort_by(|a, b| a.mint.cmp(&b.mint));
+
+        Self {
+            wallet: wallet.into(),
+            holdings: merged,
+        }
+    }
+
+    /// The balance of `mint`. Wrapped SOL resolves to the native SOL balance that funds it.
+    pub fn get(&self, mint: &str) -> Option<&Holding> {
+        let mint = if mint == WSOL_MINT { NATIVE_SOL } else { mint };
+        self.holdings.iter().find(|h| h.mint == mint)
+    }
+
+    /// Raw amount of `mint` held, zero when there is none.
+    pub fn amount(&self, mint: &str) -> u64 {
+        self.get(mint).map(|h| h.amount).unwrap_or_default()
+    }
+
+    /// Whether the wallet has a token account for `mint`, even an empty one. Unlike
+    /// [`get`](Self::get), wrapped SOL is only found in a wrapped SOL account.
+    pub fn has_token_account(&self, mint: &str) -> bool {
+        mint != NATIVE_SOL && self.holdings.iter().any(|h| h.mint == mint)
+    }
+
+    /// Every mint with a non-zero balance, native SOL excluded.
+    pub fn token_mints(&self) -> impl Iterator<Item = &str> {
+        self.holdings
+            .iter()
+            .filter(|h| h.mint != NATIVE_SOL && h.amount > 0)
+            .map(|h| h.mint.as_str())
+    }
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+use super::QuoteResponse;
+use crate::JupiterClientError;
+
+/// A mid price and spread derived from quoting a pair in both directions.
+///
+/// Prices are quoted as units of the quote mint per unit of the base mint, in raw (atomic)
+/// amounts; use [`ui_mid`](Self::ui_mid) for decimal-adjusted prices.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MidPrice {
+    /// Price received when selling the base mint.
+    pub bid: f64,
+
+    /// Price paid when buying the base mint.
+    pub ask: f64,
+
+    pub mid: f64,
+
+    /// `(ask - bid) / mid`, in basis points.
+    pub spread_bps: f64,
+}
+
+impl MidPrice {
+    /// Derives the price from a `base -> quote` quote and a `quote -> base` quote.
+    pub fn from_quotes(
+        sell_base: &QuoteResponse,
+        buy_base: &QuoteResponse,
+    ) -> Result<Self, JupiterClientError> {
+        let amount = |value: &str| {
+            value
+                .parse::<f64>()
+                .ok()
+                .filter(|v| *v > 0.0)
+                .ok_or_else(|| {
+                    JupiterClientError::deserialization(format!(
+                        "quote amount must be positive, got {value:?}"
+                    ))
+                })
+        };
+
+        let bid = amount(&sell_base.out_amount)? / amount(&sell_base.in_amount)?;
+        let ask = amount(&buy_base.in_amount)? / amount(&buy_base.out_amount)?;
+        let mid = (bid + ask) / 2.0;
+
+        Ok(Self {
+            bid,
+            ask,
+            mid,
+            spread_bps: (ask - bid) / mid * 10_000.0,
+        })
+    }
+
+    /// The mid price in decimal units of the quote mint per unit of the base mint.
+    pub fn ui_mid(&self, base_decimals: u8, quote_decimals: u8) -> f64 {
+        self.mid * 10f64.powi(base_decimals as i32 - quote_decimals as i32)
+    }
+}
@@ -1,23 +1,100 @@
+mod amount;
+
 pub mod quote_request;
 pub use quote_request::*;
 
+pub mod account_setup;
+pub use account_setup::*;
+
+pub mod batch_execution;
+pub use batch_execution::*;
+
 pub mod dex_enum;
 pub use dex_enum::*;
 
+pub mod dust_sweep;
+pub use dust_sweep::*;
+
+pub mod enriched_quote;
+pub use enriched_quote::*;
+
+pub mod health;
+pub use health::*;
+
+pub mod fee_attribution;
+pub use fee_attribution::*;
+
+pub mod holdings;
+pub use holdings::*;
+
+pub mod mid_price;
+pub use mid_price::*;
+
 pub mod quote_response;
 pub use quote_response::*;
 
+pub mod quote_template;
+pub use quote_template::*;
+
+pub mod swap_preview;
+pub use swap_preview::*;
+
 pub mod swap_transaction;
 pub use swap_transaction::*;
 
+pub mod request_defaults;
+pub use request_defaults::*;
+
+pub mod route_stability;
+pub use route_stability::*;
+
+pub mod route_instruction;
+pub use route_instruction::*;
+
+pub mod sign_request;
+pub use sign_request::*;
+
+pub mod size_suggestion;
+pub use size_suggestion::*;
+
+pub mod slippage;
+pub use slippage::*;
+
+pub mod slippage_comparison;
+pub use slippage_comparison::*;
+
+pub mod simulation;
+pub use simulation::*;
+
+pub mod withdrawal;
+pub use withdrawal::*;
+
 pub mod ultra;
 pub use ultra::*;
 
 pub mod token;
 pub use token::*;
 
+pub mod token_meta;
+pub use token_meta::*;
+
+pub mod token_vetting;
+pub use token_vetting::*;
+
+// The trigger and recurring APIs both have an `Order` and a `Trade`. They are re-exported with
+// a prefix and stay reachable as `trigger::Order` and `recurring::Order`.
 pub mod trigger;
-pub use trigger::*;
+pub use trigger::{
+    CancelTriggerOrder, CancelTriggerOrders, CreateTriggerOrder, ExecuteTriggerOrder,
+    ExecuteTriggerOrderResponse, GetTriggerOrders, Order as TriggerOrder, OrderResponse,
+    OrderStatus, Params, Trade as TriggerTrade, TriggerResponse,
+};
 
 pub mod recurring;
-pub use recurring::*;
+pub use recurring::{
+    CancelRecurringOrderRequest, CreateRecurringOrderRequest, ExecuteRecurringRequest,
+    ExecuteRecurringResponse, GetRecurringOrders, InputOrOutput, Order as RecurringOrder,
+    OrderParams, PriceDeposit, PriceOrder, PriceParams, PriceWithdraw, ProductMeta,
+    RecurringOrderType, RecurringOrders, RecurringResponse, TimeOrder, TimeParams,
+    Trade as RecurringTrade,
+};
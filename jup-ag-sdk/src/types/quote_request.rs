@@ -1,11 +1,17 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize, Serializer};
 
-use super::{DexEnum, dex_vec_to_comma_string};
+use super::{DexEnum, Slippage, deserialize_dex_list, dex_vec_to_comma_string};
+use crate::JupiterClientError;
 
 /// A request struct for fetching a quote from Jupiter's `/quote` endpoint.
 ///
 /// Use `QuoteRequest::new()` and the fluent setters to configure parameters.
 ///
+/// Serializes DEX lists as arrays of labels, so requests round-trip through JSON bodies and
+/// config files. Use [`query`](Self::query) for the query string of the `/quote` endpoint.
+///
 /// [Official API docs](https://docs.jup.ag/apis/quote)
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -43,19 +49,20 @@ pub struct QuoteRequest {
 
     /// A list of DEXes to exclusively include in routing.
     ///
-    /// Example: `["Orca", "Meteora+DLMM"]`
-    #[serde(serialize_with = "dex_vec_to_comma_string")]
+    /// Example: `["Orca", "Meteora DLMM"]`
+    #[serde(default, deserialize_with = "deserialize_dex_list")]
     pub dexes: Option<Vec<DexEnum>>,
 
     /// A list of DEXes to exclude from routing.
     ///
     /// Example: `["Raydium", "Lifinity"]`
-    #[serde(serialize_with = "dex_vec_to_comma_string")]
+    #[serde(default, deserialize_with = "deserialize_dex_list")]
     pub exclude_dexes: Option<Vec<DexEnum>>,
 
     /// If true, restricts intermediate tokens to a stable set.
     ///
-    /// Reduces slippage risk. Default: `false`.
+    /// Reduces slippage risk. Left unset, the API default (or the client's
+    /// [`RequestDefaults`](super::RequestDefaults)) applies.
     pub restrict_intermediate_tokens: Option<bool>,
 
     /// If true, only direct (single-hop) routes are allowed.
@@ -84,6 +91,75 @@ pub struct QuoteRequest {
     pub dynamic_slippage: Option<bool>,
 }
 
+/// A [`QuoteRequest`] serialized as query parameters, see [`QuoteRequest::query`].
+///
+/// Borrows the request, so a [`QuoteTemplate`](super::QuoteTemplate) can be quoted for any
+/// amount without copying its mints.
+#[derive(Clone, Copy, Debug)]
+pub struct QuoteQuery<'a> {
+    request: &'a QuoteRequest,
+    amount: u64,
+}
+
+/// The fields of a [`QuoteQuery`], borrowed from its request.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryFields<'a> {
+    input_mint: &'a str,
+    output_mint: &'a str,
+    amount: u64,
+    slippage_bps: Option<u16>,
+    swap_mode: &'a Option<QuoteGetSwapModeEnum>,
+    #[serde(serialize_with = "dex_vec_to_comma_string")]
+    dexes: &'a Option<Vec<DexEnum>>,
+    #[serde(serialize_with = "dex_vec_to_comma_string")]
+    exclude_dexes: &'a Option<Vec<DexEnum>>,
+    restrict_intermediate_tokens: Option<bool>,
+    only_direct_routes: Option<bool>,
+    as_legacy_transaction: Option<bool>,
+    platform_fee_bps: Option<u64>,
+    max_accounts: Option<u8>,
+    dynamic_slippage: Option<bool>,
+}
+
+impl Serialize for QuoteQuery<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // no `..`: a field added to `QuoteRequest` doesn't compile until it's sent here too
+        let QuoteRequest {
+            input_mint,
+            output_mint,
+            amount: _,
+            slippage_bps,
+            swap_mode,
+            dexes,
+            exclude_dexes,
+            restrict_intermediate_tokens,
+            only_direct_routes,
+            as_legacy_transaction,
+            platform_fee_bps,
+            max_accounts,
+            dynamic_slippage,
+        } = self.request;
+
+        QueryFields {
+            input_mint,
+            output_mint,
+            amount: self.amount,
+            slippage_bps: *slippage_bps,
+            swap_mode,
+            dexes,
+            exclude_dexes,
+            restrict_intermediate_tokens: *restrict_intermediate_tokens,
+            only_direct_routes: *only_direct_routes,
+            as_legacy_transaction: *as_legacy_transaction,
+            platform_fee_bps: *platform_fee_bps,
+            max_accounts: *max_accounts,
+            dynamic_slippage: *dynamic_slippage,
+        }
+        .serialize(serializer)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub enum QuoteGetSwapModeEnum {
@@ -100,7 +176,10 @@ impl QuoteRequest {
     /// * `amount` - The amount to swap (raw, before decimals). Meaning depends on `swap_mode`.
     ///
     /// # Returns
-    /// A new `QuoteRequest` instance with None value for optional fields.
+    /// A new `QuoteRequest` instance with None value for optional fields, except
+    /// `restrict_intermediate_tokens`, which is `Some(false)`. Call
+    /// [`use_default_intermediate_tokens`](Self::use_default_intermediate_tokens) to leave it
+    /// to the client's [`RequestDefaults`](super::RequestDefaults).
     ///
     /// # Example
     /// ```
@@ -132,6 +211,31 @@ impl QuoteRequest {
         }
     }
 
+    /// The request as query parameters, with DEX lists comma-joined as the `/quote` endpoint
+    /// expects.
+    ///
+    /// # Example
+    /// ```
+    /// let request = QuoteRequest::new(SOL_MINT, JUP_MINT, 1_000_000_000)
+    ///     .dexes(vec![DexEnum::Whirlpool, DexEnum::MeteoraDlmm]);
+    /// let http = reqwest::Client::new()
+    ///     .get("https://lite-api.jup.ag/swap/v1/quote")
+    ///     .query(&request.query())
+    ///     .build()?;
+    /// assert!(http.url().as_str().contains("dexes=Whirlpool%2CMeteora+DLMM"));
+    /// ```
+    pub fn query(&self) -> QuoteQuery<'_> {
+        self.query_for(self.amount)
+    }
+
+    /// Like [`query`](Self::query), with `amount` in place of the request's amount.
+    pub(crate) fn query_for(&self, amount: u64) -> QuoteQuery<'_> {
+        QuoteQuery {
+            request: self,
+            amount,
+        }
+    }
+
     /// Sets the slippage tolerance in basis points (bps).
     ///
     /// Only used if `dynamic_slippage` is `false`. 100 bps = 1% slippage.
@@ -158,6 +262,18 @@ impl QuoteRequest {
         self
     }
 
+    /// Sets the slippage tolerance from a [`Slippage`], e.g. one parsed from `"0.5%"`.
+    ///
+    /// # Example
+    /// ```
+    /// let request = QuoteRequest::new(SOL_MINT, JUP_MINT, 1_000_000_000)
+    ///     .slippage("0.5%".parse()?);
+    /// assert_eq!(request.slippage_bps, Some(50));
+    /// ```
+    pub fn slippage(self, slippage: Slippage) -> Self {
+        self.slippage_bps(slippage.bps())
+    }
+
     /// Sets the swap mode (`ExactIn` or `ExactOut`).
     ///
     /// - `ExactIn`: Specifies input amount, computes output.
@@ -204,7 +320,10 @@ impl QuoteRequest {
     ///     1_000_000_000
     /// ).dexes(vec![DexEnum::MeteoraDlmm, DexEnum::Meteora]);
     /// ```
-    /// [list of dexes](https://lite-api.jup.ag/swap/v1/program-id-to-label)
+    /// [list of dexes](https://lite-api.jup.ag/swap/v1/program-id-to-label), also served by
+    /// [`JupiterClient::get_program_id_to_label`](crate::JupiterClient::get_program_id_to_label).
+    /// Check a list against it with
+    /// [`JupiterClient::validate_dexes`](crate::JupiterClient::validate_dexes).
     pub fn dexes(mut self, dexes: Vec<DexEnum>) -> Self {
         self.dexes = Some(dexes);
         self
@@ -236,9 +355,57 @@ impl QuoteRequest {
         self
     }
 
+    /// Excludes venues by their on-chain program id instead of their `DexEnum` label.
+    ///
+    /// Each program id is translated through the label registry returned by
+    /// [`JupiterClient::get_program_id_to_label`](crate::JupiterClient::get_program_id_to_label)
+    /// and appended to `exclude_dexes`, skipping labels that are already excluded.
+    ///
+    /// # Arguments
+    /// * `program_ids` - The program ids to block.
+    /// * `labels` - The program id to label registry.
+    ///
+    /// # Returns
+    /// The modified `QuoteRequest`, or [`JupiterClientError::UnknownProgramId`] if a program id
+    /// is missing from the registry.
+    ///
+    /// # Example
+    /// ```
+    /// let labels = client.get_program_id_to_label().await?;
+    /// let request = QuoteRequest::new(
+    ///     "So11111111111111111111111111111111111111112",
+    ///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+    ///     1_000_000_000
+    /// )
+    /// .exclude_program_ids(&["LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo"], &labels)?;
+    /// ```
+    pub fn exclude_program_ids<S: AsRef<str>>(
+        mut self,
+        program_ids: &[S],
+        labels: &HashMap<String, String>,
+    ) -> Result<Self, JupiterClientError> {
+        let mut excluded = self.exclude_dexes.take().unwrap_or_default();
+
+        for program_id in program_ids {
+            let program_id = program_id.as_ref();
+            let label = labels
+                .get(program_id)
+                .ok_or_else(|| JupiterClientError::UnknownProgramId(program_id.to_string()))?;
+
+            let dex = DexEnum::from(label.as_str());
+            if !excluded.contains(&dex) {
+                excluded.push(dex);
+            }
+        }
+
+        self.exclude_dexes = Some(excluded);
+        Ok(self)
+    }
+
     /// Sets whether to restrict intermediate tokens to a stable set.
     ///
-    /// Reduces slippage risk by limiting intermediate tokens. Default: `true`.
+    /// Reduces slippage risk by limiting intermediate tokens. [`QuoteRequest::new`] sets it to
+    /// `false`; the API default is `true`.
     ///
     /// # Arguments
     /// * `restrict_intermediate_tokens` - Whether to restrict intermediate tokens.
@@ -263,6 +430,24 @@ impl QuoteRequest {
         self
     }
 
+    /// Leaves `restrict_intermediate_tokens` unset, so the client's
+    /// [`RequestDefaults`](super::RequestDefaults) decides, or else the API default (`true`).
+    ///
+    /// # Example
+    /// ```
+    /// let client = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_defaults(RequestDefaults::new().restrict_intermediate_tokens(true));
+    /// let request = QuoteRequest::new(SOL_MINT, JUP_MINT, 1_000_000_000)
+    ///     .use_default_intermediate_tokens();
+    /// assert_eq!(request.restrict_intermediate_tokens, None);
+    /// // quoted with restrictIntermediateTokens=true
+    /// let quote = client.get_quote(&request).await?;
+    /// ```
+    pub fn use_default_intermediate_tokens(mut self) -> Self {
+        self.restrict_intermediate_tokens = None;
+        self
+    }
+
     /// Sets whether to allow only direct (single-hop) routes.
     ///
     /// May result in suboptimal pricing. Default: `false`.
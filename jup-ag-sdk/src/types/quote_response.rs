@@ -1,6 +1,9 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 use super::QuoteGetSwapModeEnum;
+use crate::JupiterClientError;
 
 /// A response returned by Jupiter’s `/quote` endpoint.
 ///
@@ -12,17 +15,20 @@ pub struct QuoteResponse {
     pub input_mint: String,
 
     /// The raw input token amount.
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub in_amount: String,
 
     /// The output token mint address.
     pub output_mint: String,
 
     /// The raw output token amount (excluding slippage or fees).
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub out_amount: String,
 
     /// The worst-case output amount after slippage & fees.
     ///
     /// Not used by `/swap`, but useful for displaying expectations.
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub other_amount_threshold: String,
 
     /// Indicates the swap mode used (ExactIn or ExactOut).
@@ -68,9 +74,112 @@ pub struct QuoteResponse {
     pub use_incurred_slippage_for_quoting: Option<serde_json::Value>,
 }
 
+impl QuoteResponse {
+    /// The mint whose amount is bounded by `other_amount_threshold`: the output mint for
+    /// ExactIn quotes, the input mint for ExactOut quotes.
+    pub fn worst_case_mint(&self) -> &str {
+        match self.swap_mode {
+            QuoteGetSwapModeEnum::ExactIn => &self.output_mint,
+            QuoteGetSwapModeEnum::ExactOut => &self.input_mint,
+        }
+    }
+
+    /// Renders the guaranteed minimum received (ExactIn) or maximum spent (ExactOut) in UI units.
+    ///
+    /// `decimals` and `symbol` describe the token returned by [`worst_case_mint`](Self::worst_case_mint).
+    ///
+    /// # Example
+    /// ```
+    /// let output = api.get_token_info(quote.worst_case_mint()).await?;
+    /// let fill = quote.worst_case_fill(output.decimals as u8, &output.symbol)?;
+    ///
+    /// println!("You will {fill}"); // "You will receive at least 151.2304 USDC"
+    /// ```
+    pub fn worst_case_fill(
+        &self,
+        decimals: u8,
+        symbol: impl Into<String>,
+    ) -> Result<WorstCaseFill, JupiterClientError> {
+        let amount = self.other_amount_threshold.parse::<u64>().map_err(|e| {
+            JupiterClientError::deserialization(format!(
+                "invalid other_amount_threshold {:?}: {e}",
+                self.other_amount_threshold
+            ))
+        })?;
+
+        Ok(WorstCaseFill {
+            bound: match self.swap_mode {
+                QuoteGetSwapModeEnum::ExactIn => FillBound::MinimumReceived,
+                QuoteGetSwapModeEnum::ExactOut => FillBound::MaximumSpent,
+            },
+            mint: self.worst_case_mint().to_string(),
+            amount,
+            ui_amount: format_ui_amount(amount, decimals),
+            symbol: symbol.into(),
+        })
+    }
+}
+
+/// Which side of a swap [`WorstCaseFill`] bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FillBound {
+    /// ExactIn: the least amount of the output token received.
+    MinimumReceived,
+    /// ExactOut: the most amount of the input token spent.
+    MaximumSpent,
+}
+
+/// The worst-case fill of a quote after slippage, ready for display.
+///
+/// Formats as `"receive at least 151.2304 USDC"` or `"spend at most 1.0105 SOL"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorstCaseFill {
+    pub bound: FillBound,
+    pub mint: String,
+
+    /// Raw amount.
+    pub amount: u64,
+
+    /// The amount in decimal UI units, without trailing zeros.
+    pub ui_amount: String,
+
+    pub symbol: String,
+}
+
+impl fmt::Display for WorstCaseFill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = match self.bound {
+            FillBound::MinimumReceived => "receive at least",
+            FillBound::MaximumSpent => "spend at most",
+        };
+        write!(f, "{verb} {} {}", self.ui_amount, self.symbol)
+    }
+}
+
+/// Formats a raw amount in decimal UI units without going through floating point.
+pub(crate) fn format_ui_amount(amount: u64, decimals: u8) -> String {
+    let digits = amount.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+
+    let digits = format!("{digits:0>width$}", width = decimals + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PlatformFee {
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub amount: String,
     #[serde(default)]
     pub fee_bps: Option<u16>,
@@ -92,9 +201,12 @@ pub struct SwapInfo {
     pub label: String,
     pub input_mint: String,
     pub output_mint: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub in_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub out_amount: String,
     #[serde(default = "default_fee_amount")]
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub fee_amount: String,
     #[serde(default = "default_fee_mint")]
     pub fee_mint: String,
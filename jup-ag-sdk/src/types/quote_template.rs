@@ -0,0 +1,49 @@
+use super::{QuoteQuery, QuoteRequest};
+
+/// A validated pair and its quote constraints, with the amount left open.
+///
+/// Created with [`JupiterClient::quote_template`](crate::JupiterClient::quote_template), which
+/// applies the client's defaults and checks the request once. Quoting through
+/// [`JupiterClient::get_quote_from_template`](crate::JupiterClient::get_quote_from_template)
+/// then only stamps in the amount, so tight quoting loops skip re-resolving and re-validating
+/// identical parameters on every call.
+///
+/// Defaults are captured when the template is created; later changes to the client's
+/// defaults don't affect it.
+#[derive(Debug, Clone)]
+pub struct QuoteTemplate {
+    request: QuoteRequest,
+}
+
+impl QuoteTemplate {
+    pub(crate) fn new(request: QuoteRequest) -> Self {
+        Self { request }
+    }
+
+    /// The concrete request for `amount`.
+    pub fn request(&self, amount: u64) -> QuoteRequest {
+        QuoteRequest {
+            amount,
+            ..self.request.clone()
+        }
+    }
+
+    /// The query parameters of [`request`](Self::request) for `amount`, borrowing the
+    /// template instead of copying its mints and DEX lists.
+    pub fn query(&self, amount: u64) -> QuoteQuery<'_> {
+        self.request.query_for(amount)
+    }
+
+    /// The resolved request, with the amount it was created with.
+    pub(crate) fn params(&self) -> &QuoteRequest {
+        &self.request
+    }
+
+    pub fn input_mint(&self) -> &str {
+        &self.request.input_mint
+    }
+
+    pub fn output_mint(&self) -> &str {
+        &self.request.output_mint
+    }
+}
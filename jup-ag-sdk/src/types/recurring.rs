@@ -1,4 +1,11 @@
-use super::OrderStatus;
+use std::time::Duration;
+
+use super::{OrderStatus, Status};
+use crate::{
+    JupiterClientError,
+    ata::decode_pubkey,
+    rt::{SystemTime, UNIX_EPOCH},
+};
 use serde::{Deserialize, Serialize};
 
 /// Represents a request to create a recurring order, either time-based or price-based.
@@ -45,6 +52,15 @@ pub struct TimeParams {
     pub start_at: Option<u64>,
 }
 
+impl TimeParams {
+    /// The input amount spent each cycle, rounded down.
+    pub fn in_amount_per_cycle(&self) -> u64 {
+        self.in_amount
+            .checked_div(self.number_of_orders)
+            .unwrap_or_default()
+    }
+}
+
 /// Parameters for creating a price-based recurring order.
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -127,6 +143,44 @@ impl CreateRecurringOrderRequest {
         }
     }
 
+    /// The smallest number of orders a time-based order may be split into.
+    pub const MIN_NUMBER_OF_ORDERS: u64 = 2;
+
+    /// Like [`new_time_order`](Self::new_time_order), with the interval as a [`Duration`],
+    /// truncated to whole seconds.
+    ///
+    /// # Example
+    /// ```
+    /// // buy SOL with 1,000 USDC over 10 days, skipping days SOL trades above $250
+    /// let order = CreateRecurringOrderRequest::time_order(
+    ///     wallet,
+    ///     USDC_MINT,
+    ///     SOL_MINT,
+    ///     1_000_000_000,
+    ///     10,
+    ///     Duration::from_secs(24 * 60 * 60),
+    /// )
+    /// .with_max_price(250.0);
+    /// let response = client.create_recurring_order(&order).await?;
+    /// ```
+    pub fn time_order(
+        user: impl Into<String>,
+        input_mint: impl Into<String>,
+        output_mint: impl Into<String>,
+        in_amount: u64,
+        number_of_orders: u64,
+        interval: Duration,
+    ) -> Self {
+        Self::new_time_order(
+            user,
+            input_mint,
+            output_mint,
+            in_amount,
+            number_of_orders,
+            interval.as_secs(),
+        )
+    }
+
     /// Sets the `start_at` Unix timestamp to delay the start of the recurring order.
     pub fn with_start_at(mut self, start_at: u64) -> Self {
         match &mut self.params {
@@ -136,6 +190,26 @@ impl CreateRecurringOrderRequest {
         self
     }
 
+    /// Delays the start of the recurring order until `at`, truncated to whole seconds.
+    pub fn with_start_time(self, at: SystemTime) -> Self {
+        self.with_start_at(at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs())
+    }
+
+    /// Sets the time between orders, truncated to whole seconds.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        match &mut self.params {
+            OrderParams::TimeWrapper { time } => time.interval = interval.as_secs(),
+            OrderParams::PriceWrapper { price } => price.interval = interval.as_secs(),
+        }
+        self
+    }
+
+    /// Sets both price bounds of a time-based order: cycles are skipped while the price is
+    /// outside `min..=max`.
+    pub fn with_price_range(self, min: f64, max: f64) -> Self {
+        self.with_min_price(min).with_max_price(max)
+    }
+
     /// Sets the optional `min_price` threshold for a time-based order.
     pub fn with_min_price(mut self, price: f64) -> Self {
         if let OrderParams::TimeWrapper { time } = &mut self.params {
@@ -151,6 +225,64 @@ impl CreateRecurringOrderRequest {
         }
         self
     }
+
+    /// Checks what Jupiter would reject, without a round trip.
+    ///
+    /// # Errors
+    /// * [`JupiterClientError::InvalidPubkey`] when the user or a mint is not a public key.
+    /// * [`JupiterClientError::InvalidRecurringOrder`] for identical mints, zero amounts or
+    ///   intervals, fewer than [`MIN_NUMBER_OF_ORDERS`](Self::MIN_NUMBER_OF_ORDERS) orders,
+    ///   a cycle that would spend nothing, or price bounds that exclude every price.
+    pub fn validate(&self) -> Result<(), JupiterClientError> {
+        let invalid = |reason: String| Err(JupiterClientError::InvalidRecurringOrder(reason));
+        for address in [&self.user, &self.input_mint, &self.output_mint] {
+            decode_pubkey(address)?;
+        }
+        if self.input_mint == self.output_mint {
+            return invalid("input and output mints are the same".to_string());
+        }
+        match &self.params {
+            OrderParams::TimeWrapper { time } => {
+                if time.number_of_orders < Self::MIN_NUMBER_OF_ORDERS {
+                    return invalid(format!(
+                        "{} order(s), at least {} are required",
+                        time.number_of_orders,
+                        Self::MIN_NUMBER_OF_ORDERS
+                    ));
+                }
+                if time.in_amount_per_cycle() == 0 {
+                    return invalid(format!(
+                        "{} split into {} orders spends nothing per cycle",
+                        time.in_amount, time.number_of_orders
+                    ));
+                }
+                if time.interval == 0 {
+                    return invalid("the interval must be at least one second".to_string());
+                }
+                for (name, bound) in [("min", time.min_price), ("max", time.max_price)] {
+                    if bound.is_some_and(|price| !price.is_finite() || price <= 0.0) {
+                        return invalid(format!("{name} price must be positive"));
+                    }
+                }
+                if let (Some(min), Some(max)) = (time.min_price, time.max_price)
+                    && min > max
+                {
+                    return invalid(format!("min price {min} is above max price {max}"));
+                }
+            }
+            OrderParams::PriceWrapper { price } => {
+                if price.deposit_amount == 0 || price.increment_usdc_value == 0 {
+                    return invalid(
+                        "deposit amount and USDC increment must be positive".to_string(),
+                    );
+                }
+                if price.interval == 0 {
+                    return invalid("the interval must be at least one second".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -186,8 +318,10 @@ pub enum RecurringOrderType {
     All,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Tops up a price-based recurring order with more of its input token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceDeposit {
+    /// Raw amount of the input token to add.
     pub amount: u64,
 
     pub order: String,
@@ -208,42 +342,90 @@ impl PriceDeposit {
             user: user.into(),
         }
     }
+
+    /// Checks that the user and order are public keys and the amount is positive.
+    pub fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_position_change(&self.user, &self.order, Some(self.amount))
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Which token a [`PriceWithdraw`] takes out of the order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputOrOutput {
+    /// The input token not spent yet.
+    In,
+
+    /// The output token bought so far.
+    Out,
+}
+
+/// Drains some or all of a token from a price-based recurring order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PriceWithdraw {
-    /// If no amount is provided, it will withdraw the entire amount
-    pub amount: u64,
+    /// Raw amount to withdraw, everything when `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u64>,
 
     pub order: String,
 
     pub user: String,
 
-    /// Possible values: [In, Out]
-    pub input_or_output: String,
+    pub input_or_output: InputOrOutput,
 }
 
 impl PriceWithdraw {
     /// # Arguments
     ///
-    /// * `amount` - The amount to withdraw
+    /// * `amount` - The raw amount to withdraw
     /// * `order` - The recurring order account address
     /// * `user` - The user account address
-    /// * `input_or_output` - The withdrawal direction ("In" or "Out")
+    /// * `input_or_output` - Whether to withdraw the input or the output token
     pub fn new(
         amount: u64,
         order: impl Into<String>,
         user: impl Into<String>,
-        input_or_output: impl Into<String>,
+        input_or_output: InputOrOutput,
     ) -> Self {
         Self {
-            amount,
+            amount: Some(amount),
+            ..Self::all(order, user, input_or_output)
+        }
+    }
+
+    /// Withdraws the whole balance of the input or output token.
+    pub fn all(
+        order: impl Into<String>,
+        user: impl Into<String>,
+        input_or_output: InputOrOutput,
+    ) -> Self {
+        Self {
+            amount: None,
             order: order.into(),
             user: user.into(),
-            input_or_output: input_or_output.into(),
+            input_or_output,
         }
     }
+
+    /// Checks that the user and order are public keys and an amount, if set, is positive.
+    pub fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_position_change(&self.user, &self.order, self.amount)
+    }
+}
+
+fn validate_position_change(
+    user: &str,
+    order: &str,
+    amount: Option<u64>,
+) -> Result<(), JupiterClientError> {
+    decode_pubkey(user)?;
+    decode_pubkey(order)?;
+    if amount == Some(0) {
+        return Err(JupiterClientError::InvalidRecurringOrder(
+            "amount must be positive".to_string(),
+        ));
+    }
+    Ok(())
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -275,9 +457,37 @@ impl ExecuteRecurringRequest {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteRecurringResponse {
+    /// transaction signature, empty when the transaction was rejected before sending
+    #[serde(default)]
     pub signature: String,
 
-    pub status: String,
+    pub status: Status,
+
+    /// recurring order account the transaction created or changed
+    #[serde(default)]
+    pub order: Option<String>,
+
+    /// why the transaction failed
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl ExecuteRecurringResponse {
+    pub fn is_success(&self) -> bool {
+        matches!(self.status, Status::Success)
+    }
+
+    /// The response on success, otherwise a
+    /// [`RecurringExecutionFailed`](JupiterClientError::RecurringExecutionFailed) carrying the
+    /// error message.
+    pub fn into_result(self) -> Result<Self, JupiterClientError> {
+        if self.is_success() {
+            return Ok(self);
+        }
+        Err(JupiterClientError::RecurringExecutionFailed(
+            self.error.unwrap_or_else(|| format!("{:?}", self.status)),
+        ))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -355,30 +565,48 @@ pub struct PriceOrder {
     pub close_tx: String,
     pub closed_by: String,
     pub created_at: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub estimated_usdc_value_spent: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub in_deposited: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub in_left: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub in_used: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub in_withdrawn: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub incremental_usd_value: String,
     pub input_mint: String,
     pub open_tx: String,
     pub order_interval: String,
     pub order_key: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub out_received: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub out_withdrawn: String,
     pub output_mint: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_estimated_usdc_value_spent: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_in_deposited: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_in_left: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_in_used: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_in_withdrawn: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_incremental_usd_value: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_out_received: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_out_withdrawn: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_supposed_usd_value: String,
     pub start_at: String,
     pub status: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub supposed_usd_value: String,
     pub trades: Vec<Trade>,
     pub updated_at: String,
@@ -391,25 +619,41 @@ pub struct TimeOrder {
     pub close_tx: String,
     pub created_at: String,
     pub cycle_frequency: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub in_amount_per_cycle: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub in_deposited: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub in_used: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub in_withdrawn: String,
     pub input_mint: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub max_out_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub min_out_amount: String,
     pub open_tx: String,
     pub order_key: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub out_received: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub out_withdrawn: String,
     pub output_mint: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_in_amount_per_cycle: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_in_deposited: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_in_used: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_in_withdrawn: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_max_out_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_min_out_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_out_received: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_out_withdrawn: String,
     pub trades: Vec<Trade>,
     pub updated_at: String,
@@ -422,18 +666,24 @@ pub struct TimeOrder {
 pub struct Trade {
     pub action: String,
     pub confirmed_at: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub fee_amount: String,
     pub fee_mint: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub input_amount: String,
     pub input_mint: String,
     pub keeper: String,
     pub order_key: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub output_amount: String,
     pub output_mint: String,
     #[serde(default)]
     pub product_meta: Option<ProductMeta>,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_fee_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_input_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_output_amount: String,
     pub tx_id: String,
 }
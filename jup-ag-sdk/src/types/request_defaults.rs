@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+
+use super::{
+    DexEnum, PrioritizationFeeLamports, PriorityLevel, PriorityLevelWithMaxLamports, QuoteRequest,
+    QuoteResponse, Slippage, SwapRequest,
+};
+
+/// Defaults applied by [`JupiterClient`](crate::JupiterClient) to every quote and swap request.
+///
+/// A default only fills a field the request left unset, so anything set explicitly on a
+/// [`QuoteRequest`] or [`SwapRequest`] always wins.
+///
+/// # Example
+/// ```
+/// let defaults = RequestDefaults::new()
+///     .slippage_bps(50)
+///     .restrict_intermediate_tokens(true)
+///     .prioritization_fee_config(1_000_000, PriorityLevel::High);
+///
+/// let client = JupiterClient::new("https://lite-api.jup.ag").with_defaults(defaults);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RequestDefaults {
+    /// Slippage tolerance in basis points, used when a quote sets neither `slippage_bps` nor `dynamic_slippage`.
+    pub slippage_bps: Option<u16>,
+
+    /// Enables dynamic slippage on both quote and swap requests.
+    pub dynamic_slippage: Option<bool>,
+
+    /// Restricts intermediate tokens to a stable set.
+    ///
+    /// [`QuoteRequest::new`] sets the field, so only quotes built with
+    /// [`use_default_intermediate_tokens`](QuoteRequest::use_default_intermediate_tokens)
+    /// take this default.
+    pub restrict_intermediate_tokens: Option<bool>,
+
+    /// Upper bound on the number of accounts used in the quote.
+    pub max_accounts: Option<u8>,
+
+    /// DEXes to exclusively route through, used when a quote sets no `dexes`.
+    pub dexes: Option<Vec<DexEnum>>,
+
+    /// DEXes to exclude from routing, used when a quote sets no `exclude_dexes`, e.g. venues
+    /// known to be thin for one pair.
+    pub exclude_dexes: Option<Vec<DexEnum>>,
+
+    /// Priority fee configuration, used when a swap sets neither a priority fee nor a compute unit price.
+    pub prioritization_fee_lamports: Option<PrioritizationFeeLamports>,
+
+    /// Platform fee charged on every quote, and the fee accounts that collect it on swaps.
+    pub integrator_fee: Option<IntegratorFee>,
+
+    /// Tracking account attached to every swap so integrator volume can be queried on-chain.
+    pub tracking_account: Option<String>,
+}
+
+/// Where the fee token account for a swap comes from.
+#[derive(Debug, Clone)]
+pub enum FeeAccountSource {
+    /// A single fee token account, used for every swap.
+    ///
+    /// Its mint must be the input or output mint of every swap you make.
+    Fixed(String),
+
+    /// Fee token accounts keyed by mint, e.g. the referral token accounts you created per mint.
+    ByMint(HashMap<String, String>),
+}
+
+/// Integrator fee settings injected into quotes (`platform_fee_bps`) and swaps (`fee_account`).
+#[derive(Debug, Clone)]
+pub struct IntegratorFee {
+    pub platform_fee_bps: u64,
+    pub fee_accounts: FeeAccountSource,
+}
+
+impl IntegratorFee {
+    pub fn new(platform_fee_bps: u64, fee_accounts: FeeAccountSource) -> Self {
+        Self {
+            platform_fee_bps,
+            fee_accounts,
+        }
+    }
+
+    /// Resolves the fee account for a quote.
+    ///
+    /// Prefers the fee mint reported in the quote's platform fee, then the output mint,
+    /// then the input mint.
+    pub fn fee_account_for(&self, quote: &QuoteResponse) -> Option<String> {
+        match &self.fee_accounts {
+            FeeAccountSource::Fixed(account) => Some(account.clone()),
+            FeeAccountSource::ByMint(accounts) => quote
+                .platform_fee
+                .as_ref()
+                .and_then(|fee| fee.fee_mint.as_ref())
+                .into_iter()
+                .chain([&quote.output_mint, &quote.input_mint])
+                .find_map(|mint| accounts.get(mint))
+                .cloned(),
+        }
+    }
+}
+
+impl RequestDefaults {
+    /// Creates an empty set of defaults that leaves every request untouched.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the default slippage tolerance in basis points (bps).
+    pub fn slippage_bps(mut self, slippage_bps: u16) -> Self {
+        self.slippage_bps = Some(slippage_bps);
+        self
+    }
+
+    /// Sets the default slippage tolerance from a [`Slippage`], e.g. one parsed from config.
+    pub fn slippage(self, slippage: Slippage) -> Self {
+        self.slippage_bps(slippage.bps())
+    }
+
+    /// Sets whether dynamic slippage is enabled by default.
+    pub fn dynamic_slippage(mut self, dynamic_slippage: bool) -> Self {
+        self.dynamic_slippage = Some(dynamic_slippage);
+        self
+    }
+
+    /// Sets whether intermediate tokens are restricted by default.
+    pub fn restrict_intermediate_tokens(mut self, restrict_intermediate_tokens: bool) -> Self {
+        self.restrict_intermediate_tokens = Some(restrict_intermediate_tokens);
+        self
+    }
+
+    /// Sets the default upper bound on the number of accounts used in a quote.
+    pub fn max_accounts(mut self, max_accounts: u8) -> Self {
+        self.max_accounts = Some(max_accounts);
+        self
+    }
+
+    /// Sets the DEXes quotes route through by default.
+    pub fn dexes(mut self, dexes: Vec<DexEnum>) -> Self {
+        self.dexes = Some(dexes);
+        self
+    }
+
+    /// Sets the DEXes excluded from routing by default.
+    ///
+    /// # Example
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag").with_pair_defaults(
+    ///     SOL_MINT,
+    ///     BONK_MINT,
+    ///     RequestDefaults::new().slippage_bps(300).exclude_dexes(vec![DexEnum::Raydium]),
+    /// );
+    /// ```
+    pub fn exclude_dexes(mut self, exclude_dexes: Vec<DexEnum>) -> Self {
+        self.exclude_dexes = Some(exclude_dexes);
+        self
+    }
+
+    /// Sets a default Jito tip in lamports.
+    pub fn prioritization_fee_jito_tip(mut self, fee: u64) -> Self {
+        self.prioritization_fee_lamports = Some(PrioritizationFeeLamports {
+            jito_tip_lamports: Some(fee),
+            priority_level_with_max_lamports: None,
+        });
+        self
+    }
+
+    /// Sets a default priority level with a cap on lamports paid.
+    pub fn prioritization_fee_config(
+        mut self,
+        max_lamports: u32,
+        priority_level: PriorityLevel,
+    ) -> Self {
+        self.prioritization_fee_lamports = Some(PrioritizationFeeLamports {
+            jito_tip_lamports: None,
+            priority_level_with_max_lamports: Some(PriorityLevelWithMaxLamports {
+                max_lamports,
+                priority_level,
+            }),
+        });
+        self
+    }
+
+    /// Charges `platform_fee_bps` on every quote and injects the matching fee account into every swap.
+    ///
+    /// # Example
+    /// ```
+    /// let defaults = RequestDefaults::new().integrator_fee(IntegratorFee::new(
+    ///     20,
+    ///     FeeAccountSource::Fixed("YourUsdcFeeTokenAccount...".to_string()),
+    /// ));
+    /// ```
+    pub fn integrator_fee(mut self, integrator_fee: IntegratorFee) -> Self {
+        self.integrator_fee = Some(integrator_fee);
+        self
+    }
+
+    /// Attaches `account` as the tracking account of every swap.
+    ///
+    /// Any public key you own works; query your swaps with a block explorer or Dune/Flipside.
+    pub fn tracking_account(mut self, account: impl Into<String>) -> Self {
+        self.tracking_account = Some(account.into());
+        self
+    }
+
+    /// Returns a copy of `request` with every unset field filled from these defaults.
+    pub fn apply_to_quote(&self, request: &QuoteRequest) -> QuoteRequest {
+        let mut request = request.clone();
+
+        if request.dynamic_slippage.is_none() {
+            request.dynamic_slippage = self.dynamic_slippage;
+        }
+        if request.slippage_bps.is_none() && request.dynamic_slippage != Some(true) {
+            request.slippage_bps = self.slippage_bps;
+        }
+        if request.restrict_intermediate_tokens.is_none() {
+            request.restrict_intermediate_tokens = self.restrict_intermediate_tokens;
+        }
+        if request.max_accounts.is_none() {
+            request.max_accounts = self.max_accounts;
+        }
+        if request.dexes.is_none() {
+            request.dexes = self.dexes.clone();
+        }
+        if request.exclude_dexes.is_none() {
+            request.exclude_dexes = self.exclude_dexes.clone();
+        }
+        if let (None, Some(fee)) = (request.platform_fee_bps, &self.integrator_fee) {
+            request.platform_fee_bps = Some(fee.platform_fee_bps);
+        }
+
+        request
+    }
+
+    /// Returns a copy of `request` with every unset field filled from these defaults.
+    pub fn apply_to_swap(&self, request: &SwapRequest) -> SwapRequest {
+        let mut request = request.clone();
+
+        if request.dynamic_slippage.is_none() {
+            request.dynamic_slippage = self.dynamic_slippage;
+        }
+        if request.prioritization_fee_lamports.is_none()
+            && request.compute_unit_price_micro_lamports.is_none()
+        {
+            request.prioritization_fee_lamports = self.prioritization_fee_lamports.clone();
+        }
+        if let (None, Some(fee), Some(_)) = (
+            &request.fee_account,
+            &self.integrator_fee,
+            &request.quote_response.platform_fee,
+        ) {
+            request.fee_account = fee.fee_account_for(&request.quote_response);
+        }
+        if request.tracking_account.is_none() {
+            request.tracking_account = self.tracking_account.clone();
+        }
+
+        request
+    }
+}
+
+/// A named bundle of quote and swap options, selectable per call.
+///
+/// Profiles sit between the request and the client's [`RequestDefaults`]: fields set on the
+/// request win, then the profile, then the client defaults.
+///
+/// Built-in profiles can be parsed from config with [`str::parse`], e.g. `"aggressive".parse::<Profile>()`.
+///
+/// # Example
+/// ```
+/// let quote = client.get_quote_with_profile(&request, &Profile::Conservative).await?;
+///
+/// let custom = Profile::Custom(RequestDefaults::new().slippage_bps(25).max_accounts(30));
+/// let quote = client.get_quote_with_profile(&request, &custom).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub enum Profile {
+    /// Tight fixed slippage, stable intermediate tokens and a modest priority fee.
+    Conservative,
+    /// Dynamic slippage, stable intermediate tokens and a high priority fee.
+    Balanced,
+    /// Wide slippage, unrestricted routing and a very high priority fee, favouring fill rate.
+    Aggressive,
+    /// A user-defined set of options.
+    Custom(RequestDefaults),
+}
+
+impl Profile {
+    /// Returns the options bundled by this profile.
+    pub fn defaults(&self) -> RequestDefaults {
+        match self {
+            Profile::Conservative => RequestDefaults::new()
+                .slippage_bps(50)
+                .dynamic_slippage(false)
+                .restrict_intermediate_tokens(true)
+                .prioritization_fee_config(500_000, PriorityLevel::Medium),
+            Profile::Balanced => RequestDefaults::new()
+                .dynamic_slippage(true)
+                .restrict_intermediate_tokens(true)
+                .prioritization_fee_config(1_000_000, PriorityLevel::High),
+            Profile::Aggressive => RequestDefaults::new()
+                .slippage_bps(300)
+                .dynamic_slippage(false)
+                .restrict_intermediate_tokens(false)
+                .prioritization_fee_config(5_000_000, PriorityLevel::VeryHigh),
+            Profile::Custom(defaults) => defaults.clone(),
+        }
+    }
+}
+
+impl std::str::FromStr for Profile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "conservative" => Ok(Profile::Conservative),
+            "balanced" => Ok(Profile::Balanced),
+            "aggressive" => Ok(Profile::Aggressive),
+            other => Err(format!("unknown profile: {other}")),
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+use super::Instruction;
+use crate::JupiterClientError;
+
+/// Anchor discriminators of the Jupiter aggregator route instructions.
+const ROUTE: [u8; 8] = [229, 23, 203, 151, 122, 227, 173, 42];
+const SHARED_ACCOUNTS_ROUTE: [u8; 8] = [193, 32, 155, 51, 65, 214, 156, 129];
+const ROUTE_WITH_TOKEN_LEDGER: [u8; 8] = [150, 86, 71, 116, 167, 93, 14, 104];
+const SHARED_ACCOUNTS_ROUTE_WITH_TOKEN_LEDGER: [u8; 8] = [230, 121, 143, 80, 119, 159, 106, 170];
+const EXACT_OUT_ROUTE: [u8; 8] = [208, 51, 239, 151, 123, 43, 237, 92];
+const SHARED_ACCOUNTS_EXACT_OUT_ROUTE: [u8; 8] = [176, 209, 105, 168, 154, 125, 69, 62];
+
+/// Every route instruction ends with `quoted_amount: u64, slippage_bps: u16, platform_fee_bps: u8`.
+const TRAILER_LEN: usize = 8 + 2 + 1;
+
+/// The kind of Jupiter route instruction, decoded from its discriminator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteInstructionKind {
+    Route,
+    SharedAccountsRoute,
+    RouteWithTokenLedger,
+    SharedAccountsRouteWithTokenLedger,
+    ExactOutRoute,
+    SharedAccountsExactOutRoute,
+}
+
+impl RouteInstructionKind {
+    fn from_discriminator(discriminator: &[u8]) -> Option<Self> {
+        let kind = match discriminator {
+            d if d == ROUTE => Self::Route,
+            d if d == SHARED_ACCOUNTS_ROUTE => Self::SharedAccountsRoute,
+            d if d == ROUTE_WITH_TOKEN_LEDGER => Self::RouteWithTokenLedger,
+            d if d == SHARED_ACCOUNTS_ROUTE_WITH_TOKEN_LEDGER => {
+                Self::SharedAccountsRouteWithTokenLedger
+            }
+            d if d == EXACT_OUT_ROUTE => Self::ExactOutRoute,
+            d if d == SHARED_ACCOUNTS_EXACT_OUT_ROUTE => Self::SharedAccountsExactOutRoute,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// Returns true for the `ExactOut` flavours, where the quoted amount is the input amount.
+    pub fn is_exact_out(&self) -> bool {
+        matches!(
+            self,
+            Self::ExactOutRoute | Self::SharedAccountsExactOutRoute
+        )
+    }
+}
+
+/// The slippage-related fields decoded from a Jupiter route instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteInstruction {
+    pub kind: RouteInstructionKind,
+
+    /// `quoted_out_amount` for `ExactIn` routes, `quoted_in_amount` for `ExactOut` routes.
+    pub quoted_amount: u64,
+
+    pub slippage_bps: u16,
+
+    pub platform_fee_bps: u8,
+}
+
+impl RouteInstruction {
+    /// Decodes the route trailer from the base64 `data` of a swap instruction.
+    ///
+    /// Only the base64 groups holding the discriminator and the trailer are decoded, on the
+    /// stack, so the route plan in between is neither decoded nor validated.
+    pub fn decode(instruction: &Instruction) -> Result<Self, JupiterClientError> {
+        let data = instruction.data.as_bytes();
+        if data.len() % 4 != 0 {
+            return Err(JupiterClientError::InstructionDataError(
+                "invalid base64: length is not a multiple of 4".to_string(),
+            ));
+        }
+        let padding = data.iter().rev().take(2).filter(|&&c| c == b'=').count();
+        let len = (data.len() / 4 * 3).saturating_sub(padding);
+        check_len(len)?;
+
+        // the discriminator is in the first three groups, the trailer in the groups from the
+        // one holding its first byte to the end
+        let mut head = [0; 9];
+        decode_slice(&data[..12], &mut head)?;
+        let first_group = (len - TRAILER_LEN) / 3;
+        let mut tail = [0; 15];
+        let tail_len = decode_slice(&data[first_group * 4..], &mut tail)?;
+
+        Self::from_parts(&head[..8], &tail[tail_len - TRAILER_LEN..tail_len])
+    }
+
+    fn decode_bytes(data: &[u8]) -> Result<Self, JupiterClientError> {
+        check_len(data.len())?;
+        Self::from_parts(&data[..8], &data[data.len() - TRAILER_LEN..])
+    }
+
+    fn from_parts(discriminator: &[u8], trailer: &[u8]) -> Result<Self, JupiterClientError> {
+        let kind = RouteInstructionKind::from_discriminator(discriminator).ok_or_else(|| {
+            JupiterClientError::InstructionDataError(
+                "instruction is not a Jupiter route instruction".to_string(),
+            )
+        })?;
+
+        let quoted_amount = u64::from_le_bytes(trailer[..8].try_into().unwrap());
+        let slippage_bps = u16::from_le_bytes(trailer[8..10].try_into().unwrap());
+        let platform_fee_bps = trailer[10];
+
+        Ok(Self {
+            kind,
+            quoted_amount,
+            slippage_bps,
+            platform_fee_bps,
+        })
+    }
+
+    /// The minimum output amount enforced on-chain for `ExactIn` routes.
+    pub fn minimum_out_amount(&self) -> u64 {
+        let amount = self.quoted_amount as u128 * (10_000 - self.slippage_bps.min(10_000)) as u128;
+        (amount / 10_000) as u64
+    }
+}
+
+/// Rewrites the route instruction so the on-chain minimum out equals `minimum_out_amount`.
+///
+/// The quoted amount is replaced by `minimum_out_amount` and slippage is set to zero, so the
+/// program enforces exactly the requested threshold. Only tightening is allowed.
+pub(crate) fn patch_minimum_out(
+    instruction: &mut Instruction,
+    minimum_out_amount: u64,
+) -> Result<(), JupiterClientError> {
+    let mut data = decode_data(instruction)?;
+    let route = RouteInstruction::decode_bytes(&data)?;
+
+    if route.kind.is_exact_out() {
+        return Err(JupiterClientError::InstructionDataError(
+            "minimum out can only be overridden on ExactIn routes".to_string(),
+        ));
+    }
+
+    let current = route.minimum_out_amount();
+    if minimum_out_amount < current {
+        return Err(JupiterClientError::InstructionDataError(format!(
+            "minimum out {minimum_out_amount} is looser than the quoted threshold {current}"
+        )));
+    }
+
+    let start = data.len() - TRAILER_LEN;
+    data[start..start + 8].copy_from_slice(&minimum_out_amount.to_le_bytes());
+    data[start + 8..start + 10].copy_from_slice(&0u16.to_le_bytes());
+
+    // same length, so the encoded data is rewritten in place
+    instruction.data.clear();
+    STANDARD.encode_string(data, &mut instruction.data);
+    Ok(())
+}
+
+fn check_len(len: usize) -> Result<(), JupiterClientError> {
+    if len < 8 + TRAILER_LEN {
+        return Err(JupiterClientError::InstructionDataError(format!(
+            "route instruction data too short: {len} bytes"
+        )));
+    }
+    Ok(())
+}
+
+fn decode_slice(input: &[u8], output: &mut [u8]) -> Result<usize, JupiterClientError> {
+    STANDARD
+        .decode_slice(input, output)
+        .map_err(|e| JupiterClientError::InstructionDataError(e.to_string()))
+}
+
+fn decode_data(instruction: &Instruction) -> Result<Vec<u8>, JupiterClientError> {
+    STANDARD
+        .decode(&instruction.data)
+        .map_err(|e| JupiterClientError::InstructionDataError(e.to_string()))
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+use super::QuoteResponse;
+
+/// The pools of a route, in order, with the share of the input each one takes.
+type RouteKey<'a> = Vec<(&'a str, i32)>;
+
+/// How consistent the route and output of a pair are across repeated quotes.
+///
+/// Returned by [`sample_route_stability`](crate::JupiterClient::sample_route_stability), or
+/// computed from quotes you collected with [`from_quotes`](Self::from_quotes).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteStability {
+    pub samples: usize,
+
+    /// Number of distinct routes seen, where a route is the ordered list of pools and the
+    /// share of the input each one takes.
+    pub distinct_routes: usize,
+
+    /// Venue labels of the most frequent route.
+    pub dominant_route: Vec<String>,
+
+    /// Fraction of samples that used the most frequent route, from 0 to 1.
+    pub dominant_route_share: f64,
+
+    /// Mean raw output amount.
+    pub out_amount_mean: f64,
+
+    /// Standard deviation of the output amount relative to its mean, in basis points.
+    pub out_amount_deviation_bps: f64,
+
+    /// `dominant_route_share`, discounted linearly until the output deviation reaches 100 bps.
+    ///
+    /// 1 means every sample took the same route at the same price; 0 means the route or price
+    /// is too unstable to rely on.
+    pub score: f64,
+}
+
+impl RouteStability {
+    /// Scores a set of quotes for the same pair and size. Returns `None` for an empty set.
+    pub fn from_quotes(quotes: &[QuoteResponse]) -> Option<Self> {
+        if quotes.is_empty() {
+            return None;
+        }
+
+        let mut routes: Vec<(RouteKey, usize, &QuoteResponse)> = Vec::new();
+        for quote in quotes {
+            let route: Vec<_> = quote
+                .route_plan
+                .iter()
+                .map(|step| (step.swap_info.amm_key.as_str(), step.percent))
+                .collect();
+            match routes.iter_mut().find(|(r, ..)| *r == route) {
+                Some((_, count, _)) => *count += 1,
+                None => routes.push((route, 1, quote)),
+            }
+        }
+        let (_, dominant_count, dominant_quote) = routes
+            .iter()
+            .max_by_key(|(_, count, _)| *count)
+            .expect("at least one route");
+
+        let amounts: Vec<f64> = quotes
+            .iter()
+            .map(|quote| quote.out_amount.parse().unwrap_or_default())
+            .collect();
+        let n = amounts.len() as f64;
+        let mean = amounts.iter().sum::<f64>() / n;
+        let variance = amounts.iter().map(|a| (a - mean).powi(2)).sum::<f64>() / n;
+        let deviation_bps = if mean > 0.0 {
+            variance.sqrt() / mean * 10_000.0
+        } else {
+            0.0
+        };
+
+        let dominant_route_share = *dominant_count as f64 / n;
+        Some(Self {
+            samples: quotes.len(),
+            distinct_routes: routes.len(),
+            dominant_route: dominant_quote
+                .route_plan
+                .iter()
+                .map(|step| step.swap_info.label.clone())
+                .collect(),
+            dominant_route_share,
+            out_amount_mean: mean,
+            out_amount_deviation_bps: deviation_bps,
+            score: dominant_route_share * (1.0 - deviation_bps / 100.0).max(0.0),
+        })
+    }
+}
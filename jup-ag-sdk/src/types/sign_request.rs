@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+
+use super::{RoutePlanItem, SwapRequest, SwapResponse, UltraOrderResponse};
+
+/// Everything an external wallet or approval UI needs to review and sign a swap.
+///
+/// Bundles the unsigned transaction with a human-readable summary and the accounts that must
+/// sign it, and serializes to JSON so it can be handed across process or device boundaries.
+///
+/// # Example
+/// ```
+/// let swap = api.get_swap_transaction(&payload).await?;
+/// let bundle = SignRequest::from_swap(&payload, &swap);
+/// approval_queue.push(serde_json::to_string(&bundle)?);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignRequest {
+    /// The base64-encoded unsigned transaction.
+    pub transaction: String,
+
+    pub summary: SwapSummary,
+
+    /// Public keys that must sign the transaction.
+    pub signers: Vec<String>,
+}
+
+/// A human-readable description of what a swap transaction does.
+///
+/// Amounts are raw token amounts, as returned by Jupiter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapSummary {
+    pub input_mint: String,
+    pub output_mint: String,
+    pub in_amount: String,
+    pub out_amount: String,
+
+    /// Worst-case amount after slippage.
+    pub other_amount_threshold: String,
+
+    pub slippage_bps: u16,
+    pub price_impact_pct: String,
+
+    /// Every hop of the route, in execution order.
+    pub route: Vec<RouteHop>,
+
+    /// Integrator fee charged on the swap, if any.
+    pub platform_fee_amount: Option<String>,
+    pub platform_fee_mint: Option<String>,
+
+    pub prioritization_fee_lamports: u64,
+
+    /// Block height after which the transaction can no longer land, when known.
+    pub last_valid_block_height: Option<u64>,
+}
+
+/// One hop of a swap route.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteHop {
+    pub venue: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub in_amount: String,
+    pub out_amount: String,
+    pub fee_amount: String,
+    pub fee_mint: String,
+    /// Share of the input routed through this hop.
+    pub percent: i32,
+}
+
+impl From<&RoutePlanItem> for RouteHop {
+    fn from(step: &RoutePlanItem) -> Self {
+        let info = &step.swap_info;
+        Self {
+            venue: info.label.clone(),
+            input_mint: info.input_mint.clone(),
+            output_mint: info.output_mint.clone(),
+            in_amount: info.in_amount.clone(),
+            out_amount: info.out_amount.clone(),
+            fee_amount: info.fee_amount.clone(),
+            fee_mint: info.fee_mint.clone(),
+            percent: step.percent,
+        }
+    }
+}
+
+impl SignRequest {
+    /// Bundles the transaction returned by `/swap` for `request`.
+    pub fn from_swap(request: &SwapRequest, response: &SwapResponse) -> Self {
+        let quote = &request.quote_response;
+        let fee = quote.platform_fee.as_ref();
+
+        let mut signers = vec![request.user_public_key.clone()];
+        if !request.payer.is_empty() && request.payer != request.user_public_key {
+            signers.push(request.payer.clone());
+        }
+
+        Self {
+            transaction: response.swap_transaction.clone(),
+            summary: SwapSummary {
+                input_mint: quote.input_mint.clone(),
+                output_mint: quote.output_mint.clone(),
+                in_amount: quote.in_amount.clone(),
+                out_amount: quote.out_amount.clone(),
+                other_amount_threshold: quote.other_amount_threshold.clone(),
+                slippage_bps: quote.slippage_bps,
+                price_impact_pct: quote.price_impact_pct.clone(),
+                route: quote.route_plan.iter().map(RouteHop::from).collect(),
+                platform_fee_amount: fee.map(|f| f.amount.clone()),
+                platform_fee_mint: fee.and_then(|f| f.fee_mint.clone()),
+                prioritization_fee_lamports: response.prioritization_fee_lamports,
+                last_valid_block_height: Some(response.last_valid_block_height),
+            },
+            signers,
+        }
+    }
+
+    /// Bundles the transaction carried by an Ultra order.
+    ///
+    /// Returns `None` when the order has no transaction, i.e. no taker was given.
+    pub fn from_ultra(order: &UltraOrderResponse) -> Option<Self> {
+        let transaction = order.transaction.clone()?;
+        let fee = order.platform_fee.as_ref();
+
+        Some(Self {
+            transaction,
+            summary: SwapSummary {
+                input_mint: order.input_mint.clone(),
+                output_mint: order.output_mint.clone(),
+                in_amount: order.in_amount.clone(),
+                out_amount: order.out_amount.clone(),
+                other_amount_threshold: order.other_amount_threshold.clone(),
+                slippage_bps: order.slippage_bps.max(0) as u16,
+                price_impact_pct: order.price_impact_pct.clone(),
+                route: order.route_plan.iter().map(RouteHop::from).collect(),
+                platform_fee_amount: fee.map(|f| f.amount.clone()),
+                platform_fee_mint: fee
+                    .and_then(|f| f.fee_mint.clone())
+                    .or_else(|| order.fee_mint.clone()),
+                prioritization_fee_lamports: order.prioritization_fee_lamports,
+                last_valid_block_height: None,
+            },
+            signers: order.taker.iter().cloned().collect(),
+        })
+    }
+}
@@ -0,0 +1,313 @@
+use serde::{Deserialize, Serialize};
+
+use super::QuoteResponse;
+
+/// Program id of the Jupiter aggregator v6 program.
+pub const JUPITER_PROGRAM_ID: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+
+/// Returns the innermost program that failed during a simulation, ignoring the Jupiter
+/// aggregator program itself and the token, system and other infrastructure programs.
+///
+/// Solana logs the failure of the innermost program first (`Program <id> failed: ...`), followed
+/// by every caller up the CPI stack, so the first failure of neither kind is the AMM that broke.
+/// When an AMM's transfer through the token program fails, that AMM is returned.
+///
+/// # Arguments
+/// * `logs` - The program logs returned by `simulateTransaction`.
+///
+/// # Example
+/// ```
+/// let logs = vec![
+///     "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc failed: custom program error: 0x1771".to_string(),
+/// ];
+/// assert_eq!(failing_program_id(&logs), Some("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"));
+/// ```
+pub fn failing_program_id(logs: &[String]) -> Option<&str> {
+    logs.iter().find_map(|line| {
+        let rest = line.strip_prefix("Program ")?;
+        let (program_id, rest) = rest.split_once(' ')?;
+        if rest.starts_with("failed")
+            && program_id != JUPITER_PROGRAM_ID
+            && !INFRASTRUCTURE_PROGRAMS.contains(&program_id)
+        {
+            Some(program_id)
+        } else {
+            None
+        }
+    })
+}
+
+/// Programs invoked by the aggregator that are not routing venues.
+pub(crate) const INFRASTRUCTURE_PROGRAMS: [&str; 6] = [
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+    "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+    "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+    "11111111111111111111111111111111",
+    "ComputeBudget111111111111111111111111111111",
+    "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr",
+];
+
+/// A structured explanation of a failed `simulateTransaction` call, mapped onto the route plan.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationDiagnosis {
+    /// Index of the failing instruction within the transaction.
+    pub instruction_index: Option<u64>,
+
+    /// The innermost program that failed.
+    pub program_id: Option<String>,
+
+    /// Index into `route_plan` of the hop that was executing when the failure happened.
+    pub hop: Option<usize>,
+
+    /// Venue label of the failing hop, e.g. `"Whirlpool"`.
+    pub venue: Option<String>,
+
+    /// AMM account of the failing hop.
+    pub amm_key: Option<String>,
+
+    /// Custom program error code, e.g. `6001`.
+    pub error_code: Option<u32>,
+
+    /// Name of the error when it is a known Jupiter aggregator error.
+    pub error_name: Option<String>,
+
+    /// The raw failure message from the logs, e.g. `"custom program error: 0x1771"`.
+    pub message: Option<String>,
+}
+
+/// Maps a simulation error and its program logs onto the route plan of `quote`.
+///
+/// # Arguments
+/// * `err` - The `err` field of the `simulateTransaction` result, e.g.
+///   `{"InstructionError":[3,{"Custom":6001}]}`.
+/// * `logs` - The program logs returned by the simulation.
+/// * `quote` - The quote the transaction was built from.
+///
+/// # Example
+/// ```
+/// let diagnosis = diagnose_simulation(result.err.as_ref(), &result.logs, &quote);
+/// if let Some(venue) = diagnosis.venue {
+///     println!("hop {:?} on {} failed with {:?}", diagnosis.hop, venue, diagnosis.error_name);
+/// }
+/// ```
+pub fn diagnose_simulation(
+    err: Option<&serde_json::Value>,
+    logs: &[String],
+    quote: &QuoteResponse,
+) -> SimulationDiagnosis {
+    let mut diagnosis = SimulationDiagnosis::default();
+
+    if let Some(instruction_error) = err
+        .and_then(|e| e.get("InstructionError"))
+        .and_then(|e| e.as_array())
+    {
+        diagnosis.instruction_index = instruction_error.first().and_then(|i| i.as_u64());
+        diagnosis.error_code = instruction_error
+            .get(1)
+            .and_then(|e| e.get("Custom"))
+            .and_then(|c| c.as_u64())
+            .map(|c| c as u32);
+    }
+
+    let mut jupiter_depth = None;
+    let mut current_hop: Option<usize> = None;
+
+    for line in logs {
+        let Some(rest) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        let Some((program_id, rest)) = rest.split_once(' ') else {
+            continue;
+        };
+
+        if let Some(depth) = rest
+            .strip_prefix("invoke [")
+            .and_then(|d| d.strip_suffix(']'))
+            .and_then(|d| d.parse::<usize>().ok())
+        {
+            if program_id == JUPITER_PROGRAM_ID {
+                // Jupiter's event self-CPI is a nested invoke; hops are counted one level
+                // below the outermost Jupiter invoke only
+                if jupiter_depth.is_none_or(|j| depth <= j) {
+                    jupiter_depth = Some(depth);
+                }
+            } else if jupiter_depth.is_some_and(|j| depth == j + 1)
+                && !INFRASTRUCTURE_PROGRAMS.contains(&program_id)
+            {
+                current_hop = Some(current_hop.map_or(0, |hop| hop + 1));
+            }
+            continue;
+        }
+
+        if let Some(message) = rest.strip_prefix("failed: ") {
+            diagnosis.program_id = Some(program_id.to_string());
+            diagnosis.message = Some(message.to_string());
+            if diagnosis.error_code.is_none() {
+                diagnosis.error_code = message
+                    .strip_prefix("custom program error: 0x")
+                    .and_then(|code| u32::from_str_radix(code, 16).ok());
+            }
+            if program_id != JUPITER_PROGRAM_ID {
+                diagnosis.hop = current_hop;
+            }
+            break;
+        }
+    }
+
+    if let Some(step) = diagnosis.hop.and_then(|hop| quote.route_plan.get(hop)) {
+        diagnosis.venue = Some(step.swap_info.label.clone());
+        diagnosis.amm_key = Some(step.swap_info.amm_key.clone());
+    }
+
+    if diagnosis
+        .program_id
+        .as_deref()
+        .is_none_or(|id| id == JUPITER_PROGRAM_ID)
+    {
+        diagnosis.error_name = diagnosis
+            .error_code
+            .and_then(jupiter_error_name)
+            .map(str::to_string);
+    }
+
+    diagnosis
+}
+
+/// Returns the name of a Jupiter aggregator custom error code.
+pub fn jupiter_error_name(code: u32) -> Option<&'static str> {
+    let name = match code {
+        6000 => "EmptyRoute",
+        6001 => "SlippageToleranceExceeded",
+        6002 => "InvalidCalculation",
+        6003 => "MissingPlatformFeeAccount",
+        6004 => "InvalidSlippage",
+        6005 => "NotEnoughPercent",
+        6006 => "InvalidInputIndex",
+        6007 => "InvalidOutputIndex",
+        6008 => "NotEnoughAccountKeys",
+        6009 => "NonZeroMinimumOutAmountNotSupported",
+        6010 => "InvalidRoutePlan",
+        6011 => "InvalidReferralAuthority",
+        6012 => "LedgerTokenAccountDoesNotMatch",
+        6013 => "InvalidTokenLedger",
+        6014 => "IncorrectTokenProgramID",
+        6015 => "TokenProgramNotProvided",
+        6016 => "SwapNotSupported",
+        6017 => "ExactOutAmountNotMatched",
+        6018 => "SourceAndDestinationMintCannotBeTheSame",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// The part a top-level instruction plays in a composed swap transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InstructionRole {
+    ComputeBudget,
+    Setup,
+    Swap,
+    Cleanup,
+    /// Any instruction that is not part of the Jupiter response, e.g. your own companion instructions.
+    Other,
+}
+
+/// Compute units consumed by a single top-level instruction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstructionComputeUnits {
+    pub index: usize,
+    pub program_id: String,
+    pub role: InstructionRole,
+    pub consumed: u64,
+}
+
+/// Compute units consumed by a simulated transaction, attributed per instruction role.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputeUnitProfile {
+    pub instructions: Vec<InstructionComputeUnits>,
+    pub setup: u64,
+    pub swap: u64,
+    pub cleanup: u64,
+    pub other: u64,
+    pub total: u64,
+}
+
+impl ComputeUnitProfile {
+    /// Compute units left under `compute_unit_limit` after every profiled instruction.
+    pub fn headroom(&self, compute_unit_limit: u64) -> u64 {
+        compute_unit_limit.saturating_sub(self.total)
+    }
+}
+
+/// Attributes compute units across the top-level instructions of a simulated transaction.
+///
+/// Uses the `Program <id> invoke [1]` and `Program <id> consumed <n> of <m> compute units`
+/// log markers. Builtin programs such as the compute budget program don't log consumption
+/// and are reported with `0`.
+///
+/// # Arguments
+/// * `logs` - The program logs returned by `simulateTransaction`.
+/// * `roles` - The role of each top-level instruction, in transaction order. Use
+///   [`SwapInstructions::instruction_roles`](super::SwapInstructions::instruction_roles) and
+///   insert [`InstructionRole::Other`] wherever you add your own instructions.
+///
+/// # Example
+/// ```
+/// let roles = swap_instructions.instruction_roles();
+/// let profile = profile_compute_units(&simulation.logs, &roles);
+/// println!("swap used {} CU, {} CU left", profile.swap, profile.headroom(1_400_000));
+/// ```
+pub fn profile_compute_units(logs: &[String], roles: &[InstructionRole]) -> ComputeUnitProfile {
+    let mut profile = ComputeUnitProfile::default();
+    let mut depth = 0usize;
+
+    for line in logs {
+        let Some(rest) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        let Some((program_id, rest)) = rest.split_once(' ') else {
+            continue;
+        };
+
+        if rest.starts_with("invoke [") {
+            depth += 1;
+            if depth == 1 {
+                let index = profile.instructions.len();
+                profile.instructions.push(InstructionComputeUnits {
+                    index,
+                    program_id: program_id.to_string(),
+                    role: roles.get(index).copied().unwrap_or(InstructionRole::Other),
+                    consumed: 0,
+                });
+            }
+        } else if rest == "success" || rest.starts_with("failed") {
+            depth = depth.saturating_sub(1);
+        } else if depth == 1 {
+            let consumed = rest
+                .strip_prefix("consumed ")
+                .and_then(|r| r.split_once(' '))
+                .and_then(|(n, _)| n.parse::<u64>().ok());
+            if let (Some(consumed), Some(instruction)) = (consumed, profile.instructions.last_mut())
+            {
+                instruction.consumed = consumed;
+            }
+        }
+    }
+
+    for instruction in &profile.instructions {
+        match instruction.role {
+            InstructionRole::Setup => profile.setup += instruction.consumed,
+            InstructionRole::Swap => profile.swap += instruction.consumed,
+            InstructionRole::Cleanup => profile.cleanup += instruction.consumed,
+            InstructionRole::ComputeBudget | InstructionRole::Other => {
+                profile.other += instruction.consumed
+            }
+        }
+        profile.total += instruction.consumed;
+    }
+
+    profile
+}
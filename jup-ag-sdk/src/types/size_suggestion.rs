@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use super::QuoteResponse;
+
+/// The largest trade size that stays under a price impact threshold.
+///
+/// Returned by [`suggest_max_size`](crate::JupiterClient::suggest_max_size).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeSuggestion {
+    /// The raw amount originally requested.
+    pub requested_amount: u64,
+
+    /// The largest raw amount found under the threshold, or `0` if even the smallest probe exceeded it.
+    pub max_amount: u64,
+
+    /// The quote at `max_amount`, if any.
+    pub quote: Option<QuoteResponse>,
+
+    /// How many quotes the search used.
+    pub quotes_used: u32,
+}
+
+impl SizeSuggestion {
+    /// Whether the requested size exceeded the threshold and had to be reduced.
+    pub fn is_liquidity_limited(&self) -> bool {
+        self.max_amount < self.requested_amount
+    }
+
+    /// Price impact of the suggested size, as reported by Jupiter.
+    pub fn price_impact_pct(&self) -> Option<f64> {
+        self.quote
+            .as_ref()
+            .and_then(|quote| quote.price_impact_pct.parse().ok())
+    }
+}
@@ -0,0 +1,91 @@
+use std::{fmt, str::FromStr};
+
+use crate::JupiterClientError;
+
+/// A slippage tolerance, parsed from the forms people actually write in configs.
+///
+/// Accepts `"0.5%"`, `"50bps"`, `"50 bps"` or a bare `"50"` (bps), and percentages as `f64`.
+///
+/// # Example
+/// ```
+/// let slippage: Slippage = "0.5%".parse()?;
+/// assert_eq!(slippage.bps(), 50);
+///
+/// let request = QuoteRequest::new(SOL_MINT, USDC_MINT, 1_000_000_000)
+///     .slippage(Slippage::percent(0.5)?);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Slippage(u16);
+
+impl Slippage {
+    /// The default maximum accepted by [`JupiterClient`](crate::JupiterClient): 10%.
+    pub const DEFAULT_MAX: Slippage = Slippage(1_000);
+
+    pub const fn from_bps(bps: u16) -> Self {
+        Self(bps)
+    }
+
+    /// Converts a percentage, e.g. `0.5` for 0.5%, rounding to the nearest bps.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::InvalidSlippage`] for negative or non-finite values, and values
+    /// that would round to more than 100%.
+    pub fn percent(percent: f64) -> Result<Self, JupiterClientError> {
+        let bps = (percent * 100.0).round();
+        if !bps.is_finite() || !(0.0..=10_000.0).contains(&bps) {
+            return Err(JupiterClientError::InvalidSlippage(format!(
+                "{percent}% is not between 0% and 100%"
+            )));
+        }
+        Ok(Self(bps as u16))
+    }
+
+    pub fn bps(&self) -> u16 {
+        self.0
+    }
+
+    pub fn as_percent(&self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+}
+
+impl From<u16> for Slippage {
+    fn from(bps: u16) -> Self {
+        Self(bps)
+    }
+}
+
+impl TryFrom<f64> for Slippage {
+    type Error = JupiterClientError;
+
+    fn try_from(percent: f64) -> Result<Self, Self::Error> {
+        Self::percent(percent)
+    }
+}
+
+impl FromStr for Slippage {
+    type Err = JupiterClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || JupiterClientError::InvalidSlippage(format!("cannot parse {s:?}"));
+
+        if let Some(percent) = s.strip_suffix('%') {
+            return Self::percent(percent.trim().parse().map_err(|_| invalid())?);
+        }
+        let bps = s.strip_suffix("bps").unwrap_or(s).trim();
+        let bps: u16 = bps.parse().map_err(|_| invalid())?;
+        if bps > 10_000 {
+            return Err(JupiterClientError::InvalidSlippage(format!(
+                "{bps} bps is more than 100%"
+            )));
+        }
+        Ok(Self(bps))
+    }
+}
+
+impl fmt::Display for Slippage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.as_percent())
+    }
+}
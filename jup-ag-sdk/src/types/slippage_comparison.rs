@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+use super::{QuoteGetSwapModeEnum, QuoteResponse};
+
+/// The same quote fetched with fixed and with dynamic slippage.
+///
+/// Returned by [`compare_slippage_modes`](crate::JupiterClient::compare_slippage_modes).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlippageComparison {
+    pub fixed: QuoteResponse,
+    pub dynamic: QuoteResponse,
+
+    /// `other_amount_threshold` of the fixed-slippage quote.
+    pub fixed_threshold: u64,
+
+    /// `other_amount_threshold` of the dynamic-slippage quote.
+    pub dynamic_threshold: u64,
+}
+
+impl SlippageComparison {
+    pub fn new(fixed: QuoteResponse, dynamic: QuoteResponse) -> Self {
+        Self {
+            fixed_threshold: fixed.other_amount_threshold.parse().unwrap_or_default(),
+            dynamic_threshold: dynamic.other_amount_threshold.parse().unwrap_or_default(),
+            fixed,
+            dynamic,
+        }
+    }
+
+    /// `dynamic_threshold - fixed_threshold`, in raw units.
+    pub fn threshold_difference(&self) -> i128 {
+        self.dynamic_threshold as i128 - self.fixed_threshold as i128
+    }
+
+    /// How much better the dynamic worst case is for the user than the fixed one, in bps of
+    /// the fixed threshold. Negative when fixed slippage protects the user better.
+    ///
+    /// For `ExactIn` a higher minimum output is better, for `ExactOut` a lower maximum input.
+    pub fn dynamic_improvement_bps(&self) -> f64 {
+        if self.fixed_threshold == 0 {
+            return 0.0;
+        }
+        let difference = self.threshold_difference() as f64 / self.fixed_threshold as f64;
+        let improvement = match self.fixed.swap_mode {
+            QuoteGetSwapModeEnum::ExactIn => difference,
+            QuoteGetSwapModeEnum::ExactOut => -difference,
+        };
+        improvement * 10_000.0
+    }
+
+    /// The slippage dynamic mode settled on, in bps.
+    pub fn dynamic_slippage_bps(&self) -> u16 {
+        self.dynamic.slippage_bps
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    AccountSetup, QuoteResponse, SwapResponse, TokenMeta, WorstCaseFill, format_ui_amount,
+    holdings::WSOL_MINT,
+};
+use crate::JupiterClientError;
+
+/// Lamports paid per signature, on top of the priority fee.
+const BASE_FEE_LAMPORTS: u64 = 5_000;
+
+/// An amount of a token, ready for display.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewAmount {
+    pub mint: String,
+    pub symbol: String,
+
+    /// Raw amount.
+    pub amount: u64,
+
+    /// The amount in decimal UI units, without trailing zeros.
+    pub ui_amount: String,
+
+    pub usd_value: Option<f64>,
+}
+
+impl PreviewAmount {
+    fn new(meta: &TokenMeta, amount: u64) -> Self {
+        Self {
+            mint: meta.mint.clone(),
+            symbol: meta.symbol.clone(),
+            amount,
+            ui_amount: format_ui_amount(amount, meta.decimals),
+            usd_value: meta.usd_value(amount),
+        }
+    }
+}
+
+/// A fee charged along the route.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewFee {
+    /// The venue charging it, or `"Platform fee"` for the integrator fee.
+    pub label: String,
+    pub amount: PreviewAmount,
+}
+
+/// Everything a wallet confirm screen shows about a swap, in display units.
+///
+/// Built by [`JupiterClient::preview_swap`](crate::JupiterClient::preview_swap), or with
+/// [`SwapPreview::new`] from metadata you already have.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapPreview {
+    pub input: PreviewAmount,
+    pub output: PreviewAmount,
+
+    /// The minimum received or maximum spent after slippage.
+    pub worst_case: WorstCaseFill,
+
+    pub slippage_bps: u16,
+    pub price_impact_pct: f64,
+
+    /// Venue labels of the route, in order and without repeats.
+    pub venues: Vec<String>,
+
+    /// Non-zero fees of every hop, then the platform fee.
+    pub fees: Vec<PreviewFee>,
+
+    /// Base and priority fee in lamports, once the swap transaction is built.
+    pub network_fee_lamports: Option<u64>,
+
+    pub network_fee_usd: Option<f64>,
+
+    /// The block height after which the swap transaction expires, once built.
+    pub last_valid_block_height: Option<u64>,
+
+    /// The token accounts the swap creates for the wallet, once known, see
+    /// [`with_account_setup`](Self::with_account_setup).
+    pub account_setup: Option<AccountSetup>,
+
+    /// USD value of the rent in [`account_setup`](Self::account_setup).
+    pub account_rent_usd: Option<f64>,
+}
+
+impl SwapPreview {
+    /// Builds the preview of `quote`, and of `swap` if the transaction was already built.
+    ///
+    /// `tokens` must hold the metadata of the input and output mints and of every fee mint,
+    /// keyed by mint. Wrapped SOL's price, when present, values the network fee.
+    ///
+    /// # Errors
+    /// [`JupiterClientError::MissingTokenMetadata`] when a mint has no entry in `tokens`.
+    pub fn new(
+        quote: &QuoteResponse,
+        swap: Option<&SwapResponse>,
+        tokens: &HashMap<String, TokenMeta>,
+    ) -> Result<Self, JupiterClientError> {
+        let meta = |mint: &str| {
+            tokens
+                .get(mint)
+                .ok_or_else(|| JupiterClientError::MissingTokenMetadata(mint.to_string()))
+        };
+        let amount = |raw: &str| raw.parse::<u64>().unwrap_or_default();
+
+        let mut venues: Vec<String> = Vec::new();
+        let mut fees = Vec::new();
+        for step in &quote.route_plan {
+            let info = &step.swap_info;
+            if !venues.contains(&info.label) {
+                venues.push(info.label.clone());
+            }
+            let fee = amount(&info.fee_amount);
+            if fee > 0 {
+                fees.push(PreviewFee {
+                    label: info.label.clone(),
+                    amount: PreviewAmount::new(meta(&info.fee_mint)?, fee),
+                });
+            }
+        }
+        if let Some(platform_fee) = &quote.platform_fee {
+            let fee = amount(&platform_fee.amount);
+            let mint = platform_fee
+                .fee_mint
+                .as_deref()
+                .unwrap_or(&quote.output_mint);
+            if fee > 0 {
+                fees.push(PreviewFee {
+                    label: "Platform fee".to_string(),
+                    amount: PreviewAmount::new(meta(mint)?, fee),
+                });
+            }
+        }
+
+        let worst_case_meta = meta(quote.worst_case_mint())?;
+        let network_fee_lamports =
+            swap.map(|swap| BASE_FEE_LAMPORTS + swap.prioritization_fee_lamports);
+
+        Ok(Self {
+            input: PreviewAmount::new(meta(&quote.input_mint)?, amount(&quote.in_amount)),
+            output: PreviewAmount::new(meta(&quote.output_mint)?, amount(&quote.out_amount)),
+            worst_case: quote.worst_case_fill(worst_case_meta.decimals, &worst_case_meta.symbol)?,
+            slippage_bps: quote.slippage_bps,
+            price_impact_pct: quote.price_impact_pct.parse().unwrap_or_default(),
+            venues,
+            fees,
+            network_fee_lamports,
+            network_fee_usd: network_fee_lamports
+                .zip(tokens.get(WSOL_MINT))
+                .and_then(|(lamports, sol)| sol.usd_value(lamports)),
+            last_valid_block_height: swap.map(|swap| swap.last_valid_block_height),
+            account_setup: None,
+            account_rent_usd: None,
+        })
+    }
+
+    /// Adds the accounts the swap creates and their rent, valued with wrapped SOL's price
+    /// from `tokens` when present.
+    pub fn with_account_setup(
+        mut self,
+        setup: AccountSetup,
+        tokens: &HashMap<String, TokenMeta>,
+    ) -> Self {
+        self.account_rent_usd = tokens
+            .get(WSOL_MINT)
+            .and_then(|sol| sol.usd_value(setup.rent_lamports));
+        self.account_setup = Some(setup);
+        self
+    }
+
+    /// Total USD value of [`fees`](Self::fees), counting only priced tokens.
+    pub fn total_fees_usd(&self) -> f64 {
+        self.fees
+            .iter()
+            .filter_map(|fee| fee.amount.usd_value)
+            .sum()
+    }
+}
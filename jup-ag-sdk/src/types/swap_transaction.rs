@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
-use super::QuoteResponse;
+use super::{InstructionRole, QuoteResponse, route_instruction::patch_minimum_out};
+use crate::JupiterClientError;
 
 /// SwapRequest is a struct that represents the request body for the swap transaction.
 ///
@@ -108,7 +109,7 @@ pub enum PriorityLevel {
     VeryHigh,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SwapResponse {
     pub swap_transaction: String,
@@ -296,3 +297,55 @@ pub struct SwapInstructions {
     pub cleanup_instruction: Option<Instruction>,
     pub address_lookup_table_addresses: Vec<String>,
 }
+
+impl SwapInstructions {
+    /// Tightens the route's minimum output amount (`otherAmountThreshold`).
+    ///
+    /// Patches the slippage fields of the decoded `swap_instruction` so the on-chain program
+    /// rejects any fill below `minimum_out_amount`. Use this when you need stricter guarantees
+    /// than the quoted slippage when composing your own transaction.
+    ///
+    /// # Errors
+    /// * the swap instruction is not a Jupiter `ExactIn` route instruction.
+    /// * `minimum_out_amount` is lower than the threshold already encoded in the route.
+    ///
+    /// # Example
+    /// ```
+    /// let instructions = api.get_swap_instructions(&payload).await?;
+    /// let instructions = instructions.with_minimum_out_amount(99_500_000)?;
+    /// ```
+    pub fn with_minimum_out_amount(
+        mut self,
+        minimum_out_amount: u64,
+    ) -> Result<Self, JupiterClientError> {
+        patch_minimum_out(&mut self.swap_instruction, minimum_out_amount)?;
+        Ok(self)
+    }
+
+    /// Returns the role of each instruction when they are composed in the usual order:
+    /// compute budget, other, setup, swap, then cleanup.
+    ///
+    /// Feed this to [`profile_compute_units`](super::profile_compute_units) after simulating.
+    pub fn instruction_roles(&self) -> Vec<InstructionRole> {
+        let mut roles = Vec::new();
+        let count = |ixs: &Option<Vec<Instruction>>| ixs.as_ref().map_or(0, Vec::len);
+
+        roles.extend(std::iter::repeat_n(
+            InstructionRole::ComputeBudget,
+            count(&self.compute_budget_instructions),
+        ));
+        roles.extend(std::iter::repeat_n(
+            InstructionRole::Other,
+            count(&self.other_instructions),
+        ));
+        roles.extend(std::iter::repeat_n(
+            InstructionRole::Setup,
+            self.setup_instructions.len(),
+        ));
+        roles.push(InstructionRole::Swap);
+        if self.cleanup_instruction.is_some() {
+            roles.push(InstructionRole::Cleanup);
+        }
+        roles
+    }
+}
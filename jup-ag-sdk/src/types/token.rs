@@ -52,7 +52,78 @@ pub struct TokenPrice {
     pub price: String,
 
     #[serde(default)]
-    pub extra_info: Option<serde_json::Value>,
+    pub extra_info: Option<PriceExtraInfo>,
+}
+
+/// How much the Price API trusts a price, ordered from least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfidenceLevel {
+    Low,
+    Medium,
+    High,
+}
+
+/// The liquidity and trust signals returned with `showExtraInfo=true`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceExtraInfo {
+    #[serde(default)]
+    pub last_swapped_price: Option<LastSwappedPrice>,
+
+    #[serde(default)]
+    pub quoted_price: Option<QuotedPrice>,
+
+    pub confidence_level: ConfidenceLevel,
+
+    #[serde(default)]
+    pub depth: Option<PriceDepth>,
+}
+
+/// The prices of the most recent Jupiter swaps. Timestamps are Unix seconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LastSwappedPrice {
+    pub last_jupiter_sell_at: Option<u64>,
+    pub last_jupiter_sell_price: Option<String>,
+    pub last_jupiter_buy_at: Option<u64>,
+    pub last_jupiter_buy_price: Option<String>,
+}
+
+/// The prices Jupiter currently quotes for buying and selling. Timestamps are Unix seconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotedPrice {
+    pub buy_price: Option<String>,
+    pub buy_at: Option<u64>,
+    pub sell_price: Option<String>,
+    pub sell_at: Option<u64>,
+}
+
+/// Estimated price impact of buying and selling the token at several USD sizes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceDepth {
+    #[serde(default)]
+    pub buy_price_impact_ratio: Option<PriceImpactDepth>,
+
+    #[serde(default)]
+    pub sell_price_impact_ratio: Option<PriceImpactDepth>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceImpactDepth {
+    /// Price impact ratio keyed by trade size in USD, e.g. `"10"`, `"100"`, `"1000"`.
+    pub depth: HashMap<String, f64>,
+
+    pub timestamp: u64,
+}
+
+impl PriceImpactDepth {
+    /// The price impact ratio of a `usd_size` trade, if the API reported that size.
+    pub fn impact_at(&self, usd_size: u64) -> Option<f64> {
+        self.depth.get(&usd_size.to_string()).copied()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -101,7 +172,9 @@ pub struct NewTokens {
     pub freeze_authority: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A Token API ranking, see
+/// [`JupiterClient::get_tokens_by_category`](crate::JupiterClient::get_tokens_by_category).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Category {
     TopOrganicScore,
     TopTraded,
@@ -119,7 +192,32 @@ impl fmt::Display for Category {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Category {
+    pub const ALL: [Self; 3] = [Self::TopOrganicScore, Self::TopTraded, Self::TopTrending];
+}
+
+/// A Token API tag, see [`JupiterClient::get_tokens_by_tag`](crate::JupiterClient::get_tokens_by_tag).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenTag {
+    /// Tokens verified by Jupiter.
+    Verified,
+
+    /// Liquid staking tokens.
+    Lst,
+}
+
+impl fmt::Display for TokenTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Verified => "verified",
+            Self::Lst => "lst",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// The window a [`Category`] is ranked over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Interval {
     FiveMinutes,
     OneHour,
@@ -139,6 +237,15 @@ impl fmt::Display for Interval {
     }
 }
 
+impl Interval {
+    pub const ALL: [Self; 4] = [
+        Self::FiveMinutes,
+        Self::OneHour,
+        Self::SixHours,
+        Self::TwentyFourHours,
+    ];
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Price {
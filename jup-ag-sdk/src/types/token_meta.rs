@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use super::TokenInfo;
+
+/// The metadata needed to display amounts of a token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenMeta {
+    pub mint: String,
+    pub symbol: String,
+    pub name: String,
+    pub decimals: u8,
+    pub logo_uri: Option<String>,
+    pub usd_price: Option<f64>,
+}
+
+impl TokenMeta {
+    pub fn new(mint: impl Into<String>, symbol: impl Into<String>, decimals: u8) -> Self {
+        let symbol = symbol.into();
+        Self {
+            mint: mint.into(),
+            name: symbol.clone(),
+            symbol,
+            decimals,
+            logo_uri: None,
+            usd_price: None,
+        }
+    }
+
+    pub fn usd_price(mut self, usd_price: f64) -> Self {
+        self.usd_price = Some(usd_price);
+        self
+    }
+
+    /// `amount` in UI units, as a float for USD math. Use
+    /// [`WorstCaseFill`](super::WorstCaseFill) or [`SwapPreview`](super::SwapPreview) for display.
+    pub fn ui_amount(&self, amount: u64) -> f64 {
+        amount as f64 / 10f64.powi(self.decimals as i32)
+    }
+
+    /// The USD value of `amount`, if the token has a price.
+    pub fn usd_value(&self, amount: u64) -> Option<f64> {
+        self.usd_price.map(|price| self.ui_amount(amount) * price)
+    }
+}
+
+impl From<&TokenInfo> for TokenMeta {
+    fn from(info: &TokenInfo) -> Self {
+        Self {
+            mint: info.id.clone(),
+            symbol: info.symbol.clone(),
+            name: info.name.clone(),
+            decimals: info.decimals,
+            logo_uri: info.icon.clone(),
+            usd_price: info.usd_price,
+        }
+    }
+}
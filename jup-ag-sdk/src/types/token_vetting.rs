@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Severity, TokenInfo, TokenMeta, Warning};
+
+/// Whether a token looks safe to trade, with the reasons when it doesn't.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "verdict", content = "reasons")]
+pub enum TokenVerdict {
+    /// Known to the Token API, routable, and nothing flagged.
+    Tradable,
+
+    /// Tradable, but with risks worth showing to the user.
+    Warning(Vec<String>),
+
+    /// Unknown, unroutable, or flagged as critical by Shield.
+    Blocked(Vec<String>),
+}
+
+impl TokenVerdict {
+    pub fn is_tradable(&self) -> bool {
+        !matches!(self, TokenVerdict::Blocked(_))
+    }
+}
+
+/// The combined result of [`JupiterClient::vet_token`](crate::JupiterClient::vet_token).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenVetting {
+    pub mint: String,
+
+    /// Metadata from the Token API, when it knows the mint.
+    pub token: Option<TokenMeta>,
+
+    pub is_verified: bool,
+    pub mint_authority: Option<String>,
+    pub freeze_authority: Option<String>,
+
+    /// Shield warnings for the mint.
+    pub warnings: Vec<Warning>,
+
+    /// Whether Jupiter could route a quote out of the token.
+    pub tradable: bool,
+
+    pub verdict: TokenVerdict,
+}
+
+impl TokenVetting {
+    /// Combines what the Token API, Shield and a probe quote reported about `mint`.
+    ///
+    /// The token is [`Blocked`](TokenVerdict::Blocked) when the Token API doesn't know it, no
+    /// route exists, or Shield reports a critical warning. It gets a
+    /// [`Warning`](TokenVerdict::Warning) when it's unverified, still has a mint or freeze
+    /// authority, or Shield reports anything above info.
+    pub fn new(
+        mint: impl Into<String>,
+        info: Option<&TokenInfo>,
+        warnings: Vec<Warning>,
+        tradable: bool,
+    ) -> Self {
+        let mut blocked = Vec::new();
+        let mut flagged = Vec::new();
+
+        if info.is_none() {
+            blocked.push("unknown to the Token API".to_string());
+        }
+        if !tradable {
+            blocked.push("no route found".to_string());
+        }
+        for warning in &warnings {
+            let reason = format!("{}: {}", warning.warning_type, warning.message);
+            match warning.severity {
+                Severity::Critical => blocked.push(reason),
+                Severity::Info => {}
+                _ => flagged.push(reason),
+            }
+        }
+
+        let is_verified = info.and_then(|info| info.is_verified).unwrap_or(false);
+        let mint_authority = info.and_then(|info| info.mint_authority.clone());
+        let freeze_authority = info.and_then(|info| info.freeze_authority.clone());
+        if info.is_some() {
+            if !is_verified {
+                flagged.push("not verified".to_string());
+            }
+            if mint_authority.is_some() {
+                flagged.push("mint authority is set".to_string());
+            }
+            if freeze_authority.is_some() {
+                flagged.push("freeze authority is set".to_string());
+            }
+        }
+
+        let verdict = if !blocked.is_empty() {
+            TokenVerdict::Blocked(blocked)
+        } else if !flagged.is_empty() {
+            TokenVerdict::Warning(flagged)
+        } else {
+            TokenVerdict::Tradable
+        };
+
+        Self {
+            mint: mint.into(),
+            token: info.map(TokenMeta::from),
+            is_verified,
+            mint_authority,
+            freeze_authority,
+            warnings,
+            tradable,
+            verdict,
+        }
+    }
+}
@@ -1,4 +1,11 @@
-use crate::types::to_comma_string;
+use std::time::Duration;
+
+use crate::{
+    JupiterClientError,
+    ata::decode_pubkey,
+    rt::{SystemTime, UNIX_EPOCH},
+    types::{Slippage, Status, UltraExecuteCode},
+};
 use serde::{Deserialize, Serialize};
 
 /// Request for a base64-encoded unsigned trigger order creation transaction
@@ -122,6 +129,99 @@ impl CreateTriggerOrder {
         self.params.fee_bps = Some(fee.to_string());
         self
     }
+
+    /// Expires the order at `at`, truncated to whole seconds.
+    pub fn expires_at(mut self, at: SystemTime) -> Self {
+        let seconds = at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.params.expired_at = Some(seconds.to_string());
+        self
+    }
+
+    /// Expires the order `ttl` from now.
+    pub fn expires_in(self, ttl: Duration) -> Self {
+        self.expires_at(SystemTime::now() + ttl)
+    }
+
+    /// Sets how far the fill may deviate from the taking amount. Default: 0, an exact fill.
+    pub fn slippage(self, slippage: Slippage) -> Self {
+        self.slippage_bps(&slippage.bps().to_string())
+    }
+
+    /// Charges an integrator fee of `fee_bps`, paid into `fee_account`, a token account of
+    /// the Referral Program.
+    pub fn platform_fee(self, fee_account: &str, fee_bps: u16) -> Self {
+        self.fee_account(fee_account).fee_bps(&fee_bps.to_string())
+    }
+
+    /// Sets the compute unit price. Default: the 95th percentile of recent priority fees.
+    pub fn compute_unit_price_micro_lamports(self, price: u64) -> Self {
+        self.compute_unit_price(&price.to_string())
+    }
+
+    /// The raw amount of `input_mint` the order sells.
+    pub fn making_amount(&self) -> Result<u64, JupiterClientError> {
+        parse_param("makingAmount", &self.params.making_amount)
+    }
+
+    /// The raw amount of `output_mint` the order buys at least.
+    pub fn taking_amount(&self) -> Result<u64, JupiterClientError> {
+        parse_param("takingAmount", &self.params.taking_amount)
+    }
+
+    /// The expiry as a Unix timestamp in seconds, if one is set.
+    pub fn expiry(&self) -> Result<Option<u64>, JupiterClientError> {
+        self.params
+            .expired_at
+            .as_deref()
+            .map(|expired_at| parse_param("expiredAt", expired_at))
+            .transpose()
+    }
+
+    /// Checks what Jupiter would otherwise reject after a round trip: invalid addresses, zero
+    /// or unparsable amounts, identical mints, a fee without a fee account, and an expiry in
+    /// the past.
+    ///
+    /// Called by [`JupiterClient::create_trigger_order`](crate::JupiterClient::create_trigger_order)
+    /// before anything is sent.
+    pub fn validate(&self) -> Result<(), JupiterClientError> {
+        let invalid = |reason: String| Err(JupiterClientError::InvalidTriggerOrder(reason));
+        for address in [
+            &self.input_mint,
+            &self.output_mint,
+            &self.maker,
+            &self.payer,
+        ] {
+            decode_pubkey(address)?;
+        }
+        if self.input_mint == self.output_mint {
+            return invalid("input and output mints are the same".to_string());
+        }
+        if self.making_amount()? == 0 || self.taking_amount()? == 0 {
+            return invalid("making and taking amounts must be positive".to_string());
+        }
+        if let Some(fee_bps) = &self.params.fee_bps {
+            let fee_bps = parse_param("feeBps", fee_bps)?;
+            if fee_bps > 0 && self.fee_account.is_none() {
+                return invalid("feeBps requires a fee account".to_string());
+            }
+        }
+        if let Some(expiry) = self.expiry()? {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            if expiry <= now {
+                return invalid(format!("expiry {expiry} is in the past"));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_param(name: &str, value: &str) -> Result<u64, JupiterClientError> {
+    value.parse().map_err(|_| {
+        JupiterClientError::InvalidTriggerOrder(format!("{name} {value:?} is not an integer"))
+    })
 }
 
 impl Params {
@@ -173,9 +273,22 @@ pub struct TriggerResponse {
     #[serde(default)]
     pub order: Option<String>,
 
+    #[serde(default)]
     pub code: u8,
 }
 
+impl TriggerResponse {
+    /// Every unsigned transaction to sign and execute, in order: the single transaction of a
+    /// create or cancel, or each batch of a batch cancel.
+    pub fn unsigned_transactions(&self) -> Vec<&str> {
+        match &self.transactions {
+            Some(transactions) => transactions.iter().map(String::as_str).collect(),
+            None if self.transaction.is_empty() => Vec::new(),
+            None => vec![self.transaction.as_str()],
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteTriggerOrder {
@@ -197,19 +310,48 @@ impl ExecuteTriggerOrder {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExecuteTriggerOrderResponse {
-    pub code: u8,
+    /// `0` on success. Shares Ultra's execute codes, see [`execute_code`](Self::execute_code).
+    pub code: i32,
 
-    /// transaction signature
+    /// transaction signature, empty when the transaction was rejected before sending
+    #[serde(default)]
     pub signature: String,
 
     /// status of the transaction
-    pub status: String,
+    pub status: Status,
+
+    /// why the transaction failed
+    #[serde(default)]
+    pub error: Option<String>,
 
     /// solana PDA Trigger Order account
     #[serde(default)]
     pub order: Option<String>,
 }
 
+impl ExecuteTriggerOrderResponse {
+    pub fn is_success(&self) -> bool {
+        matches!(self.status, Status::Success) && self.code == 0
+    }
+
+    pub fn execute_code(&self) -> UltraExecuteCode {
+        UltraExecuteCode::from(self.code)
+    }
+
+    /// The response on success, otherwise a
+    /// [`TriggerExecutionFailed`](JupiterClientError::TriggerExecutionFailed) carrying the
+    /// code and error message.
+    pub fn into_result(self) -> Result<Self, JupiterClientError> {
+        if self.is_success() {
+            return Ok(self);
+        }
+        Err(JupiterClientError::TriggerExecutionFailed {
+            code: self.code,
+            error: self.error.unwrap_or_else(|| format!("{:?}", self.status)),
+        })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CancelTriggerOrder {
@@ -236,6 +378,14 @@ impl CancelTriggerOrder {
             compute_unit_price: None,
         }
     }
+
+    /// Checks that the maker and order are public keys, failing with
+    /// [`JupiterClientError::InvalidPubkey`] otherwise.
+    pub fn validate(&self) -> Result<(), JupiterClientError> {
+        decode_pubkey(&self.maker)?;
+        decode_pubkey(&self.order)?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -243,8 +393,8 @@ impl CancelTriggerOrder {
 pub struct CancelTriggerOrders {
     pub maker: String,
 
-    /// solana PDA Trigger Order account
-    #[serde(serialize_with = "to_comma_string")]
+    /// solana PDA Trigger Order accounts, every open order of the maker when empty
+    #[serde(rename = "orders", default, skip_serializing_if = "Vec::is_empty")]
     pub order: Vec<String>,
 
     /// In microlamports, defaults to 95th percentile of priority fees
@@ -254,6 +404,10 @@ pub struct CancelTriggerOrders {
 }
 
 impl CancelTriggerOrders {
+    /// How many orders Jupiter cancels per transaction; larger batches come back as several
+    /// transactions.
+    pub const ORDERS_PER_TRANSACTION: usize = 5;
+
     /// Arguments:
     /// maker: &str - The maker's wallet address
     /// orders: Vec<String> - Vector of solana PDA Trigger Order accounts
@@ -265,14 +419,40 @@ impl CancelTriggerOrders {
         }
     }
 
+    /// Cancels every open order of `maker`.
+    pub fn all(maker: &str) -> Self {
+        Self::new(maker, Vec::new())
+    }
+
     /// Sets the compute unit price in microlamports
     pub fn compute_unit_price(mut self, price: &str) -> Self {
         self.compute_unit_price = Some(price.to_string());
         self
     }
+
+    /// How many transactions the cancellation takes, `None` when cancelling every open
+    /// order, since Jupiter decides then.
+    pub fn transaction_count(&self) -> Option<usize> {
+        (!self.order.is_empty()).then(|| self.order.len().div_ceil(Self::ORDERS_PER_TRANSACTION))
+    }
+
+    /// Checks that the maker and every order are public keys, failing with
+    /// [`JupiterClientError::InvalidPubkey`] otherwise, and that no order is listed twice.
+    pub fn validate(&self) -> Result<(), JupiterClientError> {
+        decode_pubkey(&self.maker)?;
+        for (i, order) in self.order.iter().enumerate() {
+            decode_pubkey(order)?;
+            if self.order[..i].contains(order) {
+                return Err(JupiterClientError::InvalidTriggerOrder(format!(
+                    "order {order} is listed twice"
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetTriggerOrders {
     /// user wallet address to retrive orders for
@@ -296,7 +476,7 @@ pub struct GetTriggerOrders {
     pub output_mint: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum OrderStatus {
     Active,
@@ -322,6 +502,12 @@ impl GetTriggerOrders {
         self
     }
 
+    /// Sets the page number for pagination, starting at 1
+    pub fn page_number(mut self, page: u32) -> Self {
+        self.page = Some(page.to_string());
+        self
+    }
+
     /// Sets whether to include failed transactions
     pub fn include_failed_tx(mut self, include: bool) -> Self {
         self.include_failed_tx = Some(include.to_string());
@@ -358,6 +544,21 @@ pub struct OrderResponse {
     pub page: u32,
 }
 
+impl OrderResponse {
+    /// The status filter the orders were fetched with, when it is a known one.
+    pub fn status(&self) -> Option<OrderStatus> {
+        match self.order_status.as_str() {
+            "active" => Some(OrderStatus::Active),
+            "history" => Some(OrderStatus::History),
+            _ => None,
+        }
+    }
+
+    pub fn has_next_page(&self) -> bool {
+        self.page < self.total_pages
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Order {
@@ -365,13 +566,21 @@ pub struct Order {
     pub order_key: String,
     pub input_mint: String,
     pub output_mint: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub making_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub taking_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub remaining_making_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub remaining_taking_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_making_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_taking_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_remaining_making_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_remaining_taking_amount: String,
     pub slippage_bps: String,
     #[serde(default)]
@@ -392,12 +601,18 @@ pub struct Trade {
     pub keeper: String,
     pub input_mint: String,
     pub output_mint: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub input_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub output_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_input_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_output_amount: String,
     pub fee_mint: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub fee_amount: String,
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub raw_fee_amount: String,
     pub tx_id: String,
     pub confirmed_at: String,
@@ -1,11 +1,12 @@
 use super::{PlatformFee, QuoteGetSwapModeEnum, RoutePlanItem, vec_to_comma_string};
+use crate::{JupiterClientError, ata::decode_pubkey};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::RangeInclusive};
 
 /// Request for a base64-encoded unsigned swap transaction to be used in POST
 ///
 /// [Official API docs](https://dev.jup.ag/docs/api/ultra-api/order)
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UltraOrderRequest {
     /// The mint address of the input token.
@@ -117,7 +118,8 @@ impl UltraOrderRequest {
     /// The updated UltraOrderRequest with referral fee set
     ///
     /// # Panics
-    /// Panics if fee is less than 50 or greater than 255
+    /// Panics if fee is less than 50 or greater than 255. Use
+    /// [`referral`](Self::referral) to validate the fee instead.
     ///
     /// # Example
     /// ```
@@ -136,7 +138,7 @@ impl UltraOrderRequest {
     ///
     ///
     /// # Arguments
-    /// * `exclude_dexes` - A vector of DEX names to exclude (e.g., `[metis, jupiterz, hashflow, dflow, pyth, okx]`).
+    /// * `exclude_routers` - A vector of router names to exclude (e.g., `[metis, jupiterz, hashflow, dflow, pyth, okx]`).
     ///
     /// # Returns
     /// The modified `UltraOrderRequest` for chaining.
@@ -148,12 +150,89 @@ impl UltraOrderRequest {
     ///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
     ///     1_000_000_000
     /// )
-    /// .exclude_dexes(vec!["okx".to_string(), "pyth".to_string()]);
+    /// .exclude_routers(vec!["okx".to_string(), "pyth".to_string()]);
     /// ```
     pub fn exclude_routers(mut self, exclude_routers: Vec<String>) -> Self {
         self.exclude_routers = Some(exclude_routers);
         self
     }
+
+    /// Charges the integrator fee of `referral`, setting the referral account and fee
+    /// together.
+    ///
+    /// # Example
+    /// ```
+    /// let referral = UltraReferral::new("referral account address", 100)?; // 1%
+    /// let request = UltraOrderRequest::new(SOL_MINT, JUP_MINT, 1_000_000_000)
+    ///     .add_taker("taker wallet address")
+    ///     .referral(&referral);
+    /// ```
+    pub fn referral(mut self, referral: &UltraReferral) -> Self {
+        self.referral_account = Some(referral.account.clone());
+        self.referral_fee = Some(referral.fee_bps);
+        self
+    }
+
+    /// Checks the referral parameters: the account and fee are set together, the account is
+    /// a valid address and the fee within [`UltraReferral::FEE_BPS`].
+    ///
+    /// Called by [`JupiterClient::get_ultra_order`](crate::JupiterClient::get_ultra_order)
+    /// before anything is sent.
+    pub fn validate(&self) -> Result<(), JupiterClientError> {
+        match (&self.referral_account, self.referral_fee) {
+            (None, None) => Ok(()),
+            (Some(account), Some(fee_bps)) => UltraReferral::new(account, fee_bps).map(|_| ()),
+            (Some(_), None) => Err(JupiterClientError::InvalidReferral(
+                "a referral account requires a referral fee".to_string(),
+            )),
+            (None, Some(_)) => Err(JupiterClientError::InvalidReferral(
+                "a referral fee requires a referral account".to_string(),
+            )),
+        }
+    }
+}
+
+/// An integrator fee on Ultra orders, paid into a referral account.
+///
+/// Ultra takes its own referral parameters rather than the Swap API's `platformFeeBps` and
+/// `feeAccount`: the fee is collected into a Referral Program account, and Jupiter keeps a
+/// share of it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UltraReferral {
+    account: String,
+    fee_bps: u8,
+}
+
+impl UltraReferral {
+    /// The fees Ultra accepts, in bps.
+    pub const FEE_BPS: RangeInclusive<u8> = 50..=255;
+
+    /// # Errors
+    /// [`JupiterClientError::InvalidPubkey`] when `account` is not a valid address, and
+    /// [`JupiterClientError::InvalidReferral`] when `fee_bps` is outside [`Self::FEE_BPS`].
+    pub fn new(account: &str, fee_bps: u8) -> Result<Self, JupiterClientError> {
+        decode_pubkey(account)?;
+        if !Self::FEE_BPS.contains(&fee_bps) {
+            return Err(JupiterClientError::InvalidReferral(format!(
+                "fee of {fee_bps} bps is outside {}..={} bps",
+                Self::FEE_BPS.start(),
+                Self::FEE_BPS.end()
+            )));
+        }
+        Ok(Self {
+            account: account.to_string(),
+            fee_bps,
+        })
+    }
+
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    pub fn fee_bps(&self) -> u8 {
+        self.fee_bps
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -166,14 +245,17 @@ pub struct UltraOrderResponse {
     pub output_mint: String,
 
     /// The raw input token amount.
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub in_amount: String,
 
     /// The raw output token amount (excluding slippage or fees).
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub out_amount: String,
 
     /// The worst-case output amount after slippage & fees.
     ///
     /// Not used by `/swap`, but useful for displaying expectations.
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub other_amount_threshold: String,
 
     /// Indicates the swap mode used (ExactIn or ExactOut).
@@ -261,24 +343,119 @@ pub struct UltraExecuteOrderResponse {
     #[serde(default)]
     pub error: Option<String>,
 
-    pub code: u32,
+    /// `0` on success. Negative for Ultra and landing failures, `6000` and above for
+    /// aggregator program errors, see [`execute_code`](Self::execute_code).
+    pub code: i32,
 
-    #[serde(default)]
+    #[serde(default, deserialize_with = "super::amount::option_string_or_number")]
     pub total_input_amount: Option<String>,
 
-    #[serde(default)]
+    #[serde(default, deserialize_with = "super::amount::option_string_or_number")]
     pub total_output_amount: Option<String>,
 
-    #[serde(default)]
+    #[serde(default, deserialize_with = "super::amount::option_string_or_number")]
     pub input_amount_result: Option<String>,
 
-    #[serde(default)]
+    #[serde(default, deserialize_with = "super::amount::option_string_or_number")]
     pub output_amount_result: Option<String>,
 
     #[serde(default)]
     pub swap_events: Option<Vec<SwapEvent>>,
 }
 
+impl UltraExecuteOrderResponse {
+    pub fn is_success(&self) -> bool {
+        matches!(self.status, Status::Success)
+    }
+
+    pub fn execute_code(&self) -> UltraExecuteCode {
+        UltraExecuteCode::from(self.code)
+    }
+
+    /// How far the output that landed fell short of what `order` quoted, in bps. Negative
+    /// when the swap did better than quoted; `None` until an output amount is reported.
+    pub fn realized_slippage_bps(&self, order: &UltraOrderResponse) -> Option<f64> {
+        let quoted = order
+            .out_amount
+            .parse::<u64>()
+            .ok()
+            .filter(|&out| out > 0)?;
+        let landed = self.output_amount_result.as_deref()?.parse::<u64>().ok()?;
+        Some((quoted as f64 - landed as f64) / quoted as f64 * 10_000.0)
+    }
+}
+
+/// The meaning of [`UltraExecuteOrderResponse::code`].
+///
+/// [Official API docs](https://dev.jup.ag/docs/ultra-api/execute-order#response-codes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UltraExecuteCode {
+    Success,
+
+    /// The `requestId` has no cached order, e.g. because it expired.
+    MissingCachedOrder,
+    InvalidSignedTransaction,
+    InvalidMessageBytes,
+
+    /// The transaction didn't land in time. Fetch a new order and retry.
+    FailedToLand,
+    Unknown,
+    InvalidTransaction,
+    TransactionNotFullySigned,
+    InvalidBlockHeight,
+
+    /// RFQ only: the payload sent to the market maker was rejected.
+    InvalidPayload,
+
+    /// RFQ only: the market maker's quote expired.
+    QuoteExpired,
+
+    /// RFQ only: the market maker rejected the swap.
+    SwapRejected,
+
+    /// A Jupiter aggregator program error, named by
+    /// [`jupiter_error_name`](super::jupiter_error_name).
+    Program(u32),
+
+    Other(i32),
+}
+
+impl From<i32> for UltraExecuteCode {
+    fn from(code: i32) -> Self {
+        match code {
+            0 => Self::Success,
+            -1 => Self::MissingCachedOrder,
+            -2 => Self::InvalidSignedTransaction,
+            -3 => Self::InvalidMessageBytes,
+            -1000 | -2000 => Self::FailedToLand,
+            -1001 | -2001 => Self::Unknown,
+            -1002 => Self::InvalidTransaction,
+            -1003 => Self::TransactionNotFullySigned,
+            -1004 => Self::InvalidBlockHeight,
+            -2002 => Self::InvalidPayload,
+            -2003 => Self::QuoteExpired,
+            -2004 => Self::SwapRejected,
+            6000.. => Self::Program(code as u32),
+            other => Self::Other(other),
+        }
+    }
+}
+
+impl UltraExecuteCode {
+    /// Whether fetching a new order and signing it again may succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::MissingCachedOrder
+                | Self::FailedToLand
+                | Self::InvalidBlockHeight
+                | Self::QuoteExpired
+                | Self::SwapRejected
+                | Self::Program(6001)
+        )
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Status {
     Success,
@@ -289,14 +466,17 @@ pub enum Status {
 #[serde(rename_all = "camelCase")]
 pub struct SwapEvent {
     pub input_mint: Option<String>,
+    #[serde(default, deserialize_with = "super::amount::option_string_or_number")]
     pub input_amount: Option<String>,
     pub output_mint: Option<String>,
+    #[serde(default, deserialize_with = "super::amount::option_string_or_number")]
     pub output_amount: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TokenBalance {
+    #[serde(deserialize_with = "super::amount::string_or_number")]
     pub amount: String,
     pub ui_amount: f64,
     pub slot: u64,
@@ -305,17 +485,194 @@ pub struct TokenBalance {
 
 pub type TokenBalancesResponse = HashMap<String, TokenBalance>;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Token-safety warnings from Ultra Shield, see
+/// [`JupiterClient::get_shield`](crate::JupiterClient::get_shield).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Shield {
+    /// Warnings keyed by mint. Mints without warnings may be missing.
     pub warnings: HashMap<String, Vec<Warning>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Shield {
+    /// The warnings reported for `mint`, empty when it has none.
+    pub fn warnings_for(&self, mint: &str) -> &[Warning] {
+        self.warnings
+            .get(mint)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Whether `mint` is flagged with `warning_type`.
+    pub fn has_warning(&self, mint: &str, warning_type: &WarningType) -> bool {
+        self.warnings_for(mint)
+            .iter()
+            .any(|warning| &warning.warning_type == warning_type)
+    }
+
+    /// Whether any warning of `mint` is [`Severity::Critical`].
+    pub fn is_critical(&self, mint: &str) -> bool {
+        self.warnings_for(mint)
+            .iter()
+            .any(|warning| warning.severity == Severity::Critical)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Warning {
     #[serde(rename = "type")]
-    pub warning_type: String,
+    pub warning_type: WarningType,
     pub message: String,
-    pub severity: String,
+    pub severity: Severity,
+}
+
+/// The risk a Shield [`Warning`] reports.
+///
+/// Types Jupiter adds later parse as [`Other`](WarningType::Other) instead of failing, so
+/// match with a wildcard arm.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum WarningType {
+    NotVerified,
+    LowLiquidity,
+    NotSellable,
+    LowOrganicActivity,
+    HasMintAuthority,
+    HasFreezeAuthority,
+    HasPermanentDelegate,
+    NewListing,
+    VeryLowTradingActivity,
+    HighSupplyConcentration,
+    NonTransferable,
+    MutableTransferFees,
+    SuspiciousDevActivity,
+    SuspiciousTopHolderActivity,
+    HighSingleOwnership,
+    /// Any type not modelled above, as sent by Shield.
+    Other(String),
+}
+
+impl WarningType {
+    /// The type as Shield sends it, e.g. `"HAS_FREEZE_AUTHORITY"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            WarningType::NotVerified => "NOT_VERIFIED",
+            WarningType::LowLiquidity => "LOW_LIQUIDITY",
+            WarningType::NotSellable => "NOT_SELLABLE",
+            WarningType::LowOrganicActivity => "LOW_ORGANIC_ACTIVITY",
+            WarningType::HasMintAuthority => "HAS_MINT_AUTHORITY",
+            WarningType::HasFreezeAuthority => "HAS_FREEZE_AUTHORITY",
+            WarningType::HasPermanentDelegate => "HAS_PERMANENT_DELEGATE",
+            WarningType::NewListing => "NEW_LISTING",
+            WarningType::VeryLowTradingActivity => "VERY_LOW_TRADING_ACTIVITY",
+            WarningType::HighSupplyConcentration => "HIGH_SUPPLY_CONCENTRATION",
+            WarningType::NonTransferable => "NON_TRANSFERABLE",
+            WarningType::MutableTransferFees => "MUTABLE_TRANSFER_FEES",
+            WarningType::SuspiciousDevActivity => "SUSPICIOUS_DEV_ACTIVITY",
+            WarningType::SuspiciousTopHolderActivity => "SUSPICIOUS_TOP_HOLDER_ACTIVITY",
+            WarningType::HighSingleOwnership => "HIGH_SINGLE_OWNERSHIP",
+            WarningType::Other(warning_type) => warning_type,
+        }
+    }
+}
+
+impl std::fmt::Display for WarningType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for WarningType {
+    fn from(warning_type: &str) -> Self {
+        match warning_type {
+            "NOT_VERIFIED" => WarningType::NotVerified,
+            "LOW_LIQUIDITY" => WarningType::LowLiquidity,
+            "NOT_SELLABLE" => WarningType::NotSellable,
+            "LOW_ORGANIC_ACTIVITY" => WarningType::LowOrganicActivity,
+            "HAS_MINT_AUTHORITY" => WarningType::HasMintAuthority,
+            "HAS_FREEZE_AUTHORITY" => WarningType::HasFreezeAuthority,
+            "HAS_PERMANENT_DELEGATE" => WarningType::HasPermanentDelegate,
+            "NEW_LISTING" => WarningType::NewListing,
+            "VERY_LOW_TRADING_ACTIVITY" => WarningType::VeryLowTradingActivity,
+            "HIGH_SUPPLY_CONCENTRATION" => WarningType::HighSupplyConcentration,
+            "NON_TRANSFERABLE" => WarningType::NonTransferable,
+            "MUTABLE_TRANSFER_FEES" => WarningType::MutableTransferFees,
+            "SUSPICIOUS_DEV_ACTIVITY" => WarningType::SuspiciousDevActivity,
+            "SUSPICIOUS_TOP_HOLDER_ACTIVITY" => WarningType::SuspiciousTopHolderActivity,
+            "HIGH_SINGLE_OWNERSHIP" => WarningType::HighSingleOwnership,
+            other => WarningType::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for WarningType {
+    fn from(warning_type: String) -> Self {
+        match WarningType::from(warning_type.as_str()) {
+            WarningType::Other(_) => WarningType::Other(warning_type),
+            known => known,
+        }
+    }
+}
+
+impl From<WarningType> for String {
+    fn from(warning_type: WarningType) -> Self {
+        match warning_type {
+            WarningType::Other(warning_type) => warning_type,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+/// How serious a Shield [`Warning`] is. Parsed case-insensitively.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum Severity {
+    /// Shown for context; doesn't make the token riskier.
+    Info,
+    Warning,
+    /// Trading the token is likely to lose funds.
+    Critical,
+    /// Any severity not modelled above, as sent by Shield.
+    Other(String),
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+            Severity::Other(severity) => severity,
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for Severity {
+    fn from(severity: &str) -> Self {
+        match severity.to_ascii_lowercase().as_str() {
+            "info" => Severity::Info,
+            "warning" => Severity::Warning,
+            "critical" => Severity::Critical,
+            _ => Severity::Other(severity.to_string()),
+        }
+    }
+}
+
+impl From<String> for Severity {
+    fn from(severity: String) -> Self {
+        Severity::from(severity.as_str())
+    }
+}
+
+impl From<Severity> for String {
+    fn from(severity: Severity) -> Self {
+        severity.as_str().to_string()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+use super::{HoldingsSource, QuoteResponse, SwapResponse};
+use crate::JupiterClientError;
+
+/// "The user must receive exactly `amount` of `output_mint` at `destination_token_account`".
+///
+/// # Example
+/// ```
+/// let request = WithdrawalRequest::new(USDC_MINT, 250_000_000, "UserUsdcTokenAccount...")
+///     .funding_mints(vec![USDC_MINT.to_string(), SOL_MINT.to_string(), USDT_MINT.to_string()]);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalRequest {
+    pub output_mint: String,
+
+    /// Raw amount the destination must receive.
+    pub amount: u64,
+
+    /// Initialized token account of `output_mint` that receives the funds.
+    pub destination_token_account: String,
+
+    /// Mints the hot wallet may spend, in order of preference.
+    pub funding_mints: Vec<String>,
+
+    /// Slippage tolerance applied to the input side of the `ExactOut` quote.
+    pub slippage_bps: Option<u16>,
+
+    /// Where the hot wallet's balances are read from. Default: the Ultra balances endpoint.
+    #[serde(skip)]
+    pub holdings_source: HoldingsSource,
+}
+
+impl WithdrawalRequest {
+    pub fn new(
+        output_mint: impl Into<String>,
+        amount: u64,
+        destination_token_account: impl Into<String>,
+    ) -> Self {
+        Self {
+            output_mint: output_mint.into(),
+            amount,
+            destination_token_account: destination_token_account.into(),
+            funding_mints: Vec::new(),
+            slippage_bps: None,
+            holdings_source: HoldingsSource::default(),
+        }
+    }
+
+    pub fn funding_mints(mut self, funding_mints: Vec<String>) -> Self {
+        self.funding_mints = funding_mints;
+        self
+    }
+
+    pub fn slippage_bps(mut self, slippage_bps: u16) -> Self {
+        self.slippage_bps = Some(slippage_bps);
+        self
+    }
+
+    pub fn holdings_source(mut self, holdings_source: HoldingsSource) -> Self {
+        self.holdings_source = holdings_source;
+        self
+    }
+}
+
+/// A funded `ExactOut` swap ready to be signed, delivering the withdrawal amount.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalConversion {
+    /// The mint selected to fund the withdrawal.
+    pub funding_mint: String,
+
+    /// Raw amount of the funding mint spent at most, slippage included.
+    pub max_in_amount: u64,
+
+    /// Raw amount the destination must receive.
+    pub expected_out_amount: u64,
+
+    pub destination_token_account: String,
+
+    pub quote: QuoteResponse,
+
+    pub swap: SwapResponse,
+}
+
+impl WithdrawalConversion {
+    /// Checks the amount the destination actually received once the swap landed.
+    ///
+    /// `received` is the balance change of the destination token account, e.g. from the
+    /// transaction's pre and post token balances.
+    pub fn verify(&self, received: u64) -> Result<(), JupiterClientError> {
+        if received == self.expected_out_amount {
+            Ok(())
+        } else {
+            Err(JupiterClientError::FillMismatch {
+                expected: self.expected_out_amount,
+                received,
+            })
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Per-venue failure tracking that keeps failing DEXes out of routes for a while.
+//!
+//! Enable with [`JupiterClient::with_venue_health`](crate::JupiterClient::with_venue_health).
+//! Every fresh quote counts towards the venues on its route. Fills and failures are reported
+//! with [`record_fill`](crate::JupiterClient::record_fill) and
+//! [`record_venue_failure`](crate::JupiterClient::record_venue_failure), and simulation
+//! failures seen by
+//! [`get_swap_transaction_excluding_failing_amms`](crate::JupiterClient::get_swap_transaction_excluding_failing_amms)
+//! are recorded automatically.
+//!
+//! After `failure_threshold` failures of a venue without a fill in between, the venue is
+//! added to `exclude_dexes` of every quote for `cooldown`. Quotes that name their own `dexes`
+//! are left alone. Once the cooldown ends the venue is routed again, and a further
+//! `failure_threshold` failures exclude it for another cooldown.
+
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use crate::{
+    clock::Instant,
+    types::{DexEnum, QuoteResponse},
+};
+
+/// Why a fill through a venue failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VenueFailure {
+    /// The transaction failed in simulation inside the venue's hop.
+    Simulation,
+
+    /// The transaction landed and reverted inside the venue's hop.
+    Revert,
+}
+
+/// A row of [`JupiterClient::venue_health`](crate::JupiterClient::venue_health).
+#[derive(Debug, Clone, PartialEq)]
+pub struct VenueHealth {
+    /// Venue label, e.g. `"Whirlpool"`.
+    pub venue: String,
+
+    /// Fresh quotes routed through the venue.
+    pub quotes: u64,
+
+    /// Fills routed through the venue.
+    pub fills: u64,
+
+    pub simulation_failures: u64,
+
+    pub reverts: u64,
+
+    /// Failures since the last fill or exclusion.
+    pub consecutive_failures: u32,
+
+    /// When the venue is routed again, while it is excluded.
+    pub excluded_until: Option<Instant>,
+}
+
+impl VenueHealth {
+    pub fn failures(&self) -> u64 {
+        self.simulation_failures + self.reverts
+    }
+
+    /// The share of attempted fills that failed, `None` before any fill was attempted.
+    pub fn failure_rate(&self) -> Option<f64> {
+        let attempts = self.fills + self.failures();
+        (attempts > 0).then(|| self.failures() as f64 / attempts as f64)
+    }
+
+    pub fn is_excluded(&self) -> bool {
+        self.excluded_until.is_some()
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Counters {
+    quotes: u64,
+    fills: u64,
+    simulation_failures: u64,
+    reverts: u64,
+    consecutive_failures: u32,
+    excluded_until: Option<Instant>,
+}
+
+#[derive(Debug)]
+pub(crate) struct VenueHealthMonitor {
+    failure_threshold: u32,
+    cooldown: Duration,
+    venues: Mutex<HashMap<String, Counters>>,
+}
+
+impl VenueHealthMonitor {
+    pub(crate) fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            venues: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn record_quote(&self, quote: &QuoteResponse) {
+        let mut venues = self.venues.lock().unwrap_or_else(|e| e.into_inner());
+        for step in &quote.route_plan {
+            venues
+                .entry(step.swap_info.label.clone())
+                .or_default()
+                .quotes += 1;
+        }
+    }
+
+    pub(crate) fn record_fill(&self, quote: &QuoteResponse) {
+        let mut venues = self.venues.lock().unwrap_or_else(|e| e.into_inner());
+        for step in &quote.route_plan {
+            let counters = venues.entry(step.swap_info.label.clone()).or_default();
+            counters.fills += 1;
+            counters.consecutive_failures = 0;
+        }
+    }
+
+    pub(crate) fn record_failure(&self, venue: &str, failure: VenueFailure, now: Instant) {
+        let mut venues = self.venues.lock().unwrap_or_else(|e| e.into_inner());
+        let counters = venues.entry(venue.to_string()).or_default();
+        match failure {
+            VenueFailure::Simulation => counters.simulation_failures += 1,
+            VenueFailure::Revert => counters.reverts += 1,
+        }
+        counters.consecutive_failures += 1;
+        if counters.consecutive_failures >= self.failure_threshold {
+            counters.consecutive_failures = 0;
+            counters.excluded_until = Some(now + self.cooldown);
+        }
+    }
+
+    /// The venues whose cooldown hasn't ended at `now`.
+    pub(crate) fn excluded(&self, now: Instant) -> Vec<DexEnum> {
+        let venues = self.venues.lock().unwrap_or_else(|e| e.into_inner());
+        let mut excluded: Vec<DexEnum> = venues
+            .iter()
+            .filter(|(_, counters)| counters.excluded_until.is_some_and(|until| now < until))
+            .map(|(venue, _)| DexEnum::from(venue.as_str()))
+            .collect();
+        excluded.sort_by_key(|dex| dex.to_string());
+        excluded
+    }
+
+    /// Every venue seen so far, ordered by label.
+    pub(crate) fn report(&self, now: Instant) -> Vec<VenueHealth> {
+        let venues = self.venues.lock().unwrap_or_else(|e| e.into_inner());
+        let mut report: Vec<VenueHealth> = venues
+            .iter()
+            .map(|(venue, counters)| VenueHealth {
+                venue: venue.clone(),
+                quotes: counters.quotes,
+                fills: counters.fills,
+                simulation_failures: counters.simulation_failures,
+                reverts: counters.reverts,
+                consecutive_failures: counters.consecutive_failures,
+                excluded_until: counters.excluded_until.filter(|&until| now < until),
+            })
+            .collect();
+        report.sort_by(|a, b| a.venue.cmp(&b.venue));
+        report
+    }
+}
@@ -0,0 +1,141 @@
+//! Non-fatal anomalies noticed while decoding responses, delivered to pluggable sinks.
+//!
+//! Register sinks with [`JupiterClient::with_warning_sink`](crate::JupiterClient::with_warning_sink).
+//! Warnings never change what a call returns; they surface conditions that are easy to miss,
+//! such as a venue label this SDK doesn't model yet or a quote computed on a lagging slot.
+
+use std::{
+    fmt,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use crate::types::{DexEnum, QuoteResponse};
+
+/// How many slots a quote may trail the newest quote seen by the client before
+/// [`ClientWarning::StaleQuote`] is emitted. About 10 seconds at 400ms slots.
+pub const STALE_QUOTE_SLOTS: u64 = 25;
+
+/// A condition worth knowing about that did not fail the call.
+///
+/// New variants are added as new checks ship, so match with a wildcard arm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ClientWarning {
+    /// A route hop used a venue label with no dedicated [`DexEnum`] variant.
+    UnknownDexLabel { label: String },
+
+    /// A field was missing from the response and filled with a placeholder.
+    FieldDefaulted {
+        /// Path of the field in the response, e.g. `routePlan.swapInfo.feeMint`.
+        field: &'static str,
+        value: String,
+    },
+
+    /// A quote was computed on a slot well behind the newest quote seen by the client.
+    StaleQuote { context_slot: u64, latest_slot: u64 },
+}
+
+impl fmt::Display for ClientWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientWarning::UnknownDexLabel { label } => {
+                write!(f, "unknown DEX label encountered: {label}")
+            }
+            ClientWarning::FieldDefaulted { field, value } => {
+                write!(f, "response field {field} missing, defaulted to {value:?}")
+            }
+            ClientWarning::StaleQuote {
+                context_slot,
+                latest_slot,
+            } => write!(
+                f,
+                "quote computed at slot {context_slot}, {} slots behind slot {latest_slot}",
+                latest_slot - context_slot
+            ),
+        }
+    }
+}
+
+/// A destination for [`ClientWarning`]s, e.g. a log or an alerting counter.
+///
+/// Implemented for any `Fn(&ClientWarning)`, so closures can be registered directly.
+///
+/// # Example
+/// ```
+/// let client = JupiterClient::new("https://lite-api.jup.ag")
+///     .with_warning_sink(|warning: &ClientWarning| eprintln!("jupiter: {warning}"));
+/// ```
+pub trait WarningSink: Send + Sync {
+    fn warn(&self, warning: &ClientWarning);
+}
+
+impl<F> WarningSink for F
+where
+    F: Fn(&ClientWarning) + Send + Sync,
+{
+    fn warn(&self, warning: &ClientWarning) {
+        self(warning)
+    }
+}
+
+/// The warning sinks registered on a client, and the newest quote slot seen by it.
+///
+/// Clones share the slot, so staleness is judged across every copy of the client.
+#[derive(Clone, Default)]
+pub(crate) struct WarningSinks {
+    pub(crate) sinks: Vec<Arc<dyn WarningSink>>,
+    latest_slot: Arc<AtomicU64>,
+}
+
+impl WarningSinks {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    pub(crate) fn emit(&self, warning: &ClientWarning) {
+        for sink in &self.sinks {
+            sink.warn(warning);
+        }
+    }
+
+    /// Checks a freshly fetched quote for unknown venues, defaulted fields and staleness.
+    pub(crate) fn check_quote(&self, quote: &QuoteResponse) {
+        let mut labels = Vec::new();
+        for step in &quote.route_plan {
+            let info = &step.swap_info;
+            if matches!(DexEnum::from(info.label.as_str()), DexEnum::Other(_))
+                && !labels.contains(&info.label)
+            {
+                labels.push(info.label.clone());
+                self.emit(&ClientWarning::UnknownDexLabel {
+                    label: info.label.clone(),
+                });
+            }
+            if info.fee_mint == "0" {
+                self.emit(&ClientWarning::FieldDefaulted {
+                    field: "routePlan.swapInfo.feeMint",
+                    value: info.fee_mint.clone(),
+                });
+            }
+        }
+
+        let latest_slot = self
+            .latest_slot
+            .fetch_max(quote.context_slot, Ordering::Relaxed);
+        if latest_slot > quote.context_slot + STALE_QUOTE_SLOTS {
+            self.emit(&ClientWarning::StaleQuote {
+                context_slot: quote.context_slot,
+                latest_slot,
+            });
+        }
+    }
+}
+
+impl fmt::Debug for WarningSinks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WarningSinks({})", self.sinks.len())
+    }
+}
@@ -0,0 +1,78 @@
+//! Helpers for reading the Solana transaction wire format without pulling in the Solana SDK.
+//!
+//! Nothing here allocates: transactions are decoded into a stack buffer and read in place.
+
+use base64::{DecodeSliceError, Engine, engine::general_purpose::STANDARD};
+
+use crate::JupiterClientError;
+
+/// The largest serialized transaction Solana accepts.
+pub(crate) const PACKET_DATA_SIZE: usize = 1232;
+
+/// A buffer for a decoded transaction. `decode_slice` wants room for its estimate, which
+/// rounds up to whole 3-byte groups.
+pub(crate) type TransactionBuffer = [u8; TRANSACTION_BUFFER_LEN];
+
+pub(crate) const TRANSACTION_BUFFER_LEN: usize = PACKET_DATA_SIZE.div_ceil(3) * 3;
+
+/// Decodes a base64-encoded transaction into `buffer`, returning the decoded bytes.
+pub(crate) fn decode_transaction<'a>(
+    transaction: &str,
+    buffer: &'a mut TransactionBuffer,
+) -> Result<&'a [u8], JupiterClientError> {
+    match STANDARD.decode_slice(transaction, buffer) {
+        Ok(len) => Ok(&buffer[..len]),
+        Err(DecodeSliceError::DecodeError(e)) => Err(JupiterClientError::deserialization(format!(
+            "invalid base64: {e}"
+        ))),
+        Err(DecodeSliceError::OutputSliceTooSmall) => Err(JupiterClientError::deserialization(
+            format!("transaction larger than {PACKET_DATA_SIZE} bytes"),
+        )),
+    }
+}
+
+/// The static account keys of a legacy or v0 message.
+pub(crate) fn static_account_keys(
+    transaction: &[u8],
+) -> Option<impl Iterator<Item = &[u8]> + Clone> {
+    let message = message_bytes(transaction)?;
+    let mut offset = 0;
+
+    // versioned messages set the top bit of the first byte; legacy ones start with the header
+    if *message.get(offset)? & 0x80 != 0 {
+        offset += 1;
+    }
+    offset += 3;
+
+    let (count, offset) = read_compact_u16(message, offset)?;
+    Some(message.get(offset..offset + count * 32)?.chunks_exact(32))
+}
+
+/// The serialized message of a signed transaction, i.e. everything after the signatures.
+pub(crate) fn message_bytes(transaction: &[u8]) -> Option<&[u8]> {
+    let (signatures, offset) = read_compact_u16(transaction, 0)?;
+    transaction.get(offset + signatures * 64..)
+}
+
+/// The first signature of a signed transaction, which identifies it on-chain.
+pub(crate) fn first_signature(transaction: &[u8]) -> Option<&[u8]> {
+    let (signatures, offset) = read_compact_u16(transaction, 0)?;
+    if signatures == 0 {
+        return None;
+    }
+    transaction.get(offset..offset + 64)
+}
+
+/// Reads a Solana compact-u16 ("shortvec") length, returning it and the offset past it.
+fn read_compact_u16(bytes: &[u8], mut offset: usize) -> Option<(usize, usize)> {
+    let mut value = 0usize;
+    for shift in [0, 7, 14] {
+        let byte = *bytes.get(offset)?;
+        offset += 1;
+        value |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return Some((value, offset));
+        }
+    }
+    None
+}
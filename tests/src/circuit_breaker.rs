@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod circuit_breaker_tests {
+    use std::time::Duration;
+
+    use jup_ag_sdk::{JupiterClient, JupiterClientError, circuit_breaker::CircuitState};
+
+    use crate::common::{create_default_quote_request, spawn_mock_server};
+
+    #[tokio::test]
+    async fn test_circuit_breaker_fails_fast_then_probes() {
+        let server = spawn_mock_server(500, &[], "{}".to_string()).await;
+        let client =
+            JupiterClient::new(&server.url).with_circuit_breaker(2, Duration::from_millis(50));
+        let request = create_default_quote_request();
+
+        for _ in 0..2 {
+            let result = client.get_quote(&request).await;
+            assert!(matches!(result, Err(JupiterClientError::ApiError { .. })));
+        }
+        assert_eq!(client.circuit_state("swap/v1/quote"), CircuitState::Open);
+        assert_eq!(client.circuit_state("swap/v1/swap"), CircuitState::Closed);
+
+        let result = client.get_quote(&request).await;
+        assert!(
+            matches!(result, Err(JupiterClientError::CircuitOpen { endpoint, .. }) if endpoint == "swap/v1/quote")
+        );
+        assert_eq!(server.request_count(), 2, "an open circuit sends nothing");
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(
+            client.circuit_state("swap/v1/quote"),
+            CircuitState::HalfOpen
+        );
+        let result = client.get_quote(&request).await;
+        assert!(matches!(result, Err(JupiterClientError::ApiError { .. })));
+        assert_eq!(server.request_count(), 3, "the probe reaches the server");
+        assert_eq!(client.circuit_state("swap/v1/quote"), CircuitState::Open);
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod clock_tests {
+    use std::{sync::Arc, time::Duration};
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        circuit_breaker::CircuitState,
+        clock::{Clock, ManualClock},
+        rate_limit::RateLimitMode,
+        retry::RetryPolicy,
+    };
+
+    use crate::common::{create_default_quote_request, sample_quote_response, spawn_mock_server};
+
+    #[tokio::test]
+    async fn test_manual_clock_fast_forwards() {
+        let real = std::time::Instant::now();
+        let request = create_default_quote_request();
+
+        let server = spawn_mock_server(503, &[], "{}".to_string()).await;
+        let clock = Arc::new(ManualClock::new());
+        let client = JupiterClient::new(&server.url)
+            .with_clock(clock.clone())
+            .with_retry_policy(
+                RetryPolicy::new(3)
+                    .initial_backoff(Duration::from_secs(5))
+                    .max_backoff(Duration::from_secs(60))
+                    .jitter(false),
+            )
+            .with_circuit_breaker(1, Duration::from_secs(30));
+        let started = clock.now();
+        assert!(client.get_quote(&request).await.is_err());
+        assert_eq!(server.request_count(), 3);
+        assert_eq!(
+            clock.now() - started,
+            Duration::from_secs(15),
+            "5s + 10s of backoff"
+        );
+        assert_eq!(client.circuit_state("swap/v1/quote"), CircuitState::Open);
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(
+            client.circuit_state("swap/v1/quote"),
+            CircuitState::HalfOpen
+        );
+
+        let quote = serde_json::to_string(&sample_quote_response()).unwrap();
+        let server = spawn_mock_server(200, &[], quote).await;
+        let clock = Arc::new(ManualClock::new());
+        let client = JupiterClient::new(&server.url)
+            .with_clock(clock.clone())
+            .with_quote_cache(Duration::from_secs(60))
+            .with_rate_limit(0.1, 1, RateLimitMode::Queue);
+        client.get_quote(&request).await.unwrap();
+        clock.advance(Duration::from_secs(59));
+        client.get_quote(&request).await.unwrap();
+        assert_eq!(server.request_count(), 1, "still cached");
+        clock.advance(Duration::from_secs(1));
+        client.get_quote(&request).await.unwrap();
+        client
+            .get_quote(&request.clone().slippage_bps(5))
+            .await
+            .unwrap();
+        assert_eq!(server.request_count(), 3);
+        assert_eq!(
+            clock.elapsed(),
+            Duration::from_secs(70),
+            "waited 10s for the limiter"
+        );
+
+        assert!(real.elapsed() < Duration::from_secs(5), "no real waiting");
+    }
+}
@@ -1,5 +1,18 @@
 #[cfg(test)]
-use jup_ag_sdk::JupiterClient;
+use std::sync::{Arc, Mutex};
+
+#[cfg(test)]
+use base64::{Engine, engine::general_purpose::STANDARD};
+#[cfg(test)]
+use jup_ag_sdk::{
+    JupiterClient,
+    types::{Instruction, QuoteGetSwapModeEnum, QuoteRequest, SwapInstructions},
+};
+#[cfg(test)]
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
 
 #[cfg(test)]
 pub const BASE_URL: &str = "https://lite-api.jup.ag";
@@ -20,3 +33,208 @@ pub const DEFAULT_SLIPPAGE_BPS: u16 = 100;
 pub fn create_test_client() -> JupiterClient {
     JupiterClient::new("https://lite-api.jup.ag")
 }
+
+#[cfg(test)]
+pub fn create_default_quote_request() -> QuoteRequest {
+    QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+        .slippage_bps(DEFAULT_SLIPPAGE_BPS)
+        .swap_mode(QuoteGetSwapModeEnum::ExactOut)
+}
+
+/// A two-hop SOL -> USDC -> JUP quote used by the offline tests.
+#[cfg(test)]
+pub fn sample_quote_response() -> jup_ag_sdk::types::QuoteResponse {
+    serde_json::from_value(serde_json::json!({
+        "inputMint": SOL_MINT,
+        "inAmount": "1000000000",
+        "outputMint": JUP_MINT,
+        "outAmount": "300000000",
+        "otherAmountThreshold": "297000000",
+        "swapMode": "ExactIn",
+        "slippageBps": 100,
+        "platformFee": null,
+        "priceImpactPct": "0.0012",
+        "routePlan": [
+            {
+                "swapInfo": {
+                    "ammKey": "HJPjoWUrhoZzkNfRpHuieeFk9WcZWjwy6PBjZ81ngndJ",
+                    "label": "Whirlpool",
+                    "inputMint": SOL_MINT,
+                    "outputMint": USDC_MINT,
+                    "inAmount": "1000000000",
+                    "outAmount": "150000000",
+                    "feeAmount": "100000",
+                    "feeMint": SOL_MINT
+                },
+                "percent": 100
+            },
+            {
+                "swapInfo": {
+                    "ammKey": "5cuy7pMhTPhVZN9xuhgSbykRb986siGJb6vnEtkuBrSU",
+                    "label": "Meteora DLMM",
+                    "inputMint": USDC_MINT,
+                    "outputMint": JUP_MINT,
+                    "inAmount": "150000000",
+                    "outAmount": "300000000",
+                    "feeAmount": "15000",
+                    "feeMint": USDC_MINT
+                },
+                "percent": 100
+            }
+        ],
+        "contextSlot": 343000000,
+        "timeTaken": 0.012,
+        "swapUsdValue": "150.0"
+    }))
+    .expect("sample quote should deserialize")
+}
+
+/// A Token API v2 entry, as returned by the search and tag endpoints.
+#[cfg(test)]
+pub fn sample_token_json(mint: &str, symbol: &str, decimals: u8) -> serde_json::Value {
+    serde_json::json!({
+        "id": mint,
+        "name": format!("{symbol} Token"),
+        "symbol": symbol,
+        "icon": format!("https://example.com/{symbol}.png"),
+        "decimals": decimals,
+        "circSupply": 1_000_000.0,
+        "totalSupply": 10_000_000.0,
+        "tokenProgram": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+        "firstPool": { "id": "Pool111", "createdAt": "2024-01-29T15:00:00Z" },
+        "holderCount": 750_000,
+        "organicScore": 98.5,
+        "organicScoreLabel": "high",
+        "isVerified": true,
+        "tags": ["verified", "strict"],
+        "usdPrice": 0.5
+    })
+}
+
+/// A local HTTP server answering every request with the same canned response.
+#[cfg(test)]
+pub struct MockServer {
+    pub url: String,
+
+    /// Every request received, as raw HTTP text.
+    pub requests: Arc<Mutex<Vec<String>>>,
+}
+
+#[cfg(test)]
+impl MockServer {
+    pub fn request_count(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+pub async fn spawn_mock_server(status: u16, headers: &[(&str, &str)], body: String) -> MockServer {
+    spawn_sequenced_mock_server(status, headers, vec![body]).await
+}
+
+/// Like [`spawn_mock_server`], answering the n-th request with the n-th body and every
+/// request after the last body with the last one.
+#[cfg(test)]
+pub async fn spawn_sequenced_mock_server(
+    status: u16,
+    headers: &[(&str, &str)],
+    bodies: Vec<String>,
+) -> MockServer {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let responses: Vec<String> = bodies
+        .iter()
+        .map(|body| {
+            let mut response = format!(
+                "HTTP/1.1 {status} Mock\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n",
+                body.len()
+            );
+            for (name, value) in headers {
+                response.push_str(&format!("{name}: {value}\r\n"));
+            }
+            response.push_str("\r\n");
+            response.push_str(body);
+            response
+        })
+        .collect();
+
+    let received = requests.clone();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                let complete = text.split_once("\r\n\r\n").is_some_and(|(head, body)| {
+                    let length = head
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    body.len() >= length
+                });
+                if n == 0 || complete {
+                    break;
+                }
+            }
+            let response = {
+                let mut received = received.lock().unwrap();
+                received.push(String::from_utf8_lossy(&request).to_string());
+                &responses[(received.len() - 1).min(responses.len() - 1)]
+            };
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    });
+
+    MockServer { url, requests }
+}
+
+#[cfg(test)]
+pub fn quote_body() -> String {
+    serde_json::to_string(&sample_quote_response()).unwrap()
+}
+
+/// A signed legacy transaction whose only account keys are the payer and `extra_key`.
+#[cfg(test)]
+pub fn transaction_with_key(extra_key: &str) -> String {
+    let mut bytes = vec![1];
+    bytes.extend([0u8; 64]);
+    bytes.extend([1, 0, 1, 2]);
+    bytes.extend([7u8; 32]);
+    bytes.extend(bs58::decode(extra_key).into_vec().unwrap());
+    bytes.extend([0u8; 32]);
+    STANDARD.encode(bytes)
+}
+
+/// A swap instruction calling `route`, quoting `quoted_out` with `slippage_bps`.
+#[cfg(test)]
+pub fn route_swap_instructions(quoted_out: u64, slippage_bps: u16) -> SwapInstructions {
+    // `route` discriminator, a dummy route plan, then the slippage trailer
+    let mut data = vec![
+        229, 23, 203, 151, 122, 227, 173, 42, 1, 0, 0, 0, 7, 100, 0, 1,
+    ];
+    data.extend_from_slice(&TEST_AMOUNT.to_le_bytes());
+    data.extend_from_slice(&quoted_out.to_le_bytes());
+    data.extend_from_slice(&slippage_bps.to_le_bytes());
+    data.push(0);
+
+    SwapInstructions {
+        other_instructions: None,
+        compute_budget_instructions: None,
+        setup_instructions: vec![],
+        swap_instruction: Instruction {
+            program_id: "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4".to_string(),
+            accounts: vec![],
+            data: STANDARD.encode(data),
+        },
+        cleanup_instruction: None,
+        address_lookup_table_addresses: vec![],
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod fill_history_tests {
+    use jup_ag_sdk::{
+        audit::{AuditOperation, AuditRecord, AuditSink, JsonLinesSink},
+        fill_history::FillHistory,
+    };
+
+    use crate::common::{JUP_MINT, SOL_MINT, USDC_MINT, sample_quote_response};
+
+    #[test]
+    fn test_fill_history_queries() {
+        let confirmation = |signature: &str, timestamp_ms: u64, out_amount: u64| {
+            let mut record = AuditRecord::new(AuditOperation::Confirmation)
+                .signature(signature)
+                .quote(sample_quote_response())
+                .out_amount(out_amount);
+            record.timestamp_ms = timestamp_ms;
+            record
+        };
+        const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+        let jsonl = JsonLinesSink::new(Vec::new());
+        for record in [
+            confirmation("sig1", 20_000 * DAY_MS, 297_000_000),
+            // a later confirmation of the same signature replaces the earlier one
+            confirmation("sig1", 20_000 * DAY_MS + 1, 298_500_000),
+            confirmation("sig2", 20_001 * DAY_MS, 301_500_000),
+            AuditRecord::new(AuditOperation::Quote).quote(sample_quote_response()),
+            AuditRecord::new(AuditOperation::Confirmation)
+                .signature("sig3")
+                .quote(sample_quote_response())
+                .error("InstructionError"),
+        ] {
+            jsonl.record(&record);
+        }
+        let history = FillHistory::from_json_lines(jsonl.into_inner().as_slice()).unwrap();
+
+        assert_eq!(history.fills().len(), 2);
+        assert_eq!(history.fills()[0].out_amount, Some(298_500_000));
+        assert_eq!(history.by_pair(SOL_MINT, JUP_MINT).len(), 2);
+        assert!(history.by_pair(JUP_MINT, SOL_MINT).is_empty());
+
+        let days = history.by_day();
+        assert_eq!(
+            days.keys().copied().collect::<Vec<_>>(),
+            vec![20_000, 20_001]
+        );
+
+        let stats = history.pair_slippage_stats(SOL_MINT, JUP_MINT).unwrap();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.max_bps, 50.0);
+        assert_eq!(stats.mean_bps, 0.0);
+
+        let fees = history.fee_totals();
+        assert_eq!(fees[SOL_MINT], 200_000);
+        assert_eq!(fees[USDC_MINT], 30_000);
+
+        assert!(FillHistory::from_json_lines("not json\n".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod jito_tests {
+    use jup_ag_sdk::{
+        JupiterClientError,
+        jito::{JITO_TIP_ACCOUNTS, JitoBundleSubmitter, jito_tip_account},
+    };
+
+    use crate::common::{USDC_MINT, spawn_mock_server, transaction_with_key};
+
+    #[tokio::test]
+    async fn test_jito_bundle_requires_a_tip() {
+        let tipped = transaction_with_key(JITO_TIP_ACCOUNTS[3]);
+        let untipped = transaction_with_key(USDC_MINT);
+        assert_eq!(
+            jito_tip_account(&tipped).unwrap(),
+            Some(JITO_TIP_ACCOUNTS[3])
+        );
+        assert_eq!(jito_tip_account(&untipped).unwrap(), None);
+        assert!(jito_tip_account("AAAA").is_err());
+
+        let server = spawn_mock_server(
+            200,
+            &[],
+            r#"{"jsonrpc":"2.0","result":"bundle-1","id":1}"#.to_string(),
+        )
+        .await;
+        let jito = JitoBundleSubmitter::new(&server.url);
+
+        let result = jito.send_bundle(std::slice::from_ref(&untipped)).await;
+        assert!(matches!(result, Err(JupiterClientError::MissingJitoTip)));
+        assert_eq!(server.request_count(), 0, "untipped bundles are never sent");
+
+        let bundle_id = jito.send_bundle(&[untipped, tipped.clone()]).await.unwrap();
+        assert_eq!(bundle_id, "bundle-1");
+        assert!(server.requests.lock().unwrap()[0].contains("POST /api/v1/bundles"));
+
+        let jito = JitoBundleSubmitter::new(&server.url).require_mainnet(true);
+        let result = jito.send_bundle(std::slice::from_ref(&tipped)).await;
+        assert!(matches!(result, Err(JupiterClientError::ConfigError(_))));
+        assert_eq!(
+            server.request_count(),
+            1,
+            "non-mainnet block engines are refused"
+        );
+    }
+}
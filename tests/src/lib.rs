@@ -1,6 +1,20 @@
+mod circuit_breaker;
+mod client;
+mod clock;
 mod common;
+mod fill_history;
+mod jito;
+mod policy;
+mod pool;
+mod portfolio;
+mod program_test;
+mod rate_limit;
 mod recurring;
+mod retry;
+mod shutdown;
+mod submission;
 mod swap;
 mod token;
 mod trigger;
 mod ultra;
+mod venue_health;
@@ -0,0 +1,320 @@
+#[cfg(test)]
+mod policy_tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        audit::{AuditOperation, AuditRecord, AuditSink, JsonLinesSink},
+        clock::ManualClock,
+        policy::{
+            AllowedPairs, DailyVolumeCap, DestinationWhitelist, IntentSource, MaxNotional,
+            MaxSlippageBps, MintAllowlist, PolicyDecision, SigningIntent, SigningPolicy,
+        },
+        types::{
+            CreateRecurringOrderRequest, CreateTriggerOrder, QuoteGetSwapModeEnum, RequestDefaults,
+            SwapRequest,
+        },
+    };
+
+    use crate::common::{
+        BASE_URL, JUP_MINT, SOL_MINT, TEST_AMOUNT, TEST_USER_PUBKEY, USDC_MINT,
+        sample_quote_response, spawn_mock_server,
+    };
+
+    #[test]
+    fn test_signing_policies() {
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response());
+        let intent = SigningIntent::from_swap(IntentSource::Swap, &swap).unwrap();
+        assert_eq!(intent.in_amount, TEST_AMOUNT);
+        assert_eq!(intent.venues, vec!["Whirlpool", "Meteora DLMM"]);
+
+        let permissive = JupiterClient::new(BASE_URL)
+            .with_policy(MaxSlippageBps(100))
+            .with_policy(MintAllowlist(
+                [SOL_MINT, JUP_MINT].iter().map(|m| m.to_string()).collect(),
+            ));
+        assert!(permissive.check_signing_intent(&intent).is_ok());
+
+        let strict = JupiterClient::new(BASE_URL)
+            .with_policy(MaxSlippageBps(100))
+            .with_policy(|intent: &SigningIntent| {
+                if intent.venues.iter().any(|venue| venue == "Meteora DLMM") {
+                    PolicyDecision::Deny("Meteora DLMM is blocked".to_string())
+                } else {
+                    PolicyDecision::Allow
+                }
+            });
+        assert!(matches!(
+            strict.check_signing_intent(&intent),
+            Err(JupiterClientError::PolicyViolation(reason)) if reason == "Meteora DLMM is blocked"
+        ));
+
+        let tight = JupiterClient::new(BASE_URL).with_policy(MaxSlippageBps(50));
+        assert!(tight.check_signing_intent(&intent).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_audit_records_policy_denial() {
+        let records = Arc::new(Mutex::new(Vec::<AuditRecord>::new()));
+        let sink = records.clone();
+
+        let client = JupiterClient::new(BASE_URL)
+            .with_defaults(RequestDefaults::new().tracking_account(TEST_USER_PUBKEY))
+            .with_policy(MaxSlippageBps(50))
+            .with_audit_sink(move |record: &AuditRecord| sink.lock().unwrap().push(record.clone()));
+
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response());
+        let result = client.get_swap_transaction(&swap).await;
+        assert!(matches!(
+            result,
+            Err(JupiterClientError::PolicyViolation(_))
+        ));
+
+        client.audit(AuditRecord::new(AuditOperation::Confirmation).confirmations(32));
+
+        let records = records.lock().unwrap();
+        assert_eq!(
+            records.len(),
+            2,
+            "should record the denial and the manual entry"
+        );
+        assert_eq!(records[0].operation, AuditOperation::SwapTransaction);
+        assert!(records[0].duration_ms.is_some());
+        assert!(
+            records[0].error.as_deref().unwrap().contains("slippage"),
+            "should record the denial reason"
+        );
+        assert_eq!(
+            records[0]
+                .swap_request
+                .as_ref()
+                .unwrap()
+                .tracking_account
+                .as_deref(),
+            Some(TEST_USER_PUBKEY),
+            "should record the request after defaults"
+        );
+        assert_eq!(records[1].confirmations, Some(32));
+
+        let jsonl = JsonLinesSink::new(Vec::new());
+        jsonl.record(&records[0]);
+        let output = String::from_utf8(jsonl.into_inner()).unwrap();
+        let parsed: AuditRecord = serde_json::from_str(output.trim_end()).unwrap();
+        assert_eq!(parsed.operation, AuditOperation::SwapTransaction);
+        assert!(
+            !output.contains("signature"),
+            "unset fields should be omitted"
+        );
+    }
+
+    #[test]
+    fn test_notional_pair_and_destination_policies() {
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response());
+        let intent = SigningIntent::from_swap(IntentSource::Swap, &swap).unwrap();
+
+        let notional = |cap| MaxNotional([(SOL_MINT.to_string(), cap)].into());
+        assert_eq!(
+            notional(TEST_AMOUNT).evaluate(&intent),
+            PolicyDecision::Allow
+        );
+        assert!(matches!(
+            notional(TEST_AMOUNT - 1).evaluate(&intent),
+            PolicyDecision::Deny(_)
+        ));
+        assert!(
+            matches!(
+                MaxNotional([(USDC_MINT.to_string(), u64::MAX)].into()).evaluate(&intent),
+                PolicyDecision::Deny(_)
+            ),
+            "mints without a cap are denied"
+        );
+
+        let pairs = AllowedPairs([(SOL_MINT.to_string(), JUP_MINT.to_string())].into());
+        assert_eq!(pairs.evaluate(&intent), PolicyDecision::Allow);
+        let reverse = AllowedPairs([(JUP_MINT.to_string(), SOL_MINT.to_string())].into());
+        assert!(matches!(reverse.evaluate(&intent), PolicyDecision::Deny(_)));
+
+        let whitelist = DestinationWhitelist([TEST_USER_PUBKEY.to_string()].into());
+        assert_eq!(
+            whitelist.evaluate(&intent),
+            PolicyDecision::Allow,
+            "the user's own accounts"
+        );
+        let elsewhere =
+            SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response())
+                .destination_token_account("SomeoneElsesAccount".to_string());
+        let intent = SigningIntent::from_swap(IntentSource::Swap, &elsewhere).unwrap();
+        assert_eq!(
+            intent.destination_token_account.as_deref(),
+            Some("SomeoneElsesAccount")
+        );
+        assert!(matches!(
+            whitelist.evaluate(&intent),
+            PolicyDecision::Deny(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_max_slippage_denies_dynamic_slippage() {
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response())
+            .dynamic_slippage(true);
+        let intent = SigningIntent::from_swap(IntentSource::Swap, &swap).unwrap();
+        assert!(intent.dynamic_slippage);
+        assert!(matches!(
+            MaxSlippageBps(100).evaluate(&intent),
+            PolicyDecision::Deny(_)
+        ));
+
+        // dynamic slippage switched on by the client defaults is caught as well
+        let server = spawn_mock_server(200, &[], "{}".to_string()).await;
+        let client = JupiterClient::new(&server.url)
+            .with_defaults(RequestDefaults::new().dynamic_slippage(true))
+            .with_policy(MaxSlippageBps(100));
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response());
+        assert!(matches!(
+            client.get_swap_instructions(&swap).await,
+            Err(JupiterClientError::PolicyViolation(reason)) if reason.contains("dynamic slippage")
+        ));
+        assert_eq!(server.request_count(), 0);
+    }
+
+    #[test]
+    fn test_exact_out_swaps_are_capped_at_their_worst_case_spend() {
+        let mut quote = sample_quote_response();
+        quote.swap_mode = QuoteGetSwapModeEnum::ExactOut;
+        quote.other_amount_threshold = (TEST_AMOUNT + 5_000_000).to_string();
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, quote);
+        let intent = SigningIntent::from_swap(IntentSource::Swap, &swap).unwrap();
+        assert_eq!(intent.in_amount, TEST_AMOUNT);
+        assert_eq!(intent.max_in_amount, TEST_AMOUNT + 5_000_000);
+
+        let notional = MaxNotional([(SOL_MINT.to_string(), TEST_AMOUNT)].into());
+        assert!(
+            matches!(notional.evaluate(&intent), PolicyDecision::Deny(_)),
+            "the input can slip past the quoted amount"
+        );
+
+        let cap = DailyVolumeCap::new([(SOL_MINT.to_string(), 2 * TEST_AMOUNT)].into());
+        assert!(cap.reserve(&intent).unwrap().is_some());
+        assert_eq!(cap.spent(SOL_MINT), TEST_AMOUNT + 5_000_000);
+    }
+
+    #[test]
+    fn test_daily_volume_cap_counts_approvals_over_24_hours() {
+        let clock = Arc::new(ManualClock::new());
+        let cap = DailyVolumeCap::new([(SOL_MINT.to_string(), 2 * TEST_AMOUNT)].into())
+            .with_clock(clock.clone());
+        let client = JupiterClient::new(BASE_URL).with_policy(cap.clone());
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response());
+        let intent = SigningIntent::from_swap(IntentSource::Swap, &swap).unwrap();
+
+        assert!(client.check_signing_intent(&intent).is_ok());
+        assert!(client.check_signing_intent(&intent).is_ok());
+        assert!(matches!(
+            client.check_signing_intent(&intent),
+            Err(JupiterClientError::PolicyViolation(_))
+        ));
+        assert_eq!(cap.spent(SOL_MINT), 2 * TEST_AMOUNT);
+
+        clock.advance(Duration::from_secs(24 * 60 * 60));
+        assert_eq!(cap.spent(SOL_MINT), 0, "a day later the window is empty");
+
+        // a denial by a later policy releases what the cap reserved
+        let strict = JupiterClient::new(BASE_URL)
+            .with_policy(cap.clone())
+            .with_policy(MaxSlippageBps(0));
+        assert!(strict.check_signing_intent(&intent).is_err());
+        assert_eq!(cap.spent(SOL_MINT), 0);
+    }
+
+    #[test]
+    fn test_daily_volume_cap_releases_the_exact_reservation() {
+        let clock = Arc::new(ManualClock::new());
+        let cap = DailyVolumeCap::new([(SOL_MINT.to_string(), 2 * TEST_AMOUNT)].into())
+            .with_clock(clock.clone());
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response());
+        let intent = SigningIntent::from_swap(IntentSource::Swap, &swap).unwrap();
+
+        assert_eq!(cap.evaluate(&intent), PolicyDecision::Allow);
+        assert_eq!(cap.spent(SOL_MINT), 0, "evaluating alone records nothing");
+
+        let older = cap.reserve(&intent).unwrap().unwrap();
+        clock.advance(Duration::from_secs(12 * 60 * 60));
+        let newer = cap.reserve(&intent).unwrap().unwrap();
+        assert_ne!(older, newer);
+        assert!(cap.reserve(&intent).is_err());
+
+        // releasing the older reservation leaves the newer one, same amount or not
+        cap.release(older);
+        assert_eq!(cap.spent(SOL_MINT), TEST_AMOUNT);
+        clock.advance(Duration::from_secs(12 * 60 * 60));
+        assert_eq!(
+            cap.spent(SOL_MINT),
+            TEST_AMOUNT,
+            "the newer reservation is still inside its window"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_daily_volume_cap_released_when_the_swap_fails() {
+        let server = spawn_mock_server(500, &[], "{}".to_string()).await;
+        let cap = DailyVolumeCap::new([(SOL_MINT.to_string(), TEST_AMOUNT)].into());
+        let client = JupiterClient::new(&server.url).with_policy(cap.clone());
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response());
+
+        assert!(client.get_swap_transaction(&swap).await.is_err());
+        assert_eq!(server.request_count(), 1);
+        assert_eq!(cap.spent(SOL_MINT), 0, "a failed build releases its volume");
+    }
+
+    #[tokio::test]
+    async fn test_policies_fail_closed_and_cover_orders() {
+        let mut quote = sample_quote_response();
+        quote.in_amount = "not-a-number".to_string();
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, quote);
+        assert!(matches!(
+            SigningIntent::from_swap(IntentSource::Swap, &swap),
+            Err(JupiterClientError::PolicyViolation(_))
+        ));
+        let client = JupiterClient::new(BASE_URL).with_policy(MaxSlippageBps(100));
+        assert!(matches!(
+            client.get_swap_transaction(&swap).await,
+            Err(JupiterClientError::PolicyViolation(_))
+        ));
+
+        let server = spawn_mock_server(200, &[], "{}".to_string()).await;
+        let client = JupiterClient::new(&server.url)
+            .with_policy(MaxNotional([(SOL_MINT.to_string(), TEST_AMOUNT)].into()));
+        let trigger = CreateTriggerOrder::new(
+            SOL_MINT,
+            USDC_MINT,
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            TEST_AMOUNT + 1,
+            200_000_000,
+        );
+        assert!(matches!(
+            client.create_trigger_order(&trigger).await,
+            Err(JupiterClientError::PolicyViolation(_))
+        ));
+        let recurring = CreateRecurringOrderRequest::time_order(
+            TEST_USER_PUBKEY,
+            USDC_MINT,
+            SOL_MINT,
+            1_000_000_000,
+            10,
+            Duration::from_secs(86_400),
+        );
+        assert!(
+            matches!(
+                client.create_recurring_order(&recurring).await,
+                Err(JupiterClientError::PolicyViolation(reason)) if reason.contains("no notional cap")
+            ),
+            "USDC has no cap"
+        );
+        assert_eq!(server.request_count(), 0, "denied orders are never sent");
+    }
+}
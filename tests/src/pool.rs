@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod pool_tests {
+    use std::{sync::Arc, time::Duration};
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        clock::ManualClock,
+        pool::{ClientPool, TenantConfig},
+    };
+
+    use crate::common::{BASE_URL, create_default_quote_request, quote_body, spawn_mock_server};
+
+    #[tokio::test]
+    async fn test_client_pool_tenants() {
+        let pool = ClientPool::new(BASE_URL);
+        pool.add_tenant("acme", TenantConfig::new("acme-key").rate_limit(1000.0, 2))
+            .unwrap();
+        pool.add_tenant_client("globex", JupiterClient::new(BASE_URL), None);
+
+        let mut tenants = pool.tenants();
+        tenants.sort();
+        assert_eq!(tenants, vec!["acme", "globex"]);
+
+        let base_url = pool
+            .execute("acme", |client| async move { Ok(client.base_url) })
+            .await
+            .unwrap();
+        assert_eq!(base_url, BASE_URL);
+
+        let failed: Result<(), _> = pool
+            .execute("acme", |_| async {
+                Err(JupiterClientError::ConfigError("boom".to_string()))
+            })
+            .await;
+        assert!(failed.is_err());
+
+        let metrics = pool.metrics("acme").unwrap();
+        assert_eq!((metrics.requests, metrics.errors), (2, 1));
+        assert_eq!(
+            pool.metrics("globex").unwrap().requests,
+            0,
+            "per-tenant metrics"
+        );
+
+        let unknown = pool.execute("initech", |_| async { Ok(()) }).await;
+        assert!(matches!(unknown, Err(JupiterClientError::UnknownTenant(_))));
+
+        assert!(pool.remove_tenant("globex"));
+        assert!(pool.client("globex").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tenant_rate_limit_applies_per_request() {
+        let server = spawn_mock_server(200, &[], quote_body()).await;
+        let clock = Arc::new(ManualClock::new());
+        let pool = ClientPool::new(BASE_URL);
+        pool.add_tenant_client(
+            "acme",
+            JupiterClient::new(&server.url).with_clock(clock.clone()),
+            Some((1.0, 1)),
+        );
+
+        let request = create_default_quote_request();
+        pool.execute("acme", |client| async move {
+            for _ in 0..3 {
+                client.get_quote(&request).await?;
+            }
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(server.request_count(), 3);
+        assert!(
+            clock.elapsed() >= Duration::from_secs(2),
+            "each request after the burst waited a second, not just the call"
+        );
+        assert_eq!(pool.metrics("acme").unwrap().requests, 1);
+    }
+}
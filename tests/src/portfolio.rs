@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod portfolio_tests {
+    use std::sync::Arc;
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        audit::{AuditOperation, AuditRecord},
+        fill_history::FillHistory,
+        portfolio::{
+            JsonLinesSnapshotStore, MemorySnapshotStore, PortfolioSnapshot, PortfolioTracker,
+            Position, SnapshotStore,
+        },
+        types::QuoteResponse,
+    };
+
+    use crate::common::{
+        JUP_MINT, SOL_MINT, TEST_USER_PUBKEY, sample_quote_response, spawn_mock_server,
+    };
+
+    #[tokio::test]
+    async fn test_portfolio_pnl() {
+        let entry = |amount: &str, ui_amount: f64, usd_price: f64| {
+            serde_json::json!({
+                "amount": amount, "uiAmount": ui_amount, "slot": 1, "isFrozen": false,
+                "usdPrice": usd_price, "blockId": 1, "decimals": 9, "priceChange24h": 0.0,
+            })
+        };
+        // one body answers both the balances and the price request
+        let body = serde_json::json!({
+            "SOL": entry("2000000000", 2.0, 100.0),
+            SOL_MINT: entry("0", 0.0, 100.0),
+        });
+        let server = spawn_mock_server(200, &[], body.to_string()).await;
+        let path = std::env::temp_dir().join(format!("portfolio-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let tracker = PortfolioTracker::new(
+            JupiterClient::new(&server.url),
+            TEST_USER_PUBKEY,
+            Arc::new(JsonLinesSnapshotStore::new(&path)),
+        );
+        let opening = tracker.snapshot().await.unwrap();
+        assert_eq!(opening.positions.len(), 1);
+        assert_eq!(opening.get(SOL_MINT).unwrap().usd_value, Some(200.0));
+        assert_eq!(tracker.snapshots().unwrap(), vec![opening.clone()]);
+        std::fs::remove_file(&path).unwrap();
+
+        let mut quote = sample_quote_response();
+        quote.swap_usd_value = Some("150".to_string());
+        let mut record = AuditRecord::new(AuditOperation::Confirmation)
+            .signature("sig1")
+            .quote(quote)
+            .out_amount(298_500_000);
+        record.timestamp_ms = opening.taken_at_ms + 1;
+        let mut unvalued = AuditRecord::new(AuditOperation::Confirmation)
+            .signature("sig2")
+            .quote(QuoteResponse {
+                swap_usd_value: None,
+                ..sample_quote_response()
+            });
+        unvalued.timestamp_ms = opening.taken_at_ms + 2;
+        let mut after = AuditRecord::new(AuditOperation::Confirmation)
+            .signature("sig3")
+            .quote(sample_quote_response());
+        after.timestamp_ms = opening.taken_at_ms + 20_000_000;
+        let fills = FillHistory::from_records([record, unvalued, after]);
+
+        let closing = PortfolioSnapshot {
+            wallet: TEST_USER_PUBKEY.to_string(),
+            taken_at_ms: opening.taken_at_ms + 10_000_000,
+            positions: vec![
+                Position {
+                    mint: "SOL".to_string(),
+                    amount: 1_000_000_000,
+                    ui_amount: 1.0,
+                    usd_price: Some(120.0),
+                    usd_value: Some(120.0),
+                },
+                Position {
+                    mint: JUP_MINT.to_string(),
+                    amount: 298_500_000,
+                    ui_amount: 298.5,
+                    usd_price: Some(0.6),
+                    usd_value: Some(179.1),
+                },
+            ],
+        };
+        let store = Arc::new(MemorySnapshotStore::new());
+        store.save(&closing).unwrap();
+        store.save(&opening).unwrap();
+        let tracker =
+            PortfolioTracker::new(JupiterClient::new(&server.url), TEST_USER_PUBKEY, store);
+        let report = tracker.pnl(&fills).unwrap().unwrap();
+
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        let sol = &report.tokens[SOL_MINT];
+        assert!(
+            close(sol.realized_usd, 50.0),
+            "sold half the SOL for $150 at a $100 cost"
+        );
+        assert!(close(sol.cost_basis_usd, 100.0));
+        assert!(close(sol.unrealized_usd.unwrap(), 20.0));
+        let jup = &report.tokens[JUP_MINT];
+        assert_eq!(jup.amount, 298_500_000);
+        assert!(close(jup.cost_basis_usd, 150.0));
+        assert!(close(jup.unrealized_usd.unwrap(), 29.1));
+        assert!(close(report.realized_usd, 50.0));
+        assert!(close(report.total_usd(), 99.1));
+        assert_eq!(report.unvalued_fills, 1);
+
+        let empty = PortfolioTracker::new(
+            JupiterClient::new(&server.url),
+            TEST_USER_PUBKEY,
+            Arc::new(MemorySnapshotStore::new()),
+        );
+        assert!(empty.pnl(&fills).unwrap().is_none());
+    }
+}
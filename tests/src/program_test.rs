@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod program_test_tests {
+    use std::collections::HashMap;
+
+    use base64::Engine;
+    use jup_ag_sdk::{
+        program_test::{ProgramTestFixture, execute_transaction},
+        types::{AccountMeta, Instruction},
+    };
+
+    use crate::common::{
+        TEST_USER_PUBKEY, USDC_MINT, route_swap_instructions, spawn_sequenced_mock_server,
+    };
+
+    #[test]
+    fn test_program_test_fixture() {
+        let meta = |pubkey: &str, is_signer: bool| AccountMeta {
+            pubkey: pubkey.to_string(),
+            is_signer,
+            is_writable: true,
+        };
+
+        let mut instructions = route_swap_instructions(1_000_000, 100);
+        instructions.swap_instruction.accounts = vec![
+            meta("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", false),
+            meta(TEST_USER_PUBKEY, true),
+            meta("SysvarC1ock11111111111111111111111111111111", false),
+            meta("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc", false),
+            meta("WhirlpoolPoolAccount", false),
+            meta("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4", false),
+        ];
+        instructions.address_lookup_table_addresses = vec!["LookupTable".to_string()];
+
+        let memo = Instruction {
+            program_id: "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr".to_string(),
+            accounts: vec![meta(TEST_USER_PUBKEY, true)],
+            data: String::new(),
+        };
+        let labels = HashMap::from([(
+            "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc".to_string(),
+            "Whirlpool".to_string(),
+        )]);
+
+        let fixture = ProgramTestFixture::from_swap_instructions(&instructions)
+            .with_instruction(&memo)
+            .with_program_labels(&labels);
+
+        assert_eq!(
+            fixture.programs,
+            vec![
+                "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4",
+                "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"
+            ]
+        );
+        assert_eq!(
+            fixture.accounts,
+            vec!["WhirlpoolPoolAccount"],
+            "builtins should be skipped"
+        );
+        assert_eq!(fixture.signers, vec![TEST_USER_PUBKEY]);
+
+        let args = fixture.test_validator_args("https://api.mainnet-beta.solana.com");
+        assert_eq!(args.iter().filter(|a| *a == "--clone").count(), 2);
+        assert_eq!(
+            args.iter()
+                .filter(|a| *a == "--clone-upgradeable-program")
+                .count(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_fixture_and_execute_transaction() {
+        let jupiter = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+        let pool = "HJPjoWUrhoZzkNfRpHuieeFk9WcZWjwy6PBjZ81ngndJ";
+        let fixture = ProgramTestFixture {
+            programs: vec![jupiter.to_string()],
+            accounts: vec![pool.to_string()],
+            address_lookup_tables: Vec::new(),
+            signers: vec![TEST_USER_PUBKEY.to_string()],
+        };
+
+        // an upgradeable program pointing at its program data account, USDC_MINT for the test
+        let mut program = vec![2, 0, 0, 0];
+        program.extend(bs58::decode(USDC_MINT).into_vec().unwrap());
+        let program = base64::engine::general_purpose::STANDARD.encode(program);
+        let account = |data: &str, owner: &str, executable: bool| {
+            format!(
+                r#"{{"lamports":1141440,"data":["{data}","base64"],"owner":"{owner}","executable":{executable},"rentEpoch":18446744073709551615,"space":36}}"#
+            )
+        };
+        let bodies = vec![
+            format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":{{"context":{{"slot":1}},"value":[{},{},null]}}}}"#,
+                account(&program, "BPFLoaderUpgradeab1e11111111111111111111111", true),
+                account("AQID", "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc", false),
+            ),
+            format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":{{"context":{{"slot":1}},"value":[{}]}}}}"#,
+                account("AwAAAA==", "BPFLoaderUpgradeab1e11111111111111111111111", false),
+            ),
+            r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":{"err":{"InstructionError":[2,{"Custom":6001}]},"logs":["Program log: slippage"],"unitsConsumed":52000,"accounts":null}}}"#.to_string(),
+        ];
+        let server = spawn_sequenced_mock_server(200, &[], bodies).await;
+
+        let loaded = fixture.load(&server.url).await.unwrap();
+        let pubkeys: Vec<&str> = loaded.accounts.iter().map(|a| a.pubkey.as_str()).collect();
+        assert_eq!(pubkeys, vec![jupiter, pool, USDC_MINT]);
+        assert_eq!(loaded.missing, vec![TEST_USER_PUBKEY]);
+        assert_eq!(loaded.accounts[1].account.data.0, "AQID");
+
+        let dir = std::env::temp_dir().join(format!("jup-fixture-{}", std::process::id()));
+        let args = loaded.write_account_files(&dir).unwrap();
+        assert_eq!(args.len(), 9);
+        assert_eq!(args[..2], ["--account", jupiter]);
+        let written: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&args[5]).unwrap()).unwrap();
+        assert_eq!(written["pubkey"], pool);
+        assert_eq!(written["account"]["rentEpoch"], u64::MAX);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let outcome = execute_transaction(&server.url, "AQ==").await.unwrap();
+        assert_eq!(outcome.units_consumed, Some(52000));
+        assert_eq!(outcome.logs, vec!["Program log: slippage"]);
+        assert!(outcome.err.is_some());
+
+        let requests = server.requests.lock().unwrap();
+        assert!(
+            requests[1].contains(USDC_MINT),
+            "program data should be fetched"
+        );
+        assert!(requests[2].contains(r#""sigVerify":false"#));
+    }
+}
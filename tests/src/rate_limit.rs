@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod rate_limit_tests {
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        rate_limit::{RateLimitMode, RateLimiter},
+    };
+
+    use crate::common::create_default_quote_request;
+
+    #[tokio::test]
+    async fn test_client_rate_limit_fail_fast() {
+        let client = JupiterClient::new("http://127.0.0.1:1").with_rate_limit(
+            0.1,
+            1,
+            RateLimitMode::FailFast,
+        );
+        let request = create_default_quote_request();
+
+        let first = client.get_quote(&request).await;
+        assert!(matches!(
+            first,
+            Err(JupiterClientError::RequestError { .. })
+        ));
+
+        let second = client.clone().get_quote(&request).await;
+        assert!(
+            matches!(second, Err(JupiterClientError::RateLimitExceeded)),
+            "clones should share the limiter"
+        );
+    }
+
+    #[test]
+    fn test_rate_limiter_burst() {
+        let limiter = RateLimiter::new(1.0, 2);
+        assert!(limiter.try_acquire());
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire(), "burst should be exhausted");
+    }
+}
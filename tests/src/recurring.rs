@@ -1,10 +1,95 @@
 #[cfg(test)]
 mod recurring_tests {
-    use jup_ag_sdk::types::{
-        CreateRecurringOrderRequest, GetRecurringOrders, OrderStatus, RecurringOrderType,
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        types::{
+            CreateRecurringOrderRequest, ExecuteRecurringRequest, GetRecurringOrders,
+            InputOrOutput, OrderParams, OrderStatus, PriceDeposit, PriceOrder, PriceWithdraw,
+            RecurringOrderType,
+        },
+    };
+
+    use crate::common::{
+        JUP_MINT, SOL_MINT, TEST_USER_PUBKEY, USDC_MINT, create_test_client, spawn_mock_server,
     };
 
-    use crate::common::{SOL_MINT, TEST_USER_PUBKEY, USDC_MINT, create_test_client};
+    #[tokio::test]
+    async fn test_create_time_order_typed_params() {
+        let body = serde_json::json!({ "requestId": "req-1", "transaction": "AQ==" });
+        let server = spawn_mock_server(200, &[], body.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+        let day = Duration::from_secs(24 * 60 * 60);
+
+        let order = CreateRecurringOrderRequest::time_order(
+            TEST_USER_PUBKEY,
+            USDC_MINT,
+            SOL_MINT,
+            1_000_000_000,
+            10,
+            day,
+        )
+        .with_price_range(100.0, 250.0)
+        .with_start_time(UNIX_EPOCH + Duration::from_secs(1_900_000_000));
+        let OrderParams::TimeWrapper { time } = &order.params else {
+            panic!("expected a time order");
+        };
+        assert_eq!(time.interval, 86_400);
+        assert_eq!(time.in_amount_per_cycle(), 100_000_000);
+
+        let res = client.create_recurring_order(&order).await.unwrap();
+        assert_eq!(res.request_id, "req-1");
+        let sent = server.requests.lock().unwrap()[0].clone();
+        assert!(sent.starts_with("POST /recurring/v1/createOrder "));
+        assert!(sent.contains(
+            r#""params":{"time":{"inAmount":1000000000,"numberOfOrders":10,"interval":86400,"minPrice":100.0,"maxPrice":250.0,"startAt":1900000000}}"#
+        ));
+
+        let rejected = [
+            CreateRecurringOrderRequest::time_order(
+                TEST_USER_PUBKEY,
+                USDC_MINT,
+                SOL_MINT,
+                1_000_000_000,
+                1,
+                day,
+            ),
+            CreateRecurringOrderRequest::time_order(
+                TEST_USER_PUBKEY,
+                USDC_MINT,
+                SOL_MINT,
+                5,
+                10,
+                day,
+            ),
+            CreateRecurringOrderRequest::time_order(
+                TEST_USER_PUBKEY,
+                USDC_MINT,
+                SOL_MINT,
+                1_000_000_000,
+                10,
+                Duration::from_millis(500),
+            ),
+            order.with_price_range(250.0, 100.0),
+            CreateRecurringOrderRequest::new_price_order(
+                TEST_USER_PUBKEY,
+                USDC_MINT,
+                USDC_MINT,
+                1_000_000_000,
+                100,
+                86_400,
+            ),
+        ];
+        for order in &rejected {
+            let result = client.create_recurring_order(order).await;
+            assert!(
+                matches!(result, Err(JupiterClientError::InvalidRecurringOrder(_))),
+                "{order:?}"
+            );
+        }
+        assert_eq!(server.request_count(), 1, "invalid orders are never sent");
+    }
 
     #[tokio::test]
     async fn test_create_recurring_time_order() {
@@ -119,4 +204,113 @@ mod recurring_tests {
             "Should have at least one order in history"
         );
     }
+
+    #[test]
+    fn test_price_order_accepts_numeric_amounts() {
+        let order: PriceOrder = serde_json::from_value(serde_json::json!({
+            "closeTx": "", "closedBy": "", "createdAt": "2025-01-01T00:00:00",
+            "estimatedUsdcValueSpent": 12.5, "inDeposited": 100, "inLeft": "40",
+            "inUsed": 60, "inWithdrawn": 0, "incrementalUsdValue": 5,
+            "inputMint": USDC_MINT, "openTx": "", "orderInterval": "86400",
+            "orderKey": "key", "outReceived": 0.25, "outWithdrawn": 0,
+            "outputMint": SOL_MINT, "rawEstimatedUsdcValueSpent": 12500000,
+            "rawInDeposited": 100000000, "rawInLeft": 40000000, "rawInUsed": 60000000,
+            "rawInWithdrawn": 0, "rawIncrementalUsdValue": 5000000, "rawOutReceived": 250000000,
+            "rawOutWithdrawn": 0, "rawSupposedUsdValue": 15000000, "startAt": "",
+            "status": "active", "supposedUsdValue": 15, "trades": [],
+            "updatedAt": "", "userPubkey": TEST_USER_PUBKEY,
+        }))
+        .unwrap();
+        assert_eq!(order.in_deposited, "100");
+        assert_eq!(order.in_left, "40");
+        assert_eq!(order.out_received, "0.25");
+        assert_eq!(order.raw_in_used, "60000000");
+    }
+
+    #[tokio::test]
+    async fn test_price_order_deposit_and_withdraw() {
+        let body = serde_json::json!({ "requestId": "req-1", "transaction": "AQ==" });
+        let server = spawn_mock_server(200, &[], body.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+        let order = JUP_MINT;
+
+        let res = client
+            .price_deposit_recurring(&PriceDeposit::new(50_000_000, order, TEST_USER_PUBKEY))
+            .await
+            .unwrap();
+        assert_eq!(res.request_id, "req-1");
+        let res = client
+            .price_withdraw_recurring(&PriceWithdraw::new(
+                1_000,
+                order,
+                TEST_USER_PUBKEY,
+                InputOrOutput::Out,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(res.transaction, "AQ==");
+        client
+            .price_withdraw_recurring(&PriceWithdraw::all(
+                order,
+                TEST_USER_PUBKEY,
+                InputOrOutput::In,
+            ))
+            .await
+            .unwrap();
+
+        {
+            let requests = server.requests.lock().unwrap();
+            assert!(requests[0].starts_with("POST /recurring/v1/priceDeposit "));
+            assert!(requests[0].ends_with(&format!(
+                r#"{{"amount":50000000,"order":"{order}","user":"{TEST_USER_PUBKEY}"}}"#
+            )));
+            assert!(requests[1].starts_with("POST /recurring/v1/priceWithdraw "));
+            assert!(requests[1].contains(r#"{"amount":1000,"#));
+            assert!(requests[1].ends_with(r#""inputOrOutput":"Out"}"#));
+            assert!(
+                !requests[2].contains("amount"),
+                "no amount withdraws everything"
+            );
+            assert!(requests[2].ends_with(r#""inputOrOutput":"In"}"#));
+        }
+
+        let result = client
+            .price_deposit_recurring(&PriceDeposit::new(0, order, TEST_USER_PUBKEY))
+            .await;
+        assert!(matches!(
+            result,
+            Err(JupiterClientError::InvalidRecurringOrder(_))
+        ));
+        let result = client
+            .price_withdraw_recurring(&PriceWithdraw::all(
+                "not-an-order",
+                TEST_USER_PUBKEY,
+                InputOrOutput::In,
+            ))
+            .await;
+        assert!(matches!(result, Err(JupiterClientError::InvalidPubkey(_))));
+        assert_eq!(server.request_count(), 3, "invalid requests are never sent");
+    }
+
+    #[tokio::test]
+    async fn test_execute_recurring_typed_result() {
+        let body = serde_json::json!({
+            "signature": "",
+            "status": "Failed",
+            "error": "Insufficient funds",
+        });
+        let server = spawn_mock_server(200, &[], body.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+
+        let res = client
+            .execute_recurring_order(&ExecuteRecurringRequest::new("req-1", "AQ=="))
+            .await
+            .unwrap();
+        assert!(!res.is_success());
+        let err = res.into_result().unwrap_err();
+        assert!(
+            matches!(&err, JupiterClientError::RecurringExecutionFailed(error) if error == "Insufficient funds"),
+            "{err:?}"
+        );
+    }
 }
@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod retry_tests {
+    use std::{sync::Arc, time::Duration};
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        client::CallOpts,
+        clock::{Clock, ManualClock},
+        retry::RetryPolicy,
+        types::{BatchExecutionConfig, BatchSwap, SwapRequest},
+    };
+
+    use crate::common::{
+        TEST_USER_PUBKEY, create_default_quote_request, sample_quote_response, spawn_mock_server,
+    };
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy::new(5)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(350))
+            .jitter(false);
+        let backoffs: Vec<_> = (1..=4).map(|retry| policy.backoff(retry)).collect();
+        assert_eq!(
+            backoffs,
+            [100, 200, 350, 350].map(Duration::from_millis),
+            "should double up to the cap"
+        );
+
+        let jittered = policy.jitter(true).backoff(3);
+        assert!(jittered <= Duration::from_millis(350));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_responses_honor_retry_after() {
+        let policy = RetryPolicy::new(3)
+            .initial_backoff(Duration::from_millis(10))
+            .max_backoff(Duration::from_secs(1))
+            .jitter(false);
+
+        // short waits are retried, for POST endpoints too
+        let server = spawn_mock_server(429, &[("retry-after", "0")], "{}".to_string()).await;
+        let client = JupiterClient::new(&server.url).with_retry_policy(policy);
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response());
+        let result = client.get_swap_transaction(&swap).await;
+        assert!(matches!(
+            result.unwrap_err(),
+            JupiterClientError::RateLimited {
+                retry_after: Some(wait),
+                ..
+            } if wait.is_zero()
+        ));
+        assert_eq!(server.request_count(), 3);
+
+        // waits longer than the policy allows fail right away
+        let server = spawn_mock_server(429, &[("retry-after", "60")], "{}".to_string()).await;
+        let client = JupiterClient::new(&server.url).with_retry_policy(policy);
+        let result = client.get_quote(&create_default_quote_request()).await;
+        assert!(matches!(
+            result.unwrap_err(),
+            JupiterClientError::RateLimited {
+                retry_after: Some(wait),
+                ..
+            } if wait == Duration::from_secs(60)
+        ));
+        assert_eq!(server.request_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_deadline_bounds_retries_and_composites() {
+        let request = create_default_quote_request();
+        let server = spawn_mock_server(503, &[], "{}".to_string()).await;
+        let clock = Arc::new(ManualClock::new());
+        let client = JupiterClient::new(&server.url)
+            .with_clock(clock.clone())
+            .with_retry_policy(
+                RetryPolicy::new(3)
+                    .initial_backoff(Duration::from_secs(5))
+                    .jitter(false),
+            );
+        let opts = CallOpts::new().deadline(clock.now() + Duration::from_secs(8));
+        let result = client.for_call(&opts).get_quote(&request).await;
+        assert!(matches!(
+            result,
+            Err(JupiterClientError::DeadlineExceeded { .. })
+        ));
+        assert_eq!(
+            server.request_count(),
+            2,
+            "the 10s backoff would pass the deadline"
+        );
+        assert_eq!(clock.elapsed(), Duration::from_secs(5));
+
+        let expired = CallOpts::new().deadline(clock.now());
+        let result = client.for_call(&expired).get_quote(&request).await;
+        assert!(matches!(
+            result,
+            Err(JupiterClientError::DeadlineExceeded { .. })
+        ));
+        assert_eq!(
+            server.request_count(),
+            2,
+            "nothing is sent past the deadline"
+        );
+
+        let quote = serde_json::to_string(&sample_quote_response()).unwrap();
+        let server = spawn_mock_server(200, &[], quote).await;
+        let client = JupiterClient::new(&server.url).with_clock(clock.clone());
+        let opts = CallOpts::new().deadline(clock.now() + Duration::from_secs(3));
+        let result = client
+            .for_call(&opts)
+            .sample_route_stability(&request, 3, Duration::from_secs(2))
+            .await;
+        assert!(matches!(
+            result,
+            Err(JupiterClientError::DeadlineExceeded { .. })
+        ));
+        assert_eq!(server.request_count(), 2);
+
+        let swaps = vec![BatchSwap::new("late", TEST_USER_PUBKEY, request.clone())];
+        let report = client
+            .for_call(&CallOpts::new().deadline(clock.now()))
+            .execute_batch(
+                swaps,
+                &BatchExecutionConfig::new(Duration::from_secs(60)),
+                |_, _| async { Ok("sig".to_string()) },
+            )
+            .await;
+        assert_eq!(report.skipped().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_policy_retries_connection_errors() {
+        let client = JupiterClient::new("http://127.0.0.1:1").with_retry_policy(
+            RetryPolicy::new(3)
+                .initial_backoff(Duration::from_millis(50))
+                .jitter(false),
+        );
+
+        let started = std::time::Instant::now();
+        let result = client.get_quote(&create_default_quote_request()).await;
+        assert!(matches!(
+            result,
+            Err(JupiterClientError::RequestError { .. })
+        ));
+        assert!(
+            started.elapsed() >= Duration::from_millis(150),
+            "should back off 50ms then 100ms between attempts"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_swap_build_retries_are_opt_in() {
+        let server = spawn_mock_server(503, &[], "unavailable".to_string()).await;
+        let policy = RetryPolicy::new(3).initial_backoff(Duration::from_millis(1));
+        let request = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response());
+
+        let default = JupiterClient::new(&server.url).with_retry_policy(policy);
+        assert!(matches!(
+            default.get_swap_transaction(&request).await.unwrap_err(),
+            JupiterClientError::ApiError { status, .. } if status.as_u16() == 503
+        ));
+        assert_eq!(
+            server.requests.lock().unwrap().len(),
+            1,
+            "POSTs are not retried on 5xx by default"
+        );
+
+        let opted_in = JupiterClient::new(&server.url).with_swap_build_retries(policy);
+        assert!(opted_in.get_swap_transaction(&request).await.is_err());
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests.len(), 4);
+        let body = |request: &String| request.split_once("\r\n\r\n").unwrap().1.to_string();
+        assert!(
+            requests[1..].iter().all(|r| body(r) == body(&requests[1])),
+            "every attempt resends the same quote"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod shutdown_tests {
+    use std::time::Duration;
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        shutdown::Shutdown,
+        submission::{ResubmitConfig, resubmit_until_landed},
+    };
+
+    use crate::common::{
+        USDC_MINT, create_default_quote_request, quote_body, spawn_mock_server,
+        transaction_with_key,
+    };
+
+    #[tokio::test]
+    async fn test_shutdown_rejects_new_work() {
+        let server = spawn_mock_server(200, &[], quote_body()).await;
+        let shutdown = Shutdown::new();
+        let client = JupiterClient::new(&server.url).with_shutdown(shutdown.clone());
+        let request = create_default_quote_request();
+
+        client.get_quote(&request).await.unwrap();
+        assert_eq!(shutdown.in_flight(), 0);
+
+        let waiter = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.triggered().await }
+        });
+        tokio::time::timeout(Duration::from_secs(1), client.clone().shutdown())
+            .await
+            .expect("nothing in flight, so shutdown completes at once");
+        waiter.await.unwrap();
+
+        assert!(matches!(
+            client.get_quote(&request).await,
+            Err(JupiterClientError::ShuttingDown)
+        ));
+        assert_eq!(server.request_count(), 1);
+
+        let rpc = spawn_mock_server(200, &[], "{}".to_string()).await;
+        let config = ResubmitConfig::new(vec![rpc.url.clone()], 1_000).shutdown(shutdown);
+        assert!(matches!(
+            resubmit_until_landed(&transaction_with_key(USDC_MINT), &config).await,
+            Err(JupiterClientError::ShuttingDown)
+        ));
+        assert_eq!(
+            rpc.request_count(),
+            0,
+            "nothing is broadcast after shutdown"
+        );
+    }
+}
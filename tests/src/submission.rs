@@ -0,0 +1,164 @@
+#[cfg(test)]
+mod submission_tests {
+    use std::time::Duration;
+
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use jup_ag_sdk::{
+        JupiterClientError,
+        cluster::{MAINNET_BETA_GENESIS_HASH, ensure_mainnet_genesis},
+        submission::{
+            DuplicatePolicy, LandingOutcome, ResubmitConfig, SubmissionStatus, SubmissionTracker,
+            message_hash, resubmit_until_landed,
+        },
+    };
+
+    use crate::common::{
+        JUP_MINT, USDC_MINT, spawn_mock_server, spawn_sequenced_mock_server, transaction_with_key,
+    };
+
+    #[test]
+    fn test_submission_tracker_flags_confirmed_resubmissions() {
+        let transaction = transaction_with_key(USDC_MINT);
+        // same message, different signature
+        let mut resigned = STANDARD.decode(&transaction).unwrap();
+        resigned[1] = 9;
+        let resigned = STANDARD.encode(resigned);
+        assert_eq!(
+            message_hash(&transaction).unwrap(),
+            message_hash(&resigned).unwrap()
+        );
+        assert_eq!(message_hash(&transaction).unwrap().len(), 64);
+        assert!(matches!(
+            message_hash(&STANDARD.encode([1u8; 1300])),
+            Err(JupiterClientError::DeserializationError { message: e, .. }) if e.contains("larger than 1232")
+        ));
+
+        let tracker = SubmissionTracker::new(Duration::from_secs(120));
+        assert_eq!(
+            tracker.register(&transaction).unwrap(),
+            SubmissionStatus::New
+        );
+        assert_eq!(
+            tracker.register(&resigned).unwrap(),
+            SubmissionStatus::InFlight
+        );
+
+        tracker.mark_confirmed(&transaction).unwrap();
+        assert!(matches!(
+            tracker.register(&resigned),
+            Err(JupiterClientError::DuplicateSubmission(_))
+        ));
+
+        let tracker =
+            SubmissionTracker::new(Duration::from_secs(120)).policy(DuplicatePolicy::Warn);
+        tracker.mark_confirmed(&transaction).unwrap();
+        assert_eq!(
+            tracker.register(&transaction).unwrap(),
+            SubmissionStatus::AlreadyConfirmed
+        );
+        assert_eq!(
+            tracker.register(&transaction_with_key(JUP_MINT)).unwrap(),
+            SubmissionStatus::New
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resubmit_until_landed_reports_confirmation() {
+        let status = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":42},"value":[{"slot":42,"confirmations":0,"err":null,"confirmationStatus":"confirmed"}]}}"#;
+        let primary = spawn_mock_server(200, &[], status.to_string()).await;
+        let backup = spawn_mock_server(500, &[], "{}".to_string()).await;
+
+        let transaction = transaction_with_key(USDC_MINT);
+        let config = ResubmitConfig::new(vec![primary.url.clone(), backup.url.clone()], 1_000)
+            .interval(Duration::from_millis(10));
+        let report = resubmit_until_landed(&transaction, &config).await.unwrap();
+
+        assert_eq!(report.outcome, LandingOutcome::Confirmed { slot: 42 });
+        assert_eq!(report.signature, bs58::encode([0u8; 64]).into_string());
+        assert_eq!(report.rounds, 1);
+        assert_eq!(report.failed_sends, 1, "the backup RPC rejected the send");
+        assert_eq!(backup.request_count(), 1);
+        let requests = primary.requests.lock().unwrap();
+        assert!(requests[0].contains(r#""method":"sendTransaction""#));
+        assert!(requests[1].contains(r#""method":"getSignatureStatuses""#));
+    }
+
+    #[tokio::test]
+    async fn test_resubmit_until_landed_survives_failed_polls() {
+        let sent = r#"{"jsonrpc":"2.0","id":1,"result":"sig"}"#;
+        let busy = r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32005,"message":"node is behind"}}"#;
+        let status = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":43},"value":[{"slot":43,"confirmations":0,"err":null,"confirmationStatus":"confirmed"}]}}"#;
+        let rpc = spawn_sequenced_mock_server(
+            200,
+            &[],
+            vec![
+                sent.to_string(),
+                busy.to_string(),
+                sent.to_string(),
+                status.to_string(),
+            ],
+        )
+        .await;
+
+        let config =
+            ResubmitConfig::new(vec![rpc.url.clone()], 1_000).interval(Duration::from_millis(10));
+        let report = resubmit_until_landed(&transaction_with_key(USDC_MINT), &config)
+            .await
+            .unwrap();
+
+        assert_eq!(report.outcome, LandingOutcome::Confirmed { slot: 43 });
+        assert_eq!(report.rounds, 2, "the failed poll didn't end the loop");
+        assert_eq!(report.failed_polls, 1);
+        assert_eq!(report.failed_sends, 0);
+    }
+
+    #[tokio::test]
+    async fn test_resubmit_until_landed_requires_mainnet_rpcs() {
+        let devnet = spawn_mock_server(
+            200,
+            &[],
+            r#"{"jsonrpc":"2.0","id":1,"result":"EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG"}"#
+                .to_string(),
+        )
+        .await;
+        let config = ResubmitConfig::new(vec![devnet.url.clone()], 1_000).require_mainnet(true);
+        for _ in 0..2 {
+            let result = resubmit_until_landed(&transaction_with_key(USDC_MINT), &config).await;
+            assert!(matches!(
+                result,
+                Err(JupiterClientError::ClusterMismatch(_))
+            ));
+        }
+        assert_eq!(
+            devnet.request_count(),
+            1,
+            "nothing is sent and the genesis hash is cached"
+        );
+
+        let genesis =
+            format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{MAINNET_BETA_GENESIS_HASH}"}}"#);
+        let sent = r#"{"jsonrpc":"2.0","id":1,"result":"sig"}"#.to_string();
+        let status = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":44},"value":[{"slot":44,"confirmations":0,"err":null,"confirmationStatus":"confirmed"}]}}"#.to_string();
+        let mainnet = spawn_sequenced_mock_server(200, &[], vec![genesis, sent, status]).await;
+        let config = ResubmitConfig::new(vec![mainnet.url.clone()], 1_000)
+            .interval(Duration::from_millis(10))
+            .require_mainnet(true);
+        let report = resubmit_until_landed(&transaction_with_key(USDC_MINT), &config)
+            .await
+            .unwrap();
+        assert_eq!(report.outcome, LandingOutcome::Confirmed { slot: 44 });
+        assert!(mainnet.requests.lock().unwrap()[0].contains("getGenesisHash"));
+    }
+
+    #[test]
+    fn test_mainnet_genesis_guard() {
+        assert!(ensure_mainnet_genesis(MAINNET_BETA_GENESIS_HASH).is_ok());
+
+        // devnet
+        let result = ensure_mainnet_genesis("EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG");
+        assert!(matches!(
+            result,
+            Err(JupiterClientError::ClusterMismatch(_))
+        ));
+    }
+}
@@ -1,13 +1,36 @@
 #[cfg(test)]
 mod swap_tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use base64::{Engine, engine::general_purpose::STANDARD};
     use jup_ag_sdk::{
-        JupiterClient,
-        types::{DexEnum, QuoteGetSwapModeEnum, QuoteRequest, SwapRequest},
+        JupiterClient, JupiterClientError,
+        bucketing::AmountBucketing,
+        client::CallOpts,
+        policy::PolicyDecision,
+        predicate::{ExecutionPredicate, QuoteDrift},
+        types::{
+            AccountSetup, BatchExecutionConfig, BatchSwap, BatchSwapOutcome, DexEnum,
+            EnrichedQuote, FeeAccountSource, FeeAttribution, FillBound, Holding, Instruction,
+            InstructionRole, IntegratorFee, MidPrice, NATIVE_SOL, PlatformFee, PriorityLevel,
+            Profile, QuoteGetSwapModeEnum, QuoteRequest, QuoteResponse, RequestDefaults,
+            RouteInstruction, RouteStability, SignRequest, SizeSuggestion, Slippage,
+            SlippageComparison, SwapPreview, SwapRequest, SwapResponse,
+            TOKEN_ACCOUNT_RENT_LAMPORTS, TokenBalance, TokenMeta, WalletHoldings,
+            WithdrawalConversion, WithdrawalRequest, diagnose_simulation, failing_program_id,
+            profile_compute_units, rent_exempt_lamports,
+        },
+        warnings::ClientWarning,
     };
 
     use crate::common::{
         BASE_URL, DEFAULT_SLIPPAGE_BPS, JUP_MINT, SOL_MINT, TEST_AMOUNT, TEST_USER_PUBKEY,
-        create_test_client,
+        USDC_MINT, create_test_client, quote_body, route_swap_instructions, sample_quote_response,
+        spawn_mock_server,
     };
 
     fn create_default_quote_request() -> QuoteRequest {
@@ -22,6 +45,627 @@ mod swap_tests {
         assert_eq!(client.base_url, BASE_URL);
     }
 
+    #[tokio::test]
+    async fn test_quote_cache_deduplicates_within_ttl() {
+        let quote = serde_json::to_string(&sample_quote_response()).unwrap();
+        let server = spawn_mock_server(200, &[], quote).await;
+        let client = JupiterClient::new(&server.url).with_quote_cache(Duration::from_millis(200));
+
+        let request =
+            create_default_quote_request().dexes(vec![DexEnum::Whirlpool, DexEnum::MeteoraDlmm]);
+        let reordered =
+            create_default_quote_request().dexes(vec![DexEnum::MeteoraDlmm, DexEnum::Whirlpool]);
+
+        client.get_quote(&request).await.unwrap();
+        client.get_quote(&reordered).await.unwrap();
+        assert_eq!(server.request_count(), 1, "DEX order is normalized");
+
+        client
+            .get_quote(&create_default_quote_request().slippage_bps(5))
+            .await
+            .unwrap();
+        assert_eq!(server.request_count(), 2, "different requests miss");
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        client.get_quote(&request).await.unwrap();
+        assert_eq!(server.request_count(), 3, "expired entries are refetched");
+
+        client.clear_quote_cache();
+        client.get_quote(&request).await.unwrap();
+        assert_eq!(server.request_count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_amount_bucketing() {
+        let log = AmountBucketing::Log {
+            buckets_per_decade: 10,
+        };
+        assert_eq!(log.bucket(0), 0);
+        assert_eq!(log.bucket(1_000_000), 1_000_000);
+        assert_eq!(log.bucket(1_100_000), 1_000_000);
+        assert_eq!(log.bucket(1_300_000), 1_258_925);
+        assert_eq!(log.bucket(u64::MAX), log.bucket(u64::MAX - 1));
+
+        let step = AmountBucketing::FixedStep(1_000);
+        assert_eq!(step.bucket(12_345), 12_000);
+        assert_eq!(AmountBucketing::FixedStep(0).bucket(12_345), 12_345);
+        assert_eq!(AmountBucketing::custom(|a| a / 2).bucket(10), 5);
+        assert_eq!(AmountBucketing::Exact.bucket(7), 7);
+
+        let quote = serde_json::to_string(&sample_quote_response()).unwrap();
+        let server = spawn_mock_server(200, &[], quote).await;
+        let client = JupiterClient::new(&server.url)
+            .with_bucketed_quote_cache(Duration::from_secs(60), step);
+        for amount in [12_000, 12_999, 13_000] {
+            let request = QuoteRequest::new(SOL_MINT, USDC_MINT, amount);
+            client.get_quote(&request).await.unwrap();
+        }
+        assert_eq!(
+            server.request_count(),
+            2,
+            "amounts in one bucket share a quote"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_swaps_skip_bucketed_quotes_of_other_amounts() {
+        let server = spawn_mock_server(200, &[], quote_body()).await;
+        let client = JupiterClient::new(&server.url).with_bucketed_quote_cache(
+            Duration::from_secs(60),
+            AmountBucketing::FixedStep(1_000_000),
+        );
+        let quote_count = || {
+            server
+                .requests
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|r| r.contains("/quote?"))
+                .count()
+        };
+        // the sample quote is for exactly TEST_AMOUNT
+        client
+            .get_quote(&QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT))
+            .await
+            .unwrap();
+        client
+            .get_quote(&QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT + 500))
+            .await
+            .unwrap();
+        assert_eq!(quote_count(), 1, "display quotes share the bucket");
+
+        let config = BatchExecutionConfig::new(Duration::from_secs(30));
+        for amount in [TEST_AMOUNT, TEST_AMOUNT + 500] {
+            let swap = BatchSwap::new(
+                "swap",
+                TEST_USER_PUBKEY,
+                QuoteRequest::new(SOL_MINT, JUP_MINT, amount),
+            );
+            client
+                .execute_batch(vec![swap], &config, |_, _| async { Ok("sig".to_string()) })
+                .await;
+        }
+        assert_eq!(
+            quote_count(),
+            2,
+            "a swap of the cached amount reuses the quote, one of another amount is quoted afresh"
+        );
+        let requests = server.requests.lock().unwrap();
+        let last_quote = requests.iter().rfind(|r| r.contains("/quote?")).unwrap();
+        assert!(last_quote.contains(&format!("amount={}", TEST_AMOUNT + 500)));
+    }
+
+    #[tokio::test]
+    async fn test_execute_batch_orders_by_priority_and_decay() {
+        let server = spawn_mock_server(200, &[], quote_body()).await;
+        let client = JupiterClient::new(&server.url);
+        let swap = |id: &str, amount: u64| {
+            BatchSwap::new(
+                id,
+                TEST_USER_PUBKEY,
+                QuoteRequest::new(SOL_MINT, JUP_MINT, amount),
+            )
+        };
+        let swaps = vec![
+            swap("low", 1_000),
+            swap("slow", 2_000).priority(5),
+            swap("fast", 3_000).priority(5).decay_bps_per_sec(20.0),
+            swap("urgent", 4_000).priority(10),
+        ];
+
+        let config = BatchExecutionConfig::new(Duration::from_secs(30)).max_concurrency(1);
+        let report = client
+            .execute_batch(swaps.clone(), &config, |_, _| async {
+                Ok("sig".to_string())
+            })
+            .await;
+
+        let order: Vec<&str> = report.results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(order, ["urgent", "fast", "slow", "low"]);
+        let quoted: Vec<bool> = server
+            .requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.contains("/quote?"))
+            .zip(["4000", "3000", "2000", "1000"])
+            .map(|(request, amount)| request.contains(&format!("amount={amount}")))
+            .collect();
+        assert_eq!(
+            quoted, [true; 4],
+            "swaps should be quoted in execution order"
+        );
+        // the mock answers /swap with a quote, so building fails and is reported per swap
+        assert!(
+            report
+                .results
+                .iter()
+                .all(|r| matches!(r.outcome, BatchSwapOutcome::Failed { .. }))
+        );
+
+        let expired = client
+            .execute_batch(
+                swaps,
+                &BatchExecutionConfig::new(Duration::ZERO),
+                |_, _| async { Ok("sig".to_string()) },
+            )
+            .await;
+        assert_eq!(expired.skipped().count(), 4);
+        assert!(expired.results.iter().all(|r| r.started_after.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_execute_batch_last_look_predicates() {
+        // answers both /quote and /swap, since unknown fields are ignored
+        let mut body = serde_json::to_value(sample_quote_response()).unwrap();
+        body["swapTransaction"] = "AQ==".into();
+        body["lastValidBlockHeight"] = 100.into();
+        body["prioritizationFeeLamports"] = 0.into();
+        let server = spawn_mock_server(200, &[], body.to_string()).await;
+        let client = JupiterClient::new(&server.url).with_quote_cache(Duration::from_secs(60));
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, 1_000);
+
+        let swaps = vec![
+            BatchSwap::new("drift", TEST_USER_PUBKEY, request.clone()).predicate(QuoteDrift(30)),
+            BatchSwap::new("closed", TEST_USER_PUBKEY, request.clone()).predicate(
+                |_: &QuoteRequest, _: &QuoteResponse| async {
+                    PolicyDecision::Deny("spread closed".to_string())
+                },
+            ),
+        ];
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let config = BatchExecutionConfig::new(Duration::from_secs(30)).max_concurrency(1);
+        let report = client
+            .execute_batch(swaps, &config, |swap, _| {
+                sent.lock().unwrap().push(swap.id.clone());
+                async { Ok("sig".to_string()) }
+            })
+            .await;
+
+        assert!(matches!(
+            &report.results[0].outcome,
+            BatchSwapOutcome::Executed { signature, .. } if signature == "sig"
+        ));
+        assert_eq!(
+            report.results[1].outcome,
+            BatchSwapOutcome::Aborted {
+                reason: "spread closed".to_string()
+            }
+        );
+        assert_eq!(
+            *sent.lock().unwrap(),
+            ["drift"],
+            "an aborted swap is never sent"
+        );
+        let quotes = server
+            .requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.contains("/quote?"))
+            .count();
+        assert_eq!(quotes, 2, "the drift check bypasses the quote cache");
+
+        // the fresh quote is 300000000
+        let mut built = sample_quote_response();
+        built.out_amount = "301000000".to_string();
+        assert!(matches!(
+            QuoteDrift(30).evaluate(&client, &request, &built).await,
+            PolicyDecision::Deny(reason) if reason.contains("33.2 bps")
+        ));
+        built.out_amount = "300500000".to_string();
+        assert_eq!(
+            QuoteDrift(30).evaluate(&client, &request, &built).await,
+            PolicyDecision::Allow
+        );
+
+        // ExactOut routes move the input instead; the fresh quote spends 1000000000
+        let mut exact_out = built.clone();
+        exact_out.swap_mode = QuoteGetSwapModeEnum::ExactOut;
+        exact_out.out_amount = "1".to_string();
+        exact_out.in_amount = "995000000".to_string();
+        assert!(matches!(
+            QuoteDrift(30).evaluate(&client, &request, &exact_out).await,
+            PolicyDecision::Deny(reason) if reason.contains("50.3 bps")
+        ));
+        exact_out.in_amount = "1000000000".to_string();
+        assert_eq!(
+            QuoteDrift(30).evaluate(&client, &request, &exact_out).await,
+            PolicyDecision::Allow
+        );
+
+        let mut unreadable = built.clone();
+        unreadable.out_amount = "0".to_string();
+        assert!(matches!(
+            QuoteDrift(30)
+                .evaluate(&client, &request, &unreadable)
+                .await,
+            PolicyDecision::Deny(_)
+        ));
+        unreadable.out_amount = "n/a".to_string();
+        assert!(matches!(
+            QuoteDrift(30)
+                .evaluate(&client, &request, &unreadable)
+                .await,
+            PolicyDecision::Deny(_)
+        ));
+
+        let down = JupiterClient::new("http://127.0.0.1:1");
+        assert!(matches!(
+            QuoteDrift(30).evaluate(&down, &request, &built).await,
+            PolicyDecision::Deny(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_venue_allowlist_is_enforced() {
+        let server = spawn_mock_server(200, &[], quote_body()).await;
+        let allowed = JupiterClient::new(&server.url)
+            .with_venue_allowlist(vec![DexEnum::Whirlpool, DexEnum::MeteoraDlmm]);
+
+        allowed
+            .get_quote(&create_default_quote_request())
+            .await
+            .unwrap();
+        let sent = server.requests.lock().unwrap()[0].clone();
+        assert!(sent.contains("dexes=Whirlpool%2CMeteora+DLMM"), "{sent}");
+
+        let excluded = create_default_quote_request().exclude_dexes(vec![DexEnum::MeteoraDlmm]);
+        allowed.get_quote(&excluded).await.unwrap_err();
+        let sent = server.requests.lock().unwrap()[1].clone();
+        assert!(
+            sent.split(['?', '&', ' '])
+                .any(|param| param == "dexes=Whirlpool"),
+            "excluded venues are dropped: {sent}"
+        );
+        assert!(!sent.contains("excludeDexes"));
+
+        // the mocked route goes through Meteora DLMM, which this client doesn't allow
+        let strict = JupiterClient::new(&server.url).with_venue_allowlist(vec![DexEnum::Whirlpool]);
+        assert!(matches!(
+            strict.get_quote(&create_default_quote_request()).await,
+            Err(JupiterClientError::VenueNotAllowed(label)) if label == "Meteora DLMM"
+        ));
+
+        let sent_before = server.requests.lock().unwrap().len();
+        assert!(matches!(
+            strict
+                .get_quote(&create_default_quote_request().dexes(vec![DexEnum::Raydium]))
+                .await,
+            Err(JupiterClientError::VenueNotAllowed(label)) if label == "Raydium"
+        ));
+        assert!(matches!(
+            strict
+                .get_quote(&create_default_quote_request().exclude_dexes(vec![DexEnum::Whirlpool]))
+                .await,
+            Err(JupiterClientError::ConfigError(_))
+        ));
+        assert_eq!(
+            server.requests.lock().unwrap().len(),
+            sent_before,
+            "invalid requests are rejected before sending"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_attribute_fees_normalizes_hops() {
+        let price = |usd_price: f64, decimals: u8| {
+            serde_json::json!({
+                "usdPrice": usd_price,
+                "blockId": 348000000u64,
+                "decimals": decimals,
+                "priceChange24h": 0.0
+            })
+        };
+        let prices = serde_json::json!({
+            SOL_MINT: price(150.0, 9),
+            USDC_MINT: price(1.0, 6),
+            JUP_MINT: price(0.5, 6),
+        });
+        let server = spawn_mock_server(200, &[], prices.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+
+        let quote = sample_quote_response();
+        let fees = client.attribute_fees(&quote).await.unwrap();
+        // 0.0001 SOL and 0.015 USDC, $0.015 each, are 0.03 JUP each
+        assert_eq!(fees.output_mint, JUP_MINT);
+        assert_eq!(fees.fees.len(), 2);
+        assert_eq!(fees.fees[0].label, "Whirlpool");
+        assert_eq!(fees.fees[0].output_amount, Some(30_000));
+        assert_eq!(fees.fees[1].output_amount, Some(30_000));
+        assert_eq!(fees.total_output_amount, 60_000);
+        assert!((fees.total_usd - 0.03).abs() < 1e-9);
+        assert!((fees.total_bps - 2.0).abs() < 1e-9);
+        assert!(fees.complete);
+        let sent = server.requests.lock().unwrap()[0].clone();
+        assert!(sent.contains(&format!("ids={JUP_MINT}%2C{SOL_MINT}%2C{USDC_MINT}")));
+
+        let unpriced = FeeAttribution::new(&quote, &HashMap::new());
+        assert!(!unpriced.complete);
+        assert_eq!(unpriced.total_output_amount, 0);
+    }
+
+    #[tokio::test]
+    async fn test_quote_template_stamps_amounts() {
+        let server = spawn_mock_server(200, &[], quote_body()).await;
+        let client = JupiterClient::new(&server.url)
+            .with_defaults(RequestDefaults::new().slippage_bps(30).max_accounts(40));
+
+        let template = client
+            .quote_template(QuoteRequest::new(SOL_MINT, USDC_MINT, 0))
+            .unwrap();
+        let request = template.request(5_000);
+        assert_eq!(request.amount, 5_000);
+        assert_eq!(request.slippage_bps, Some(30), "defaults are captured");
+        assert_eq!(request.max_accounts, Some(40));
+
+        client
+            .get_quote_from_template(&template, 7_000)
+            .await
+            .unwrap();
+        let sent = server.requests.lock().unwrap()[0].clone();
+        assert!(sent.contains("amount=7000"));
+        assert!(sent.contains("slippageBps=30"));
+        assert_eq!(
+            serde_json::to_value(template.query(7_000)).unwrap(),
+            serde_json::to_value(template.request(7_000).query()).unwrap(),
+            "the borrowed query matches the request it stands for"
+        );
+
+        assert!(matches!(
+            client.quote_template(QuoteRequest::new("not-a-mint", USDC_MINT, 0)),
+            Err(JupiterClientError::InvalidPubkey(_))
+        ));
+        assert!(matches!(
+            client.quote_template(QuoteRequest::new(SOL_MINT, USDC_MINT, 0).slippage_bps(5_000)),
+            Err(JupiterClientError::SlippageTooHigh { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_validate_dexes() {
+        let labels = serde_json::json!({
+            "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc": "Whirlpool",
+            "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo": "Meteora DLMM",
+            "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG": "Meteora DAMM v2",
+        });
+        let server = spawn_mock_server(200, &[], labels.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+
+        let labels = client.get_program_id_to_label().await.unwrap();
+        assert_eq!(
+            labels["LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo"],
+            "Meteora DLMM"
+        );
+
+        client
+            .validate_dexes(&[
+                DexEnum::Whirlpool,
+                DexEnum::MeteoraDlmm,
+                DexEnum::Other("Meteora DAMM v2".to_string()),
+            ])
+            .await
+            .unwrap();
+        assert!(matches!(
+            client
+                .validate_dexes(&[DexEnum::Whirlpool, DexEnum::Other("Whirpool".to_string())])
+                .await,
+            Err(JupiterClientError::UnknownDex(dex)) if dex == "Whirpool"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_quotes_preserves_order_and_errors() {
+        let server = spawn_mock_server(200, &[], quote_body()).await;
+        let client = JupiterClient::new(&server.url);
+
+        let mut requests = vec![create_default_quote_request(); 5];
+        requests[2] = requests[2].clone().slippage_bps(5_000);
+        let results = client.get_quotes(&requests, 2).await;
+
+        assert_eq!(results.len(), 5);
+        assert!(matches!(
+            results[2],
+            Err(JupiterClientError::SlippageTooHigh { bps: 5_000, .. })
+        ));
+        for result in results.iter().enumerate().filter(|(i, _)| *i != 2) {
+            assert_eq!(result.1.as_ref().unwrap().context_slot, 343_000_000);
+        }
+        assert_eq!(server.request_count(), 4);
+        assert!(client.get_quotes(&[], 0).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_warning_sink_reports_quote_anomalies() {
+        let mut fresh = sample_quote_response();
+        fresh.context_slot = 343_000_100;
+        let fresh_server =
+            spawn_mock_server(200, &[], serde_json::to_string(&fresh).unwrap()).await;
+
+        let mut stale = serde_json::to_value(sample_quote_response()).unwrap();
+        let hop = &mut stale["routePlan"][0]["swapInfo"];
+        hop["label"] = "Brand New AMM".into();
+        hop.as_object_mut().unwrap().remove("feeMint");
+        let stale_server = spawn_mock_server(200, &[], stale.to_string()).await;
+
+        let warnings = Arc::new(Mutex::new(Vec::new()));
+        let sink = warnings.clone();
+        let client = JupiterClient::new(&fresh_server.url)
+            .with_warning_sink(move |w: &ClientWarning| sink.lock().unwrap().push(w.clone()));
+
+        let request = create_default_quote_request();
+        client.get_quote(&request).await.unwrap();
+        assert!(
+            warnings.lock().unwrap().is_empty(),
+            "known venues, fresh slot"
+        );
+
+        let opts = CallOpts::new().base_url(&stale_server.url);
+        client.for_call(&opts).get_quote(&request).await.unwrap();
+        assert_eq!(
+            *warnings.lock().unwrap(),
+            vec![
+                ClientWarning::UnknownDexLabel {
+                    label: "Brand New AMM".to_string()
+                },
+                ClientWarning::FieldDefaulted {
+                    field: "routePlan.swapInfo.feeMint",
+                    value: "0".to_string()
+                },
+                ClientWarning::StaleQuote {
+                    context_slot: 343_000_000,
+                    latest_slot: 343_000_100
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_slippage_parsing_and_maximum() {
+        for (input, bps) in [
+            ("0.5%", 50),
+            (" 1 %", 100),
+            ("50bps", 50),
+            ("75 bps", 75),
+            ("30", 30),
+        ] {
+            assert_eq!(input.parse::<Slippage>().unwrap().bps(), bps, "{input}");
+        }
+        for input in ["", "abc", "-1%", "101%", "10001", "0.5 percent"] {
+            assert!(
+                matches!(
+                    input.parse::<Slippage>(),
+                    Err(JupiterClientError::InvalidSlippage(_))
+                ),
+                "{input} should be rejected"
+            );
+        }
+        assert_eq!(Slippage::try_from(0.25).unwrap().bps(), 25);
+        assert!(Slippage::try_from(f64::NAN).is_err());
+        assert_eq!(Slippage::from_bps(50).to_string(), "0.5%");
+
+        // rejected before anything is sent, so nothing needs to listen
+        let client = JupiterClient::new("http://127.0.0.1:1");
+        let request = create_default_quote_request().slippage("50%".parse().unwrap());
+        assert!(matches!(
+            client.get_quote(&request).await,
+            Err(JupiterClientError::SlippageTooHigh {
+                bps: 5_000,
+                max_bps: 1_000
+            })
+        ));
+
+        let client = client.with_max_slippage(Slippage::percent(50.0).unwrap());
+        assert!(matches!(
+            client.get_quote(&request).await,
+            Err(JupiterClientError::RequestError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_quote_request_dex_lists_round_trip() {
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .dexes(vec![
+                DexEnum::MeteoraDlmm,
+                DexEnum::Other("Meteora DAMM v2".to_string()),
+            ])
+            .exclude_dexes(vec![DexEnum::Raydium]);
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json["dexes"],
+            serde_json::json!(["Meteora DLMM", "Meteora DAMM v2"])
+        );
+        assert_eq!(json["excludeDexes"], serde_json::json!(["Raydium"]));
+        let parsed: QuoteRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.dexes, request.dexes);
+        assert_eq!(parsed.exclude_dexes, request.exclude_dexes);
+
+        let query = reqwest::Client::new()
+            .get("https://lite-api.jup.ag/swap/v1/quote")
+            .query(&request.query())
+            .build()
+            .unwrap();
+        let query = query.url().query().unwrap();
+        assert!(
+            query.contains("dexes=Meteora+DLMM%2CMeteora+DAMM+v2"),
+            "{query}"
+        );
+        assert!(query.contains("excludeDexes=Raydium"), "{query}");
+        assert!(
+            !query.contains("maxAccounts"),
+            "unset fields are skipped: {query}"
+        );
+
+        // configs written with the query form still load
+        let joined: QuoteRequest = serde_json::from_value(serde_json::json!({
+            "inputMint": SOL_MINT,
+            "outputMint": JUP_MINT,
+            "amount": TEST_AMOUNT,
+            "dexes": "Whirlpool, Meteora DLMM",
+        }))
+        .unwrap();
+        assert_eq!(
+            joined.dexes,
+            Some(vec![DexEnum::Whirlpool, DexEnum::MeteoraDlmm])
+        );
+        assert_eq!(joined.exclude_dexes, None);
+    }
+
+    #[test]
+    fn test_quote_request_loads_variant_name_configs() {
+        // written before DEX lists were serialized as labels
+        let old = serde_json::json!({
+            "inputMint": SOL_MINT,
+            "outputMint": JUP_MINT,
+            "amount": TEST_AMOUNT,
+            "dexes": ["MeteoraDlmm", "Whirlpool", "Dex1"],
+            "excludeDexes": ["RaydiumClmm", "OpenBook"],
+        });
+        let request: QuoteRequest = serde_json::from_value(old).unwrap();
+        assert_eq!(
+            request.dexes,
+            Some(vec![
+                DexEnum::MeteoraDlmm,
+                DexEnum::Whirlpool,
+                DexEnum::Dex1
+            ])
+        );
+        assert_eq!(
+            request.exclude_dexes,
+            Some(vec![DexEnum::RaydiumClmm, DexEnum::OpenBook])
+        );
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json["dexes"],
+            serde_json::json!(["Meteora DLMM", "Whirlpool", "1DEX"])
+        );
+        let reloaded: QuoteRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(reloaded.dexes, request.dexes);
+        assert_eq!(reloaded.exclude_dexes, request.exclude_dexes);
+    }
+
     #[test]
     fn test_quote_request_builder_methods() {
         let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
@@ -77,6 +721,816 @@ mod swap_tests {
         );
     }
 
+    #[test]
+    fn test_request_defaults_fill_unset_fields() {
+        let defaults = RequestDefaults::new()
+            .slippage_bps(50)
+            .restrict_intermediate_tokens(true)
+            .max_accounts(40)
+            .prioritization_fee_config(1_000_000, PriorityLevel::High)
+            .tracking_account(TEST_USER_PUBKEY);
+        let client = JupiterClient::new(BASE_URL).with_defaults(defaults.clone());
+        assert_eq!(client.defaults().max_accounts, Some(40));
+
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT);
+        assert_eq!(request.restrict_intermediate_tokens, Some(false));
+        let quote = defaults.apply_to_quote(&request);
+        assert_eq!(quote.slippage_bps, Some(50));
+        assert_eq!(
+            quote.restrict_intermediate_tokens,
+            Some(false),
+            "new() keeps routing through any intermediate token"
+        );
+        assert_eq!(quote.max_accounts, Some(40));
+
+        let request = request.use_default_intermediate_tokens();
+        assert!(
+            serde_json::to_value(request.query()).unwrap()["restrictIntermediateTokens"].is_null()
+        );
+        let quote = defaults.apply_to_quote(&request);
+        assert_eq!(
+            quote.restrict_intermediate_tokens,
+            Some(true),
+            "requests that opt in take the default"
+        );
+
+        let explicit = defaults.apply_to_quote(
+            &QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+                .slippage_bps(DEFAULT_SLIPPAGE_BPS)
+                .max_accounts(20),
+        );
+        assert_eq!(explicit.slippage_bps, Some(DEFAULT_SLIPPAGE_BPS));
+        assert_eq!(explicit.max_accounts, Some(20), "explicit values win");
+
+        let swap = defaults.apply_to_swap(&SwapRequest::new(
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            sample_quote_response(),
+        ));
+        assert!(swap.prioritization_fee_lamports.is_some());
+        assert_eq!(swap.tracking_account.as_deref(), Some(TEST_USER_PUBKEY));
+
+        let swap = defaults.apply_to_swap(
+            &SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response())
+                .compute_unit_price_micro_lamports(1_000)
+                .tracking_account("ExplicitTracker".to_string()),
+        );
+        assert_eq!(swap.tracking_account.as_deref(), Some("ExplicitTracker"));
+        assert!(
+            swap.prioritization_fee_lamports.is_none(),
+            "an explicit compute unit price disables the default priority fee"
+        );
+    }
+
+    #[test]
+    fn test_pair_defaults_apply_in_both_directions() {
+        let client = JupiterClient::new(BASE_URL)
+            .with_defaults(RequestDefaults::new().slippage_bps(100))
+            .with_pair_defaults(USDC_MINT, JUP_MINT, RequestDefaults::new().slippage_bps(5));
+
+        let forward = client
+            .pair_defaults(USDC_MINT, JUP_MINT)
+            .expect("pair override should be registered");
+        let reverse = client
+            .pair_defaults(JUP_MINT, USDC_MINT)
+            .expect("pair override should match the reverse direction");
+        assert_eq!(forward.slippage_bps, Some(5));
+        assert_eq!(reverse.slippage_bps, Some(5));
+        assert!(client.pair_defaults(SOL_MINT, JUP_MINT).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pair_defaults_shape_the_sent_quote() {
+        let server = spawn_mock_server(200, &[], quote_body()).await;
+        let client = JupiterClient::new(&server.url)
+            .with_defaults(RequestDefaults::new().slippage_bps(50))
+            .with_pair_defaults(
+                JUP_MINT,
+                SOL_MINT,
+                RequestDefaults::new()
+                    .slippage_bps(300)
+                    .max_accounts(20)
+                    .exclude_dexes(vec![DexEnum::Raydium, DexEnum::MeteoraDlmm]),
+            );
+
+        client
+            .get_quote(&QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT))
+            .await
+            .unwrap();
+        client
+            .get_quote(&QuoteRequest::new(SOL_MINT, USDC_MINT, TEST_AMOUNT))
+            .await
+            .unwrap();
+        client
+            .get_quote(
+                &QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+                    .exclude_dexes(vec![DexEnum::Phoenix]),
+            )
+            .await
+            .unwrap();
+
+        let requests = server.requests.lock().unwrap();
+        let query = |i: usize| requests[i].lines().next().unwrap().to_string();
+        let pair = query(0);
+        assert!(pair.contains("slippageBps=300"), "{pair}");
+        assert!(pair.contains("maxAccounts=20"), "{pair}");
+        assert!(
+            pair.contains("excludeDexes=Raydium%2CMeteora+DLMM"),
+            "{pair}"
+        );
+        let other = query(1);
+        assert!(other.contains("slippageBps=50"), "{other}");
+        assert!(!other.contains("excludeDexes"), "{other}");
+        let explicit = query(2);
+        assert!(
+            explicit.contains("excludeDexes=Phoenix&")
+                || explicit.ends_with("excludeDexes=Phoenix"),
+            "the request's own exclusions win: {explicit}"
+        );
+    }
+
+    #[test]
+    fn test_sign_request_bundle() {
+        let request = SwapRequest::new(TEST_USER_PUBKEY, "FeePayer", sample_quote_response());
+        let response = SwapResponse {
+            swap_transaction: "AQAAAA==".to_string(),
+            last_valid_block_height: 250_000_000,
+            prioritization_fee_lamports: 5000,
+        };
+
+        let bundle = SignRequest::from_swap(&request, &response);
+        assert_eq!(bundle.transaction, "AQAAAA==");
+        assert_eq!(bundle.signers, vec![TEST_USER_PUBKEY, "FeePayer"]);
+        assert_eq!(bundle.summary.other_amount_threshold, "297000000");
+        assert_eq!(bundle.summary.last_valid_block_height, Some(250_000_000));
+
+        let venues: Vec<_> = bundle.summary.route.iter().map(|hop| &hop.venue).collect();
+        assert_eq!(venues, vec!["Whirlpool", "Meteora DLMM"]);
+
+        let json = serde_json::to_value(&bundle).unwrap();
+        assert_eq!(
+            json["summary"]["inAmount"], "1000000000",
+            "should serialize camelCase"
+        );
+    }
+
+    #[test]
+    fn test_withdrawal_conversion_verify() {
+        let request = WithdrawalRequest::new(USDC_MINT, 250_000_000, "UserUsdcAccount")
+            .funding_mints(vec![SOL_MINT.to_string(), JUP_MINT.to_string()])
+            .slippage_bps(50);
+        assert_eq!(request.funding_mints.len(), 2);
+
+        let conversion = WithdrawalConversion {
+            funding_mint: SOL_MINT.to_string(),
+            max_in_amount: 1_010_000_000,
+            expected_out_amount: request.amount,
+            destination_token_account: request.destination_token_account.clone(),
+            quote: sample_quote_response(),
+            swap: SwapResponse {
+                swap_transaction: String::new(),
+                last_valid_block_height: 0,
+                prioritization_fee_lamports: 0,
+            },
+        };
+
+        assert!(conversion.verify(250_000_000).is_ok());
+        assert!(matches!(
+            conversion.verify(249_999_999),
+            Err(JupiterClientError::FillMismatch {
+                expected: 250_000_000,
+                received: 249_999_999
+            })
+        ));
+    }
+
+    #[test]
+    fn test_mid_price_from_two_sided_quotes() {
+        // sell 1 SOL for 150 USDC, buy back 0.99 SOL with 150 USDC
+        let mut sell = sample_quote_response();
+        sell.in_amount = "1000000000".to_string();
+        sell.out_amount = "150000000".to_string();
+        let mut buy = sample_quote_response();
+        buy.in_amount = "150000000".to_string();
+        buy.out_amount = "990000000".to_string();
+
+        let price = MidPrice::from_quotes(&sell, &buy).unwrap();
+        assert!(price.ask > price.bid);
+        assert!(
+            (price.ui_mid(9, 6) - 150.757_575).abs() < 1e-3,
+            "mid in USDC per SOL"
+        );
+        assert!((price.spread_bps - 100.5).abs() < 0.1);
+
+        buy.out_amount = "0".to_string();
+        assert!(MidPrice::from_quotes(&sell, &buy).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_suggest_max_size_propagates_transport_errors() {
+        // only "no route" API errors count as exceeding the threshold, not a dead connection
+        let client = JupiterClient::new("http://127.0.0.1:1");
+        let result = client
+            .suggest_max_size(&create_default_quote_request(), 1.0)
+            .await;
+        assert!(matches!(
+            result,
+            Err(JupiterClientError::RequestError { .. })
+        ));
+
+        let suggestion = SizeSuggestion {
+            requested_amount: TEST_AMOUNT,
+            max_amount: TEST_AMOUNT / 4,
+            quote: Some(sample_quote_response()),
+            quotes_used: 3,
+        };
+        assert!(suggestion.is_liquidity_limited());
+        assert!(suggestion.price_impact_pct().is_some());
+    }
+
+    #[test]
+    fn test_route_stability_scoring() {
+        let stable = vec![sample_quote_response(); 4];
+        let stability = RouteStability::from_quotes(&stable).unwrap();
+        assert_eq!(stability.distinct_routes, 1);
+        assert_eq!(stability.dominant_route, vec!["Whirlpool", "Meteora DLMM"]);
+        assert_eq!(stability.score, 1.0);
+
+        // one sample routes directly, and output drifts by ~0.5%
+        let mut flipped = sample_quote_response();
+        flipped.route_plan.truncate(1);
+        let mut drifted = sample_quote_response();
+        drifted.out_amount = "303000000".to_string();
+        let quotes = vec![
+            sample_quote_response(),
+            sample_quote_response(),
+            flipped,
+            drifted,
+        ];
+        let stability = RouteStability::from_quotes(&quotes).unwrap();
+        assert_eq!(stability.distinct_routes, 2);
+        assert_eq!(stability.dominant_route_share, 0.75);
+        assert!(stability.out_amount_deviation_bps > 0.0);
+        assert!(stability.score < 0.75);
+
+        assert!(RouteStability::from_quotes(&[]).is_none());
+    }
+
+    #[test]
+    fn test_worst_case_fill_in_ui_units() {
+        let mut quote = sample_quote_response();
+        assert_eq!(quote.worst_case_mint(), JUP_MINT);
+
+        let fill = quote.worst_case_fill(6, "JUP").unwrap();
+        assert_eq!(fill.bound, FillBound::MinimumReceived);
+        assert_eq!(fill.amount, 297_000_000);
+        assert_eq!(fill.to_string(), "receive at least 297 JUP");
+
+        quote.swap_mode = QuoteGetSwapModeEnum::ExactOut;
+        quote.other_amount_threshold = "1010500".to_string();
+        let fill = quote.worst_case_fill(9, "SOL").unwrap();
+        assert_eq!(fill.mint, SOL_MINT);
+        assert_eq!(fill.to_string(), "spend at most 0.0010105 SOL");
+
+        quote.other_amount_threshold = "not a number".to_string();
+        assert!(quote.worst_case_fill(9, "SOL").is_err());
+    }
+
+    #[test]
+    fn test_amounts_accept_json_numbers() {
+        let mut quote = serde_json::to_value(sample_quote_response()).unwrap();
+        quote["inAmount"] = serde_json::json!(18_446_744_073_709_551_615u64);
+        quote["outAmount"] = serde_json::json!(300000000);
+        quote["routePlan"][0]["swapInfo"]["feeAmount"] = serde_json::json!(100000);
+        quote["platformFee"] = serde_json::json!({ "amount": 15, "feeBps": 20 });
+
+        let quote: QuoteResponse = serde_json::from_value(quote).unwrap();
+        assert_eq!(quote.in_amount, "18446744073709551615");
+        assert_eq!(quote.out_amount, "300000000");
+        assert_eq!(quote.route_plan[0].swap_info.fee_amount, "100000");
+        assert_eq!(quote.platform_fee.unwrap().amount, "15");
+        assert_eq!(quote.route_plan[1].swap_info.fee_amount, "15000");
+
+        let bad =
+            serde_json::json!({ "amount": true, "uiAmount": 0.0, "slot": 1, "isFrozen": false });
+        assert!(serde_json::from_value::<TokenBalance>(bad).is_err());
+    }
+
+    #[test]
+    fn test_swap_preview() {
+        let quote = sample_quote_response();
+        let swap = SwapResponse {
+            swap_transaction: String::new(),
+            last_valid_block_height: 279_000_000,
+            prioritization_fee_lamports: 95_000,
+        };
+        let mut tokens = HashMap::from([
+            (
+                SOL_MINT.to_string(),
+                TokenMeta::new(SOL_MINT, "SOL", 9).usd_price(150.0),
+            ),
+            (JUP_MINT.to_string(), TokenMeta::new(JUP_MINT, "JUP", 6)),
+            (
+                USDC_MINT.to_string(),
+                TokenMeta::new(USDC_MINT, "USDC", 6).usd_price(1.0),
+            ),
+        ]);
+
+        let preview = SwapPreview::new(&quote, Some(&swap), &tokens).unwrap();
+        assert_eq!(preview.input.ui_amount, "1");
+        assert_eq!(preview.input.usd_value, Some(150.0));
+        assert_eq!(preview.output.ui_amount, "300");
+        assert_eq!(preview.output.usd_value, None, "JUP has no price");
+        assert_eq!(preview.worst_case.to_string(), "receive at least 297 JUP");
+        assert_eq!(preview.venues, vec!["Whirlpool", "Meteora DLMM"]);
+        assert_eq!(preview.fees.len(), 2);
+        assert_eq!(preview.fees[1].amount.ui_amount, "0.015");
+        assert!((preview.total_fees_usd() - 0.03).abs() < 1e-9);
+        assert_eq!(preview.network_fee_lamports, Some(100_000));
+        assert!((preview.network_fee_usd.unwrap() - 0.015).abs() < 1e-9);
+        assert_eq!(preview.last_valid_block_height, Some(279_000_000));
+
+        let without_swap = SwapPreview::new(&quote, None, &tokens).unwrap();
+        assert_eq!(without_swap.network_fee_lamports, None);
+
+        tokens.remove(USDC_MINT);
+        assert!(matches!(
+            SwapPreview::new(&quote, None, &tokens),
+            Err(JupiterClientError::MissingTokenMetadata(mint)) if mint == USDC_MINT
+        ));
+    }
+
+    #[test]
+    fn test_account_setup_detects_missing_accounts() {
+        let holding = |mint: &str, amount: u64| Holding {
+            mint: mint.to_string(),
+            amount,
+            ui_amount: 0.0,
+            decimals: None,
+            is_frozen: false,
+        };
+        let fresh =
+            WalletHoldings::from_holdings(TEST_USER_PUBKEY, [holding(NATIVE_SOL, 5_000_000_000)]);
+        let request = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response());
+
+        // without use_shared_accounts Jupiter may route through the wallet's own accounts
+        let setup = AccountSetup::new(&request, &fresh);
+        assert!(!setup.shared_accounts);
+        assert_eq!(setup.missing_accounts, vec![SOL_MINT, USDC_MINT, JUP_MINT]);
+        assert_eq!(
+            setup.rented_accounts,
+            vec![USDC_MINT, JUP_MINT],
+            "the wrapped SOL account is closed again"
+        );
+        assert_eq!(setup.rent_lamports, 2 * TOKEN_ACCOUNT_RENT_LAMPORTS);
+        assert!(!setup.can_skip_account_checks());
+
+        // an empty output account still counts
+        let set_up = WalletHoldings::from_holdings(
+            TEST_USER_PUBKEY,
+            [holding(SOL_MINT, 0), holding(JUP_MINT, 0)],
+        );
+        let unshared = AccountSetup::new(&request, &set_up);
+        assert_eq!(unshared.missing_accounts, vec![USDC_MINT]);
+        assert_eq!(unshared.rent_lamports, TOKEN_ACCOUNT_RENT_LAMPORTS);
+        let shared = request.clone().use_shared_accounts(true);
+        assert!(AccountSetup::new(&shared, &set_up).can_skip_account_checks());
+
+        // a Token-2022 output account with a withheld fee amount is 182 bytes
+        let sized = AccountSetup::new(&shared, &fresh)
+            .with_account_sizes(&HashMap::from([(JUP_MINT.to_string(), 182)]));
+        assert_eq!(sized.rented_accounts, vec![JUP_MINT]);
+        assert_eq!(sized.rent_lamports, rent_exempt_lamports(182));
+        assert_eq!(rent_exempt_lamports(165), TOKEN_ACCOUNT_RENT_LAMPORTS);
+
+        let into_own_account = shared
+            .clone()
+            .wrap_and_unwrap_sol(false)
+            .destination_token_account(TEST_USER_PUBKEY.to_string());
+        assert!(AccountSetup::new(&into_own_account, &fresh).can_skip_account_checks());
+
+        let tokens = HashMap::from([
+            (
+                SOL_MINT.to_string(),
+                TokenMeta::new(SOL_MINT, "SOL", 9).usd_price(150.0),
+            ),
+            (JUP_MINT.to_string(), TokenMeta::new(JUP_MINT, "JUP", 6)),
+            (
+                USDC_MINT.to_string(),
+                TokenMeta::new(USDC_MINT, "USDC", 6).usd_price(1.0),
+            ),
+        ]);
+        let preview = SwapPreview::new(&request.quote_response, None, &tokens)
+            .unwrap()
+            .with_account_setup(setup, &tokens);
+        assert!((preview.account_rent_usd.unwrap() - 0.611784).abs() < 1e-9);
+        assert!(preview.account_setup.unwrap().creates_accounts());
+    }
+
+    #[test]
+    fn test_enriched_quote_resolves_every_hop() {
+        let quote = sample_quote_response();
+        assert_eq!(
+            EnrichedQuote::mints(&quote),
+            vec![SOL_MINT, JUP_MINT, USDC_MINT]
+        );
+
+        let mut tokens = HashMap::from([
+            (
+                SOL_MINT.to_string(),
+                TokenMeta::new(SOL_MINT, "SOL", 9).usd_price(150.0),
+            ),
+            (JUP_MINT.to_string(), TokenMeta::new(JUP_MINT, "JUP", 6)),
+            (USDC_MINT.to_string(), TokenMeta::new(USDC_MINT, "USDC", 6)),
+        ]);
+
+        let enriched = EnrichedQuote::new(&quote, &tokens).unwrap();
+        assert_eq!(enriched.input.token.symbol, "SOL");
+        assert_eq!(enriched.input.usd_value(), Some(150.0));
+        assert_eq!(enriched.output.ui_amount, "300");
+        assert_eq!(enriched.hops.len(), 2);
+        let hop = &enriched.hops[1];
+        assert_eq!(hop.label, "Meteora DLMM");
+        assert_eq!(hop.input.token.symbol, "USDC");
+        assert_eq!(hop.input.ui_amount, "150");
+        assert_eq!(hop.output.token.symbol, "JUP");
+        assert_eq!(hop.fee.as_ref().unwrap().ui_amount, "0.015");
+
+        tokens.remove(USDC_MINT);
+        assert!(matches!(
+            EnrichedQuote::new(&quote, &tokens),
+            Err(JupiterClientError::MissingTokenMetadata(mint)) if mint == USDC_MINT
+        ));
+    }
+
+    #[test]
+    fn test_slippage_comparison() {
+        let fixed = sample_quote_response();
+        let mut dynamic = sample_quote_response();
+        dynamic.other_amount_threshold = "299970000".to_string();
+        dynamic.slippage_bps = 1;
+
+        let comparison = SlippageComparison::new(fixed.clone(), dynamic.clone());
+        assert_eq!(comparison.fixed_threshold, 297_000_000);
+        assert_eq!(comparison.threshold_difference(), 2_970_000);
+        assert!((comparison.dynamic_improvement_bps() - 100.0).abs() < 1e-6);
+        assert_eq!(comparison.dynamic_slippage_bps(), 1);
+
+        // for ExactOut the threshold is a maximum input, so higher is worse
+        let (mut fixed, mut dynamic) = (fixed, dynamic);
+        fixed.swap_mode = QuoteGetSwapModeEnum::ExactOut;
+        dynamic.swap_mode = QuoteGetSwapModeEnum::ExactOut;
+        let comparison = SlippageComparison::new(fixed, dynamic);
+        assert!(comparison.dynamic_improvement_bps() < 0.0);
+    }
+
+    #[test]
+    fn test_integrator_fee_injection() {
+        let fee_accounts = HashMap::from([
+            (JUP_MINT.to_string(), "JupFeeAccount".to_string()),
+            (USDC_MINT.to_string(), "UsdcFeeAccount".to_string()),
+        ]);
+        let defaults = RequestDefaults::new().integrator_fee(IntegratorFee::new(
+            20,
+            FeeAccountSource::ByMint(fee_accounts),
+        ));
+
+        let quote = defaults.apply_to_quote(&QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT));
+        assert_eq!(quote.platform_fee_bps, Some(20));
+
+        let no_fee = defaults.apply_to_swap(&SwapRequest::new(
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            sample_quote_response(),
+        ));
+        assert_eq!(
+            no_fee.fee_account, None,
+            "quotes without a fee need no account"
+        );
+
+        let mut quote_res = sample_quote_response();
+        quote_res.platform_fee = Some(PlatformFee {
+            amount: "600000".to_string(),
+            fee_bps: Some(20),
+            fee_mint: None,
+        });
+        let swap = defaults.apply_to_swap(&SwapRequest::new(
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            quote_res.clone(),
+        ));
+        assert_eq!(swap.fee_account.as_deref(), Some("JupFeeAccount"));
+
+        quote_res.platform_fee.as_mut().unwrap().fee_mint = Some(USDC_MINT.to_string());
+        let swap = defaults.apply_to_swap(&SwapRequest::new(
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            quote_res,
+        ));
+        assert_eq!(
+            swap.fee_account.as_deref(),
+            Some("UsdcFeeAccount"),
+            "the quoted fee mint takes precedence"
+        );
+    }
+
+    #[test]
+    fn test_request_profiles() {
+        let request =
+            QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).use_default_intermediate_tokens();
+
+        let conservative = "Conservative"
+            .parse::<Profile>()
+            .expect("built-in profile should parse");
+        let quote = conservative.defaults().apply_to_quote(&request);
+        assert_eq!(quote.slippage_bps, Some(50));
+        assert_eq!(quote.restrict_intermediate_tokens, Some(true));
+
+        let quote = Profile::Balanced.defaults().apply_to_quote(&request);
+        assert_eq!(quote.dynamic_slippage, Some(true));
+        assert_eq!(
+            quote.slippage_bps, None,
+            "dynamic slippage skips fixed slippage"
+        );
+
+        let custom = Profile::Custom(RequestDefaults::new().max_accounts(30));
+        let quote = custom
+            .defaults()
+            .apply_to_quote(&request.clone().max_accounts(20));
+        assert_eq!(
+            quote.max_accounts,
+            Some(20),
+            "request fields win over profiles"
+        );
+
+        assert!("reckless".parse::<Profile>().is_err());
+    }
+
+    #[test]
+    fn test_exclude_program_ids() {
+        let labels = HashMap::from([
+            (
+                "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo".to_string(),
+                "Meteora DLMM".to_string(),
+            ),
+            (
+                "NewVenue1111111111111111111111111111111111".to_string(),
+                "New Venue".to_string(),
+            ),
+        ]);
+
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .exclude_dexes(vec![DexEnum::Raydium])
+            .exclude_program_ids(
+                &[
+                    "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo",
+                    "NewVenue1111111111111111111111111111111111",
+                    "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo",
+                ],
+                &labels,
+            )
+            .expect("known program ids should translate");
+
+        assert_eq!(
+            request.exclude_dexes,
+            Some(vec![
+                DexEnum::Raydium,
+                DexEnum::MeteoraDlmm,
+                DexEnum::Other("New Venue".to_string())
+            ]),
+            "program ids should map to deduplicated dex labels"
+        );
+
+        let unknown = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .exclude_program_ids(&["unknown"], &labels);
+        assert!(
+            matches!(unknown, Err(JupiterClientError::UnknownProgramId(id)) if id == "unknown"),
+            "unknown program ids should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_route_instruction_decode_alignments() {
+        // every route plan length mod 3 puts the trailer at a different base64 offset
+        for plan_len in 0..6 {
+            let mut data = vec![229, 23, 203, 151, 122, 227, 173, 42];
+            data.extend(std::iter::repeat_n(7, plan_len));
+            data.extend_from_slice(&123_456_789u64.to_le_bytes());
+            data.extend_from_slice(&250u16.to_le_bytes());
+            data.push(20);
+            let instruction = Instruction {
+                program_id: String::new(),
+                accounts: vec![],
+                data: STANDARD.encode(&data),
+            };
+
+            let route = RouteInstruction::decode(&instruction).unwrap();
+            assert_eq!(route.quoted_amount, 123_456_789, "plan of {plan_len} bytes");
+            assert_eq!(route.slippage_bps, 250);
+            assert_eq!(route.platform_fee_bps, 20);
+        }
+
+        let invalid = |data: &str| Instruction {
+            program_id: String::new(),
+            accounts: vec![],
+            data: data.to_string(),
+        };
+        assert!(matches!(
+            RouteInstruction::decode(&invalid(&STANDARD.encode([229u8; 18]))),
+            Err(JupiterClientError::InstructionDataError(e)) if e.contains("18 bytes")
+        ));
+        assert!(RouteInstruction::decode(&invalid("abc")).is_err());
+        assert!(RouteInstruction::decode(&invalid(&"é".repeat(16))).is_err());
+        assert!(RouteInstruction::decode(&invalid(&STANDARD.encode([1u8; 40]))).is_err());
+    }
+
+    #[test]
+    fn test_swap_instructions_minimum_out_override() {
+        let instructions = route_swap_instructions(1_000_000, 100);
+        let route = RouteInstruction::decode(&instructions.swap_instruction)
+            .expect("route instruction should decode");
+        assert_eq!(route.minimum_out_amount(), 990_000, "quoted threshold");
+
+        let tightened = instructions
+            .with_minimum_out_amount(995_000)
+            .expect("tightening should succeed");
+        let route = RouteInstruction::decode(&tightened.swap_instruction)
+            .expect("patched route instruction should decode");
+        assert_eq!(route.quoted_amount, 995_000);
+        assert_eq!(route.slippage_bps, 0);
+        assert_eq!(route.minimum_out_amount(), 995_000, "patched threshold");
+
+        let loosened = route_swap_instructions(1_000_000, 100).with_minimum_out_amount(900_000);
+        assert!(
+            matches!(loosened, Err(JupiterClientError::InstructionDataError(_))),
+            "loosening the threshold should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_failing_program_id_from_logs() {
+        let logs = vec![
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]".to_string(),
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc invoke [2]".to_string(),
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc failed: custom program error: 0x1771"
+                .to_string(),
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 failed: custom program error: 0x1771"
+                .to_string(),
+        ];
+        assert_eq!(
+            failing_program_id(&logs),
+            Some("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"),
+            "innermost non-Jupiter failure should be reported"
+        );
+
+        let jupiter_only = vec![
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 failed: custom program error: 0x1788"
+                .to_string(),
+        ];
+        assert_eq!(failing_program_id(&jupiter_only), None);
+    }
+
+    #[test]
+    fn test_failing_program_id_blames_amm_over_token_program() {
+        let logs: Vec<String> = [
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc invoke [2]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [3]",
+            "Program log: Error: insufficient funds",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA failed: custom program error: 0x1",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc failed: custom program error: 0x1",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 failed: custom program error: 0x1",
+        ]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+        assert_eq!(
+            failing_program_id(&logs),
+            Some("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"),
+            "the AMM whose token transfer failed should be reported"
+        );
+
+        let transfer_only = vec![
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA failed: custom program error: 0x1"
+                .to_string(),
+        ];
+        assert_eq!(failing_program_id(&transfer_only), None);
+    }
+
+    #[test]
+    fn test_diagnose_simulation_maps_failing_hop() {
+        let quote = sample_quote_response();
+        let logs: Vec<String> = [
+            "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+            "Program ComputeBudget111111111111111111111111111111 success",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc invoke [2]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [3]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc success",
+            "Program LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo invoke [2]",
+            "Program LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo failed: custom program error: 0x1774",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 failed: custom program error: 0x1774",
+        ]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+        let err = serde_json::json!({"InstructionError": [1, {"Custom": 6004}]});
+
+        let diagnosis = diagnose_simulation(Some(&err), &logs, &quote);
+        assert_eq!(diagnosis.instruction_index, Some(1));
+        assert_eq!(diagnosis.hop, Some(1), "second hop should be blamed");
+        assert_eq!(diagnosis.venue.as_deref(), Some("Meteora DLMM"));
+        assert_eq!(
+            diagnosis.program_id.as_deref(),
+            Some("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo")
+        );
+        assert_eq!(diagnosis.error_code, Some(6004));
+        assert_eq!(
+            diagnosis.error_name, None,
+            "AMM codes are not Jupiter codes"
+        );
+
+        let slippage_logs = vec![
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]".to_string(),
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 failed: custom program error: 0x1771"
+                .to_string(),
+        ];
+        let diagnosis = diagnose_simulation(None, &slippage_logs, &quote);
+        assert_eq!(diagnosis.hop, None);
+        assert_eq!(diagnosis.error_code, Some(6001));
+        assert_eq!(
+            diagnosis.error_name.as_deref(),
+            Some("SlippageToleranceExceeded")
+        );
+    }
+
+    #[test]
+    fn test_diagnose_simulation_ignores_jupiter_event_cpi() {
+        let quote = sample_quote_response();
+        let logs: Vec<String> = [
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc invoke [2]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [3]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc success",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [2]",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 success",
+            "Program LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo invoke [2]",
+            "Program LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo failed: custom program error: 0x1774",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 failed: custom program error: 0x1774",
+        ]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+
+        let diagnosis = diagnose_simulation(None, &logs, &quote);
+        assert_eq!(diagnosis.hop, Some(1), "the event CPI is not a hop");
+        assert_eq!(diagnosis.venue.as_deref(), Some("Meteora DLMM"));
+    }
+
+    #[test]
+    fn test_profile_compute_units() {
+        let instructions = route_swap_instructions(1_000_000, 100);
+        let mut roles = instructions.instruction_roles();
+        assert_eq!(roles, vec![InstructionRole::Swap]);
+        roles.insert(0, InstructionRole::ComputeBudget);
+        roles.insert(1, InstructionRole::Setup);
+        roles.push(InstructionRole::Other);
+
+        let logs: Vec<String> = [
+            "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+            "Program ComputeBudget111111111111111111111111111111 success",
+            "Program ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL invoke [1]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [2]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA consumed 1000 of 180000 compute units",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+            "Program ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL consumed 20000 of 200000 compute units",
+            "Program ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL success",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc invoke [2]",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc consumed 40000 of 170000 compute units",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc success",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 consumed 90000 of 180000 compute units",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 success",
+            "Program MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr invoke [1]",
+            "Program MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr consumed 5000 of 90000 compute units",
+            "Program MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr success",
+        ]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+
+        let profile = profile_compute_units(&logs, &roles);
+        assert_eq!(profile.instructions.len(), 4);
+        assert_eq!(profile.setup, 20_000);
+        assert_eq!(profile.swap, 90_000);
+        assert_eq!(profile.cleanup, 0);
+        assert_eq!(profile.other, 5_000);
+        assert_eq!(profile.total, 115_000);
+        assert_eq!(profile.headroom(200_000), 85_000);
+    }
+
     #[tokio::test]
     async fn test_get_quote_successful() {
         let client = create_test_client();
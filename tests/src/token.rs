@@ -1,6 +1,154 @@
 #[cfg(test)]
 mod token_tests {
-    use crate::common::{JUP_MINT, SOL_MINT, USDC_MINT, create_test_client};
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        ata::{
+            ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+            associated_token_address, close_token_account,
+            create_associated_token_account_idempotent, plan_account_closure, plan_ata_prewarm,
+            token_account_sizes,
+        },
+        token_snapshot::{TokenChange, TokenSnapshot},
+        types::{Category, ConfidenceLevel, Interval, TokenTag},
+    };
+
+    use crate::common::{
+        JUP_MINT, SOL_MINT, TEST_USER_PUBKEY, USDC_MINT, create_test_client, sample_token_json,
+        spawn_mock_server, spawn_sequenced_mock_server,
+    };
+
+    #[test]
+    fn test_associated_token_address() {
+        assert_eq!(
+            associated_token_address(TEST_USER_PUBKEY, USDC_MINT, TOKEN_PROGRAM_ID).unwrap(),
+            "GuXwzhWVJcsMKjVmwwvbase1g33T6uY9msZDb686zZEX"
+        );
+        // PYUSD, a Token-2022 mint
+        assert_eq!(
+            associated_token_address(
+                TEST_USER_PUBKEY,
+                "2b1kV6DkPAnxd5ixfnxCpjxmKwqjjaYmCZfHsFu24GXo",
+                TOKEN_2022_PROGRAM_ID
+            )
+            .unwrap(),
+            "FUMCJ936jhP1RFniJ8rJcNv3mSCzcuhwsYv4MSKNHQKD"
+        );
+        assert!(matches!(
+            associated_token_address("not-a-key", USDC_MINT, TOKEN_PROGRAM_ID),
+            Err(JupiterClientError::InvalidPubkey(_))
+        ));
+
+        let instruction = create_associated_token_account_idempotent(
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            USDC_MINT,
+            TOKEN_PROGRAM_ID,
+        )
+        .unwrap();
+        assert_eq!(instruction.program_id, ASSOCIATED_TOKEN_PROGRAM_ID);
+        assert_eq!(
+            instruction.accounts[1].pubkey,
+            "GuXwzhWVJcsMKjVmwwvbase1g33T6uY9msZDb686zZEX"
+        );
+        assert!(instruction.accounts[0].is_signer);
+        assert_eq!(instruction.data, "AQ==");
+    }
+
+    #[tokio::test]
+    async fn test_plan_account_closure_closes_only_empty_accounts() {
+        let owned_by_token = r#"{"owner":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","data":["","base64"],"lamports":1,"executable":false}"#;
+        let mints = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":{{"value":[{owned_by_token},{owned_by_token},null]}}}}"#
+        );
+        // USDC's ATA is empty, JUP's still holds 5 tokens
+        let accounts = r#"{"jsonrpc":"2.0","id":1,"result":{"value":[
+            {"owner":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","data":["AAAAAAAAAAA=","base64"],"lamports":1,"executable":false},
+            {"owner":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","data":["BQAAAAAAAAA=","base64"],"lamports":1,"executable":false}
+        ]}}"#;
+        let server = spawn_sequenced_mock_server(200, &[], vec![mints, accounts.to_string()]).await;
+
+        let plan = plan_account_closure(
+            &server.url,
+            TEST_USER_PUBKEY,
+            &[USDC_MINT, JUP_MINT, SOL_MINT],
+        )
+        .await
+        .unwrap();
+
+        let usdc_ata =
+            associated_token_address(TEST_USER_PUBKEY, USDC_MINT, TOKEN_PROGRAM_ID).unwrap();
+        assert_eq!(plan.closable.len(), 1);
+        assert_eq!(plan.closable[0].address, usdc_ata);
+        assert_eq!(plan.not_empty[0].mint, JUP_MINT);
+        assert_eq!(plan.missing, vec![SOL_MINT.to_string()]);
+        assert_eq!(plan.batches.len(), 1);
+        assert_eq!(plan.batches[0].len(), 1);
+
+        let instruction = &plan.batches[0][0];
+        assert_eq!(instruction.program_id, TOKEN_PROGRAM_ID);
+        assert_eq!(instruction.data, "CQ==");
+        assert_eq!(instruction.accounts[0].pubkey, usdc_ata);
+        assert_eq!(instruction.accounts[1].pubkey, TEST_USER_PUBKEY);
+        assert!(instruction.accounts[0].is_writable && instruction.accounts[2].is_signer);
+        assert!(matches!(
+            close_token_account(
+                "not-a-key",
+                TEST_USER_PUBKEY,
+                TEST_USER_PUBKEY,
+                TOKEN_PROGRAM_ID
+            ),
+            Err(JupiterClientError::InvalidPubkey(_))
+        ));
+
+        let requests = server.requests.lock().unwrap();
+        assert!(requests[1].contains(r#""offset":64"#));
+    }
+
+    #[tokio::test]
+    async fn test_token_account_sizes_follow_mint_extensions() {
+        use base64::Engine;
+
+        // a Token-2022 mint with a 108-byte transfer fee config and a 64-byte metadata pointer
+        let mut mint = vec![0u8; 165];
+        mint.push(1);
+        mint.extend([1, 0, 108, 0]);
+        mint.extend([0; 108]);
+        mint.extend([18, 0, 64, 0]);
+        mint.extend([0; 64]);
+        let mint = base64::engine::general_purpose::STANDARD.encode(mint);
+        let body = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"result":{{"value":[
+                {{"owner":"TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb","data":["{mint}","base64"]}},
+                {{"owner":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","data":["","base64"]}},
+                null
+            ]}}}}"#
+        );
+        let server = spawn_mock_server(200, &[], body).await;
+
+        let sizes = token_account_sizes(&server.url, &[JUP_MINT, USDC_MINT, SOL_MINT])
+            .await
+            .unwrap();
+        // base, account type, immutable owner and transfer fee amount
+        assert_eq!(sizes[JUP_MINT], 165 + 1 + 4 + 12);
+        assert_eq!(sizes[USDC_MINT], 165);
+        assert!(!sizes.contains_key(SOL_MINT));
+        assert!(!server.requests.lock().unwrap()[0].contains("dataSlice"));
+    }
+
+    #[tokio::test]
+    async fn test_plan_ata_prewarm_needs_a_reachable_rpc() {
+        let result = plan_ata_prewarm(
+            "http://127.0.0.1:1",
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            &[USDC_MINT],
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(JupiterClientError::RequestError { .. })
+        ));
+    }
 
     #[tokio::test]
     async fn test_get_token_balances() {
@@ -38,6 +186,259 @@ mod token_tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_prices_batches_mints() {
+        let body = serde_json::json!({
+            SOL_MINT: {
+                "usdPrice": 150.25,
+                "blockId": 348000000u64,
+                "decimals": 9,
+                "priceChange24h": -1.5
+            }
+        });
+        let server = spawn_mock_server(200, &[], body.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+
+        let mints: Vec<String> = std::iter::once(SOL_MINT.to_string())
+            .chain((0..59).map(|i| format!("Mint{i}")))
+            .collect();
+        let prices = client.get_prices(&mints).await.unwrap();
+
+        assert_eq!(prices[SOL_MINT].usd_price, 150.25);
+        assert_eq!(prices[SOL_MINT].decimals, 9);
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2, "60 mints should take two requests");
+        assert!(requests[0].starts_with("GET /price/v3?ids="));
+        assert!(requests[1].contains("Mint58"));
+    }
+
+    #[tokio::test]
+    async fn test_get_prices_with_extra_info() {
+        let body = serde_json::json!({
+            "data": {
+                JUP_MINT: {
+                    "id": JUP_MINT,
+                    "type": "derivedPrice",
+                    "price": "0.7443",
+                    "extraInfo": {
+                        "lastSwappedPrice": {
+                            "lastJupiterSellAt": 1726231876,
+                            "lastJupiterSellPrice": "0.7442999683998961",
+                            "lastJupiterBuyAt": 1726231877,
+                            "lastJupiterBuyPrice": "0.7442999683998961"
+                        },
+                        "quotedPrice": {
+                            "buyPrice": "0.744138",
+                            "buyAt": 1726231878,
+                            "sellPrice": "0.743877",
+                            "sellAt": 1726231878
+                        },
+                        "confidenceLevel": "high",
+                        "depth": {
+                            "buyPriceImpactRatio": {
+                                "depth": { "10": 0.0018, "100": 0.0025, "1000": 0.0253 },
+                                "timestamp": 1726231876
+                            },
+                            "sellPriceImpactRatio": {
+                                "depth": { "10": 0.0013, "100": 0.0021, "1000": 0.0219 },
+                                "timestamp": 1726231876
+                            }
+                        }
+                    }
+                },
+                "UnpricedMint": null
+            },
+            "timeTaken": 0.003
+        });
+        let server = spawn_mock_server(200, &[], body.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+
+        let prices = client
+            .get_prices_with_extra_info(&[JUP_MINT.to_string(), "UnpricedMint".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(prices.len(), 1, "unpriced mints are dropped");
+        let info = prices[JUP_MINT].extra_info.as_ref().unwrap();
+        assert_eq!(info.confidence_level, ConfidenceLevel::High);
+        assert!(info.confidence_level > ConfidenceLevel::Medium);
+        let sell = info
+            .depth
+            .as_ref()
+            .unwrap()
+            .sell_price_impact_ratio
+            .as_ref();
+        assert_eq!(sell.unwrap().impact_at(1000), Some(0.0219));
+        assert_eq!(sell.unwrap().impact_at(5000), None);
+        assert_eq!(
+            info.quoted_price.as_ref().unwrap().buy_price.as_deref(),
+            Some("0.744138")
+        );
+        assert!(server.requests.lock().unwrap()[0].contains("showExtraInfo=true"));
+    }
+
+    #[tokio::test]
+    async fn test_get_token_by_mint() {
+        let body = serde_json::json!([sample_token_json(JUP_MINT, "JUP", 6)]);
+        let server = spawn_mock_server(200, &[], body.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+
+        let jup = client.get_token(JUP_MINT).await.unwrap();
+        assert_eq!(jup.symbol, "JUP");
+        assert_eq!(jup.decimals, 6);
+        assert_eq!(jup.icon.as_deref(), Some("https://example.com/JUP.png"));
+        assert_eq!(jup.tags, ["verified", "strict"]);
+        assert_eq!(
+            jup.token_program,
+            "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        );
+        assert!(
+            server.requests.lock().unwrap()[0]
+                .starts_with(&format!("GET /tokens/v2/search?query={JUP_MINT} "))
+        );
+
+        assert!(matches!(
+            client.get_token(USDC_MINT).await,
+            Err(JupiterClientError::MissingTokenMetadata(mint)) if mint == USDC_MINT
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_search_tokens() {
+        let body = serde_json::json!([sample_token_json(JUP_MINT, "JUP", 6)]);
+        let server = spawn_mock_server(200, &[], body.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+
+        let results = client.search_tokens(" jup ").await.unwrap();
+        assert_eq!(results[0].id, JUP_MINT);
+        assert!(server.requests.lock().unwrap()[0].starts_with("GET /tokens/v2/search?query=jup "));
+    }
+
+    #[tokio::test]
+    async fn test_get_tokens_by_tag() {
+        let body = serde_json::json!([
+            sample_token_json(JUP_MINT, "JUP", 6),
+            sample_token_json(USDC_MINT, "USDC", 6),
+        ]);
+        let server = spawn_mock_server(200, &[], body.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+
+        let verified = client.get_tokens_by_tag(TokenTag::Verified).await.unwrap();
+        assert_eq!(verified.len(), 2);
+        assert!(verified.iter().all(|token| token.is_verified == Some(true)));
+        client.get_tokens_by_tag(TokenTag::Lst).await.unwrap();
+
+        let requests = server.requests.lock().unwrap();
+        assert!(requests[0].starts_with("GET /tokens/v2/tag?query=verified "));
+        assert!(requests[1].starts_with("GET /tokens/v2/tag?query=lst "));
+    }
+
+    #[tokio::test]
+    async fn test_category_feeds() {
+        let body = serde_json::json!([sample_token_json(JUP_MINT, "JUP", 6)]).to_string();
+        let server = spawn_mock_server(200, &[], body).await;
+        let client = JupiterClient::new(&server.url);
+
+        for category in Category::ALL {
+            let tokens = client
+                .get_tokens_by_category(category, Interval::SixHours, Some(10))
+                .await
+                .unwrap();
+            assert_eq!(tokens[0].symbol, "JUP");
+        }
+        client
+            .get_tokens_by_category(Category::TopTraded, Interval::FiveMinutes, None)
+            .await
+            .unwrap();
+        client.get_recent_tokens().await.unwrap();
+
+        let requests = server.requests.lock().unwrap();
+        assert!(requests[0].starts_with("GET /tokens/v2/toporganicscore/6h?limit=10 "));
+        assert!(requests[1].starts_with("GET /tokens/v2/toptraded/6h?limit=10 "));
+        assert!(requests[2].starts_with("GET /tokens/v2/toptrending/6h?limit=10 "));
+        assert!(requests[3].starts_with("GET /tokens/v2/toptraded/5m "));
+        assert!(requests[4].starts_with("GET /tokens/v2/recent "));
+    }
+
+    #[tokio::test]
+    async fn test_token_snapshot_diff() {
+        let body = serde_json::json!([
+            sample_token_json(JUP_MINT, "JUP", 6),
+            sample_token_json(USDC_MINT, "USDC", 6),
+        ])
+        .to_string();
+        let server = spawn_mock_server(200, &[], body).await;
+        let client = JupiterClient::new(&server.url);
+
+        let current = client.snapshot_tokens(TokenTag::Verified).await.unwrap();
+        assert_eq!(current.tokens.len(), 2);
+        assert!(current.tokens[JUP_MINT].tags.contains("verified"));
+
+        // a day earlier, SOL was listed instead of JUP and USDC wasn't strict yet
+        let mut previous = current.clone();
+        let jup = previous.tokens.remove(JUP_MINT).unwrap();
+        previous.tokens.insert(SOL_MINT.to_string(), jup);
+        previous.tokens.get_mut(SOL_MINT).unwrap().symbol = "SOL".to_string();
+        let usdc = previous.tokens.get_mut(USDC_MINT).unwrap();
+        usdc.tags.remove("strict");
+        usdc.tags.insert("stable".to_string());
+
+        let mut file = Vec::new();
+        previous.write_to(&mut file).unwrap();
+        let previous = TokenSnapshot::read_from(file.as_slice()).unwrap();
+
+        let changes = previous.diff(&current);
+        assert_eq!(
+            changes,
+            vec![
+                TokenChange::Listed {
+                    mint: JUP_MINT.to_string(),
+                    symbol: "JUP".to_string(),
+                },
+                TokenChange::Delisted {
+                    mint: SOL_MINT.to_string(),
+                    symbol: "SOL".to_string(),
+                },
+                TokenChange::TagsChanged {
+                    mint: USDC_MINT.to_string(),
+                    symbol: "USDC".to_string(),
+                    added: vec!["strict".to_string()],
+                    removed: vec!["stable".to_string()],
+                },
+            ]
+        );
+        assert!(current.diff(&current).is_empty());
+        assert!(TokenSnapshot::read_from(&b"[]"[..]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_max_response_size() {
+        let body = serde_json::json!([
+            sample_token_json(JUP_MINT, "JUP", 6),
+            sample_token_json(USDC_MINT, "USDC", 6),
+        ])
+        .to_string();
+        let server = spawn_mock_server(200, &[], body.clone()).await;
+
+        let capped = JupiterClient::new(&server.url).with_max_response_size(body.len() - 1);
+        assert!(matches!(
+            capped.get_recent_tokens().await,
+            Err(JupiterClientError::ResponseTooLarge { limit, .. }) if limit == body.len() - 1
+        ));
+        // every endpoint reads its body through the same limit
+        assert!(matches!(
+            capped.get_tokens_price(&[USDC_MINT.to_string()]).await,
+            Err(JupiterClientError::ResponseTooLarge { .. })
+        ));
+        assert!(matches!(
+            capped.get_program_id_to_label().await,
+            Err(JupiterClientError::ResponseTooLarge { .. })
+        ));
+
+        let roomy = JupiterClient::new(&server.url).with_max_response_size(body.len());
+        assert_eq!(roomy.get_recent_tokens().await.unwrap().len(), 2);
+    }
+
     #[tokio::test]
     pub async fn test_token_info() {
         let client = create_test_client();
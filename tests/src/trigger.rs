@@ -1,10 +1,20 @@
 #[cfg(test)]
 mod trigger_tests {
-    use jup_ag_sdk::types::{
-        CreateTriggerOrder, ExecuteTriggerOrder, GetTriggerOrders, OrderStatus,
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        trigger_grid::{GridConfig, GridSide, GridSpacing, TriggerGrid},
+        types::{
+            CancelTriggerOrder, CancelTriggerOrders, CreateTriggerOrder, ExecuteTriggerOrder,
+            GetTriggerOrders, OrderStatus, Slippage, TokenMeta, UltraExecuteCode,
+        },
     };
 
-    use crate::common::{SOL_MINT, TEST_USER_PUBKEY, USDC_MINT, create_test_client};
+    use crate::common::{
+        JUP_MINT, SOL_MINT, TEST_USER_PUBKEY, USDC_MINT, create_test_client, spawn_mock_server,
+        spawn_sequenced_mock_server,
+    };
 
     #[test]
     fn test_trigger_create_order_builder() {
@@ -37,6 +47,93 @@ mod trigger_tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_create_order_typed_params() {
+        let order = CreateTriggerOrder::new(
+            SOL_MINT,
+            USDC_MINT,
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            1_000_000_000,
+            200_000_000,
+        )
+        .expires_in(Duration::from_secs(3600))
+        .slippage(Slippage::from_bps(50))
+        .platform_fee(TEST_USER_PUBKEY, 20)
+        .compute_unit_price_micro_lamports(1_000);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        assert_eq!(order.making_amount().unwrap(), 1_000_000_000);
+        assert_eq!(order.taking_amount().unwrap(), 200_000_000);
+        assert!((now + 3599..=now + 3600).contains(&order.expiry().unwrap().unwrap()));
+        let body = serde_json::to_value(&order).unwrap();
+        assert_eq!(body["params"]["slippageBps"], "50");
+        assert_eq!(body["params"]["feeBps"], "20");
+        assert_eq!(body["feeAccount"], TEST_USER_PUBKEY);
+        assert_eq!(body["computeUnitPrice"], "1000");
+        order.validate().unwrap();
+
+        let invalid = |order: CreateTriggerOrder| {
+            matches!(
+                order.validate(),
+                Err(JupiterClientError::InvalidTriggerOrder(_))
+            )
+        };
+        let base = || {
+            CreateTriggerOrder::new(
+                SOL_MINT,
+                USDC_MINT,
+                TEST_USER_PUBKEY,
+                TEST_USER_PUBKEY,
+                1_000,
+                2_000,
+            )
+        };
+        assert!(invalid(base().fee_bps("10")), "fee without an account");
+        assert!(invalid(base().expired_at("1700000000")), "expired");
+        assert!(invalid(base().expired_at("tomorrow")));
+        assert!(invalid(CreateTriggerOrder::new(
+            SOL_MINT,
+            SOL_MINT,
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            1_000,
+            2_000,
+        )));
+        assert!(matches!(
+            CreateTriggerOrder::new(SOL_MINT, USDC_MINT, "maker", TEST_USER_PUBKEY, 1, 2)
+                .validate(),
+            Err(JupiterClientError::InvalidPubkey(_))
+        ));
+
+        let response = serde_json::json!({
+            "requestId": "req-1",
+            "transaction": "AQID",
+            "order": "Order1111111111111111111111111111111111111",
+            "code": 0,
+        });
+        let server = spawn_mock_server(200, &[], response.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+        assert!(
+            client
+                .create_trigger_order(&base().fee_bps("10"))
+                .await
+                .is_err()
+        );
+        assert_eq!(server.request_count(), 0, "invalid orders are never sent");
+
+        let created = client.create_trigger_order(&order).await.unwrap();
+        assert_eq!(created.request_id, "req-1");
+        assert_eq!(
+            created.order.as_deref(),
+            Some("Order1111111111111111111111111111111111111")
+        );
+        assert!(server.requests.lock().unwrap()[0].starts_with("POST /trigger/v1/createOrder"));
+    }
+
     #[tokio::test]
     async fn test_create_order() {
         let client = create_test_client();
@@ -108,4 +205,378 @@ mod trigger_tests {
             "order status should be history"
         );
     }
+
+    #[tokio::test]
+    async fn test_execute_order_typed_result() {
+        let landed = serde_json::json!({
+            "code": 0,
+            "signature": "5sig",
+            "status": "Success",
+            "order": "Order1111111111111111111111111111111111111",
+        });
+        let server = spawn_mock_server(200, &[], landed.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+
+        let res = client
+            .execute_trigger_order(&ExecuteTriggerOrder::new("req-1", "AQ=="))
+            .await
+            .unwrap();
+        assert!(res.is_success());
+        assert_eq!(res.execute_code(), UltraExecuteCode::Success);
+        assert_eq!(res.into_result().unwrap().signature, "5sig");
+        let sent = server.requests.lock().unwrap()[0].clone();
+        assert!(sent.starts_with("POST /trigger/v1/execute "));
+        assert!(sent.ends_with(r#"{"requestId":"req-1","signedTransaction":"AQ=="}"#));
+
+        let failed = serde_json::json!({
+            "code": -1000,
+            "status": "Failed",
+            "error": "Failed to land",
+        });
+        let server = spawn_mock_server(200, &[], failed.to_string()).await;
+        let res = JupiterClient::new(&server.url)
+            .execute_trigger_order(&ExecuteTriggerOrder::new("req-1", "AQ=="))
+            .await
+            .unwrap();
+        assert!(!res.is_success());
+        assert!(res.signature.is_empty());
+        assert!(res.execute_code().is_retryable());
+        assert!(matches!(
+            res.into_result(),
+            Err(JupiterClientError::TriggerExecutionFailed { code: -1000, ref error })
+                if error == "Failed to land"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_orders_batches() {
+        let body = serde_json::json!({
+            "requestId": "req-1",
+            "transactions": ["AQ==", "Ag=="],
+        });
+        let server = spawn_mock_server(200, &[], body.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+        let orders: Vec<String> = [SOL_MINT, USDC_MINT, JUP_MINT, TEST_USER_PUBKEY]
+            .iter()
+            .chain(&[
+                "So11111111111111111111111111111111111111111",
+                "11111111111111111111111111111111",
+            ])
+            .map(|order| order.to_string())
+            .collect();
+        let cancel = CancelTriggerOrders::new(TEST_USER_PUBKEY, orders.clone());
+        assert_eq!(cancel.transaction_count(), Some(2));
+
+        let res = client.cancel_trigger_orders(&cancel).await.unwrap();
+        assert_eq!(res.request_id, "req-1");
+        assert_eq!(res.unsigned_transactions(), vec!["AQ==", "Ag=="]);
+        let sent = server.requests.lock().unwrap()[0].clone();
+        assert!(sent.starts_with("POST /trigger/v1/cancelOrders "));
+        assert!(sent.contains(&format!(r#""orders":["{SOL_MINT}","{USDC_MINT}""#)));
+
+        let all = CancelTriggerOrders::all(TEST_USER_PUBKEY);
+        assert_eq!(all.transaction_count(), None);
+        client.cancel_trigger_orders(&all).await.unwrap();
+        let sent = server.requests.lock().unwrap()[1].clone();
+        assert!(sent.ends_with(&format!(r#"{{"maker":"{TEST_USER_PUBKEY}"}}"#)));
+
+        let mut duplicated = orders;
+        duplicated.push(SOL_MINT.to_string());
+        let result = client
+            .cancel_trigger_orders(&CancelTriggerOrders::new(TEST_USER_PUBKEY, duplicated))
+            .await;
+        assert!(matches!(
+            result,
+            Err(JupiterClientError::InvalidTriggerOrder(_))
+        ));
+        let result = client
+            .cancel_trigger_order(&CancelTriggerOrder::new(TEST_USER_PUBKEY, "not-an-order"))
+            .await;
+        assert!(matches!(result, Err(JupiterClientError::InvalidPubkey(_))));
+        assert_eq!(server.request_count(), 2, "invalid cancels are never sent");
+    }
+
+    #[tokio::test]
+    async fn test_trigger_orders_follow_pagination() {
+        let order = serde_json::json!({
+            "userPubkey": TEST_USER_PUBKEY,
+            "orderKey": "Order1111111111111111111111111111111111111",
+            "inputMint": SOL_MINT,
+            "outputMint": USDC_MINT,
+            "makingAmount": "1",
+            "takingAmount": "150",
+            "remainingMakingAmount": "1",
+            "remainingTakingAmount": "150",
+            "rawMakingAmount": "1000000000",
+            "rawTakingAmount": "150000000",
+            "rawRemainingMakingAmount": "1000000000",
+            "rawRemainingTakingAmount": "150000000",
+            "slippageBps": "0",
+            "createdAt": "2025-06-01T00:00:00Z",
+            "updatedAt": "2025-06-01T00:00:00Z",
+            "status": "Open",
+            "openTx": "5open",
+            "closeTx": "",
+            "programVersion": "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X",
+            "trades": [],
+        });
+        let body = serde_json::json!({
+            "user": TEST_USER_PUBKEY,
+            "orderStatus": "active",
+            "orders": [order],
+            "totalPages": 3,
+            "page": 1,
+        });
+        let server = spawn_mock_server(200, &[], body.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+        let params = GetTriggerOrders::new(TEST_USER_PUBKEY, OrderStatus::Active);
+
+        let mut pages = client.trigger_orders(&params);
+        let first = pages.next_page().await.unwrap().unwrap();
+        assert_eq!(first.status(), Some(OrderStatus::Active));
+        assert!(first.has_next_page());
+
+        let orders = client
+            .trigger_orders(&params.clone().page_number(2))
+            .collect_orders()
+            .await
+            .unwrap();
+        assert_eq!(orders.len(), 2, "pages 2 and 3");
+
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[0].starts_with("GET /trigger/v1/getTriggerOrders?user="));
+        assert!(requests[0].contains("page=1&"));
+        assert!(requests[1].contains("page=2&"));
+        assert!(requests[2].contains("page=3&"));
+    }
+
+    #[tokio::test]
+    async fn test_trigger_grid_places_tracks_and_cancels() {
+        let sol = TokenMeta::new(SOL_MINT, "SOL", 9);
+        let usdc = TokenMeta::new(USDC_MINT, "USDC", 6);
+        let config = GridConfig::new(
+            TEST_USER_PUBKEY,
+            GridSide::Sell,
+            sol.clone(),
+            usdc.clone(),
+            100.0..=200.0,
+            3,
+            1_000_000_000,
+        )
+        .expires_in(Duration::from_secs(3600));
+        let levels = config.grid_levels().unwrap();
+        let taking: Vec<u64> = levels.iter().map(|level| level.taking_amount).collect();
+        assert_eq!(taking, vec![100_000_000, 150_000_000, 200_000_000]);
+        assert!(
+            levels
+                .iter()
+                .all(|level| level.making_amount == 1_000_000_000)
+        );
+
+        let buy = GridConfig::new(
+            TEST_USER_PUBKEY,
+            GridSide::Buy,
+            sol,
+            usdc,
+            100.0..=400.0,
+            3,
+            1,
+        )
+        .spacing(GridSpacing::Geometric);
+        assert_eq!(buy.prices(), vec![100.0, 200.0, 400.0]);
+        assert_eq!(buy.mints(), (USDC_MINT, SOL_MINT));
+        assert!(matches!(
+            buy.grid_levels(),
+            Err(JupiterClientError::InvalidTriggerOrder(_))
+        ));
+
+        let keys = [
+            "So11111111111111111111111111111111111111111",
+            "11111111111111111111111111111111",
+            JUP_MINT,
+        ];
+        let mut bodies = Vec::new();
+        for (i, key) in keys.iter().enumerate() {
+            bodies.push(
+                serde_json::json!({"requestId": format!("req-{i}"), "transaction": "AQ==", "order": key, "code": 0})
+                    .to_string(),
+            );
+            bodies.push(
+                serde_json::json!({"code": 0, "signature": format!("open-{i}"), "status": "Success"})
+                    .to_string(),
+            );
+        }
+        let order = |key: &str, status: &str| {
+            serde_json::json!({
+                "userPubkey": TEST_USER_PUBKEY,
+                "orderKey": key,
+                "inputMint": SOL_MINT,
+                "outputMint": USDC_MINT,
+                "makingAmount": "1",
+                "takingAmount": "200",
+                "remainingMakingAmount": "1",
+                "remainingTakingAmount": "200",
+                "rawMakingAmount": "1000000000",
+                "rawTakingAmount": "200000000",
+                "rawRemainingMakingAmount": "1000000000",
+                "rawRemainingTakingAmount": "200000000",
+                "slippageBps": "0",
+                "createdAt": "2025-06-01T00:00:00Z",
+                "updatedAt": "2025-06-01T00:00:00Z",
+                "status": status,
+                "openTx": "open-2",
+                "closeTx": "",
+                "programVersion": "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X",
+                "trades": [],
+            })
+        };
+        bodies.push(
+            serde_json::json!({
+                "user": TEST_USER_PUBKEY,
+                "orderStatus": "active",
+                "orders": [order(keys[2], "Open")],
+                "totalPages": 1,
+                "page": 1,
+            })
+            .to_string(),
+        );
+        // the second order isn't indexed yet: neither active nor in the history
+        bodies.push(
+            serde_json::json!({
+                "user": TEST_USER_PUBKEY,
+                "orderStatus": "history",
+                "orders": [order(keys[0], "Completed")],
+                "totalPages": 1,
+                "page": 1,
+            })
+            .to_string(),
+        );
+        bodies
+            .push(serde_json::json!({"requestId": "cancel", "transactions": ["Ag=="]}).to_string());
+        bodies.push(
+            serde_json::json!({"code": 0, "signature": "cancelled", "status": "Success"})
+                .to_string(),
+        );
+        let server = spawn_sequenced_mock_server(200, &[], bodies).await;
+
+        let sign = |tx: String| async move { Ok(format!("signed-{tx}")) };
+        let mut grid = TriggerGrid::new(JupiterClient::new(&server.url), config).unwrap();
+        assert_eq!(grid.place(sign).await.unwrap(), 3);
+        assert!(grid.is_placed());
+        assert_eq!(grid.place(sign).await.unwrap(), 0, "every level is placed");
+        {
+            let requests = server.requests.lock().unwrap();
+            assert!(requests[0].starts_with("POST /trigger/v1/createOrder "));
+            assert!(requests[0].contains(r#""takingAmount":"100000000","expiredAt":"#));
+            assert!(
+                requests[1].contains(r#""requestId":"req-0","signedTransaction":"signed-AQ==""#)
+            );
+            assert!(requests[4].contains(r#""takingAmount":"200000000""#));
+        }
+
+        let closed = grid.refresh().await.unwrap();
+        let closed: Vec<&str> = closed
+            .iter()
+            .map(|order| order.order_key.as_str())
+            .collect();
+        assert_eq!(closed, keys[..1], "only orders in the history are closed");
+        let open: Vec<&str> = grid
+            .orders()
+            .map(|order| order.signature.as_str())
+            .collect();
+        assert_eq!(open, vec!["open-1", "open-2"]);
+        assert!(!grid.is_placed());
+
+        let signatures = grid.cancel_all(sign).await.unwrap();
+        assert_eq!(signatures, vec!["cancelled"]);
+        assert_eq!(grid.orders().count(), 0);
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests.len(), 10);
+        assert!(requests[6].starts_with("GET /trigger/v1/getTriggerOrders?user="));
+        assert!(requests[7].contains("orderStatus=history"));
+        assert!(requests[8].contains(&format!(r#""orders":["{}","{}"]"#, keys[1], keys[2])));
+        assert!(requests[9].contains(r#""requestId":"cancel","signedTransaction":"signed-Ag==""#));
+    }
+
+    #[tokio::test]
+    async fn test_trigger_grid_tracks_an_order_that_opened_despite_a_failed_execute() {
+        let config = GridConfig::new(
+            TEST_USER_PUBKEY,
+            GridSide::Sell,
+            TokenMeta::new(SOL_MINT, "SOL", 9),
+            TokenMeta::new(USDC_MINT, "USDC", 6),
+            100.0..=200.0,
+            2,
+            1_000_000_000,
+        );
+        let keys = ["So11111111111111111111111111111111111111111", JUP_MINT];
+        let created = |i: usize| {
+            serde_json::json!({"requestId": format!("req-{i}"), "transaction": "AQ==", "order": keys[i], "code": 0})
+                .to_string()
+        };
+        let bodies = vec![
+            created(0),
+            serde_json::json!({"code": -1, "status": "Failed", "error": "timed out"}).to_string(),
+            // the order opened after all
+            serde_json::json!({
+                "user": TEST_USER_PUBKEY,
+                "orderStatus": "active",
+                "orders": [{
+                    "userPubkey": TEST_USER_PUBKEY,
+                    "orderKey": keys[0],
+                    "inputMint": SOL_MINT,
+                    "outputMint": USDC_MINT,
+                    "makingAmount": "1",
+                    "takingAmount": "100",
+                    "remainingMakingAmount": "1",
+                    "remainingTakingAmount": "100",
+                    "rawMakingAmount": "1000000000",
+                    "rawTakingAmount": "100000000",
+                    "rawRemainingMakingAmount": "1000000000",
+                    "rawRemainingTakingAmount": "100000000",
+                    "slippageBps": "0",
+                    "createdAt": "2025-06-01T00:00:00Z",
+                    "updatedAt": "2025-06-01T00:00:00Z",
+                    "status": "Open",
+                    "openTx": "open-0",
+                    "closeTx": "",
+                    "programVersion": "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X",
+                    "trades": [],
+                }],
+                "totalPages": 1,
+                "page": 1,
+            })
+            .to_string(),
+            created(1),
+            serde_json::json!({"code": 0, "signature": "open-1", "status": "Success"}).to_string(),
+        ];
+        let server = spawn_sequenced_mock_server(200, &[], bodies).await;
+
+        let sign = |tx: String| async move { Ok(format!("signed-{tx}")) };
+        let mut grid = TriggerGrid::new(JupiterClient::new(&server.url), config).unwrap();
+        assert!(matches!(
+            grid.place(sign).await,
+            Err(JupiterClientError::TriggerExecutionFailed { .. })
+        ));
+        assert_eq!(grid.orders().count(), 0);
+
+        assert_eq!(
+            grid.place(sign).await.unwrap(),
+            1,
+            "only the second level is placed"
+        );
+        assert!(grid.is_placed());
+        let orders: Vec<(&str, &str)> = grid
+            .orders()
+            .map(|order| (order.order_key.as_str(), order.signature.as_str()))
+            .collect();
+        assert_eq!(orders, vec![(keys[0], "open-0"), (keys[1], "open-1")]);
+
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests.len(), 5);
+        assert!(requests[2].starts_with("GET /trigger/v1/getTriggerOrders?user="));
+        assert!(requests[2].contains("orderStatus=active"));
+        assert!(requests[3].contains(r#""takingAmount":"200000000""#));
+    }
 }
@@ -1,8 +1,114 @@
 #[cfg(test)]
 mod ultra_tests {
-    use jup_ag_sdk::types::{UltraExecuteOrderRequest, UltraOrderRequest};
+    use std::collections::HashMap;
 
-    use crate::common::{JUP_MINT, SOL_MINT, TEST_AMOUNT, TEST_USER_PUBKEY, create_test_client};
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        types::{
+            DustSweepConfig, Holding, Price, Severity, TokenBalance, TokenVerdict, TokenVetting,
+            UltraExecuteCode, UltraExecuteOrderRequest, UltraExecuteOrderResponse,
+            UltraOrderRequest, UltraReferral, WalletHoldings, Warning, WarningType, select_dust,
+        },
+    };
+
+    use crate::common::{
+        JUP_MINT, SOL_MINT, TEST_AMOUNT, TEST_USER_PUBKEY, USDC_MINT, create_test_client,
+        spawn_mock_server,
+    };
+
+    fn balance(amount: u64, decimals: u32, is_frozen: bool) -> TokenBalance {
+        TokenBalance {
+            amount: amount.to_string(),
+            ui_amount: amount as f64 / 10f64.powi(decimals as i32),
+            slot: 0,
+            is_frozen,
+        }
+    }
+
+    fn usd_price(usd_price: f64) -> Price {
+        Price {
+            usd_price,
+            block_id: 0,
+            decimals: 6,
+            price_change_24h: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_select_dust() {
+        let balances = WalletHoldings::from_balances(
+            TEST_USER_PUBKEY,
+            HashMap::from([
+                ("SOL".to_string(), balance(1_000_000, 9, false)),
+                (USDC_MINT.to_string(), balance(500_000, 6, false)),
+                (JUP_MINT.to_string(), balance(1_000_000, 6, false)),
+                ("BigBag".to_string(), balance(1_000_000_000, 6, false)),
+                ("Frozen".to_string(), balance(1_000, 6, true)),
+                ("Unpriced".to_string(), balance(1_000, 6, false)),
+                ("Empty".to_string(), balance(0, 6, false)),
+            ]),
+        );
+        let prices = HashMap::from([
+            (JUP_MINT.to_string(), usd_price(0.5)),
+            ("BigBag".to_string(), usd_price(2.0)),
+            ("Frozen".to_string(), usd_price(1.0)),
+        ]);
+
+        let config = DustSweepConfig::new(USDC_MINT, 1.0);
+        let (candidates, skipped) = select_dust(&balances, &prices, &config);
+
+        assert_eq!(candidates.len(), 1, "only JUP is dust");
+        assert_eq!(candidates[0].mint, JUP_MINT);
+        assert_eq!(candidates[0].amount, 1_000_000);
+        assert!((candidates[0].usd_value - 0.5).abs() < 1e-9);
+
+        let skipped: Vec<_> = skipped.iter().map(|s| s.mint.as_str()).collect();
+        assert_eq!(skipped, vec!["Frozen", "Unpriced"]);
+
+        let excluded = config.exclude_mint(JUP_MINT);
+        assert!(select_dust(&balances, &prices, &excluded).0.is_empty());
+    }
+
+    #[test]
+    fn test_wallet_holdings_merge_token_accounts() {
+        let holding = |mint: &str, amount: u64, is_frozen: bool| Holding {
+            mint: mint.to_string(),
+            amount,
+            ui_amount: amount as f64 / 1e6,
+            decimals: Some(6),
+            is_frozen,
+        };
+        let holdings = WalletHoldings::from_holdings(
+            TEST_USER_PUBKEY,
+            [
+                holding(USDC_MINT, 1_000_000, false),
+                holding("SOL", 5_000, false),
+                holding(JUP_MINT, 0, false),
+                holding(USDC_MINT, 500_000, true),
+            ],
+        );
+
+        let usdc = holdings.get(USDC_MINT).unwrap();
+        assert_eq!(
+            usdc.amount, 1_500_000,
+            "balances of the same mint are summed"
+        );
+        assert!((usdc.ui_amount - 1.5).abs() < 1e-9);
+        assert!(usdc.is_frozen);
+        assert_eq!(
+            holdings.amount(SOL_MINT),
+            5_000,
+            "wSOL resolves to native SOL"
+        );
+        assert_eq!(holdings.amount("Missing"), 0);
+
+        let mints: Vec<_> = holdings.token_mints().collect();
+        assert_eq!(
+            mints,
+            vec![USDC_MINT],
+            "native SOL and empty balances are skipped"
+        );
+    }
 
     #[test]
     fn test_ultra_order_request_builder() {
@@ -19,6 +125,123 @@ mod ultra_tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_ultra_order_query() {
+        let server = spawn_mock_server(400, &[], r#"{"error":"bad"}"#.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+
+        let order = UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .add_taker(TEST_USER_PUBKEY)
+            .add_referral_account(TEST_USER_PUBKEY)
+            .add_referral_fee(50)
+            .exclude_routers(vec!["okx".to_string(), "pyth".to_string()]);
+        assert_eq!(order.clone(), order);
+        assert!(client.get_ultra_order(&order).await.is_err());
+        client
+            .get_ultra_order(&UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT))
+            .await
+            .unwrap_err();
+
+        let requests = server.requests.lock().unwrap();
+        assert!(requests[0].starts_with(&format!(
+            "GET /ultra/v1/order?inputMint={SOL_MINT}&outputMint={JUP_MINT}&amount={TEST_AMOUNT}\
+             &taker={TEST_USER_PUBKEY}&referralAccount={TEST_USER_PUBKEY}&referralFee=50\
+             &excludeRouters=okx%2Cpyth "
+        )));
+        assert!(requests[1].starts_with(&format!(
+            "GET /ultra/v1/order?inputMint={SOL_MINT}&outputMint={JUP_MINT}&amount={TEST_AMOUNT} "
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_ultra_referral() {
+        let referral = UltraReferral::new(TEST_USER_PUBKEY, 100).unwrap();
+        assert_eq!(referral.account(), TEST_USER_PUBKEY);
+        assert_eq!(referral.fee_bps(), 100);
+        assert!(matches!(
+            UltraReferral::new(TEST_USER_PUBKEY, 49),
+            Err(JupiterClientError::InvalidReferral(_))
+        ));
+        assert!(matches!(
+            UltraReferral::new("referral", 100),
+            Err(JupiterClientError::InvalidPubkey(_))
+        ));
+
+        let order = UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).referral(&referral);
+        assert_eq!(order.referral_account.as_deref(), Some(TEST_USER_PUBKEY));
+        assert_eq!(order.referral_fee, Some(100));
+        order.validate().unwrap();
+
+        let server = spawn_mock_server(400, &[], r#"{"error":"bad"}"#.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+        let mut missing_fee = UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .add_referral_account(TEST_USER_PUBKEY);
+        assert!(matches!(
+            client.get_ultra_order(&missing_fee).await,
+            Err(JupiterClientError::InvalidReferral(_))
+        ));
+        missing_fee.referral_fee = Some(20);
+        assert!(matches!(
+            client.get_ultra_order(&missing_fee).await,
+            Err(JupiterClientError::InvalidReferral(_))
+        ));
+        assert_eq!(
+            server.request_count(),
+            0,
+            "invalid referrals are never sent"
+        );
+
+        client.get_ultra_order(&order).await.unwrap_err();
+        assert!(server.requests.lock().unwrap()[0].contains(&format!(
+            "&referralAccount={TEST_USER_PUBKEY}&referralFee=100"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_ultra_execute_result() {
+        let failed = serde_json::json!({
+            "status": "Failed",
+            "signature": "5sig",
+            "slot": "348000000",
+            "error": "Slippage tolerance exceeded",
+            "code": 6001,
+        });
+        let server = spawn_mock_server(200, &[], failed.to_string()).await;
+        let client = JupiterClient::new(&server.url);
+
+        let res = client
+            .ultra_execute_order(&UltraExecuteOrderRequest::new("AQ==", "req-1"))
+            .await
+            .unwrap();
+        assert!(!res.is_success());
+        assert_eq!(res.execute_code(), UltraExecuteCode::Program(6001));
+        assert!(res.execute_code().is_retryable());
+        let sent = server.requests.lock().unwrap()[0].clone();
+        assert!(sent.starts_with("POST /ultra/v1/execute "));
+        assert!(sent.ends_with(r#"{"signedTransaction":"AQ==","requestId":"req-1"}"#));
+
+        let expired: UltraExecuteOrderResponse =
+            serde_json::from_value(serde_json::json!({ "status": "Failed", "code": -2003 }))
+                .unwrap();
+        assert_eq!(expired.execute_code(), UltraExecuteCode::QuoteExpired);
+        assert_eq!(
+            UltraExecuteCode::from(-1000),
+            UltraExecuteCode::FailedToLand
+        );
+        assert!(!UltraExecuteCode::from(-2).is_retryable());
+        assert_eq!(UltraExecuteCode::from(-42), UltraExecuteCode::Other(-42));
+
+        let landed: UltraExecuteOrderResponse = serde_json::from_value(serde_json::json!({
+            "status": "Success",
+            "signature": "5sig",
+            "code": 0,
+            "outputAmountResult": "299700000",
+        }))
+        .unwrap();
+        assert!(landed.is_success());
+        assert_eq!(landed.execute_code(), UltraExecuteCode::Success);
+    }
+
     #[tokio::test]
     async fn test_get_ultra_order_successful() {
         let client = create_test_client();
@@ -119,15 +342,92 @@ mod ultra_tests {
 
         let mints = vec!["EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string()];
 
-        let shield_res = client.shield(&mints).await.expect("Failed to get shield");
+        let shield_res = client
+            .get_shield(&mints)
+            .await
+            .expect("Failed to get shield");
         assert_eq!(
             shield_res.warnings.get(&mints[0]).expect("token not found")[0].warning_type,
-            "HAS_FREEZE_AUTHORITY"
+            WarningType::HasFreezeAuthority
         );
 
         assert_eq!(
             shield_res.warnings.get(&mints[0]).expect("token not found")[0].severity,
-            "warning"
+            Severity::Warning
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shield_typed_warnings() {
+        let body = serde_json::json!({
+            "warnings": {
+                "mint1": [
+                    {"type": "HAS_MINT_AUTHORITY", "message": "m", "severity": "warning"},
+                    {"type": "NOT_SELLABLE", "message": "s", "severity": "Critical"},
+                ],
+                "mint2": [
+                    {"type": "SOMETHING_NEW", "message": "n", "severity": "notice"},
+                ],
+            }
+        });
+        let server = spawn_mock_server(200, &[], body.to_string()).await;
+        let mints = ["mint1".to_string(), "mint2".to_string()];
+        let shield = JupiterClient::new(&server.url)
+            .get_shield(&mints)
+            .await
+            .unwrap();
+        assert!(
+            server.requests.lock().unwrap()[0].contains("/ultra/v1/shield?mints=mint1%2Cmint2")
+        );
+
+        assert!(shield.has_warning("mint1", &WarningType::HasMintAuthority));
+        assert!(shield.is_critical("mint1"));
+        assert_eq!(shield.warnings_for("mint1")[1].severity, Severity::Critical);
+
+        let unknown = &shield.warnings_for("mint2")[0];
+        assert_eq!(
+            unknown.warning_type,
+            WarningType::Other("SOMETHING_NEW".to_string())
+        );
+        assert_eq!(unknown.severity, Severity::Other("notice".to_string()));
+        assert!(!shield.is_critical("mint2"));
+        assert!(shield.warnings_for("mint3").is_empty());
+
+        let round_trip: Warning =
+            serde_json::from_str(&serde_json::to_string(unknown).unwrap()).unwrap();
+        assert_eq!(&round_trip, unknown);
+        assert_eq!(
+            serde_json::to_value(&shield.warnings_for("mint1")[0]).unwrap()["type"],
+            "HAS_MINT_AUTHORITY"
+        );
+    }
+
+    #[test]
+    fn test_token_vetting_verdict() {
+        let warning = |severity: &str| Warning {
+            warning_type: WarningType::HasFreezeAuthority,
+            message: "The authority can freeze your tokens".to_string(),
+            severity: Severity::from(severity),
+        };
+
+        let unknown = TokenVetting::new(USDC_MINT, None, vec![warning("info")], false);
+        assert_eq!(
+            unknown.verdict,
+            TokenVerdict::Blocked(vec![
+                "unknown to the Token API".to_string(),
+                "no route found".to_string(),
+            ])
+        );
+        assert!(!unknown.verdict.is_tradable());
+        assert!(unknown.token.is_none());
+
+        let critical = TokenVetting::new(USDC_MINT, None, vec![warning("critical")], false);
+        let TokenVerdict::Blocked(reasons) = critical.verdict else {
+            panic!("critical warnings should block");
+        };
+        assert_eq!(
+            reasons[2],
+            "HAS_FREEZE_AUTHORITY: The authority can freeze your tokens"
         );
     }
 
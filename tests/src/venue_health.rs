@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod venue_health_tests {
+    use std::{sync::Arc, time::Duration};
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        clock::{Clock, ManualClock},
+        types::DexEnum,
+        venue_health::VenueFailure,
+    };
+
+    use crate::common::{create_default_quote_request, sample_quote_response, spawn_mock_server};
+
+    #[tokio::test]
+    async fn test_venue_health_excludes_failing_venue_for_cooldown() {
+        let quote = serde_json::to_string(&sample_quote_response()).unwrap();
+        let server = spawn_mock_server(200, &[], quote).await;
+        let clock = Arc::new(ManualClock::new());
+        let client = JupiterClient::new(&server.url)
+            .with_clock(clock.clone())
+            .with_venue_health(2, Duration::from_secs(60));
+        let request = create_default_quote_request();
+
+        let quote = client.get_quote(&request).await.unwrap();
+        client.record_venue_failure("Whirlpool", VenueFailure::Simulation);
+        client.record_fill(&quote);
+        client.record_venue_failure("Whirlpool", VenueFailure::Simulation);
+        assert!(
+            client.unhealthy_venues().is_empty(),
+            "a fill resets the streak"
+        );
+        client.record_venue_failure("Whirlpool", VenueFailure::Revert);
+        assert_eq!(client.unhealthy_venues(), vec![DexEnum::Whirlpool]);
+
+        let report = client.venue_health();
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].venue, "Meteora DLMM");
+        assert!(!report[0].is_excluded());
+        assert_eq!(report[0].failure_rate(), Some(0.0));
+        let whirlpool = &report[1];
+        assert_eq!((whirlpool.quotes, whirlpool.fills), (1, 1));
+        assert_eq!((whirlpool.simulation_failures, whirlpool.reverts), (2, 1));
+        assert_eq!(whirlpool.failure_rate(), Some(0.75));
+        assert_eq!(
+            whirlpool.excluded_until,
+            Some(clock.now() + Duration::from_secs(60))
+        );
+
+        client.get_quote(&request).await.unwrap();
+        let own_dexes = request.clone().dexes(vec![DexEnum::Whirlpool]);
+        client.get_quote(&own_dexes).await.unwrap();
+        clock.advance(Duration::from_secs(60));
+        assert!(client.unhealthy_venues().is_empty());
+        client.get_quote(&request).await.unwrap();
+
+        let requests = server.requests.lock().unwrap();
+        assert!(!requests[0].contains("excludeDexes"));
+        assert!(requests[1].contains("excludeDexes=Whirlpool"));
+        assert!(
+            !requests[2].contains("excludeDexes"),
+            "requests naming their dexes are left alone"
+        );
+        assert!(!requests[3].contains("excludeDexes"), "the cooldown ended");
+    }
+}